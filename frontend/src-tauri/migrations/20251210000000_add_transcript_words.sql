@@ -0,0 +1,17 @@
+-- Migration: Add word-level timestamps for transcripts
+-- Stores per-word timings (currently produced by Parakeet) so the meeting view
+-- can highlight words during playback and seek to an exact word.
+--   - start_time / end_time: Seconds from recording start, same base as
+--     transcripts.audio_start_time
+CREATE TABLE IF NOT EXISTS transcript_words (
+    transcript_id TEXT NOT NULL,
+    word_index INTEGER NOT NULL,
+    word TEXT NOT NULL,
+    start_time REAL NOT NULL,
+    end_time REAL NOT NULL,
+    PRIMARY KEY (transcript_id, word_index),
+    FOREIGN KEY (transcript_id) REFERENCES transcripts(id) ON DELETE CASCADE
+);
+
+-- Create index for ordering words by time within a transcript
+CREATE INDEX IF NOT EXISTS idx_transcript_words_start ON transcript_words(transcript_id, start_time);
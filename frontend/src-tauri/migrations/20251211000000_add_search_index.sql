@@ -0,0 +1,92 @@
+-- Migration: Full-text search over transcripts, summaries and chat messages
+-- A single FTS5 table indexes all three sources and is kept in sync by triggers.
+--
+-- The FTS rowid encodes the source row so triggers can update/delete by rowid
+-- instead of scanning the index:
+--   transcripts.rowid * 4 + 1
+--   summary_processes.rowid * 4 + 2
+--   chat_messages.rowid * 4 + 3
+CREATE VIRTUAL TABLE IF NOT EXISTS search_index USING fts5(
+    content,
+    source_type UNINDEXED, -- 'transcript' | 'summary' | 'chat'
+    source_id UNINDEXED,   -- transcripts.id / summary_processes.meeting_id / chat_messages.id
+    meeting_id UNINDEXED,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+-- Transcripts
+CREATE TRIGGER IF NOT EXISTS search_index_transcripts_ai AFTER INSERT ON transcripts BEGIN
+    INSERT INTO search_index(rowid, content, source_type, source_id, meeting_id)
+    VALUES (new.rowid * 4 + 1, new.transcript, 'transcript', new.id, new.meeting_id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS search_index_transcripts_ad AFTER DELETE ON transcripts BEGIN
+    DELETE FROM search_index WHERE rowid = old.rowid * 4 + 1;
+END;
+
+CREATE TRIGGER IF NOT EXISTS search_index_transcripts_au AFTER UPDATE OF transcript, meeting_id ON transcripts BEGIN
+    DELETE FROM search_index WHERE rowid = old.rowid * 4 + 1;
+    INSERT INTO search_index(rowid, content, source_type, source_id, meeting_id)
+    VALUES (new.rowid * 4 + 1, new.transcript, 'transcript', new.id, new.meeting_id);
+END;
+
+-- Summaries: index the markdown of the stored result JSON, or the raw text if it isn't JSON
+CREATE TRIGGER IF NOT EXISTS search_index_summaries_ai AFTER INSERT ON summary_processes
+WHEN new.result IS NOT NULL BEGIN
+    INSERT INTO search_index(rowid, content, source_type, source_id, meeting_id)
+    VALUES (
+        new.rowid * 4 + 2,
+        CASE WHEN json_valid(new.result)
+            THEN COALESCE(json_extract(new.result, '$.markdown'), new.result)
+            ELSE new.result END,
+        'summary', new.meeting_id, new.meeting_id
+    );
+END;
+
+CREATE TRIGGER IF NOT EXISTS search_index_summaries_ad AFTER DELETE ON summary_processes BEGIN
+    DELETE FROM search_index WHERE rowid = old.rowid * 4 + 2;
+END;
+
+CREATE TRIGGER IF NOT EXISTS search_index_summaries_au AFTER UPDATE OF result ON summary_processes BEGIN
+    DELETE FROM search_index WHERE rowid = old.rowid * 4 + 2;
+    INSERT INTO search_index(rowid, content, source_type, source_id, meeting_id)
+    SELECT
+        new.rowid * 4 + 2,
+        CASE WHEN json_valid(new.result)
+            THEN COALESCE(json_extract(new.result, '$.markdown'), new.result)
+            ELSE new.result END,
+        'summary', new.meeting_id, new.meeting_id
+    WHERE new.result IS NOT NULL;
+END;
+
+-- Chat messages
+CREATE TRIGGER IF NOT EXISTS search_index_chat_ai AFTER INSERT ON chat_messages BEGIN
+    INSERT INTO search_index(rowid, content, source_type, source_id, meeting_id)
+    VALUES (new.rowid * 4 + 3, new.content, 'chat', new.id, new.meeting_id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS search_index_chat_ad AFTER DELETE ON chat_messages BEGIN
+    DELETE FROM search_index WHERE rowid = old.rowid * 4 + 3;
+END;
+
+CREATE TRIGGER IF NOT EXISTS search_index_chat_au AFTER UPDATE OF content ON chat_messages BEGIN
+    DELETE FROM search_index WHERE rowid = old.rowid * 4 + 3;
+    INSERT INTO search_index(rowid, content, source_type, source_id, meeting_id)
+    VALUES (new.rowid * 4 + 3, new.content, 'chat', new.id, new.meeting_id);
+END;
+
+-- Backfill existing data
+INSERT INTO search_index(rowid, content, source_type, source_id, meeting_id)
+SELECT rowid * 4 + 1, transcript, 'transcript', id, meeting_id FROM transcripts;
+
+INSERT INTO search_index(rowid, content, source_type, source_id, meeting_id)
+SELECT
+    rowid * 4 + 2,
+    CASE WHEN json_valid(result)
+        THEN COALESCE(json_extract(result, '$.markdown'), result)
+        ELSE result END,
+    'summary', meeting_id, meeting_id
+FROM summary_processes WHERE result IS NOT NULL;
+
+INSERT INTO search_index(rowid, content, source_type, source_id, meeting_id)
+SELECT rowid * 4 + 3, content, 'chat', id, meeting_id FROM chat_messages;
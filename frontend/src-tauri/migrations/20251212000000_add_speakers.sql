@@ -0,0 +1,16 @@
+-- Migration: Add speaker diarization
+--   - transcripts.speaker_id: Per-meeting speaker label ("speaker_1", "speaker_2", ...)
+--     assigned by clustering speaker embeddings, NULL if unknown
+--   - meeting_speakers: Display names users give to those labels
+ALTER TABLE transcripts ADD COLUMN speaker_id TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_transcripts_meeting_speaker ON transcripts(meeting_id, speaker_id);
+
+CREATE TABLE IF NOT EXISTS meeting_speakers (
+    meeting_id TEXT NOT NULL,
+    speaker_id TEXT NOT NULL,
+    display_name TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (meeting_id, speaker_id),
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
@@ -0,0 +1,5 @@
+-- Migration: Add transcript channel
+--   - transcripts.channel: 'mic' or 'system' when the recording was transcribed
+--     per channel (mic = the local user, system = remote participants),
+--     NULL for mixed-audio transcripts
+ALTER TABLE transcripts ADD COLUMN channel TEXT;
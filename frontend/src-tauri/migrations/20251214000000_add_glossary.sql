@@ -0,0 +1,13 @@
+-- Migration: Add custom vocabulary for transcription
+--   - glossary_terms: Product names, people and acronyms fed to Whisper as the
+--     initial prompt and to Parakeet as hotwords.
+--     meeting_id NULL = applies to every meeting, otherwise only to that meeting
+CREATE TABLE IF NOT EXISTS glossary_terms (
+    id TEXT PRIMARY KEY,
+    meeting_id TEXT,
+    term TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_glossary_terms_meeting_id ON glossary_terms(meeting_id);
@@ -0,0 +1,22 @@
+-- Migration: Add find-and-replace rules for transcripts
+-- Rules run in `position` order on every new transcript segment and can be
+-- re-applied to saved meetings.
+--   - is_regex: pattern is a regular expression ($1 etc. allowed in replacement),
+--     otherwise a literal phrase
+--   - whole_word: literal patterns only match whole words
+--   - preserve_case: "FOO" / "Foo" matches get an upper-case / capitalized replacement
+CREATE TABLE IF NOT EXISTS replacement_rules (
+    id TEXT PRIMARY KEY,
+    pattern TEXT NOT NULL,
+    replacement TEXT NOT NULL,
+    is_regex INTEGER NOT NULL DEFAULT 0,
+    case_sensitive INTEGER NOT NULL DEFAULT 0,
+    whole_word INTEGER NOT NULL DEFAULT 1,
+    preserve_case INTEGER NOT NULL DEFAULT 1,
+    enabled INTEGER NOT NULL DEFAULT 1,
+    position INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_replacement_rules_position ON replacement_rules(position);
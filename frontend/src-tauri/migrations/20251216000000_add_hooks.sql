@@ -0,0 +1,42 @@
+-- Migration: Add automation hooks and their delivery log
+-- A hook runs on a meeting event (recording_stopped, transcript_finalized,
+-- summary_completed, summary_failed), either as an HTTP POST to `target`
+-- (kind = 'webhook') or as a local shell command (kind = 'command').
+--   - headers: JSON object of extra HTTP headers (webhooks only)
+--   - payload_template: optional body with {{placeholders}}; the default
+--     JSON payload is sent when NULL
+--   - max_attempts: tries per event before the delivery is marked failed
+CREATE TABLE IF NOT EXISTS hooks (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    event TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    target TEXT NOT NULL,
+    headers TEXT,
+    payload_template TEXT,
+    max_attempts INTEGER NOT NULL DEFAULT 3,
+    enabled INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_hooks_event ON hooks(event);
+
+-- One row per hook run; status is 'pending', 'retrying', 'succeeded' or 'failed'
+CREATE TABLE IF NOT EXISTS hook_deliveries (
+    id TEXT PRIMARY KEY,
+    hook_id TEXT NOT NULL,
+    event TEXT NOT NULL,
+    meeting_id TEXT,
+    status TEXT NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    response_code INTEGER,
+    last_error TEXT,
+    payload TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (hook_id) REFERENCES hooks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_hook_deliveries_hook ON hook_deliveries(hook_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_hook_deliveries_created ON hook_deliveries(created_at);
@@ -0,0 +1,22 @@
+-- Migration: Add structured action items
+--   - action_items: Tasks extracted from a meeting's transcript after its
+--     summary is generated, tracked as 'open' or 'done' across meetings.
+--     transcript_id points at the segment the item was found in, due_date is
+--     YYYY-MM-DD and confidence is the model's own 0-1 estimate.
+CREATE TABLE IF NOT EXISTS action_items (
+    id TEXT PRIMARY KEY,
+    meeting_id TEXT NOT NULL,
+    text TEXT NOT NULL,
+    owner TEXT,
+    due_date TEXT,
+    transcript_id TEXT,
+    confidence REAL,
+    status TEXT NOT NULL DEFAULT 'open',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    completed_at TEXT,
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_action_items_meeting_id ON action_items(meeting_id);
+CREATE INDEX IF NOT EXISTS idx_action_items_status ON action_items(status);
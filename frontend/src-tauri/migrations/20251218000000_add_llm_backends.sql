@@ -0,0 +1,7 @@
+-- Add API key and endpoint columns for the Gemini, Azure OpenAI, Mistral and llama.cpp backends
+ALTER TABLE settings ADD COLUMN geminiApiKey TEXT;
+ALTER TABLE settings ADD COLUMN azureOpenaiApiKey TEXT;
+ALTER TABLE settings ADD COLUMN azureOpenaiEndpoint TEXT;
+ALTER TABLE settings ADD COLUMN mistralApiKey TEXT;
+ALTER TABLE settings ADD COLUMN llamaCppApiKey TEXT;
+ALTER TABLE settings ADD COLUMN llamaCppEndpoint TEXT;
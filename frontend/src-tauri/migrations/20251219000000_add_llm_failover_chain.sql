@@ -0,0 +1,3 @@
+-- Fallback LLM backends tried in order when the selected one fails,
+-- stored as a JSON array of {"provider": ..., "model": ...}
+ALTER TABLE settings ADD COLUMN llmFailoverChain TEXT;
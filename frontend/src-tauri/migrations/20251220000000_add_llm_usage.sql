@@ -0,0 +1,43 @@
+-- Migration: Add LLM usage and cost accounting
+--   - llm_usage: One row per successful LLM call (summary chunks, combine
+--     passes, final pass, action items, chat). Tokens are the provider's own
+--     counts, or estimates when it reports none (estimated = 1). cost_usd is
+--     priced at the time of the call and NULL when no price is known; calls
+--     to local backends cost 0. meeting_id is kept after a meeting is
+--     deleted so past spend still adds up.
+--   - llm_prices: USD per million tokens, entered by the user (source 'user')
+--     or synced from OpenRouter's model list (source 'openrouter'). User
+--     prices win and are never overwritten by a sync.
+--   - settings: Optional monthly and per-meeting budget caps in USD. Cloud
+--     calls are blocked once one is reached.
+CREATE TABLE IF NOT EXISTS llm_usage (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    meeting_id TEXT,
+    stage TEXT NOT NULL,
+    provider TEXT NOT NULL,
+    model TEXT NOT NULL,
+    local INTEGER NOT NULL DEFAULT 0,
+    prompt_tokens INTEGER NOT NULL,
+    completion_tokens INTEGER NOT NULL,
+    estimated INTEGER NOT NULL DEFAULT 0,
+    ttft_us INTEGER,
+    total_time_us INTEGER NOT NULL,
+    cost_usd REAL,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_llm_usage_meeting_id ON llm_usage(meeting_id);
+CREATE INDEX IF NOT EXISTS idx_llm_usage_created_at ON llm_usage(created_at);
+
+CREATE TABLE IF NOT EXISTS llm_prices (
+    provider TEXT NOT NULL,
+    model TEXT NOT NULL,
+    prompt_usd_per_mtok REAL NOT NULL,
+    completion_usd_per_mtok REAL NOT NULL,
+    source TEXT NOT NULL DEFAULT 'user',
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (provider, model)
+);
+
+ALTER TABLE settings ADD COLUMN llmMonthlyBudget REAL;
+ALTER TABLE settings ADD COLUMN llmMeetingBudget REAL;
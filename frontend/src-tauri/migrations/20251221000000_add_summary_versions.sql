@@ -0,0 +1,48 @@
+-- Migration: Add summary version history
+--   - summary_versions: Every generated summary and every manual edit, so
+--     regenerating with another template or model keeps the summary it
+--     replaces. source is 'generated' or 'edited'; parent_id is the version
+--     that was current when this one was made. provider, model, template_id
+--     and custom_prompt describe how a generated version was produced.
+--   - summary_processes.current_version_id: The version the meeting's summary
+--     shows. Restoring a version points it back at that version.
+--   Existing summaries become their meeting's first version.
+CREATE TABLE IF NOT EXISTS summary_versions (
+    id TEXT PRIMARY KEY,
+    meeting_id TEXT NOT NULL,
+    parent_id TEXT,
+    source TEXT NOT NULL,
+    result TEXT NOT NULL,
+    provider TEXT,
+    model TEXT,
+    template_id TEXT,
+    custom_prompt TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE,
+    FOREIGN KEY (parent_id) REFERENCES summary_versions(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_summary_versions_meeting_id ON summary_versions(meeting_id);
+
+ALTER TABLE summary_processes ADD COLUMN current_version_id TEXT;
+
+-- Summaries saved from the editor carry BlockNote blocks in summary_json
+INSERT INTO summary_versions (id, meeting_id, source, result, provider, model, created_at)
+SELECT
+    'summary-version-' || lower(hex(randomblob(16))),
+    p.meeting_id,
+    CASE WHEN json_valid(p.result) AND json_extract(p.result, '$.summary_json') IS NOT NULL
+        THEN 'edited' ELSE 'generated' END,
+    p.result,
+    t.model,
+    t.model_name,
+    p.updated_at
+FROM summary_processes p
+LEFT JOIN transcript_chunks t ON t.meeting_id = p.meeting_id
+WHERE p.result IS NOT NULL;
+
+UPDATE summary_processes
+SET current_version_id = (
+    SELECT v.id FROM summary_versions v WHERE v.meeting_id = summary_processes.meeting_id
+)
+WHERE result IS NOT NULL;
@@ -0,0 +1,10 @@
+-- Migration: Add a persistent summary job queue
+--   - summary_processes.job: The request a queued or running job was started
+--     with (provider, model, template, prompt, language and sampling params)
+--     as JSON, so jobs left unfinished when the app quit can be resumed.
+--   - summary_processes.attempts: How many times the job has been started.
+--     A job interrupted too often is failed on startup instead of resumed.
+--   Job statuses: 'PENDING' (queued), 'processing', then 'completed',
+--   'failed' or 'cancelled'.
+ALTER TABLE summary_processes ADD COLUMN job TEXT;
+ALTER TABLE summary_processes ADD COLUMN attempts INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,2 @@
+-- Whether new recordings are diarized; NULL keeps the default (on)
+ALTER TABLE settings ADD COLUMN diarizationEnabled INTEGER;
@@ -5,12 +5,14 @@ use tauri::{AppHandle, Runtime};
 use tauri_plugin_store::StoreExt;
 
 use crate::{
+    audio::{transcription::WordTimestamp, TranscriptChannel},
     database::{
         models::MeetingModel,
         repositories::{
-            meeting::MeetingsRepository, setting::SettingsRepository,
+            meeting::MeetingsRepository, search::SearchRepository, setting::SettingsRepository,
             transcript::TranscriptsRepository,
             transcript_chunk::TranscriptChunksRepository,
+            transcript_word::TranscriptWordsRepository,
         },
     },
     state::AppState,
@@ -46,6 +48,37 @@ pub struct TranscriptSearchResult {
     pub timestamp: String,
 }
 
+/// Optional filters for full-text search
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SearchFilters {
+    pub meeting_id: Option<String>,
+    /// Restrict to "transcript", "summary" and/or "chat"
+    pub source_types: Option<Vec<String>>,
+    /// Inclusive meeting creation date range, "YYYY-MM-DD"
+    pub from_date: Option<String>,
+    pub to_date: Option<String>,
+    pub limit: Option<i64>,
+}
+
+/// A single ranked full-text search hit
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SearchResult {
+    pub meeting_id: String,
+    pub meeting_title: String,
+    pub meeting_created_at: String,
+    pub source_type: String,
+    pub source_id: String,
+    /// Matched excerpt with hits wrapped in <mark></mark>
+    pub snippet: String,
+    /// bm25 score - lower is a better match
+    pub rank: f64,
+    // Only set for transcript hits
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio_start_time: Option<f64>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ProfileRequest {
     pub email: String,
@@ -141,6 +174,17 @@ pub struct MeetingTranscript {
     pub audio_end_time: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub duration: Option<f64>,
+    // Word-level timings for playback highlighting (empty if not available)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<WordTimestamp>,
+    // Diarization label ("speaker_1", ...) and the name shown for it in this meeting
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker_name: Option<String>,
+    // Source channel when mic and system audio were transcribed separately
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<TranscriptChannel>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -173,6 +217,15 @@ pub struct TranscriptSegment {
     pub audio_end_time: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub duration: Option<f64>,
+    // Word-level timings, recording-relative (empty if the engine doesn't provide them)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<WordTimestamp>,
+    // Diarization label, if speaker diarization ran for this segment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker_id: Option<String>,
+    // Source channel for per-channel recordings ("mic" / "system")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<TranscriptChannel>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -367,6 +420,32 @@ pub async fn api_search_transcripts<R: Runtime>(
     }
 }
 
+/// Full-text search across transcripts, summaries and chat messages.
+/// Supports "phrase" and prefix* queries; results are bm25-ranked with highlighted snippets.
+#[tauri::command]
+pub async fn api_search<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    query: String,
+    filters: Option<SearchFilters>,
+) -> Result<Vec<SearchResult>, String> {
+    log_info!("api_search called with query: '{}', filters: {:?}", query, filters);
+
+    let pool = state.db_manager.pool();
+    let filters = filters.unwrap_or_default();
+
+    match SearchRepository::search(pool, &query, &filters).await {
+        Ok(results) => {
+            log_info!("Search completed successfully with {} results.", results.len());
+            Ok(results)
+        }
+        Err(e) => {
+            log_error!("Error searching for query '{}': {}", query, e);
+            Err(format!("Failed to search: {}", e))
+        }
+    }
+}
+
 #[tauri::command]
 pub async fn api_get_profile<R: Runtime>(
     app: AppHandle<R>,
@@ -927,6 +1006,11 @@ pub async fn api_save_transcript<R: Runtime>(
                 "Successfully saved transcript and created meeting with id: {}",
                 meeting_id
             );
+            crate::hooks::fire(
+                pool,
+                crate::hooks::HookEvent::TranscriptFinalized,
+                crate::hooks::HookContext::meeting(&meeting_id),
+            );
             Ok(serde_json::json!({
                 "status": "success",
                 "message": "Transcript saved successfully",
@@ -1014,6 +1098,23 @@ pub async fn api_update_meeting_transcripts<R: Runtime>(
                 format!("Failed to update meeting folder_path: {}", e)
             })?;
         log_info!("✅ Updated meeting folder_path: {}", path);
+
+        // Leading silence may have been trimmed from audio.mp4 on save; shift
+        // the transcripts saved during recording once so they stay in sync
+        let folder = std::path::Path::new(path);
+        if let Some(mut metadata) = crate::recovery::read_metadata(folder) {
+            if let Some(trim) = metadata.silence_trim.as_mut().filter(|t| !t.database_synced) {
+                match TranscriptsRepository::shift_audio_times(pool, &meeting_id, trim.start_seconds).await {
+                    Ok(_) => {
+                        trim.database_synced = true;
+                        if let Err(e) = crate::recovery::write_metadata(folder, &metadata) {
+                            log_warn!("Failed to record transcript shift in metadata: {}", e);
+                        }
+                    }
+                    Err(e) => log_error!("Failed to shift transcript times after trim: {}", e),
+                }
+            }
+        }
     } else {
         // Just update the updated_at timestamp
         sqlx::query("UPDATE meetings SET updated_at = ? WHERE id = ?")
@@ -1044,6 +1145,11 @@ pub async fn api_update_meeting_transcripts<R: Runtime>(
         meeting_id,
         transcript_count.0
     );
+    crate::hooks::fire(
+        pool,
+        crate::hooks::HookEvent::TranscriptFinalized,
+        crate::hooks::HookContext::meeting(&meeting_id),
+    );
 
     Ok(serde_json::json!({
         "status": "success",
@@ -1079,12 +1185,22 @@ pub async fn api_save_single_transcript<R: Runtime>(
     // Generate transcript_id using sequence_id or timestamp
     let transcript_id = format!("{}-{}", meeting_id, segment.id);
 
-    // Use INSERT OR REPLACE to handle potential duplicates
+    // Upsert to handle potential duplicates. ON CONFLICT keeps the row (and its rowid),
+    // so the search index triggers see an UPDATE instead of a silent REPLACE delete.
     sqlx::query(
         r#"
-        INSERT OR REPLACE INTO transcripts
-        (id, meeting_id, transcript, timestamp, audio_start_time, audio_end_time, duration)
-        VALUES (?, ?, ?, ?, ?, ?, ?)
+        INSERT INTO transcripts
+        (id, meeting_id, transcript, timestamp, audio_start_time, audio_end_time, duration, speaker_id, channel)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT(id) DO UPDATE SET
+            meeting_id = excluded.meeting_id,
+            transcript = excluded.transcript,
+            timestamp = excluded.timestamp,
+            audio_start_time = excluded.audio_start_time,
+            audio_end_time = excluded.audio_end_time,
+            duration = excluded.duration,
+            speaker_id = excluded.speaker_id,
+            channel = excluded.channel
         "#
     )
     .bind(&transcript_id)
@@ -1094,6 +1210,8 @@ pub async fn api_save_single_transcript<R: Runtime>(
     .bind(segment.audio_start_time)
     .bind(segment.audio_end_time)
     .bind(segment.duration)
+    .bind(&segment.speaker_id)
+    .bind(segment.channel.map(|c| c.as_str()))
     .execute(pool)
     .await
     .map_err(|e| {
@@ -1101,6 +1219,16 @@ pub async fn api_save_single_transcript<R: Runtime>(
         format!("Failed to insert transcript: {}", e)
     })?;
 
+    if !segment.words.is_empty() {
+        let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+        if let Err(e) =
+            TranscriptWordsRepository::save_words(&mut *conn, &transcript_id, &segment.words).await
+        {
+            // Word timings are an enhancement - keep the transcript even if they fail
+            log_warn!("Failed to save word timings for {}: {}", transcript_id, e);
+        }
+    }
+
     log_info!(
         "✅ Saved transcript segment {} to meeting {}",
         transcript_id,
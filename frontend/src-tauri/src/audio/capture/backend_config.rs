@@ -16,6 +16,11 @@ pub enum AudioCaptureBackend {
     /// Uses direct Core Audio API with aggregate device + tap
     #[cfg(target_os = "macos")]
     CoreAudio,
+
+    /// PulseAudio backend (Linux only)
+    /// Uses pactl/parec; works with PulseAudio and PipeWire, can capture selected apps
+    #[cfg(target_os = "linux")]
+    PulseAudio,
 }
 
 impl AudioCaptureBackend {
@@ -25,6 +30,8 @@ impl AudioCaptureBackend {
             AudioCaptureBackend::ScreenCaptureKit => "ScreenCaptureKit",
             #[cfg(target_os = "macos")]
             AudioCaptureBackend::CoreAudio => "Core Audio",
+            #[cfg(target_os = "linux")]
+            AudioCaptureBackend::PulseAudio => "PulseAudio / PipeWire",
         }
     }
 
@@ -38,6 +45,10 @@ impl AudioCaptureBackend {
             AudioCaptureBackend::CoreAudio => {
                 "Direct Core Audio API - Lower latency, more control over audio pipeline"
             }
+            #[cfg(target_os = "linux")]
+            AudioCaptureBackend::PulseAudio => {
+                "Sound server capture - Records the default output or only selected applications"
+            }
         }
     }
 
@@ -47,6 +58,8 @@ impl AudioCaptureBackend {
             "screencapturekit" => Some(AudioCaptureBackend::ScreenCaptureKit),
             #[cfg(target_os = "macos")]
             "coreaudio" | "core_audio" => Some(AudioCaptureBackend::CoreAudio),
+            #[cfg(target_os = "linux")]
+            "pulseaudio" | "pipewire" => Some(AudioCaptureBackend::PulseAudio),
             _ => None,
         }
     }
@@ -57,6 +70,8 @@ impl AudioCaptureBackend {
             AudioCaptureBackend::ScreenCaptureKit => "screencapturekit".to_string(),
             #[cfg(target_os = "macos")]
             AudioCaptureBackend::CoreAudio => "coreaudio".to_string(),
+            #[cfg(target_os = "linux")]
+            AudioCaptureBackend::PulseAudio => "pulseaudio".to_string(),
         }
     }
 
@@ -67,7 +82,12 @@ impl AudioCaptureBackend {
             vec![AudioCaptureBackend::ScreenCaptureKit, AudioCaptureBackend::CoreAudio]
         }
 
-        #[cfg(not(target_os = "macos"))]
+        #[cfg(target_os = "linux")]
+        {
+            vec![AudioCaptureBackend::ScreenCaptureKit, AudioCaptureBackend::PulseAudio]
+        }
+
+        #[cfg(not(any(target_os = "macos", target_os = "linux")))]
         {
             vec![AudioCaptureBackend::ScreenCaptureKit]
         }
@@ -177,6 +197,14 @@ mod tests {
                 Some(AudioCaptureBackend::CoreAudio)
             );
         }
+        #[cfg(target_os = "linux")]
+        {
+            assert_eq!(
+                AudioCaptureBackend::from_string("pipewire"),
+                Some(AudioCaptureBackend::PulseAudio)
+            );
+            assert_eq!(AudioCaptureBackend::PulseAudio.to_string(), "pulseaudio");
+        }
     }
 
     #[test]
@@ -0,0 +1,223 @@
+// audio/capture/file_source.rs
+//
+// File-backed virtual capture device for headless runs and regression tests.
+//
+// A device named `file:<path>` (as mic or system device) streams a WAV, MP3,
+// M4A or MP4 file into the same `AudioCapture` -> pipeline -> transcription
+// path a live device uses, so a whole record -> transcribe -> save run can be
+// scripted on machines without a sound card. Blocks are paced at real time or
+// faster (`set_playback_speed`). Transcript timings come from sample counts,
+// so they match the file at any speed.
+
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::audio::decoder::{decode_audio_file, DecodedAudio};
+use crate::audio::pipeline::AudioCapture;
+use crate::audio::recording_state::RecordingState;
+
+pub const FILE_DEVICE_PREFIX: &str = "file:";
+
+/// Fastest supported pacing; beyond this the pipeline's mixer and VAD fall behind
+pub const MAX_PLAYBACK_SPEED: f32 = 16.0;
+
+/// Audio delivered per block, like a sound card callback
+const BLOCK_MS: u64 = 20;
+
+/// Silence appended after the file so VAD closes the last speech segment
+const TRAILING_SILENCE_MS: u64 = 1500;
+
+// Playback speed in percent (100 = real time)
+static PLAYBACK_SPEED_PERCENT: AtomicU32 = AtomicU32::new(100);
+static ACTIVE_FILE_SOURCES: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileCaptureStatus {
+    /// File sources still streaming (0 once every file reached its end)
+    pub active_sources: usize,
+    pub playback_speed: f32,
+}
+
+/// Path of a `file:<path>` device name, None for regular devices
+pub fn file_device_path(device_name: &str) -> Option<PathBuf> {
+    let path = device_name.strip_prefix(FILE_DEVICE_PREFIX)?.trim();
+    if path.is_empty() {
+        None
+    } else {
+        Some(PathBuf::from(path))
+    }
+}
+
+pub fn is_file_device(device_name: &str) -> bool {
+    file_device_path(device_name).is_some()
+}
+
+/// Device name that streams `path`
+pub fn file_device_name(path: &Path) -> String {
+    format!("{}{}", FILE_DEVICE_PREFIX, path.display())
+}
+
+pub fn playback_speed() -> f32 {
+    PLAYBACK_SPEED_PERCENT.load(Ordering::SeqCst) as f32 / 100.0
+}
+
+/// Set the pacing of file devices (1.0 = real time). Applies to file
+/// sources started afterwards.
+pub fn set_playback_speed(speed: f32) -> Result<()> {
+    if !speed.is_finite() || !(0.01..=MAX_PLAYBACK_SPEED).contains(&speed) {
+        return Err(anyhow!(
+            "Playback speed must be between 0.01 and {}",
+            MAX_PLAYBACK_SPEED
+        ));
+    }
+    PLAYBACK_SPEED_PERCENT.store((speed * 100.0).round() as u32, Ordering::SeqCst);
+    info!("File capture playback speed set to {:.2}x", speed);
+    Ok(())
+}
+
+pub fn status() -> FileCaptureStatus {
+    FileCaptureStatus {
+        active_sources: ACTIVE_FILE_SOURCES.load(Ordering::SeqCst),
+        playback_speed: playback_speed(),
+    }
+}
+
+/// Decode the file behind a file device (blocking work runs off the runtime)
+pub async fn open(path: &Path) -> Result<DecodedAudio> {
+    let path = path.to_path_buf();
+    let decoded = tokio::task::spawn_blocking(move || decode_audio_file(&path))
+        .await
+        .map_err(|e| anyhow!("File decode task failed: {}", e))??;
+    if decoded.samples.is_empty() || decoded.sample_rate == 0 {
+        return Err(anyhow!("File contains no audio"));
+    }
+    Ok(decoded)
+}
+
+/// Samples per block at `sample_rate`
+fn block_len(sample_rate: u32) -> usize {
+    ((sample_rate as u64 * BLOCK_MS) / 1000).max(1) as usize
+}
+
+/// Split decoded audio into delivery blocks, followed by trailing silence
+fn blocks(samples: &[f32], sample_rate: u32) -> impl Iterator<Item = Vec<f32>> + '_ {
+    let len = block_len(sample_rate);
+    let silence_blocks = (TRAILING_SILENCE_MS / BLOCK_MS) as usize;
+    samples
+        .chunks(len)
+        .map(|block| block.to_vec())
+        .chain(std::iter::repeat(vec![0.0; len]).take(silence_blocks))
+}
+
+/// Counts a source as active until its task finishes or is aborted
+struct ActiveSourceGuard;
+
+impl ActiveSourceGuard {
+    fn new() -> Self {
+        ACTIVE_FILE_SOURCES.fetch_add(1, Ordering::SeqCst);
+        Self
+    }
+}
+
+impl Drop for ActiveSourceGuard {
+    fn drop(&mut self) {
+        ACTIVE_FILE_SOURCES.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Stream `audio` into `capture` until the file ends or recording stops.
+/// While recording is paused the file waits at its position.
+pub fn spawn(
+    name: String,
+    audio: DecodedAudio,
+    capture: AudioCapture,
+    state: Arc<RecordingState>,
+) -> tokio::task::JoinHandle<()> {
+    let speed = playback_speed();
+    let interval = Duration::from_secs_f64(BLOCK_MS as f64 / 1000.0 / speed as f64);
+    let guard = ActiveSourceGuard::new();
+
+    tokio::spawn(async move {
+        let _guard = guard;
+        info!(
+            "📼 File source {} started: {:.1}s at {} Hz, {:.2}x speed",
+            name,
+            audio.duration_seconds(),
+            audio.sample_rate,
+            speed
+        );
+
+        let mut ticker = tokio::time::interval(interval);
+        let mut delivered = 0usize;
+        let mut stopped_early = false;
+
+        for block in blocks(&audio.samples, audio.sample_rate) {
+            loop {
+                ticker.tick().await;
+                if !state.is_recording() || !state.is_paused() {
+                    break;
+                }
+            }
+            if !state.is_recording() {
+                stopped_early = true;
+                break;
+            }
+            capture.process_audio_data(&block);
+            delivered += block.len();
+        }
+
+        if stopped_early {
+            warn!(
+                "📼 File source {} stopped with recording after {:.1}s",
+                name,
+                delivered as f64 / audio.sample_rate as f64
+            );
+        } else {
+            info!("📼 File source {} reached end of file", name);
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_device_names() {
+        assert_eq!(
+            file_device_path("file:/tmp/mic.wav"),
+            Some(PathBuf::from("/tmp/mic.wav"))
+        );
+        assert_eq!(file_device_path("file: "), None);
+        assert_eq!(file_device_path("MacBook Pro Microphone"), None);
+        assert!(is_file_device(&file_device_name(Path::new("/tmp/a b.mp4"))));
+    }
+
+    #[test]
+    fn test_blocks_cover_file_and_trailing_silence() {
+        let samples = vec![0.5; 16000 + 100];
+        let all: Vec<Vec<f32>> = blocks(&samples, 16000).collect();
+
+        let block = block_len(16000);
+        assert_eq!(block, 320);
+        let audio_blocks = samples.len().div_ceil(block);
+        assert_eq!(all.len(), audio_blocks + (TRAILING_SILENCE_MS / BLOCK_MS) as usize);
+        assert_eq!(all[..audio_blocks].iter().map(Vec::len).sum::<usize>(), samples.len());
+        assert!(all[audio_blocks..].iter().all(|b| b.iter().all(|&s| s == 0.0)));
+    }
+
+    #[test]
+    fn test_playback_speed_bounds() {
+        assert!(set_playback_speed(0.0).is_err());
+        assert!(set_playback_speed(f32::NAN).is_err());
+        assert!(set_playback_speed(MAX_PLAYBACK_SPEED + 1.0).is_err());
+        set_playback_speed(4.0).unwrap();
+        assert_eq!(playback_speed(), 4.0);
+        set_playback_speed(1.0).unwrap();
+    }
+}
@@ -3,10 +3,14 @@
 pub mod microphone;
 pub mod system;
 pub mod backend_config;
+pub mod file_source;
 
 #[cfg(target_os = "macos")]
 pub mod core_audio;
 
+#[cfg(target_os = "linux")]
+pub mod pulse;
+
 // Re-export capture functionality
 pub use system::{
     SystemAudioCapture, SystemAudioStream,
@@ -0,0 +1,462 @@
+// audio/capture/pulse.rs
+//
+// PulseAudio / PipeWire system audio capture for Linux.
+//
+// Talks to the sound server through `pactl` and `parec`, which work the same
+// against PulseAudio and against PipeWire's pulse compatibility layer. With no
+// application selected the monitor of the default sink is recorded (everything
+// the user hears). With a selection, a dedicated null sink is created, the
+// selected applications' streams are moved into it and looped back to the
+// default sink so the user still hears them, and only that sink's monitor is
+// recorded. Applications that start playing during the recording are picked
+// up by a periodic re-scan.
+
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::process::Command;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use tokio::io::AsyncReadExt;
+
+use crate::audio::pipeline::AudioCapture;
+use crate::audio::recording_state::RecordingState;
+
+/// Name of the virtual system device listed on Linux
+pub const SYSTEM_DEVICE_NAME: &str = "Desktop Audio (PulseAudio)";
+
+/// Null sink that selected applications are moved into
+const CAPTURE_SINK_NAME: &str = "meetily_capture";
+
+/// Rate `parec` delivers at; the server resamples as needed
+pub const CAPTURE_SAMPLE_RATE: u32 = 48000;
+
+/// Audio delivered per block, like a sound card callback
+const BLOCK_MS: usize = 20;
+
+/// How often newly started applications are looked for
+const RESCAN_INTERVAL: Duration = Duration::from_secs(2);
+
+// Applications to capture (matched against application name or binary).
+// Empty captures all system audio.
+static SELECTED_APPLICATIONS: Lazy<RwLock<Vec<String>>> = Lazy::new(|| RwLock::new(Vec::new()));
+
+/// A stream an application is playing (a PulseAudio "sink input")
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AudioApplication {
+    pub index: u32,
+    pub name: String,
+    pub binary: Option<String>,
+    pub pid: Option<u32>,
+    pub sink: Option<u32>,
+    /// Paused streams stay listed but produce no audio
+    pub corked: bool,
+}
+
+impl AudioApplication {
+    /// Whether the selection names this application (by name or binary)
+    pub fn matches(&self, selection: &[String]) -> bool {
+        selection.iter().any(|selected| {
+            self.name.eq_ignore_ascii_case(selected)
+                || self
+                    .binary
+                    .as_deref()
+                    .map_or(false, |binary| binary.eq_ignore_ascii_case(selected))
+        })
+    }
+}
+
+/// Run `pactl` with untranslated output
+fn pactl(args: &[&str]) -> Result<String> {
+    let output = Command::new("pactl")
+        .args(args)
+        .env("LC_ALL", "C")
+        .output()
+        .map_err(|e| anyhow!("Failed to run pactl: {}", e))?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "pactl {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Whether a PulseAudio-compatible server (PulseAudio or PipeWire) is reachable
+pub fn is_available() -> bool {
+    pactl(&["info"]).is_ok()
+}
+
+/// Streams currently open for playback, paused ones included
+pub fn list_applications() -> Result<Vec<AudioApplication>> {
+    Ok(parse_sink_inputs(&pactl(&["list", "sink-inputs"])?))
+}
+
+/// Names of the applications currently producing audio, without duplicates
+pub fn playing_application_names(apps: &[AudioApplication]) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for app in apps.iter().filter(|app| !app.corked) {
+        if !names.contains(&app.name) {
+            names.push(app.name.clone());
+        }
+    }
+    names
+}
+
+pub fn selected_applications() -> Vec<String> {
+    SELECTED_APPLICATIONS
+        .read()
+        .map(|selection| selection.clone())
+        .unwrap_or_default()
+}
+
+/// Set the applications to capture; takes effect at the next recording
+pub fn set_selected_applications(applications: Vec<String>) {
+    let mut selection: Vec<String> = Vec::new();
+    for app in applications {
+        let app = app.trim().to_string();
+        if !app.is_empty() && !selection.iter().any(|s| s.eq_ignore_ascii_case(&app)) {
+            selection.push(app);
+        }
+    }
+    info!("Linux system audio capture applications: {:?}", selection);
+    if let Ok(mut selected) = SELECTED_APPLICATIONS.write() {
+        *selected = selection;
+    }
+}
+
+fn default_sink() -> Result<String> {
+    if let Ok(output) = pactl(&["get-default-sink"]) {
+        let sink = output.trim();
+        if !sink.is_empty() {
+            return Ok(sink.to_string());
+        }
+    }
+    // `get-default-sink` is missing from older pactl versions
+    parse_default_sink(&pactl(&["info"])?).ok_or_else(|| anyhow!("No default sink"))
+}
+
+fn load_module(args: &[&str]) -> Result<u32> {
+    let mut full_args = vec!["load-module"];
+    full_args.extend_from_slice(args);
+    let output = pactl(&full_args)?;
+    output
+        .trim()
+        .parse()
+        .map_err(|_| anyhow!("Unexpected load-module output: {}", output.trim()))
+}
+
+/// Parse `pactl list sink-inputs`
+pub fn parse_sink_inputs(text: &str) -> Vec<AudioApplication> {
+    let mut apps = Vec::new();
+    let mut current: Option<AudioApplication> = None;
+
+    for line in text.lines() {
+        if let Some(index) = line.strip_prefix("Sink Input #") {
+            apps.extend(current.take());
+            current = index.trim().parse().ok().map(|index| AudioApplication {
+                index,
+                name: String::new(),
+                binary: None,
+                pid: None,
+                sink: None,
+                corked: false,
+            });
+            continue;
+        }
+        let Some(app) = current.as_mut() else {
+            continue;
+        };
+
+        let line = line.trim();
+        if let Some(sink) = line.strip_prefix("Sink:") {
+            app.sink = sink.trim().parse().ok();
+        } else if let Some(corked) = line.strip_prefix("Corked:") {
+            app.corked = corked.trim() == "yes";
+        } else if let Some((key, value)) = line.split_once(" = ") {
+            let value = value.trim().trim_matches('"').to_string();
+            match key.trim() {
+                "application.name" => app.name = value,
+                "application.process.binary" => app.binary = Some(value),
+                "application.process.id" => app.pid = value.parse().ok(),
+                _ => {}
+            }
+        }
+    }
+    apps.extend(current);
+
+    // Streams without an application name fall back to the binary
+    for app in &mut apps {
+        if app.name.is_empty() {
+            app.name = app
+                .binary
+                .clone()
+                .unwrap_or_else(|| format!("Stream {}", app.index));
+        }
+    }
+    apps
+}
+
+/// "Default Sink:" line of `pactl info`
+fn parse_default_sink(info_text: &str) -> Option<String> {
+    info_text
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("Default Sink:"))
+        .map(|sink| sink.trim().to_string())
+        .filter(|sink| !sink.is_empty())
+}
+
+/// Server-side setup for one recording; modules are unloaded on drop, which
+/// moves captured applications back to the default sink.
+struct CaptureSession {
+    source: String,
+    selection: Vec<String>,
+    modules: Vec<u32>,
+    moved: Mutex<HashSet<u32>>,
+}
+
+impl CaptureSession {
+    fn new(selection: Vec<String>) -> Result<Self> {
+        let default_sink = default_sink()?;
+        if selection.is_empty() {
+            return Ok(Self {
+                source: format!("{}.monitor", default_sink),
+                selection,
+                modules: Vec::new(),
+                moved: Mutex::new(HashSet::new()),
+            });
+        }
+
+        let mut session = Self {
+            source: format!("{}.monitor", CAPTURE_SINK_NAME),
+            selection,
+            modules: Vec::new(),
+            moved: Mutex::new(HashSet::new()),
+        };
+        session.modules.push(load_module(&[
+            "module-null-sink",
+            &format!("sink_name={}", CAPTURE_SINK_NAME),
+            "sink_properties=device.description=Meetily-Capture",
+        ])?);
+        // Keep the captured applications audible
+        session.modules.push(load_module(&[
+            "module-loopback",
+            &format!("source={}", session.source),
+            &format!("sink={}", default_sink),
+            "latency_msec=30",
+        ])?);
+        Ok(session)
+    }
+
+    /// Move matching applications that aren't captured yet into the capture
+    /// sink. Returns the names of newly captured applications.
+    fn capture_new_applications(&self) -> Vec<String> {
+        if self.selection.is_empty() {
+            return Vec::new();
+        }
+        let apps = match list_applications() {
+            Ok(apps) => apps,
+            Err(e) => {
+                warn!("Failed to list audio applications: {}", e);
+                return Vec::new();
+            }
+        };
+
+        let mut moved = match self.moved.lock() {
+            Ok(moved) => moved,
+            Err(_) => return Vec::new(),
+        };
+        let mut captured = Vec::new();
+        for app in apps.iter().filter(|app| app.matches(&self.selection)) {
+            if moved.contains(&app.index) {
+                continue;
+            }
+            let index = app.index.to_string();
+            match pactl(&["move-sink-input", &index, CAPTURE_SINK_NAME]) {
+                Ok(_) => {
+                    moved.insert(app.index);
+                    captured.push(app.name.clone());
+                }
+                Err(e) => warn!("Failed to capture {} (stream {}): {}", app.name, app.index, e),
+            }
+        }
+        captured
+    }
+}
+
+impl Drop for CaptureSession {
+    fn drop(&mut self) {
+        for module in self.modules.iter().rev() {
+            if let Err(e) = pactl(&["unload-module", &module.to_string()]) {
+                warn!("Failed to unload PulseAudio module {}: {}", module, e);
+            }
+        }
+    }
+}
+
+/// Stops the re-scan task with the capture task, including on abort
+struct AbortOnDrop(tokio::task::JoinHandle<()>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Start capturing system audio (all of it, or the selected applications)
+/// into `capture` until recording stops.
+pub async fn spawn(
+    name: String,
+    capture: AudioCapture,
+    state: Arc<RecordingState>,
+) -> Result<tokio::task::JoinHandle<()>> {
+    let selection = selected_applications();
+    let session = tokio::task::spawn_blocking(move || CaptureSession::new(selection))
+        .await
+        .map_err(|e| anyhow!("PulseAudio setup task failed: {}", e))??;
+    let session = Arc::new(session);
+
+    let mut child = tokio::process::Command::new("parec")
+        .arg(format!("--device={}", session.source))
+        .arg("--format=float32le")
+        .arg(format!("--rate={}", CAPTURE_SAMPLE_RATE))
+        .arg("--channels=1")
+        .arg("--raw")
+        .stdout(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| anyhow!("Failed to start parec: {}", e))?;
+    let mut stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| anyhow!("parec has no output"))?;
+
+    info!(
+        "🔊 PulseAudio capture {} started from {} (applications: {:?})",
+        name, session.source, session.selection
+    );
+
+    Ok(tokio::spawn(async move {
+        // Owned by the task so aborting it stops parec and unloads the modules
+        let _child = child;
+        let session_for_scan = Arc::clone(&session);
+        let _rescan = AbortOnDrop(tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(RESCAN_INTERVAL);
+            loop {
+                ticker.tick().await;
+                let session = Arc::clone(&session_for_scan);
+                if let Ok(captured) =
+                    tokio::task::spawn_blocking(move || session.capture_new_applications()).await
+                {
+                    if !captured.is_empty() {
+                        info!("🔊 Capturing audio from {:?}", captured);
+                    }
+                }
+            }
+        }));
+
+        let mut bytes = vec![0u8; CAPTURE_SAMPLE_RATE as usize * BLOCK_MS / 1000 * 4];
+        while state.is_recording() {
+            if let Err(e) = stdout.read_exact(&mut bytes).await {
+                warn!("PulseAudio capture {} ended: {}", name, e);
+                break;
+            }
+            if state.is_paused() {
+                continue;
+            }
+            let block: Vec<f32> = bytes
+                .chunks_exact(4)
+                .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .collect();
+            capture.process_audio_data(&block);
+        }
+
+        info!("🔊 PulseAudio capture {} stopped", name);
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SINK_INPUTS: &str = "Sink Input #42
+\tDriver: protocol-native.c
+\tOwner Module: 10
+\tClient: 57
+\tSink: 0
+\tSample Specification: float32le 2ch 48000Hz
+\tCorked: no
+\tMute: no
+\tProperties:
+\t\tmedia.name = \"Playback\"
+\t\tapplication.name = \"Firefox\"
+\t\tapplication.process.id = \"1234\"
+\t\tapplication.process.binary = \"firefox\"
+
+Sink Input #43
+\tDriver: protocol-native.c
+\tSink: 1
+\tCorked: yes
+\tProperties:
+\t\tapplication.name = \"ZOOM VoiceEngine\"
+\t\tapplication.process.binary = \"zoom\"
+
+Sink Input #44
+\tSink: 0
+\tCorked: no
+\tProperties:
+\t\tapplication.process.binary = \"mpv\"
+";
+
+    #[test]
+    fn test_parse_sink_inputs() {
+        let apps = parse_sink_inputs(SINK_INPUTS);
+        assert_eq!(apps.len(), 3);
+
+        assert_eq!(apps[0].index, 42);
+        assert_eq!(apps[0].name, "Firefox");
+        assert_eq!(apps[0].binary.as_deref(), Some("firefox"));
+        assert_eq!(apps[0].pid, Some(1234));
+        assert_eq!(apps[0].sink, Some(0));
+        assert!(!apps[0].corked);
+
+        assert_eq!(apps[1].name, "ZOOM VoiceEngine");
+        assert!(apps[1].corked);
+        // No application.name: named after the binary
+        assert_eq!(apps[2].name, "mpv");
+
+        assert!(parse_sink_inputs("").is_empty());
+    }
+
+    #[test]
+    fn test_playing_application_names() {
+        let mut apps = parse_sink_inputs(SINK_INPUTS);
+        apps.push(AudioApplication { index: 45, ..apps[0].clone() });
+        assert_eq!(playing_application_names(&apps), vec!["Firefox", "mpv"]);
+    }
+
+    #[test]
+    fn test_selection_matches_name_or_binary() {
+        let apps = parse_sink_inputs(SINK_INPUTS);
+        let selection = vec!["zoom".to_string(), "FIREFOX".to_string()];
+        let matched: Vec<u32> = apps
+            .iter()
+            .filter(|app| app.matches(&selection))
+            .map(|app| app.index)
+            .collect();
+        assert_eq!(matched, vec![42, 43]);
+    }
+
+    #[test]
+    fn test_parse_default_sink() {
+        let info = "Server Name: PulseAudio (on PipeWire 1.0.5)\nDefault Sink: alsa_output.pci-0000_00_1f.3.analog-stereo\nDefault Source: alsa_input.mic\n";
+        assert_eq!(
+            parse_default_sink(info).as_deref(),
+            Some("alsa_output.pci-0000_00_1f.3.analog-stereo")
+        );
+        assert_eq!(parse_default_sink("Server Name: x\n"), None);
+    }
+}
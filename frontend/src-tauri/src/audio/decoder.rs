@@ -0,0 +1,214 @@
+// audio/decoder.rs
+//
+// Decodes audio/video files from disk into mono f32 samples.
+// Uses symphonia in-process and falls back to the FFmpeg sidecar for
+// containers/codecs symphonia cannot handle.
+
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{DecoderOptions, CODEC_TYPE_NULL};
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+use super::audio_processing::audio_to_mono;
+use super::ffmpeg::find_ffmpeg_path;
+
+/// Sample rate requested from FFmpeg when falling back (matches VAD/Whisper input)
+const FFMPEG_FALLBACK_SAMPLE_RATE: u32 = 16000;
+
+/// Decoded mono audio
+#[derive(Debug, Clone)]
+pub struct DecodedAudio {
+    pub samples: Vec<f32>,
+    pub sample_rate: u32,
+}
+
+impl DecodedAudio {
+    /// Duration of the decoded audio in seconds
+    pub fn duration_seconds(&self) -> f64 {
+        if self.sample_rate == 0 {
+            return 0.0;
+        }
+        self.samples.len() as f64 / self.sample_rate as f64
+    }
+}
+
+/// Decode an audio or video file to mono f32 samples.
+///
+/// Tries symphonia first (WAV, MP3, AAC/M4A, MP4 audio tracks) and falls back
+/// to FFmpeg if symphonia cannot probe or decode the file.
+pub fn decode_audio_file(path: &Path) -> Result<DecodedAudio> {
+    if !path.exists() {
+        return Err(anyhow!("File not found: {}", path.display()));
+    }
+
+    match decode_with_symphonia(path) {
+        Ok(decoded) => {
+            info!(
+                "Decoded {} with symphonia: {:.1}s at {}Hz",
+                path.display(),
+                decoded.duration_seconds(),
+                decoded.sample_rate
+            );
+            Ok(decoded)
+        }
+        Err(e) => {
+            warn!(
+                "Symphonia could not decode {} ({}), falling back to FFmpeg",
+                path.display(),
+                e
+            );
+            let decoded = decode_with_ffmpeg(path)?;
+            info!(
+                "Decoded {} with FFmpeg: {:.1}s at {}Hz",
+                path.display(),
+                decoded.duration_seconds(),
+                decoded.sample_rate
+            );
+            Ok(decoded)
+        }
+    }
+}
+
+fn decode_with_symphonia(path: &Path) -> Result<DecodedAudio> {
+    let file = std::fs::File::open(path)?;
+    let mss = MediaSourceStream::new(Box::new(file), Default::default());
+
+    let mut hint = Hint::new();
+    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+        hint.with_extension(ext);
+    }
+
+    let probed = symphonia::default::get_probe().format(
+        &hint,
+        mss,
+        &FormatOptions::default(),
+        &MetadataOptions::default(),
+    )?;
+    let mut format = probed.format;
+
+    // Video files carry several tracks - pick the first decodable audio track
+    let track = format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL && t.codec_params.sample_rate.is_some())
+        .ok_or_else(|| anyhow!("No audio track found"))?;
+    let track_id = track.id;
+    let sample_rate = track
+        .codec_params
+        .sample_rate
+        .ok_or_else(|| anyhow!("Audio track has no sample rate"))?;
+
+    let mut decoder =
+        symphonia::default::get_codecs().make(&track.codec_params, &DecoderOptions::default())?;
+
+    let mut samples: Vec<f32> = Vec::new();
+
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                break;
+            }
+            Err(SymphoniaError::ResetRequired) => break,
+            Err(e) => return Err(e.into()),
+        };
+
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        match decoder.decode(&packet) {
+            Ok(decoded) => {
+                let spec = *decoded.spec();
+                let channels = spec.channels.count().max(1) as u16;
+
+                let mut sample_buf = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+                sample_buf.copy_interleaved_ref(decoded);
+
+                if channels == 1 {
+                    samples.extend_from_slice(sample_buf.samples());
+                } else {
+                    samples.extend(audio_to_mono(sample_buf.samples(), channels));
+                }
+            }
+            // Corrupt packets are skipped, the rest of the stream is still usable
+            Err(SymphoniaError::DecodeError(e)) => {
+                warn!("Skipping undecodable packet: {}", e);
+                continue;
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    if samples.is_empty() {
+        return Err(anyhow!("No audio samples decoded"));
+    }
+
+    Ok(DecodedAudio {
+        samples,
+        sample_rate,
+    })
+}
+
+fn decode_with_ffmpeg(path: &Path) -> Result<DecodedAudio> {
+    let ffmpeg_path = find_ffmpeg_path().ok_or_else(|| {
+        anyhow!("FFmpeg not found. Please install FFmpeg to import this file format.")
+    })?;
+
+    let mut command = Command::new(ffmpeg_path);
+    command
+        .args([
+            "-nostdin",
+            "-i",
+            path.to_str()
+                .ok_or_else(|| anyhow!("Invalid file path: {}", path.display()))?,
+            "-vn", // Drop video streams
+            "-f",
+            "f32le",
+            "-ac",
+            "1",
+            "-ar",
+            &FFMPEG_FALLBACK_SAMPLE_RATE.to_string(),
+            "pipe:1",
+        ])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    // Hide console window on Windows to prevent CMD popup during import
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let output = command.output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("FFmpeg decode failed: {}", stderr.trim()));
+    }
+
+    let samples: Vec<f32> = output
+        .stdout
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect();
+
+    if samples.is_empty() {
+        return Err(anyhow!("No audio samples decoded"));
+    }
+
+    Ok(DecodedAudio {
+        samples,
+        sample_rate: FFMPEG_FALLBACK_SAMPLE_RATE,
+    })
+}
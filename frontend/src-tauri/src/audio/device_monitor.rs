@@ -113,6 +113,13 @@ impl AudioDeviceMonitor {
 
         let mut monitored_devices = Vec::new();
 
+        // File devices can't disconnect
+        let is_live = |device: &Arc<AudioDevice>| {
+            !crate::audio::capture::file_source::is_file_device(&device.name)
+        };
+        let microphone = microphone.filter(is_live);
+        let system_audio = system_audio.filter(is_live);
+
         if let Some(mic) = microphone {
             monitored_devices.push(MonitoredDevice::new(
                 mic.name.clone(),
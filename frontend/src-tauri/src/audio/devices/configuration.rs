@@ -68,6 +68,11 @@ impl AudioDevice {
             return Err(anyhow!("Device name cannot be empty"));
         }
 
+        // Virtual file devices ("file:<path>") are taken as-is
+        if crate::audio::capture::file_source::is_file_device(name) {
+            return Ok(AudioDevice::new(name.trim().to_string(), DeviceType::Input));
+        }
+
         let (name, device_type) = if name.to_lowercase().ends_with("(input)") {
             (
                 name.trim_end_matches("(input)").trim().to_string(),
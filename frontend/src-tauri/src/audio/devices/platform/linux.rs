@@ -14,6 +14,15 @@ pub fn configure_linux_audio(host: &cpal::Host) -> Result<Vec<AudioDevice>> {
         }
     }
 
+    // Sound server capture (PulseAudio or PipeWire), which can also record
+    // selected applications only
+    if crate::audio::capture::pulse::is_available() {
+        devices.push(AudioDevice::new(
+            crate::audio::capture::pulse::SYSTEM_DEVICE_NAME.to_string(),
+            DeviceType::Output,
+        ));
+    }
+
     // Add PulseAudio monitor sources for system audio
     if let Ok(pulse_host) = cpal::host_from_id(cpal::HostId::Alsa) {
         for device in pulse_host.input_devices()? {
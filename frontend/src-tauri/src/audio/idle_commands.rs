@@ -0,0 +1,29 @@
+// Commands for the idle recording policy (see idle_detector.rs)
+
+use super::idle_detector::{IdlePolicy, IdleStatus};
+use log::{error as log_error, info as log_info};
+
+#[tauri::command]
+pub async fn get_idle_policy() -> Result<IdlePolicy, String> {
+    Ok(super::idle_detector::policy().await)
+}
+
+/// Save the idle policy; a running recording uses it from its next check
+#[tauri::command]
+pub async fn set_idle_policy(policy: IdlePolicy) -> Result<(), String> {
+    log_info!(
+        "set_idle_policy called: enabled={}, idle_minutes={}, trim_silence={}",
+        policy.enabled,
+        policy.idle_minutes,
+        policy.trim_silence
+    );
+    super::idle_detector::save_policy(policy).await.map_err(|e| {
+        log_error!("Failed to save idle policy: {}", e);
+        format!("Failed to save idle policy: {}", e)
+    })
+}
+
+#[tauri::command]
+pub async fn get_idle_status() -> Result<IdleStatus, String> {
+    Ok(super::idle_detector::status().await)
+}
@@ -0,0 +1,525 @@
+// Idle Recording Detection
+//
+// Watches a running recording for activity: a window counts as active when
+// any VAD of the pipeline is in speech or the recorded level is above the
+// policy's RMS threshold (same RMS as `AudioLevelMonitor`). After
+// `idle_minutes` without activity the user is warned, the recording is paused
+// after a grace period, and finalized once it has stayed paused for
+// `pause_grace_secs`. Speech coming back or the user resuming resets it.
+//
+// The policy also controls trimming long silence before the first and after
+// the last transcribed speech from the saved `audio.mp4` (see
+// `RecordingSaver::stop_and_save`).
+
+use super::level_monitor::rms_level;
+use crate::notifications::commands::NotificationManagerState;
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+/// Shortest and longest time without speech before a recording counts as idle
+pub const MIN_IDLE_MINUTES: u64 = 1;
+pub const MAX_IDLE_MINUTES: u64 = 240;
+
+/// How often the idle watcher checks the recording
+const WATCH_INTERVAL: Duration = Duration::from_secs(5);
+
+static POLICY: Lazy<Mutex<Option<IdlePolicy>>> = Lazy::new(|| Mutex::new(None));
+static CLOCK: Lazy<Mutex<SilenceClock>> = Lazy::new(|| Mutex::new(SilenceClock::default()));
+static STAGE: Mutex<Option<IdleStage>> = Mutex::new(None);
+// Fast checks for the audio pipeline before it takes the clock lock
+static TRACKING: AtomicBool = AtomicBool::new(false);
+static RMS_THRESHOLD_BITS: AtomicU32 = AtomicU32::new(0);
+// Bumped for every recording so a watcher from an earlier one exits
+static SESSION: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IdlePolicy {
+    /// Warn, pause and stop recordings that have gone idle
+    pub enabled: bool,
+    pub idle_minutes: u64,
+    /// Time between the warning and the automatic pause
+    pub warning_grace_secs: u64,
+    /// Time the recording stays paused before it is finalized
+    pub pause_grace_secs: u64,
+    /// RMS level (0.0 to 1.0) above which audio counts as activity
+    pub rms_threshold: f32,
+    /// Cut long silence at the start and end of the saved audio
+    pub trim_silence: bool,
+    /// Only silence at least this long is cut
+    pub trim_min_silence_secs: f64,
+    /// Silence kept around the first and last speech
+    pub trim_padding_secs: f64,
+}
+
+impl Default for IdlePolicy {
+    fn default() -> Self {
+        Self {
+            // Opt-in: existing recordings keep running until stopped by hand
+            enabled: false,
+            idle_minutes: 15,
+            warning_grace_secs: 60,
+            pause_grace_secs: 120,
+            rms_threshold: 0.01,
+            trim_silence: false,
+            trim_min_silence_secs: 30.0,
+            trim_padding_secs: 2.0,
+        }
+    }
+}
+
+impl IdlePolicy {
+    pub fn validate(&self) -> Result<()> {
+        if !(MIN_IDLE_MINUTES..=MAX_IDLE_MINUTES).contains(&self.idle_minutes) {
+            return Err(anyhow!(
+                "Idle time must be between {} and {} minutes",
+                MIN_IDLE_MINUTES,
+                MAX_IDLE_MINUTES
+            ));
+        }
+        if self.warning_grace_secs > 600 || self.pause_grace_secs > 3600 {
+            return Err(anyhow!("Grace periods are limited to 10 minutes (warning) and 1 hour (pause)"));
+        }
+        if !(0.0..=1.0).contains(&self.rms_threshold) {
+            return Err(anyhow!("RMS threshold must be between 0.0 and 1.0"));
+        }
+        if self.trim_min_silence_secs < 5.0 {
+            return Err(anyhow!("Only silence of at least 5 seconds can be trimmed"));
+        }
+        if !(0.0..=30.0).contains(&self.trim_padding_secs) {
+            return Err(anyhow!("Trim padding must be between 0 and 30 seconds"));
+        }
+        Ok(())
+    }
+
+    fn idle_secs(&self) -> f64 {
+        self.idle_minutes as f64 * 60.0
+    }
+}
+
+/// Where an idle recording is in the warn → pause → stop sequence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IdleStage {
+    Active,
+    Warned,
+    Paused,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IdleAction {
+    Warn,
+    Pause,
+    Finalize,
+    Reset,
+}
+
+/// Next step of the idle sequence. `paused` is whether the recording is
+/// paused right now, `paused_for_secs` how long since the watcher paused it.
+fn next_action(
+    stage: IdleStage,
+    idle_secs: f64,
+    paused: bool,
+    paused_for_secs: f64,
+    policy: &IdlePolicy,
+) -> Option<IdleAction> {
+    let idle_limit = policy.idle_secs();
+    match stage {
+        // A recording the user paused by hand isn't idle
+        IdleStage::Active => (!paused && idle_secs >= idle_limit).then_some(IdleAction::Warn),
+        IdleStage::Warned => {
+            if paused {
+                None
+            } else if idle_secs < idle_limit {
+                Some(IdleAction::Reset)
+            } else if idle_secs >= idle_limit + policy.warning_grace_secs as f64 {
+                Some(IdleAction::Pause)
+            } else {
+                None
+            }
+        }
+        IdleStage::Paused => {
+            if !paused {
+                Some(IdleAction::Reset)
+            } else if paused_for_secs >= policy.pause_grace_secs as f64 {
+                Some(IdleAction::Finalize)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct IdleStatus {
+    pub enabled: bool,
+    /// None when no recording is being watched
+    pub stage: Option<IdleStage>,
+    pub idle_secs: Option<f64>,
+}
+
+/// Silence bookkeeping in samples, so it follows the audio rather than the
+/// wall clock (a paused recording doesn't grow idle)
+#[derive(Debug, Default)]
+pub(crate) struct SilenceClock {
+    sample_rate: u32,
+    samples_seen: u64,
+    last_speech_sample: u64,
+}
+
+impl SilenceClock {
+    pub(crate) fn observe(&mut self, samples: usize, sample_rate: u32, speech: bool) {
+        self.sample_rate = sample_rate;
+        self.samples_seen += samples as u64;
+        if speech {
+            self.last_speech_sample = self.samples_seen;
+        }
+    }
+
+    pub(crate) fn silent_secs(&self) -> f64 {
+        if self.sample_rate == 0 {
+            return 0.0;
+        }
+        (self.samples_seen - self.last_speech_sample) as f64 / self.sample_rate as f64
+    }
+
+    /// Count from now, as if speech just ended
+    pub(crate) fn reset(&mut self) {
+        self.last_speech_sample = self.samples_seen;
+    }
+}
+
+/// Feed a recorded window from the pipeline. `speech` is whether a VAD saw
+/// speech in it. Cheap no-op unless a recording is being watched.
+pub fn observe_window(samples: &[f32], sample_rate: u32, speech: bool) {
+    if !TRACKING.load(Ordering::Relaxed) || samples.is_empty() {
+        return;
+    }
+    let threshold = f32::from_bits(RMS_THRESHOLD_BITS.load(Ordering::Relaxed));
+    let active = speech || rms_level(samples) >= threshold;
+    if let Ok(mut clock) = CLOCK.lock() {
+        clock.observe(samples.len(), sample_rate, active);
+    }
+}
+
+fn idle_secs() -> f64 {
+    CLOCK.lock().map(|clock| clock.silent_secs()).unwrap_or(0.0)
+}
+
+fn reset_clock() {
+    if let Ok(mut clock) = CLOCK.lock() {
+        clock.reset();
+    }
+}
+
+fn set_stage(stage: Option<IdleStage>) {
+    if let Ok(mut current) = STAGE.lock() {
+        *current = stage;
+    }
+}
+
+fn settings_path() -> Result<PathBuf> {
+    let mut path = dirs::config_dir().ok_or_else(|| anyhow!("Could not find config directory"))?;
+    path.push("meetily");
+    path.push("idle_policy.json");
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    Ok(path)
+}
+
+/// Current policy, loaded from disk on first use
+pub async fn policy() -> IdlePolicy {
+    if let Some(policy) = POLICY.lock().ok().and_then(|p| p.clone()) {
+        return policy;
+    }
+
+    let loaded = match load_policy().await {
+        Ok(policy) => policy,
+        Err(e) => {
+            warn!("Failed to load idle policy, using defaults: {}", e);
+            IdlePolicy::default()
+        }
+    };
+    if let Ok(mut current) = POLICY.lock() {
+        *current = Some(loaded.clone());
+    }
+    loaded
+}
+
+async fn load_policy() -> Result<IdlePolicy> {
+    let path = settings_path()?;
+    if !path.exists() {
+        return Ok(IdlePolicy::default());
+    }
+    let content = tokio::fs::read_to_string(&path).await?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Validate and store a new policy. A running recording picks it up on the
+/// watcher's next check.
+pub async fn save_policy(policy: IdlePolicy) -> Result<()> {
+    policy.validate()?;
+    let content = serde_json::to_string_pretty(&policy)?;
+    tokio::fs::write(settings_path()?, content).await?;
+
+    RMS_THRESHOLD_BITS.store(policy.rms_threshold.to_bits(), Ordering::Relaxed);
+    if let Ok(mut current) = POLICY.lock() {
+        *current = Some(policy.clone());
+    }
+    info!(
+        "Saved idle policy: enabled={}, idle_minutes={}, trim_silence={}",
+        policy.enabled, policy.idle_minutes, policy.trim_silence
+    );
+    Ok(())
+}
+
+pub async fn status() -> IdleStatus {
+    let stage = STAGE.lock().ok().and_then(|s| *s);
+    IdleStatus {
+        enabled: policy().await.enabled,
+        stage,
+        idle_secs: stage.map(|_| idle_secs()),
+    }
+}
+
+/// Start watching a new recording (called when recording starts)
+pub async fn start_session<R: Runtime>(app: &AppHandle<R>) {
+    let policy = policy().await;
+    let session = SESSION.fetch_add(1, Ordering::SeqCst) + 1;
+
+    if let Ok(mut clock) = CLOCK.lock() {
+        *clock = SilenceClock::default();
+    }
+    RMS_THRESHOLD_BITS.store(policy.rms_threshold.to_bits(), Ordering::Relaxed);
+    set_stage(Some(IdleStage::Active));
+    TRACKING.store(true, Ordering::SeqCst);
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        watch_recording(app, session).await;
+    });
+}
+
+fn end_session() {
+    TRACKING.store(false, Ordering::SeqCst);
+    set_stage(None);
+}
+
+async fn notify_idle<R: Runtime>(app: &AppHandle<R>, policy: &IdlePolicy) {
+    let Some(manager_state) = app.try_state::<NotificationManagerState<R>>() else {
+        return;
+    };
+    let manager = manager_state.read().await;
+    if let Some(manager) = manager.as_ref() {
+        if let Err(e) = manager
+            .show_recording_idle(policy.idle_minutes, policy.warning_grace_secs)
+            .await
+        {
+            warn!("Failed to show recording idle notification: {}", e);
+        }
+    }
+}
+
+/// Walk an idle recording through warn → pause → stop. Exits when the
+/// recording ends or a newer recording starts.
+async fn watch_recording<R: Runtime>(app: AppHandle<R>, session: u64) {
+    let mut ticker = tokio::time::interval(WATCH_INTERVAL);
+    let mut stage = IdleStage::Active;
+    let mut paused_at: Option<Instant> = None;
+
+    loop {
+        ticker.tick().await;
+
+        if SESSION.load(Ordering::SeqCst) != session {
+            break;
+        }
+        if !crate::audio::recording_commands::is_recording().await {
+            end_session();
+            break;
+        }
+
+        let policy = policy().await;
+        if !policy.enabled {
+            if stage != IdleStage::Active {
+                stage = IdleStage::Active;
+                set_stage(Some(stage));
+                let _ = app.emit("recording-idle-cleared", ());
+            }
+            continue;
+        }
+
+        let idle = idle_secs();
+        let paused = crate::audio::recording_commands::is_recording_paused().await;
+        let paused_for = paused_at.map(|t| t.elapsed().as_secs_f64()).unwrap_or(0.0);
+
+        match next_action(stage, idle, paused, paused_for, &policy) {
+            Some(IdleAction::Warn) => {
+                info!("⏳ Recording idle for {:.0}s, warning before pausing", idle);
+                stage = IdleStage::Warned;
+                notify_idle(&app, &policy).await;
+                let _ = app.emit(
+                    "recording-idle-warning",
+                    serde_json::json!({
+                        "idle_secs": idle,
+                        "pause_in_secs": policy.warning_grace_secs,
+                    }),
+                );
+            }
+            Some(IdleAction::Pause) => {
+                info!("⏸️ Pausing idle recording after {:.0}s without speech", idle);
+                if let Err(e) = crate::audio::recording_commands::pause_recording(app.clone()).await {
+                    warn!("Failed to pause idle recording: {}", e);
+                    continue;
+                }
+                stage = IdleStage::Paused;
+                paused_at = Some(Instant::now());
+                let _ = app.emit(
+                    "recording-idle-paused",
+                    serde_json::json!({ "stop_in_secs": policy.pause_grace_secs }),
+                );
+            }
+            Some(IdleAction::Finalize) => {
+                info!("🛑 Stopping recording that stayed idle");
+                match app.get_webview_window("main") {
+                    Some(window) => {
+                        let _ = window.eval("window.handleRecordingStop()");
+                    }
+                    None => warn!("Cannot stop idle recording: main window not found"),
+                }
+                let _ = app.emit("recording-idle-stopped", ());
+                end_session();
+                break;
+            }
+            Some(IdleAction::Reset) => {
+                info!("Recording active again, idle countdown reset");
+                stage = IdleStage::Active;
+                paused_at = None;
+                reset_clock();
+                let _ = app.emit("recording-idle-cleared", ());
+            }
+            None => {}
+        }
+        set_stage(Some(stage));
+    }
+}
+
+/// Part of the recording to keep, in recording time
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrimPlan {
+    pub start: f64,
+    pub end: f64,
+}
+
+/// Decide how much silence to cut, given where transcribed speech starts and
+/// ends. None when there is nothing long enough to trim.
+pub fn plan_trim(
+    first_speech: f64,
+    last_speech: f64,
+    duration: f64,
+    policy: &IdlePolicy,
+) -> Option<TrimPlan> {
+    if !policy.trim_silence || duration <= 0.0 || last_speech <= first_speech {
+        return None;
+    }
+
+    let start = if first_speech >= policy.trim_min_silence_secs {
+        (first_speech - policy.trim_padding_secs).max(0.0)
+    } else {
+        0.0
+    };
+    let end = if duration - last_speech >= policy.trim_min_silence_secs {
+        (last_speech + policy.trim_padding_secs).min(duration)
+    } else {
+        duration
+    };
+
+    if (start <= 0.0 && end >= duration) || end <= start {
+        return None;
+    }
+    Some(TrimPlan { start, end })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trimming() -> IdlePolicy {
+        IdlePolicy {
+            trim_silence: true,
+            ..IdlePolicy::default()
+        }
+    }
+
+    #[test]
+    fn test_policy_validation() {
+        assert!(IdlePolicy::default().validate().is_ok());
+        assert!(!IdlePolicy::default().enabled);
+
+        let mut policy = IdlePolicy::default();
+        policy.idle_minutes = 0;
+        assert!(policy.validate().is_err());
+
+        let mut policy = IdlePolicy::default();
+        policy.rms_threshold = 1.5;
+        assert!(policy.validate().is_err());
+    }
+
+    #[test]
+    fn test_silence_clock_reset() {
+        let mut clock = SilenceClock::default();
+        clock.observe(32000, 16000, false);
+        assert_eq!(clock.silent_secs(), 2.0);
+        clock.reset();
+        assert_eq!(clock.silent_secs(), 0.0);
+        clock.observe(8000, 16000, false);
+        assert_eq!(clock.silent_secs(), 0.5);
+    }
+
+    #[test]
+    fn test_idle_sequence() {
+        let policy = IdlePolicy::default(); // 15 min, 60s warning, 120s paused
+        let idle = 15.0 * 60.0;
+
+        assert_eq!(next_action(IdleStage::Active, idle - 1.0, false, 0.0, &policy), None);
+        assert_eq!(next_action(IdleStage::Active, idle, false, 0.0, &policy), Some(IdleAction::Warn));
+        // Paused by hand: not idle
+        assert_eq!(next_action(IdleStage::Active, idle, true, 0.0, &policy), None);
+
+        assert_eq!(next_action(IdleStage::Warned, idle + 30.0, false, 0.0, &policy), None);
+        assert_eq!(next_action(IdleStage::Warned, idle + 60.0, false, 0.0, &policy), Some(IdleAction::Pause));
+        assert_eq!(next_action(IdleStage::Warned, 3.0, false, 0.0, &policy), Some(IdleAction::Reset));
+
+        assert_eq!(next_action(IdleStage::Paused, idle, true, 119.0, &policy), None);
+        assert_eq!(next_action(IdleStage::Paused, idle, true, 120.0, &policy), Some(IdleAction::Finalize));
+        // User resumed
+        assert_eq!(next_action(IdleStage::Paused, idle, false, 10.0, &policy), Some(IdleAction::Reset));
+    }
+
+    #[test]
+    fn test_plan_trim() {
+        let policy = trimming();
+
+        // 40 minutes of silence after a 20 minute call
+        assert_eq!(
+            plan_trim(5.0, 1200.0, 3600.0, &policy),
+            Some(TrimPlan { start: 0.0, end: 1202.0 })
+        );
+        // Long silence before the first words
+        assert_eq!(
+            plan_trim(90.0, 600.0, 610.0, &policy),
+            Some(TrimPlan { start: 88.0, end: 610.0 })
+        );
+        // Short silences are kept
+        assert_eq!(plan_trim(10.0, 590.0, 600.0, &policy), None);
+        // Disabled
+        assert_eq!(plan_trim(90.0, 600.0, 3600.0, &IdlePolicy::default()), None);
+    }
+}
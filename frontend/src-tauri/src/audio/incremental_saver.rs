@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use anyhow::{Result, anyhow};
 use log::{info, warn, error};
 use super::encode::encode_single_audio;
@@ -149,84 +149,140 @@ impl IncrementalAudioSaver {
     async fn merge_checkpoints(&self, output: &PathBuf) -> Result<()> {
         info!("Merging {} checkpoints into final audio file...", self.checkpoint_count);
 
-        // Create concat list file for FFmpeg
-        let list_file = self.checkpoints_dir.join("concat_list.txt");
-        let mut list_content = String::new();
+        let checkpoints: Vec<PathBuf> = (0..self.checkpoint_count)
+            .map(|i| self.checkpoints_dir.join(format!("audio_chunk_{:03}.mp4", i)))
+            .collect();
 
-        for i in 0..self.checkpoint_count {
-            let checkpoint_path = self.checkpoints_dir
-                .join(format!("audio_chunk_{:03}.mp4", i));
+        merge_audio_files(&checkpoints, &self.checkpoints_dir, output)
+    }
 
-            // Verify checkpoint exists
-            if !checkpoint_path.exists() {
-                return Err(anyhow!("Checkpoint file missing: {}", checkpoint_path.display()));
-            }
+    /// Get the meeting folder path
+    pub fn get_meeting_folder(&self) -> &PathBuf {
+        &self.meeting_folder
+    }
 
-            // Use absolute path for FFmpeg (required for safe mode)
-            let abs_path = checkpoint_path.canonicalize()?;
-            list_content.push_str(&format!("file '{}'\n", abs_path.display()));
-        }
+    /// Get current checkpoint count
+    pub fn get_checkpoint_count(&self) -> u32 {
+        self.checkpoint_count
+    }
+}
 
-        std::fs::write(&list_file, list_content)?;
-
-        #[cfg(target_os = "macos")]
-        let ffmpeg_path = find_ffmpeg_path()
-            .ok_or_else(|| anyhow!("FFmpeg not found. Please install FFmpeg to finalize recordings."))?;
-        
-        #[cfg(not(target_os = "macos"))]
-        let ffmpeg_path = "ffmpeg";  // Assume ffmpeg is in PATH on Windows/Linux
-        info!("Using FFmpeg at: {:?}", ffmpeg_path);
-
-        // Run FFmpeg concat command
-        // Using concat demuxer with copy codec for fast merging (no re-encoding)
-        
-        let mut command = std::process::Command::new(ffmpeg_path);
-        
-        command.args(&[
-            "-f", "concat",          // Use concat demuxer
-            "-safe", "0",            // Allow absolute paths
-            "-i", list_file.to_str().unwrap(),
-            "-c", "copy",            // Copy codec - no re-encoding!
-            "-y",                    // Overwrite output file
-            output.to_str().unwrap()
-        ]);
-
-        // Hide console window on Windows to prevent CMD popup during finalization
-        #[cfg(target_os = "windows")]
-        {
-            use std::os::windows::process::CommandExt;
-            const CREATE_NO_WINDOW: u32 = 0x08000000;
-            command.creation_flags(CREATE_NO_WINDOW);
+/// Merge checkpoint files (in order) into `output` using FFmpeg concat.
+/// The concat list is written to `work_dir`.
+/// Also used by crash recovery to merge checkpoints left behind by an interrupted recording.
+pub fn merge_audio_files(files: &[PathBuf], work_dir: &Path, output: &Path) -> Result<()> {
+    // Create concat list file for FFmpeg
+    let list_file = work_dir.join("concat_list.txt");
+    let mut list_content = String::new();
+
+    for checkpoint_path in files {
+        // Verify checkpoint exists
+        if !checkpoint_path.exists() {
+            return Err(anyhow!("Checkpoint file missing: {}", checkpoint_path.display()));
         }
 
-        let ffmpeg_output = command.output()?;
+        // Use absolute path for FFmpeg (required for safe mode)
+        let abs_path = checkpoint_path.canonicalize()?;
+        list_content.push_str(&format!("file '{}'\n", abs_path.display()));
+    }
 
-        if !ffmpeg_output.status.success() {
-            let stderr = String::from_utf8_lossy(&ffmpeg_output.stderr);
-            error!("FFmpeg merge failed: {}", stderr);
-            return Err(anyhow!("FFmpeg concat failed: {}", stderr));
-        }
+    std::fs::write(&list_file, list_content)?;
+
+    #[cfg(target_os = "macos")]
+    let ffmpeg_path = find_ffmpeg_path()
+        .ok_or_else(|| anyhow!("FFmpeg not found. Please install FFmpeg to finalize recordings."))?;
+    
+    #[cfg(not(target_os = "macos"))]
+    let ffmpeg_path = "ffmpeg";  // Assume ffmpeg is in PATH on Windows/Linux
+    info!("Using FFmpeg at: {:?}", ffmpeg_path);
+
+    // Run FFmpeg concat command
+    // Using concat demuxer with copy codec for fast merging (no re-encoding)
+    
+    let mut command = std::process::Command::new(ffmpeg_path);
+    
+    command.args(&[
+        "-f", "concat",          // Use concat demuxer
+        "-safe", "0",            // Allow absolute paths
+        "-i", list_file.to_str().unwrap(),
+        "-c", "copy",            // Copy codec - no re-encoding!
+        "-y",                    // Overwrite output file
+        output.to_str().unwrap()
+    ]);
+
+    // Hide console window on Windows to prevent CMD popup during finalization
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
 
-        // Verify output file was created
-        if !output.exists() {
-            return Err(anyhow!("Merged audio file was not created: {}", output.display()));
-        }
+    let ffmpeg_output = command.output()?;
 
-        info!("✅ Successfully merged {} checkpoints → {}",
-              self.checkpoint_count, output.display());
+    if !ffmpeg_output.status.success() {
+        let stderr = String::from_utf8_lossy(&ffmpeg_output.stderr);
+        error!("FFmpeg merge failed: {}", stderr);
+        return Err(anyhow!("FFmpeg concat failed: {}", stderr));
+    }
 
-        Ok(())
+    // Verify output file was created
+    if !output.exists() {
+        return Err(anyhow!("Merged audio file was not created: {}", output.display()));
     }
 
-    /// Get the meeting folder path
-    pub fn get_meeting_folder(&self) -> &PathBuf {
-        &self.meeting_folder
+    info!("✅ Successfully merged {} checkpoints → {}",
+          files.len(), output.display());
+
+    Ok(())
+}
+
+/// Cut `audio` down to the part between `start` and `end` seconds, in place.
+/// Stream copy, no re-encoding; used to trim silence from a finished recording.
+pub fn trim_audio_file(audio: &Path, start: f64, end: f64) -> Result<()> {
+    if end <= start {
+        return Err(anyhow!("Invalid trim range {:.2}s - {:.2}s", start, end));
+    }
+    let trimmed = audio.with_extension("trimmed.mp4");
+
+    #[cfg(target_os = "macos")]
+    let ffmpeg_path = find_ffmpeg_path()
+        .ok_or_else(|| anyhow!("FFmpeg not found. Please install FFmpeg to trim recordings."))?;
+
+    #[cfg(not(target_os = "macos"))]
+    let ffmpeg_path = "ffmpeg";  // Assume ffmpeg is in PATH on Windows/Linux
+
+    let mut command = std::process::Command::new(ffmpeg_path);
+
+    command.args(&[
+        "-ss", &format!("{:.3}", start),       // Seek before opening input (fast)
+        "-i", audio.to_str().unwrap(),
+        "-t", &format!("{:.3}", end - start),  // Length to keep
+        "-c", "copy",
+        "-y",
+        trimmed.to_str().unwrap()
+    ]);
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        command.creation_flags(CREATE_NO_WINDOW);
     }
 
-    /// Get current checkpoint count
-    pub fn get_checkpoint_count(&self) -> u32 {
-        self.checkpoint_count
+    let ffmpeg_output = command.output()?;
+
+    if !ffmpeg_output.status.success() || !trimmed.exists() {
+        let stderr = String::from_utf8_lossy(&ffmpeg_output.stderr);
+        error!("FFmpeg trim failed: {}", stderr);
+        let _ = std::fs::remove_file(&trimmed);
+        return Err(anyhow!("FFmpeg trim failed: {}", stderr));
     }
+
+    std::fs::rename(&trimmed, audio)?;
+    info!("✂️ Trimmed {} to {:.2}s - {:.2}s", audio.display(), start, end);
+
+    Ok(())
 }
 
 #[cfg(test)]
@@ -281,6 +281,14 @@ impl AudioLevelMonitor {
     }
 }
 
+/// RMS level of mono samples (0.0 for no samples)
+pub fn rms_level(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    (samples.iter().map(|&x| x * x).sum::<f32>() / samples.len() as f32).sqrt()
+}
+
 /// Process audio data and calculate levels
 fn process_audio_levels(
     data: &[f32],
@@ -301,11 +309,7 @@ fn process_audio_levels(
     };
 
     // Calculate RMS level
-    let rms = if !mono_data.is_empty() {
-        (mono_data.iter().map(|&x| x * x).sum::<f32>() / mono_data.len() as f32).sqrt()
-    } else {
-        0.0
-    };
+    let rms = rms_level(&mono_data);
 
     // Calculate peak level
     let peak = mono_data.iter().map(|&x| x.abs()).fold(0.0, f32::max);
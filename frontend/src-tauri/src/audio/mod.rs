@@ -1,5 +1,6 @@
 // src/audio/mod.rs
 pub mod audio_processing;
+pub mod decoder;
 pub mod encode;
 pub mod ffmpeg;
 pub mod vad;
@@ -24,6 +25,8 @@ pub mod recording_preferences;
 pub mod recording_saver;
 pub mod incremental_saver;  // NEW: Incremental audio saving with checkpoints
 pub mod level_monitor;
+pub mod idle_detector;  // Idle recording warn/pause/stop and silence trimming
+pub mod idle_commands;
 pub mod simple_level_monitor;
 pub mod buffer_pool;
 pub mod post_processor;
@@ -63,11 +66,12 @@ pub use system_audio_commands::{
     start_system_audio_capture_command, list_system_audio_devices_command,
     check_system_audio_permissions_command, start_system_audio_monitoring,
     stop_system_audio_monitoring, get_system_audio_monitoring_status,
-    init_system_audio_state
+    list_linux_audio_applications, set_linux_capture_applications,
+    get_linux_capture_applications, init_system_audio_state
 };
 
 // Export new simplified components
-pub use recording_state::{RecordingState, AudioChunk, ProcessedAudioChunk, AudioError, DeviceType as RecordingDeviceType};
+pub use recording_state::{RecordingState, AudioChunk, ProcessedAudioChunk, AudioError, DeviceType as RecordingDeviceType, TranscriptChannel};
 pub use pipeline::{AudioPipelineManager};
 pub use stream::{AudioStreamManager};
 pub use recording_manager::{RecordingManager};
@@ -80,6 +84,7 @@ pub use recording_preferences::{
 };
 pub use recording_saver::RecordingSaver;
 pub use level_monitor::{AudioLevelMonitor, AudioLevelData, AudioLevelUpdate};
+pub use idle_commands::{get_idle_policy, set_idle_policy, get_idle_status};
 pub use buffer_pool::{AudioBufferPool, PooledBuffer};
 pub use post_processor::{PostProcessor, PostProcessRequest, PostProcessResponse};
 pub use hardware_detector::{HardwareProfile, AdaptiveWhisperConfig, PerformanceTier, GpuType};
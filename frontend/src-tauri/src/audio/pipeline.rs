@@ -9,9 +9,9 @@ use super::batch_processor::AudioMetricsBatcher;
 use rubato::{Resampler, SincFixedIn, SincInterpolationParameters, SincInterpolationType, WindowFunction};
 
 use super::devices::AudioDevice;
-use super::recording_state::{AudioChunk, AudioError, RecordingState, DeviceType};
+use super::recording_state::{AudioChunk, AudioError, RecordingState, DeviceType, TranscriptChannel};
 use super::audio_processing::{audio_to_mono, LoudnessNormalizer, NoiseSuppressionProcessor, HighPassFilter};
-use super::vad::{ContinuousVadProcessor};
+use super::vad::{ContinuousVadProcessor, SpeechSegment};
 use super::recording_commands::RecordingMode;
 
 /// Ring buffer for synchronized audio mixing
@@ -66,7 +66,7 @@ impl AudioMixerRingBuffer {
                 }
                 // Microphone samples are ignored
             }
-            RecordingMode::Mixed => {
+            RecordingMode::Mixed | RecordingMode::PerChannel => {
                 // Accept both types of samples
                 match device_type {
                     DeviceType::Microphone => self.mic_buffer.extend(samples),
@@ -115,7 +115,7 @@ impl AudioMixerRingBuffer {
             RecordingMode::SystemAudioOnly => {
                 self.system_buffer.len() >= self.window_size_samples
             }
-            RecordingMode::Mixed => {
+            RecordingMode::Mixed | RecordingMode::PerChannel => {
                 self.mic_buffer.len() >= self.window_size_samples ||
                 self.system_buffer.len() >= self.window_size_samples
             }
@@ -156,7 +156,7 @@ impl AudioMixerRingBuffer {
                 let sys_window = Self::extract_single_window(&mut self.system_buffer, window_size);
                 Some((vec![], sys_window))
             }
-            RecordingMode::Mixed => {
+            RecordingMode::Mixed | RecordingMode::PerChannel => {
                 let mic_window = Self::extract_single_window(&mut self.mic_buffer, window_size);
                 let sys_window = Self::extract_single_window(&mut self.system_buffer, window_size);
                 Some((mic_window, sys_window))
@@ -213,6 +213,51 @@ impl ProfessionalAudioMixer {
     }
 }
 
+/// Orders per-channel VAD segments by start time.
+///
+/// The mic and system VADs finish segments independently, so a long remote
+/// utterance can complete after a short local one that started later. A
+/// segment is held until neither channel has speech in progress that started
+/// before it.
+#[derive(Default)]
+struct ChannelInterleaver {
+    pending: Vec<(TranscriptChannel, SpeechSegment)>,
+}
+
+impl ChannelInterleaver {
+    fn push(&mut self, channel: TranscriptChannel, segment: SpeechSegment) {
+        self.pending.push((channel, segment));
+    }
+
+    /// Take the segments that can be emitted, in start-time order.
+    /// `in_progress_starts` are the start times (ms) of speech still being
+    /// accumulated on either channel.
+    fn drain_ready(&mut self, in_progress_starts: &[Option<f64>]) -> Vec<(TranscriptChannel, SpeechSegment)> {
+        let watermark = in_progress_starts
+            .iter()
+            .flatten()
+            .copied()
+            .fold(f64::INFINITY, f64::min);
+
+        self.pending.sort_by(|a, b| {
+            a.1.start_timestamp_ms
+                .partial_cmp(&b.1.start_timestamp_ms)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let ready = self
+            .pending
+            .iter()
+            .take_while(|(_, segment)| segment.start_timestamp_ms <= watermark)
+            .count();
+        self.pending.drain(..ready).collect()
+    }
+
+    /// Take everything (end of recording)
+    fn drain_all(&mut self) -> Vec<(TranscriptChannel, SpeechSegment)> {
+        self.drain_ready(&[])
+    }
+}
+
 /// Simplified audio capture without broadcast channels
 #[derive(Clone)]
 pub struct AudioCapture {
@@ -637,6 +682,7 @@ impl AudioCapture {
             timestamp,
             chunk_id,
             device_type: self.device_type.clone(),
+            channel: None,
         };
 
         // NOTE: Raw audio is NOT sent to recording saver to prevent echo
@@ -720,6 +766,10 @@ pub struct AudioPipeline {
     recording_sender_for_mixed: Option<mpsc::UnboundedSender<AudioChunk>>,
     // Recording mode determines mixing behavior
     recording_mode: RecordingMode,
+    // PerChannel mode: one VAD per source, merged back into start-time order
+    mic_vad_processor: Option<ContinuousVadProcessor>,
+    system_vad_processor: Option<ContinuousVadProcessor>,
+    channel_interleaver: ChannelInterleaver,
 }
 
 impl AudioPipeline {
@@ -764,6 +814,25 @@ impl AudioPipeline {
             }
         };
 
+        // Per-channel transcription needs a VAD session for each source
+        let (mic_vad_processor, system_vad_processor) = if recording_mode == RecordingMode::PerChannel {
+            match (
+                ContinuousVadProcessor::new(sample_rate, redemption_time),
+                ContinuousVadProcessor::new(sample_rate, redemption_time),
+            ) {
+                (Ok(mic), Ok(system)) => {
+                    info!("Per-channel transcription: separate VAD for microphone and system audio");
+                    (Some(mic), Some(system))
+                }
+                (Err(e), _) | (_, Err(e)) => {
+                    error!("Failed to create per-channel VAD processors: {}", e);
+                    panic!("VAD processor creation failed: {}", e);
+                }
+            }
+        } else {
+            (None, None)
+        };
+
         // Initialize professional audio mixing components
         let ring_buffer = AudioMixerRingBuffer::new(sample_rate, recording_mode);
         let mixer = ProfessionalAudioMixer::new(sample_rate);
@@ -788,6 +857,9 @@ impl AudioPipeline {
             mixer,
             recording_sender_for_mixed: None,  // Will be set by manager
             recording_mode,
+            mic_vad_processor,
+            system_vad_processor,
+            channel_interleaver: ChannelInterleaver::default(),
         }
     }
 
@@ -851,7 +923,7 @@ impl AudioPipeline {
                         RecordingMode::SystemAudioOnly => {
                             matches!(chunk.device_type, DeviceType::System)
                         }
-                        RecordingMode::Mixed => {
+                        RecordingMode::Mixed | RecordingMode::PerChannel => {
                             true // Process both types in mixed mode
                         }
                     };
@@ -872,6 +944,14 @@ impl AudioPipeline {
                     // STEP 3: Process audio in fixed windows when streams have sufficient data
                     while self.ring_buffer.can_mix() {
                         if let Some((mic_window, sys_window)) = self.ring_buffer.extract_window() {
+                            // Lets the auto-recorder notice when the call has gone quiet
+                            crate::auto_recorder::observe_system_audio(&sys_window, self.sample_rate);
+
+                            // Per-channel mode transcribes each source before they are mixed
+                            if self.recording_mode == RecordingMode::PerChannel {
+                                self.process_channel_windows(&mic_window, &sys_window);
+                            }
+
                             // Process audio based on recording mode
                             let final_audio = match self.recording_mode {
                                 RecordingMode::MicrophoneOnly => {
@@ -882,8 +962,8 @@ impl AudioPipeline {
                                     // Use system audio directly, no mixing
                                     sys_window
                                 }
-                                RecordingMode::Mixed => {
-                                    // Mix both audio sources
+                                RecordingMode::Mixed | RecordingMode::PerChannel => {
+                                    // Mix both audio sources (PerChannel still records the mix)
                                     self.mixer.mix_window(&mic_window, &sys_window)
                                 }
                             };
@@ -895,39 +975,53 @@ impl AudioPipeline {
                             let mixed_with_gain = final_audio;
 
                             // STEP 4: Send mixed audio for transcription (VAD + Whisper)
-                            match self.vad_processor.process_audio(&mixed_with_gain) {
-                                Ok(speech_segments) => {
-                                    for segment in speech_segments {
-                                        let duration_ms = segment.end_timestamp_ms - segment.start_timestamp_ms;
-
-                                        if segment.samples.len() >= 800 {  // Minimum 50ms at 16kHz - matches Parakeet capability
-                                            info!("📤 Sending VAD segment: {:.1}ms, {} samples",
-                                                  duration_ms, segment.samples.len());
-
-                                            let transcription_chunk = AudioChunk {
-                                                data: segment.samples,
-                                                sample_rate: 16000,
-                                                timestamp: segment.start_timestamp_ms / 1000.0,
-                                                chunk_id: self.chunk_id_counter,
-                                                device_type: DeviceType::Microphone,  // Mixed audio
-                                            };
-
-                                            if let Err(e) = self.transcription_sender.send(transcription_chunk) {
-                                                warn!("Failed to send VAD segment: {}", e);
+                            // PerChannel mode already sent its segments above
+                            let mut window_has_speech = false;
+                            if self.recording_mode != RecordingMode::PerChannel {
+                                match self.vad_processor.process_audio(&mixed_with_gain) {
+                                    Ok(speech_segments) => {
+                                        window_has_speech = !speech_segments.is_empty();
+                                        for segment in speech_segments {
+                                            let duration_ms = segment.end_timestamp_ms - segment.start_timestamp_ms;
+
+                                            if segment.samples.len() >= 800 {  // Minimum 50ms at 16kHz - matches Parakeet capability
+                                                info!("📤 Sending VAD segment: {:.1}ms, {} samples",
+                                                      duration_ms, segment.samples.len());
+
+                                                let transcription_chunk = AudioChunk {
+                                                    data: segment.samples,
+                                                    sample_rate: 16000,
+                                                    timestamp: segment.start_timestamp_ms / 1000.0,
+                                                    chunk_id: self.chunk_id_counter,
+                                                    device_type: DeviceType::Microphone,  // Mixed audio
+                                                    channel: None,
+                                                };
+
+                                                if let Err(e) = self.transcription_sender.send(transcription_chunk) {
+                                                    warn!("Failed to send VAD segment: {}", e);
+                                                } else {
+                                                    self.chunk_id_counter += 1;
+                                                }
                                             } else {
-                                                self.chunk_id_counter += 1;
+                                                debug!("⏭️ Dropping short VAD segment: {:.1}ms ({} samples < 800)",
+                                                       duration_ms, segment.samples.len());
                                             }
-                                        } else {
-                                            debug!("⏭️ Dropping short VAD segment: {:.1}ms ({} samples < 800)",
-                                                   duration_ms, segment.samples.len());
                                         }
                                     }
-                                }
-                                Err(e) => {
-                                    warn!("⚠️ VAD error: {}", e);
+                                    Err(e) => {
+                                        warn!("⚠️ VAD error: {}", e);
+                                    }
                                 }
                             }
 
+                            // Idle detection: speech from any VAD, or audible level in what gets recorded
+                            let speech = window_has_speech
+                                || [Some(&self.vad_processor), self.mic_vad_processor.as_ref(), self.system_vad_processor.as_ref()]
+                                    .into_iter()
+                                    .flatten()
+                                    .any(|vad| vad.pending_speech_start_ms().is_some());
+                            super::idle_detector::observe_window(&mixed_with_gain, self.sample_rate, speech);
+
                             // STEP 5: Send mixed audio for recording (WAV file)
                             if let Some(ref sender) = self.recording_sender_for_mixed {
                                 let recording_chunk = AudioChunk {
@@ -936,6 +1030,7 @@ impl AudioPipeline {
                                     timestamp: chunk.timestamp,
                                     chunk_id: self.chunk_id_counter,
                                     device_type: DeviceType::Microphone,  // Mixed audio
+                                    channel: None,
                                 };
                                 let _ = sender.send(recording_chunk);
                             }
@@ -980,6 +1075,7 @@ impl AudioPipeline {
                             timestamp: segment.start_timestamp_ms / 1000.0,
                             chunk_id: self.chunk_id_counter,
                             device_type: DeviceType::Microphone,
+                            channel: None,
                         };
 
                         if let Err(e) = self.transcription_sender.send(transcription_chunk) {
@@ -998,9 +1094,88 @@ impl AudioPipeline {
             }
         }
 
+        // Per-channel VADs: flush both and emit everything left, in order
+        for (channel, processor) in [
+            (TranscriptChannel::Mic, self.mic_vad_processor.as_mut()),
+            (TranscriptChannel::System, self.system_vad_processor.as_mut()),
+        ] {
+            if let Some(processor) = processor {
+                match processor.flush() {
+                    Ok(final_segments) => {
+                        for segment in final_segments {
+                            self.channel_interleaver.push(channel, segment);
+                        }
+                    }
+                    Err(e) => warn!("Failed to flush {:?} VAD processor: {}", channel, e),
+                }
+            }
+        }
+        for (channel, segment) in self.channel_interleaver.drain_all() {
+            self.send_channel_segment(channel, segment);
+        }
+
         Ok(())
     }
 
+    /// Run each source's window through its own VAD and send the segments
+    /// that are ready, tagged with their channel
+    fn process_channel_windows(&mut self, mic_window: &[f32], sys_window: &[f32]) {
+        for (channel, processor, window) in [
+            (TranscriptChannel::Mic, self.mic_vad_processor.as_mut(), mic_window),
+            (TranscriptChannel::System, self.system_vad_processor.as_mut(), sys_window),
+        ] {
+            let Some(processor) = processor else { continue };
+            match processor.process_audio(window) {
+                Ok(speech_segments) => {
+                    for segment in speech_segments {
+                        self.channel_interleaver.push(channel, segment);
+                    }
+                }
+                Err(e) => warn!("⚠️ {:?} VAD error: {}", channel, e),
+            }
+        }
+
+        let in_progress = [
+            self.mic_vad_processor.as_ref().and_then(|p| p.pending_speech_start_ms()),
+            self.system_vad_processor.as_ref().and_then(|p| p.pending_speech_start_ms()),
+        ];
+        for (channel, segment) in self.channel_interleaver.drain_ready(&in_progress) {
+            self.send_channel_segment(channel, segment);
+        }
+    }
+
+    fn send_channel_segment(&mut self, channel: TranscriptChannel, segment: SpeechSegment) {
+        let duration_ms = segment.end_timestamp_ms - segment.start_timestamp_ms;
+
+        // Same 50ms minimum as the mixed path
+        if segment.samples.len() < 800 {
+            debug!("⏭️ Dropping short {:?} VAD segment: {:.1}ms ({} samples < 800)",
+                   channel, duration_ms, segment.samples.len());
+            return;
+        }
+
+        info!("📤 Sending {:?} VAD segment: {:.1}ms, {} samples",
+              channel, duration_ms, segment.samples.len());
+
+        let transcription_chunk = AudioChunk {
+            data: segment.samples,
+            sample_rate: 16000,
+            timestamp: segment.start_timestamp_ms / 1000.0,
+            chunk_id: self.chunk_id_counter,
+            device_type: match channel {
+                TranscriptChannel::Mic => DeviceType::Microphone,
+                TranscriptChannel::System => DeviceType::System,
+            },
+            channel: Some(channel),
+        };
+
+        if let Err(e) = self.transcription_sender.send(transcription_chunk) {
+            warn!("Failed to send {:?} VAD segment: {}", channel, e);
+        } else {
+            self.chunk_id_counter += 1;
+        }
+    }
+
 }
 
 /// Simple audio pipeline manager
@@ -1105,6 +1280,7 @@ impl AudioPipelineManager {
                 timestamp: 0.0,
                 chunk_id: u64::MAX, // Special ID to indicate flush
                 device_type: super::recording_state::DeviceType::Microphone,
+                channel: None,
             };
 
             if let Err(e) = sender.send(flush_chunk) {
@@ -1125,6 +1301,7 @@ impl AudioPipelineManager {
                         timestamp: 0.0,
                         chunk_id: u64::MAX - (i as u64),
                         device_type: super::recording_state::DeviceType::Microphone,
+                        channel: None,
                     };
                     let _ = sender.send(additional_flush);
                 }
@@ -1143,4 +1320,47 @@ impl Default for AudioPipelineManager {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(start_ms: f64, end_ms: f64) -> SpeechSegment {
+        SpeechSegment {
+            samples: vec![0.0; 1600],
+            start_timestamp_ms: start_ms,
+            end_timestamp_ms: end_ms,
+            confidence: 0.9,
+        }
+    }
+
+    #[test]
+    fn test_interleaver_orders_by_start_time() {
+        let mut interleaver = ChannelInterleaver::default();
+        interleaver.push(TranscriptChannel::Mic, segment(4000.0, 5000.0));
+        interleaver.push(TranscriptChannel::System, segment(1000.0, 6000.0));
+
+        let ready = interleaver.drain_ready(&[None, None]);
+        let starts: Vec<f64> = ready.iter().map(|(_, s)| s.start_timestamp_ms).collect();
+        assert_eq!(starts, vec![1000.0, 4000.0]);
+        assert_eq!(ready[0].0, TranscriptChannel::System);
+    }
+
+    #[test]
+    fn test_interleaver_holds_segments_behind_speech_in_progress() {
+        let mut interleaver = ChannelInterleaver::default();
+        interleaver.push(TranscriptChannel::Mic, segment(1000.0, 2000.0));
+        interleaver.push(TranscriptChannel::Mic, segment(3000.0, 3500.0));
+
+        // Remote speech started at 2500ms and hasn't ended yet
+        let ready = interleaver.drain_ready(&[None, Some(2500.0)]);
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].1.start_timestamp_ms, 1000.0);
+
+        interleaver.push(TranscriptChannel::System, segment(2500.0, 4000.0));
+        let ready = interleaver.drain_all();
+        let starts: Vec<f64> = ready.iter().map(|(_, s)| s.start_timestamp_ms).collect();
+        assert_eq!(starts, vec![2500.0, 3000.0]);
+    }
+}
@@ -50,6 +50,21 @@ pub enum RecordingMode {
     SystemAudioOnly,
     /// Mix both microphone and system audio
     Mixed,
+    /// Record both sources into the mixed file, but run VAD and transcription
+    /// on each source separately so segments are tagged "Me" / "Remote"
+    PerChannel,
+}
+
+impl RecordingMode {
+    /// Whether the mode captures the microphone
+    pub fn uses_microphone(&self) -> bool {
+        !matches!(self, RecordingMode::SystemAudioOnly)
+    }
+
+    /// Whether the mode captures system audio
+    pub fn uses_system_audio(&self) -> bool {
+        !matches!(self, RecordingMode::MicrophoneOnly)
+    }
 }
 
 impl Default for RecordingMode {
@@ -153,6 +168,10 @@ pub async fn start_recording_with_meeting_name<R: Runtime>(
     info!("🔍 Setting IS_RECORDING to true and resetting SPEECH_DETECTED_EMITTED");
     IS_RECORDING.store(true, Ordering::SeqCst);
     reset_speech_detected_flag(); // Reset for new recording session
+    crate::diarization::reset_live_session();
+    crate::glossary::start_session(&app).await;
+    crate::transcript_rules::start_session(&app).await;
+    super::idle_detector::start_session(&app).await;
 
     // Start optimized parallel transcription task and store handle
     let task_handle = transcription::start_transcription_task(app.clone(), transcription_receiver);
@@ -180,6 +199,9 @@ pub async fn start_recording_with_meeting_name<R: Runtime>(
                     display_time: update.timestamp.clone(), // Use wall-clock timestamp for display
                     confidence: update.confidence,
                     sequence_id: update.sequence_id,
+                    words: update.words.clone(),
+                    speaker_id: update.speaker_id.clone(),
+                    channel: update.channel,
                 };
 
                 // Save to recording manager
@@ -271,7 +293,7 @@ pub async fn start_recording_with_devices_and_meeting<R: Runtime>(
         RecordingMode::SystemAudioOnly => {
             (None, system_device_name)
         }
-        RecordingMode::Mixed => {
+        RecordingMode::Mixed | RecordingMode::PerChannel => {
             (mic_device_name, system_device_name)
         }
     };
@@ -281,7 +303,7 @@ pub async fn start_recording_with_devices_and_meeting<R: Runtime>(
         Some(Arc::new(parse_audio_device(name).map_err(|e| {
             format!("Invalid microphone device '{}': {}", name, e)
         })?))
-    } else if mode == RecordingMode::MicrophoneOnly || mode == RecordingMode::Mixed {
+    } else if mode.uses_microphone() {
         match default_input_device() {
             Ok(device) => Some(Arc::new(device)),
             Err(e) => {
@@ -302,7 +324,7 @@ pub async fn start_recording_with_devices_and_meeting<R: Runtime>(
         Some(Arc::new(parse_audio_device(name).map_err(|e| {
             format!("Invalid system device '{}': {}", name, e)
         })?))
-    } else if mode == RecordingMode::SystemAudioOnly || mode == RecordingMode::Mixed {
+    } else if mode.uses_system_audio() {
         match default_output_device() {
             Ok(device) => Some(Arc::new(device)),
             Err(e) => {
@@ -341,6 +363,11 @@ pub async fn start_recording_with_devices_and_meeting<R: Runtime>(
                 return Err("At least one audio device (microphone or system audio) is required for mixed recording mode.".to_string());
             }
         }
+        RecordingMode::PerChannel => {
+            if mic_device.is_none() && system_device.is_none() {
+                return Err("At least one audio device (microphone or system audio) is required for per-channel recording mode.".to_string());
+            }
+        }
     }
 
     // Async-first approach for custom devices - no more blocking operations!
@@ -382,6 +409,10 @@ pub async fn start_recording_with_devices_and_meeting<R: Runtime>(
     info!("🔍 Setting IS_RECORDING to true and resetting SPEECH_DETECTED_EMITTED");
     IS_RECORDING.store(true, Ordering::SeqCst);
     reset_speech_detected_flag(); // Reset for new recording session
+    crate::diarization::reset_live_session();
+    crate::glossary::start_session(&app).await;
+    crate::transcript_rules::start_session(&app).await;
+    super::idle_detector::start_session(&app).await;
 
     // Start optimized parallel transcription task and store handle
     let task_handle = transcription::start_transcription_task(app.clone(), transcription_receiver);
@@ -409,6 +440,9 @@ pub async fn start_recording_with_devices_and_meeting<R: Runtime>(
                     display_time: update.timestamp.clone(), // Use wall-clock timestamp for display
                     confidence: update.confidence,
                     sequence_id: update.sequence_id,
+                    words: update.words.clone(),
+                    speaker_id: update.speaker_id.clone(),
+                    channel: update.channel,
                 };
 
                 // Save to recording manager
@@ -816,6 +850,19 @@ pub async fn stop_recording<R: Runtime>(
     )
     .map_err(|e| e.to_string())?;
 
+    // The meeting id isn't known here yet; hooks get the title and folder
+    if let Some(state) = app.try_state::<crate::state::AppState>() {
+        crate::hooks::fire(
+            state.db_manager.pool(),
+            crate::hooks::HookEvent::RecordingStopped,
+            crate::hooks::HookContext {
+                title: meeting_name_str,
+                folder_path: folder_path_str,
+                ..Default::default()
+            },
+        );
+    }
+
     // Update tray menu to reflect stopped state
     crate::tray::update_tray_menu(&app);
 
@@ -1143,3 +1190,17 @@ pub async fn attempt_device_reconnect(
         }
     }
 }
+
+/// Set how fast `file:<path>` devices play (1.0 = real time).
+/// Takes effect for the next recording.
+#[tauri::command]
+pub async fn set_file_capture_speed(speed: f32) -> Result<(), String> {
+    super::capture::file_source::set_playback_speed(speed).map_err(|e| e.to_string())
+}
+
+/// Playback speed and number of file devices still streaming. A scripted run
+/// can stop the recording once `active_sources` drops to 0.
+#[tauri::command]
+pub async fn get_file_capture_status() -> Result<super::capture::file_source::FileCaptureStatus, String> {
+    Ok(super::capture::file_source::status())
+}
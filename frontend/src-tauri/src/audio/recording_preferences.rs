@@ -216,15 +216,15 @@ pub async fn select_recording_folder<R: Runtime>(
 /// Get available audio capture backends for the current platform
 #[tauri::command]
 pub async fn get_available_audio_backends() -> Result<Vec<String>, String> {
-    #[cfg(target_os = "macos")]
+    #[cfg(any(target_os = "macos", target_os = "linux"))]
     {
         let backends = crate::audio::capture::get_available_backends();
         Ok(backends.iter().map(|b| b.to_string()).collect())
     }
 
-    #[cfg(not(target_os = "macos"))]
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
     {
-        // Only ScreenCaptureKit available on non-macOS
+        // Only ScreenCaptureKit available on other platforms
         Ok(vec!["screencapturekit".to_string()])
     }
 }
@@ -232,13 +232,13 @@ pub async fn get_available_audio_backends() -> Result<Vec<String>, String> {
 /// Get current audio capture backend
 #[tauri::command]
 pub async fn get_current_audio_backend() -> Result<String, String> {
-    #[cfg(target_os = "macos")]
+    #[cfg(any(target_os = "macos", target_os = "linux"))]
     {
         let backend = crate::audio::capture::get_current_backend();
         Ok(backend.to_string())
     }
 
-    #[cfg(not(target_os = "macos"))]
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
     {
         Ok("screencapturekit".to_string())
     }
@@ -285,7 +285,28 @@ pub async fn set_audio_backend(backend: String) -> Result<(), String> {
         Ok(())
     }
 
-    #[cfg(not(target_os = "macos"))]
+    #[cfg(target_os = "linux")]
+    {
+        use crate::audio::capture::AudioCaptureBackend;
+
+        let backend_enum = AudioCaptureBackend::from_string(&backend)
+            .ok_or_else(|| format!("Invalid backend: {}", backend))?;
+
+        if backend_enum == AudioCaptureBackend::PulseAudio
+            && !crate::audio::capture::pulse::is_available()
+        {
+            return Err(
+                "No PulseAudio or PipeWire server found. \
+                Make sure pactl and parec are installed (pulseaudio-utils).".to_string()
+            );
+        }
+
+        info!("Setting audio backend to: {:?}", backend_enum);
+        crate::audio::capture::set_current_backend(backend_enum);
+        Ok(())
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
     {
         if backend != "screencapturekit" {
             return Err(format!("Backend {} not available on this platform", backend));
@@ -323,7 +344,20 @@ pub async fn get_audio_backend_info() -> Result<Vec<BackendInfo>, String> {
         Ok(backends)
     }
 
-    #[cfg(not(target_os = "macos"))]
+    #[cfg(target_os = "linux")]
+    {
+        let backends = crate::audio::capture::get_available_backends()
+            .into_iter()
+            .map(|backend| BackendInfo {
+                id: backend.to_string(),
+                name: backend.name().to_string(),
+                description: backend.description().to_string(),
+            })
+            .collect();
+        Ok(backends)
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
     {
         Ok(vec![BackendInfo {
             id: "screencapturekit".to_string(),
@@ -5,12 +5,14 @@ use log::{info, warn, error};
 use tauri::{AppHandle, Runtime, Emitter};
 use tokio::sync::mpsc;
 use serde::{Serialize, Deserialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use super::recording_state::AudioChunk;
+use super::recording_state::{AudioChunk, TranscriptChannel};
 use super::recording_preferences::load_recording_preferences;
 use super::audio_processing::create_meeting_folder;
-use super::incremental_saver::IncrementalAudioSaver;
+use super::incremental_saver::{trim_audio_file, IncrementalAudioSaver};
+use super::idle_detector::plan_trim;
+use super::transcription::WordTimestamp;
 
 /// Structured transcript segment for JSON export
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,6 +25,24 @@ pub struct TranscriptSegment {
     pub display_time: String,   // Formatted time for display like "[02:15]"
     pub confidence: f32,
     pub sequence_id: u64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<WordTimestamp>, // Word-level timings, recording-relative
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker_id: Option<String>, // Diarization label, if available
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<TranscriptChannel>, // Source channel in per-channel mode
+}
+
+impl TranscriptSegment {
+    /// Move the segment's audio timings `offset` seconds earlier (audio was cut from the start)
+    pub fn shift_back(&mut self, offset: f64) {
+        self.audio_start_time = (self.audio_start_time - offset).max(0.0);
+        self.audio_end_time = (self.audio_end_time - offset).max(0.0);
+        for word in &mut self.words {
+            word.start = (word.start - offset).max(0.0);
+            word.end = (word.end - offset).max(0.0);
+        }
+    }
 }
 
 /// Meeting metadata structure
@@ -39,6 +59,17 @@ pub struct MeetingMetadata {
     pub transcript_file: String,
     pub sample_rate: u32,
     pub status: String,  // "recording", "completed", "error"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub silence_trim: Option<SilenceTrim>,
+}
+
+/// Silence cut from audio.mp4 when the recording was saved
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SilenceTrim {
+    pub start_seconds: f64,  // Cut from the start; transcript times are shifted back by this
+    pub end_seconds: f64,    // Original recording time the kept audio ends at
+    #[serde(default)]
+    pub database_synced: bool,  // Stored transcripts were shifted too
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -130,6 +161,9 @@ impl RecordingSaver {
             display_time: "[00:00]".to_string(),
             confidence: 1.0,
             sequence_id: 0,
+            words: Vec::new(),
+            speaker_id: None,
+            channel: None,
         };
         self.add_transcript_segment(segment);
     }
@@ -223,6 +257,7 @@ impl RecordingSaver {
             transcript_file: "transcripts.json".to_string(),
             sample_rate: 48000,
             status: "recording".to_string(),
+            silence_trim: None,
         };
 
         // Write initial metadata.json
@@ -302,6 +337,51 @@ impl RecordingSaver {
         Ok(())
     }
 
+    /// Cut long silence before the first and after the last transcribed speech
+    /// from the final audio (if the idle policy asks for it) and shift the
+    /// transcript segments to match
+    async fn trim_silence(&self, audio_path: &Path, recording_duration: Option<f64>) -> Option<SilenceTrim> {
+        let policy = super::idle_detector::policy().await;
+        if !policy.trim_silence {
+            return None;
+        }
+        let duration = recording_duration?;
+
+        let (first_speech, last_speech) = {
+            let segments = self.transcript_segments.lock().ok()?;
+            if segments.is_empty() {
+                return None;
+            }
+            let first = segments.iter().map(|s| s.audio_start_time).fold(f64::INFINITY, f64::min);
+            let last = segments.iter().map(|s| s.audio_end_time).fold(0.0, f64::max);
+            (first, last)
+        };
+
+        let plan = plan_trim(first_speech, last_speech, duration, &policy)?;
+        if let Err(e) = trim_audio_file(audio_path, plan.start, plan.end) {
+            warn!("Failed to trim silence, keeping the full recording: {}", e);
+            return None;
+        }
+
+        if plan.start > 0.0 {
+            if let Ok(mut segments) = self.transcript_segments.lock() {
+                for segment in segments.iter_mut() {
+                    segment.shift_back(plan.start);
+                }
+            }
+        }
+
+        info!(
+            "✅ Trimmed silence: kept {:.1}s - {:.1}s of {:.1}s",
+            plan.start, plan.end, duration
+        );
+        Some(SilenceTrim {
+            start_seconds: plan.start,
+            end_seconds: plan.end,
+            database_synced: plan.start <= 0.0,  // Nothing to shift
+        })
+    }
+
     // in frontend/src-tauri/src/audio/recording_saver.rs
     pub fn get_stats(&self) -> (usize, u32) {
         if let Some(ref saver) = self.incremental_saver {
@@ -367,6 +447,9 @@ impl RecordingSaver {
             return Err("No incremental saver initialized".to_string());
         };
 
+        // Trim long leading/trailing silence before transcripts are written
+        let silence_trim = self.trim_silence(&final_audio_path, recording_duration).await;
+
         // Save final transcripts.json with validation
         if let Some(folder) = &self.meeting_folder {
             if let Err(e) = self.write_transcripts_json(folder) {
@@ -397,6 +480,10 @@ impl RecordingSaver {
                     None
                 }
             });
+            if let Some(trim) = &silence_trim {
+                metadata.duration_seconds = Some(trim.end_seconds - trim.start_seconds);
+            }
+            metadata.silence_trim = silence_trim;
 
             if let Err(e) = self.write_metadata(folder, &metadata) {
                 error!("❌ Failed to update metadata to completed: {}", e);
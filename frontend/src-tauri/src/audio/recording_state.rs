@@ -3,6 +3,7 @@ use std::sync::{Arc, Mutex};
 use std::time::Instant;
 use tokio::sync::mpsc;
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
 
 use super::devices::AudioDevice;
 use super::buffer_pool::AudioBufferPool;
@@ -14,6 +15,51 @@ pub enum DeviceType {
     System,
 }
 
+/// Channel a transcript segment was heard on when mic and system audio are
+/// transcribed separately (`RecordingMode::PerChannel`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptChannel {
+    /// Microphone - the local user
+    Mic,
+    /// System audio - the remote participants
+    System,
+}
+
+impl TranscriptChannel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Mic => "mic",
+            Self::System => "system",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "mic" => Some(Self::Mic),
+            "system" => Some(Self::System),
+            _ => None,
+        }
+    }
+
+    /// Speaker label shown for the channel
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Mic => "Me",
+            Self::System => "Remote",
+        }
+    }
+}
+
+impl From<&DeviceType> for TranscriptChannel {
+    fn from(device_type: &DeviceType) -> Self {
+        match device_type {
+            DeviceType::Microphone => Self::Mic,
+            DeviceType::System => Self::System,
+        }
+    }
+}
+
 /// Audio chunk with metadata for processing
 #[derive(Debug, Clone)]
 pub struct AudioChunk {
@@ -22,6 +68,8 @@ pub struct AudioChunk {
     pub timestamp: f64,
     pub chunk_id: u64,
     pub device_type: DeviceType,
+    /// Set on VAD segments transcribed per channel; None for mixed audio
+    pub channel: Option<TranscriptChannel>,
 }
 
 /// Processed audio chunk (post-VAD) for recording
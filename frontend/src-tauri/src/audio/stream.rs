@@ -1,3 +1,4 @@
+use std::path::PathBuf;
 use std::sync::Arc;
 use anyhow::Result;
 use cpal::traits::{DeviceTrait, StreamTrait};
@@ -9,10 +10,14 @@ use super::devices::{AudioDevice, get_device_and_config};
 use super::pipeline::AudioCapture;
 use super::recording_state::{RecordingState, DeviceType};
 use super::capture::{AudioCaptureBackend, get_current_backend};
+use super::capture::file_source;
 
 #[cfg(target_os = "macos")]
 use super::capture::CoreAudioCapture;
 
+#[cfg(target_os = "linux")]
+use super::capture::pulse;
+
 /// Stream backend implementation
 pub enum StreamBackend {
     /// CPAL-based stream (ScreenCaptureKit or default)
@@ -22,6 +27,15 @@ pub enum StreamBackend {
     CoreAudio {
         task: Option<tokio::task::JoinHandle<()>>,
     },
+    /// PulseAudio / PipeWire capture via parec (Linux only)
+    #[cfg(target_os = "linux")]
+    Pulse {
+        task: Option<tokio::task::JoinHandle<()>>,
+    },
+    /// Virtual device streaming an audio file (`file:<path>`)
+    File {
+        task: Option<tokio::task::JoinHandle<()>>,
+    },
 }
 
 // SAFETY: While Stream doesn't implement Send, we ensure it's only accessed
@@ -61,6 +75,12 @@ impl AudioStream {
         info!("🎵 Stream: Creating audio stream for device: {} with backend: {:?}, device_type: {:?}",
               device.name, backend_type, device_type);
 
+        // File devices replace the capture backend entirely
+        if let Some(path) = file_source::file_device_path(&device.name) {
+            info!("🎵 Stream: Using file source for device: {}", device.name);
+            return Self::create_file_stream(device, path, state, device_type, recording_sender).await;
+        }
+
         // For system audio devices, use the selected backend
         // For microphone devices, always use CPAL
         #[cfg(target_os = "macos")]
@@ -81,6 +101,16 @@ impl AudioStream {
               use_core_audio,
               device_type == DeviceType::System);
 
+        // The PulseAudio backend, or its virtual device, captures through the sound server
+        #[cfg(target_os = "linux")]
+        if device_type == DeviceType::System
+            && (backend_type == AudioCaptureBackend::PulseAudio
+                || device.name == pulse::SYSTEM_DEVICE_NAME)
+        {
+            info!("🎵 Stream: Using PulseAudio backend for system audio");
+            return Self::create_pulse_stream(device, state, device_type, recording_sender).await;
+        }
+
         #[cfg(target_os = "macos")]
         if use_core_audio {
             info!("🎵 Stream: Using Core Audio backend (cidre) for system audio");
@@ -233,6 +263,67 @@ impl AudioStream {
         })
     }
 
+    /// Create a PulseAudio / PipeWire stream (Linux only)
+    #[cfg(target_os = "linux")]
+    async fn create_pulse_stream(
+        device: Arc<AudioDevice>,
+        state: Arc<RecordingState>,
+        device_type: DeviceType,
+        recording_sender: Option<mpsc::UnboundedSender<super::recording_state::AudioChunk>>,
+    ) -> Result<Self> {
+        info!("🔊 Stream: Creating PulseAudio stream for device: {}", device.name);
+
+        // parec is asked for mono float samples
+        let capture = AudioCapture::new(
+            device.clone(),
+            state.clone(),
+            pulse::CAPTURE_SAMPLE_RATE,
+            1,
+            device_type,
+            recording_sender,
+        );
+        let task = pulse::spawn(device.name.clone(), capture, state)
+            .await
+            .map_err(|e| {
+                error!("❌ Stream: PulseAudio capture failed: {}", e);
+                anyhow::anyhow!("Failed to start PulseAudio capture: {}", e)
+            })?;
+
+        Ok(Self {
+            device,
+            backend: StreamBackend::Pulse { task: Some(task) },
+        })
+    }
+
+    /// Create a stream that plays an audio file into the pipeline
+    async fn create_file_stream(
+        device: Arc<AudioDevice>,
+        path: PathBuf,
+        state: Arc<RecordingState>,
+        device_type: DeviceType,
+        recording_sender: Option<mpsc::UnboundedSender<super::recording_state::AudioChunk>>,
+    ) -> Result<Self> {
+        let audio = file_source::open(&path)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to open {}: {}", path.display(), e))?;
+
+        // Decoded audio is mono
+        let capture = AudioCapture::new(
+            device.clone(),
+            state.clone(),
+            audio.sample_rate,
+            1,
+            device_type,
+            recording_sender,
+        );
+        let task = file_source::spawn(device.name.clone(), audio, capture, state);
+
+        Ok(Self {
+            device,
+            backend: StreamBackend::File { task: Some(task) },
+        })
+    }
+
     /// Build stream based on sample format
     fn build_stream(
         device: &Device,
@@ -331,6 +422,18 @@ impl AudioStream {
                     task_handle.abort();
                 }
             }
+            #[cfg(target_os = "linux")]
+            StreamBackend::Pulse { task } => {
+                // Aborting drops parec and unloads the capture modules
+                if let Some(task_handle) = task {
+                    task_handle.abort();
+                }
+            }
+            StreamBackend::File { task } => {
+                if let Some(task_handle) = task {
+                    task_handle.abort();
+                }
+            }
         }
 
         info!("Audio stream stopped for device: {}", self.device.name);
@@ -96,6 +96,67 @@ pub async fn get_system_audio_monitoring_status(
     Ok(detector_guard.is_some())
 }
 
+/// List applications currently playing audio (Linux, PulseAudio or PipeWire)
+#[command]
+pub async fn list_linux_audio_applications() -> Result<Vec<LinuxAudioApplication>, String> {
+    #[cfg(target_os = "linux")]
+    {
+        use crate::audio::capture::pulse;
+
+        let apps = tokio::task::spawn_blocking(pulse::list_applications)
+            .await
+            .map_err(|e| format!("Failed to list audio applications: {}", e))?
+            .map_err(|e| format!("Failed to list audio applications: {}", e))?;
+        let selected = pulse::selected_applications();
+        Ok(apps
+            .into_iter()
+            .map(|app| LinuxAudioApplication {
+                selected: app.matches(&selected),
+                playing: !app.corked,
+                name: app.name,
+                binary: app.binary,
+                pid: app.pid,
+            })
+            .collect())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        Err("Application audio capture is only available on Linux".to_string())
+    }
+}
+
+/// Choose the applications system audio is captured from (Linux).
+/// An empty list captures all system audio. Applies to the next recording.
+#[command]
+pub async fn set_linux_capture_applications(applications: Vec<String>) -> Result<(), String> {
+    #[cfg(target_os = "linux")]
+    {
+        crate::audio::capture::pulse::set_selected_applications(applications);
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = applications;
+        Err("Application audio capture is only available on Linux".to_string())
+    }
+}
+
+/// Applications system audio is captured from (Linux); empty means all
+#[command]
+pub async fn get_linux_capture_applications() -> Result<Vec<String>, String> {
+    #[cfg(target_os = "linux")]
+    {
+        Ok(crate::audio::capture::pulse::selected_applications())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        Ok(Vec::new())
+    }
+}
+
 /// Initialize the system audio detector state in Tauri app
 pub fn init_system_audio_state() -> SystemAudioDetectorState {
     Arc::new(Mutex::new(None))
@@ -110,6 +171,18 @@ pub struct SystemAudioStartedPayload {
 #[derive(serde::Serialize, Clone)]
 pub struct SystemAudioStoppedPayload;
 
+/// An application playing audio, as listed for capture selection
+#[derive(serde::Serialize, Clone)]
+pub struct LinuxAudioApplication {
+    pub name: String,
+    pub binary: Option<String>,
+    pub pid: Option<u32>,
+    /// Producing audio right now (not paused)
+    pub playing: bool,
+    /// Part of the current capture selection
+    pub selected: bool,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
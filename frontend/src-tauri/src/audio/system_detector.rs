@@ -378,21 +378,114 @@ fn list_system_audio_using_apps() -> Vec<String> {
     }
 }
 
-// Stub implementation for non-macOS platforms
-#[cfg(not(target_os = "macos"))]
+/// Detects system audio usage on Linux by polling the sound server's
+/// playback streams (PulseAudio or PipeWire)
+#[cfg(target_os = "linux")]
+#[derive(Default)]
+pub struct LinuxSystemAudioDetector {
+    background: BackgroundTask,
+}
+
+#[cfg(target_os = "linux")]
+const LINUX_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Quiet polls before audio counts as stopped, so track changes don't flap
+#[cfg(target_os = "linux")]
+const LINUX_STOP_POLLS: u32 = 2;
+
+#[cfg(target_os = "linux")]
+#[derive(Default)]
+struct LinuxDetectorState {
+    playing: Vec<String>,
+    quiet_polls: u32,
+}
+
+#[cfg(target_os = "linux")]
+impl LinuxDetectorState {
+    /// Started when an app begins playing (again with the full list when
+    /// another app joins), Stopped once everything has been quiet for a while
+    fn update(&mut self, playing: Vec<String>) -> Option<SystemAudioEvent> {
+        if playing.is_empty() {
+            if self.playing.is_empty() {
+                return None;
+            }
+            self.quiet_polls += 1;
+            if self.quiet_polls < LINUX_STOP_POLLS {
+                return None;
+            }
+            self.playing.clear();
+            self.quiet_polls = 0;
+            return Some(SystemAudioEvent::SystemAudioStopped);
+        }
+
+        self.quiet_polls = 0;
+        let has_new_app = playing.iter().any(|app| !self.playing.contains(app));
+        self.playing = playing;
+        if has_new_app {
+            Some(SystemAudioEvent::SystemAudioStarted(self.playing.clone()))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl LinuxSystemAudioDetector {
+    pub fn start(&mut self, callback: SystemAudioCallback) {
+        self.background.start(|running, mut stop_rx| {
+            Box::pin(async move {
+                use crate::audio::capture::pulse;
+
+                let mut state = LinuxDetectorState::default();
+                let mut ticker = tokio::time::interval(LINUX_POLL_INTERVAL);
+                loop {
+                    tokio::select! {
+                        _ = &mut stop_rx => {
+                            break;
+                        }
+                        _ = ticker.tick() => {
+                            if !running.load(std::sync::atomic::Ordering::SeqCst) {
+                                break;
+                            }
+                            let apps = match tokio::task::spawn_blocking(pulse::list_applications).await {
+                                Ok(Ok(apps)) => apps,
+                                Ok(Err(e)) => {
+                                    tracing::warn!("Failed to list audio applications: {}", e);
+                                    continue;
+                                }
+                                Err(_) => continue,
+                            };
+                            if let Some(event) = state.update(pulse::playing_application_names(&apps)) {
+                                tracing::info!(event = ?event, "detected");
+                                (*callback)(event);
+                            }
+                        }
+                    }
+                }
+            })
+        });
+    }
+
+    pub fn stop(&mut self) {
+        self.background.stop();
+    }
+}
+
+// Stub implementation for other platforms
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
 pub struct MacOSSystemAudioDetector;
 
-#[cfg(not(target_os = "macos"))]
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
 impl Default for MacOSSystemAudioDetector {
     fn default() -> Self {
         Self
     }
 }
 
-#[cfg(not(target_os = "macos"))]
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
 impl MacOSSystemAudioDetector {
     pub fn start(&mut self, _callback: SystemAudioCallback) {
-        tracing::warn!("System audio detection is only supported on macOS");
+        tracing::warn!("System audio detection is only supported on macOS and Linux");
     }
 
     pub fn stop(&mut self) {}
@@ -401,7 +494,10 @@ impl MacOSSystemAudioDetector {
 /// Public interface for system audio detection
 #[derive(Default)]
 pub struct SystemAudioDetector {
+    #[cfg(not(target_os = "linux"))]
     inner: MacOSSystemAudioDetector,
+    #[cfg(target_os = "linux")]
+    inner: LinuxSystemAudioDetector,
 }
 
 impl SystemAudioDetector {
@@ -433,4 +529,29 @@ mod tests {
         tokio::time::sleep(tokio::time::Duration::from_secs(30)).await;
         detector.stop();
     }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_linux_detector_state() {
+        let mut state = LinuxDetectorState::default();
+        let apps = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+
+        assert!(state.update(vec![]).is_none());
+        assert!(matches!(
+            state.update(apps(&["Firefox"])),
+            Some(SystemAudioEvent::SystemAudioStarted(a)) if a == apps(&["Firefox"])
+        ));
+        assert!(state.update(apps(&["Firefox"])).is_none());
+        assert!(matches!(
+            state.update(apps(&["Firefox", "Zoom"])),
+            Some(SystemAudioEvent::SystemAudioStarted(a)) if a.len() == 2
+        ));
+
+        // A single quiet poll is not a stop
+        assert!(state.update(vec![]).is_none());
+        assert!(state.update(apps(&["Zoom"])).is_none());
+        assert!(state.update(vec![]).is_none());
+        assert!(matches!(state.update(vec![]), Some(SystemAudioEvent::SystemAudioStopped)));
+        assert!(state.update(vec![]).is_none());
+    }
 }
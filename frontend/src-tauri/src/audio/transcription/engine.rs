@@ -2,7 +2,7 @@
 //
 // TranscriptionEngine enum and model initialization/validation logic.
 
-use super::provider::TranscriptionProvider;
+use super::provider::{TranscriptionError, TranscriptionProvider, TranscriptResult};
 use log::{info, warn};
 use std::sync::Arc;
 use tauri::{AppHandle, Manager, Runtime};
@@ -45,6 +45,41 @@ impl TranscriptionEngine {
             Self::Provider(provider) => provider.provider_name(),
         }
     }
+
+    /// Transcribe a single 16kHz mono segment through whichever engine is active.
+    /// Used by offline paths (file import) that don't go through the worker pool.
+    pub async fn transcribe(
+        &self,
+        audio: Vec<f32>,
+        language: Option<String>,
+    ) -> std::result::Result<TranscriptResult, TranscriptionError> {
+        match self {
+            Self::Whisper(engine) => engine
+                .transcribe_audio_with_confidence(audio, language)
+                .await
+                .map(|(text, confidence, is_partial)| TranscriptResult {
+                    text: text.trim().to_string(),
+                    confidence: Some(confidence),
+                    is_partial,
+                    words: Vec::new(),
+                })
+                .map_err(|e| TranscriptionError::EngineFailed(e.to_string())),
+            Self::Parakeet(engine) => {
+                let audio_duration = audio.len() as f32 / 16000.0;
+                engine
+                    .transcribe_audio_with_timestamps(audio)
+                    .await
+                    .map(|result| TranscriptResult {
+                        text: result.text.trim().to_string(),
+                        confidence: None,
+                        is_partial: false,
+                        words: result.word_timestamps(audio_duration),
+                    })
+                    .map_err(|e| TranscriptionError::EngineFailed(e.to_string()))
+            }
+            Self::Provider(provider) => provider.transcribe(audio, language).await,
+        }
+    }
 }
 
 // ============================================================================
@@ -9,7 +9,7 @@ pub mod engine;
 pub mod worker;
 
 // Re-export commonly used types
-pub use provider::{TranscriptionError, TranscriptionProvider, TranscriptResult};
+pub use provider::{offset_words, TranscriptionError, TranscriptionProvider, TranscriptResult, WordTimestamp};
 pub use whisper_provider::WhisperProvider;
 pub use parakeet_provider::ParakeetProvider;
 pub use engine::{
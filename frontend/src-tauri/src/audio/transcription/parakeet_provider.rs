@@ -33,11 +33,13 @@ impl TranscriptionProvider for ParakeetProvider {
             );
         }
 
-        match self.engine.transcribe_audio(audio).await {
-            Ok(text) => Ok(TranscriptResult {
-                text: text.trim().to_string(),
+        let audio_duration = audio.len() as f32 / 16000.0;
+        match self.engine.transcribe_audio_with_timestamps(audio).await {
+            Ok(result) => Ok(TranscriptResult {
+                text: result.text.trim().to_string(),
                 confidence: None, // Parakeet doesn't provide confidence scores
                 is_partial: false, // Parakeet doesn't provide partial results
+                words: result.word_timestamps(audio_duration),
             }),
             Err(e) => Err(TranscriptionError::EngineFailed(e.to_string())),
         }
@@ -4,6 +4,7 @@
 // transcription engines (Whisper, Parakeet, future providers).
 
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 
 // ============================================================================
 // TRANSCRIPTION PROVIDER TRAIT & ERROR TYPES
@@ -37,12 +38,36 @@ impl std::fmt::Display for TranscriptionError {
 
 impl std::error::Error for TranscriptionError {}
 
+/// Timing of a single word within a transcript
+///
+/// Times are in seconds, relative to the start of the transcribed audio. Callers
+/// that know where the audio sits in the recording shift them with `offset_words`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WordTimestamp {
+    pub word: String,
+    pub start: f64,
+    pub end: f64,
+}
+
+/// Shift word timings by `offset` seconds (segment-relative -> recording-relative)
+pub fn offset_words(words: Vec<WordTimestamp>, offset: f64) -> Vec<WordTimestamp> {
+    words
+        .into_iter()
+        .map(|w| WordTimestamp {
+            word: w.word,
+            start: w.start + offset,
+            end: w.end + offset,
+        })
+        .collect()
+}
+
 /// Unified transcription result across all providers
 #[derive(Debug, Clone)]
 pub struct TranscriptResult {
     pub text: String,
     pub confidence: Option<f32>, // None if provider doesn't support confidence scores
     pub is_partial: bool,
+    pub words: Vec<WordTimestamp>, // Empty if provider doesn't support word timings
 }
 
 /// Trait for transcription providers (Whisper, Parakeet, future providers)
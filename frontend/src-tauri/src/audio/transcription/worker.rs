@@ -3,8 +3,8 @@
 // Parallel transcription worker pool and chunk processing logic.
 
 use super::engine::TranscriptionEngine;
-use super::provider::TranscriptionError;
-use crate::audio::AudioChunk;
+use super::provider::{offset_words, TranscriptionError, TranscriptResult, WordTimestamp};
+use crate::audio::{AudioChunk, TranscriptChannel};
 use log::{error, info, warn};
 use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
@@ -36,6 +36,15 @@ pub struct TranscriptUpdate {
     pub audio_start_time: f64, // Seconds from recording start (e.g., 125.3)
     pub audio_end_time: f64,   // Seconds from recording start (e.g., 128.6)
     pub duration: f64,          // Segment duration in seconds (e.g., 3.3)
+    // Word-level timings (recording-relative), empty if the engine doesn't provide them
+    #[serde(default)]
+    pub words: Vec<WordTimestamp>,
+    // Diarization label ("speaker_1", ...), None when diarization is off or the segment is too short
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker_id: Option<String>,
+    // "mic" / "system" in per-channel recording mode, None for mixed audio
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<TranscriptChannel>,
 }
 
 // NOTE: get_transcript_history and get_recording_meeting_name functions
@@ -142,6 +151,14 @@ pub fn start_transcription_task<R: Runtime>(
 
                             let chunk_timestamp = chunk.timestamp;
                             let chunk_duration = chunk.data.len() as f64 / chunk.sample_rate as f64;
+                            let chunk_channel = chunk.channel;
+
+                            // Keep the samples for speaker embedding (transcription consumes the chunk)
+                            let speaker_audio = if crate::diarization::is_active() {
+                                Some((chunk.data.clone(), chunk.sample_rate))
+                            } else {
+                                None
+                            };
 
                             // Transcribe with provider-agnostic approach
                             match transcribe_chunk_with_provider(
@@ -151,7 +168,12 @@ pub fn start_transcription_task<R: Runtime>(
                             )
                             .await
                             {
-                                Ok((transcript, confidence_opt, is_partial)) => {
+                                Ok(TranscriptResult {
+                                    text: transcript,
+                                    confidence: confidence_opt,
+                                    is_partial,
+                                    words,
+                                }) => {
                                     // Provider-aware confidence threshold
                                     let confidence_threshold = match &engine_clone {
                                         TranscriptionEngine::Whisper(_) | TranscriptionEngine::Provider(_) => 0.3,
@@ -203,6 +225,32 @@ pub fn start_transcription_task<R: Runtime>(
                                         // The recording_commands module listens to these events and saves them
                                         // This decouples the transcription worker from direct RECORDING_MANAGER access
 
+                                        // Label the segment with a speaker (chunks are single VAD segments)
+                                        let speaker_id = match speaker_audio {
+                                            Some((data, sample_rate)) => tokio::task::spawn_blocking(move || {
+                                                let samples_16k = if sample_rate != 16000 {
+                                                    crate::audio::audio_processing::resample_audio(&data, sample_rate, 16000)
+                                                } else {
+                                                    data
+                                                };
+                                                crate::diarization::assign_live_speaker(&samples_16k)
+                                            })
+                                            .await
+                                            .unwrap_or_else(|e| {
+                                                warn!("Worker {}: Speaker embedding task failed: {}", worker_id, e);
+                                                None
+                                            }),
+                                            None => None,
+                                        };
+
+                                        // User find-and-replace rules run before the text is emitted and saved
+                                        let rules = crate::transcript_rules::active_rules();
+                                        let transcript = rules.apply(&transcript);
+                                        let words = rules.apply_words(words);
+
+                                        // Carry the end of this chunk into the next Whisper prompt
+                                        crate::glossary::push_context(&transcript);
+
                                         // Emit transcript update with NEW recording-relative timestamps
 
                                         let update = TranscriptUpdate {
@@ -217,6 +265,9 @@ pub fn start_transcription_task<R: Runtime>(
                                             audio_start_time,
                                             audio_end_time,
                                             duration: chunk_duration,
+                                            words: offset_words(words, audio_start_time),
+                                            speaker_id,
+                                            channel: chunk_channel,
                                         };
 
                                         if let Err(e) = app_clone.emit("transcript-update", &update)
@@ -404,12 +455,12 @@ pub fn start_transcription_task<R: Runtime>(
 }
 
 /// Transcribe audio chunk using the appropriate provider (Whisper, Parakeet, or trait-based)
-/// Returns: (text, confidence Option, is_partial)
+/// Returns the transcript with text, optional confidence, partial flag and chunk-relative word timings
 async fn transcribe_chunk_with_provider<R: Runtime>(
     engine: &TranscriptionEngine,
     chunk: AudioChunk,
     app: &AppHandle<R>,
-) -> std::result::Result<(String, Option<f32>, bool), TranscriptionError> {
+) -> std::result::Result<TranscriptResult, TranscriptionError> {
     // Convert to 16kHz mono for transcription
     let transcription_data = if chunk.sample_rate != 16000 {
         crate::audio::audio_processing::resample_audio(&chunk.data, chunk.sample_rate, 16000)
@@ -455,7 +506,12 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
                 Ok((text, confidence, is_partial)) => {
                     let cleaned_text = text.trim().to_string();
                     if cleaned_text.is_empty() {
-                        return Ok((String::new(), Some(confidence), is_partial));
+                        return Ok(TranscriptResult {
+                            text: String::new(),
+                            confidence: Some(confidence),
+                            is_partial,
+                            words: Vec::new(),
+                        });
                     }
 
                     info!(
@@ -463,7 +519,12 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
                         chunk.chunk_id, cleaned_text, confidence, is_partial
                     );
 
-                    Ok((cleaned_text, Some(confidence), is_partial))
+                    Ok(TranscriptResult {
+                        text: cleaned_text,
+                        confidence: Some(confidence),
+                        is_partial,
+                        words: Vec::new(),
+                    })
                 }
                 Err(e) => {
                     error!(
@@ -486,11 +547,20 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
             }
         }
         TranscriptionEngine::Parakeet(parakeet_engine) => {
-            match parakeet_engine.transcribe_audio(speech_samples).await {
-                Ok(text) => {
-                    let cleaned_text = text.trim().to_string();
+            let audio_duration = speech_samples.len() as f32 / 16000.0;
+            match parakeet_engine
+                .transcribe_audio_with_timestamps(speech_samples)
+                .await
+            {
+                Ok(result) => {
+                    let cleaned_text = result.text.trim().to_string();
                     if cleaned_text.is_empty() {
-                        return Ok((String::new(), None, false));
+                        return Ok(TranscriptResult {
+                            text: String::new(),
+                            confidence: None,
+                            is_partial: false,
+                            words: Vec::new(),
+                        });
                     }
 
                     info!(
@@ -499,7 +569,12 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
                     );
 
                     // Parakeet doesn't provide confidence or partial results
-                    Ok((cleaned_text, None, false))
+                    Ok(TranscriptResult {
+                        text: cleaned_text,
+                        confidence: None,
+                        is_partial: false,
+                        words: result.word_timestamps(audio_duration),
+                    })
                 }
                 Err(e) => {
                     error!(
@@ -529,7 +604,10 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
                 Ok(result) => {
                     let cleaned_text = result.text.trim().to_string();
                     if cleaned_text.is_empty() {
-                        return Ok((String::new(), result.confidence, result.is_partial));
+                        return Ok(TranscriptResult {
+                            text: String::new(),
+                            ..result
+                        });
                     }
 
                     let confidence_str = match result.confidence {
@@ -546,7 +624,10 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
                         result.is_partial
                     );
 
-                    Ok((cleaned_text, result.confidence, result.is_partial))
+                    Ok(TranscriptResult {
+                        text: cleaned_text,
+                        ..result
+                    })
                 }
                 Err(e) => {
                     error!(
@@ -24,6 +24,8 @@ pub struct ContinuousVadProcessor {
     in_speech: bool,
     processed_samples: usize,
     speech_start_sample: usize,
+    // Start of the speech in progress, in the same time base as SpeechSegment timestamps
+    speech_start_ms: Option<f64>,
     // State tracking for smart logging
     last_logged_state: bool,
 }
@@ -77,6 +79,7 @@ impl ContinuousVadProcessor {
             in_speech: false,
             processed_samples: 0,
             speech_start_sample: 0,
+            speech_start_ms: None,
             // Initialize state tracking
             last_logged_state: false,
         })
@@ -109,6 +112,12 @@ impl ContinuousVadProcessor {
         Ok(completed_segments)
     }
 
+    /// Start (ms) of the speech segment still in progress, if any.
+    /// Segments returned later can't start before this.
+    pub fn pending_speech_start_ms(&self) -> Option<f64> {
+        self.speech_start_ms
+    }
+
     /// Improved resampling from input sample rate to 16kHz with anti-aliasing
     /// Uses linear interpolation and basic low-pass filtering for better quality
     fn resample_to_16k(&self, samples: &[f32]) -> Result<Vec<f32>> {
@@ -193,6 +202,7 @@ impl ContinuousVadProcessor {
             self.current_speech.clear();
             self.in_speech = false;
         }
+        self.speech_start_ms = None;
 
         // Extract all remaining segments
         while let Some(segment) = self.speech_segments.pop_front() {
@@ -216,6 +226,7 @@ impl ContinuousVadProcessor {
                         self.last_logged_state = true;
                     }
                     self.in_speech = true;
+                    self.speech_start_ms = Some(timestamp_ms as f64);
                     self.speech_start_sample = self.processed_samples + (timestamp_ms * self.sample_rate as usize / 1000);
                     self.current_speech.clear();
                 }
@@ -226,6 +237,7 @@ impl ContinuousVadProcessor {
                         self.last_logged_state = false;
                     }
                     self.in_speech = false;
+                    self.speech_start_ms = None;
 
                     // Use samples from VAD transition if available, otherwise use accumulated samples
                     let speech_samples = if !samples.is_empty() {
@@ -0,0 +1,48 @@
+use super::{AutoRecorderSettings, AutoRecorderStatus};
+use log::{error as log_error, info as log_info};
+use tauri::{AppHandle, Runtime};
+
+#[tauri::command]
+pub async fn get_auto_recorder_settings() -> Result<AutoRecorderSettings, String> {
+    Ok(super::settings().await)
+}
+
+/// Save the rules and policies; starts or stops meeting detection to match
+#[tauri::command]
+pub async fn set_auto_recorder_settings<R: Runtime>(
+    app: AppHandle<R>,
+    settings: AutoRecorderSettings,
+) -> Result<(), String> {
+    log_info!(
+        "set_auto_recorder_settings called: enabled={}, {} rule(s)",
+        settings.enabled,
+        settings.rules.len()
+    );
+    super::save_settings(&app, settings).await.map_err(|e| {
+        log_error!("Failed to save auto-recorder settings: {}", e);
+        format!("Failed to save auto-recorder settings: {}", e)
+    })
+}
+
+#[tauri::command]
+pub async fn get_auto_recorder_status() -> Result<AutoRecorderStatus, String> {
+    Ok(super::status().await)
+}
+
+/// Start the recording offered by the last "auto-recorder-prompt" event
+#[tauri::command]
+pub async fn accept_auto_recording_prompt<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+    if super::accept_prompt(&app).await {
+        log_info!("✅ Auto-recorder prompt accepted");
+        Ok(())
+    } else {
+        Err("No meeting is waiting to be recorded".to_string())
+    }
+}
+
+/// Decline the offered recording; the app isn't offered again until it stops using audio
+#[tauri::command]
+pub async fn dismiss_auto_recording_prompt() -> Result<(), String> {
+    super::dismiss_prompt();
+    Ok(())
+}
@@ -0,0 +1,635 @@
+/// Automatic Meeting Recorder
+///
+/// Watches which apps are using system audio (`SystemAudioDetector`) and acts
+/// on user rules: when an app matching a rule starts a call, the recorder
+/// either prompts the user, starts a recording named after the app, or ignores
+/// it. Rules are checked in order, so an "ignore" rule placed first wins over
+/// a broader rule below it.
+///
+/// Recordings the recorder started (directly or from an accepted prompt) are
+/// stopped again once the system audio has carried no speech for the
+/// configured time. Silence is measured by running the VAD (`audio::vad`) over
+/// the system audio the pipeline sees, so hold music or a muted call count as
+/// silence while people talking keeps the recording going.
+///
+/// Starting and stopping go through the main window, like the tray menu, so
+/// the meeting is created and saved by the usual frontend flow.
+
+pub mod commands;
+
+use crate::audio::idle_detector::SilenceClock;
+use crate::audio::vad::ContinuousVadProcessor;
+use crate::audio::{new_system_audio_callback, SystemAudioDetector, SystemAudioEvent};
+use crate::notifications::commands::NotificationManagerState;
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+/// Shortest and longest silence before an automatic recording stops
+pub const MIN_SILENCE_TIMEOUT_SECS: u64 = 30;
+pub const MAX_SILENCE_TIMEOUT_SECS: u64 = 3600;
+
+/// How often the silence watcher checks an automatic recording
+const WATCH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Time the frontend gets to actually start a recording the recorder asked for
+const START_GRACE: Duration = Duration::from_secs(30);
+
+/// Redemption time for the silence VAD; short, it only needs speech / no speech
+const SILENCE_VAD_REDEMPTION_MS: u32 = 400;
+
+static SETTINGS: Lazy<Mutex<Option<AutoRecorderSettings>>> = Lazy::new(|| Mutex::new(None));
+static DETECTOR: Lazy<Mutex<Option<SystemAudioDetector>>> = Lazy::new(|| Mutex::new(None));
+static PENDING_PROMPT: Mutex<Option<MeetingDetection>> = Mutex::new(None);
+// Rule whose prompt was dismissed; not offered again until its app goes quiet
+static DISMISSED_RULE: Mutex<Option<String>> = Mutex::new(None);
+static ACTIVE_SESSION: Mutex<Option<AutoSession>> = Mutex::new(None);
+static SILENCE_MONITOR: Lazy<Mutex<Option<SilenceMonitor>>> = Lazy::new(|| Mutex::new(None));
+// Fast check for the audio pipeline before it takes the monitor lock
+static MONITORING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AutoRecordPolicy {
+    /// Notify and let the user start the recording
+    Prompt,
+    /// Start recording right away
+    AutoStart,
+    /// Do nothing
+    Ignore,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AutoRecordRule {
+    /// Shown in prompts and used for the meeting name ("Zoom")
+    pub name: String,
+    /// Case-insensitive parts of the app names to match ("zoom", "teams").
+    /// Browser-based meetings are matched by the browser's name.
+    pub app_patterns: Vec<String>,
+    pub policy: AutoRecordPolicy,
+}
+
+impl AutoRecordRule {
+    fn new(name: &str, app_patterns: &[&str], policy: AutoRecordPolicy) -> Self {
+        Self {
+            name: name.to_string(),
+            app_patterns: app_patterns.iter().map(|p| p.to_string()).collect(),
+            policy,
+        }
+    }
+
+    /// First app in `apps` this rule matches
+    fn matching_app<'a>(&self, apps: &'a [String]) -> Option<&'a String> {
+        apps.iter().find(|app| {
+            let app = app.to_lowercase();
+            self.app_patterns
+                .iter()
+                .map(|pattern| pattern.trim().to_lowercase())
+                .any(|pattern| !pattern.is_empty() && app.contains(&pattern))
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AutoRecorderSettings {
+    pub enabled: bool,
+    pub rules: Vec<AutoRecordRule>,
+    /// Stop automatic recordings after `silence_timeout_secs` without speech
+    pub auto_stop: bool,
+    pub silence_timeout_secs: u64,
+}
+
+impl Default for AutoRecorderSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            rules: vec![
+                AutoRecordRule::new("Zoom", &["zoom"], AutoRecordPolicy::Prompt),
+                AutoRecordRule::new("Microsoft Teams", &["teams"], AutoRecordPolicy::Prompt),
+                AutoRecordRule::new("Webex", &["webex"], AutoRecordPolicy::Prompt),
+                AutoRecordRule::new(
+                    "Google Meet",
+                    &["google chrome", "chromium", "firefox", "microsoft edge", "safari"],
+                    AutoRecordPolicy::Ignore,
+                ),
+            ],
+            auto_stop: true,
+            silence_timeout_secs: 180,
+        }
+    }
+}
+
+impl AutoRecorderSettings {
+    pub fn validate(&self) -> Result<()> {
+        if !(MIN_SILENCE_TIMEOUT_SECS..=MAX_SILENCE_TIMEOUT_SECS).contains(&self.silence_timeout_secs) {
+            return Err(anyhow!(
+                "Silence timeout must be between {} and {} seconds",
+                MIN_SILENCE_TIMEOUT_SECS,
+                MAX_SILENCE_TIMEOUT_SECS
+            ));
+        }
+        for rule in &self.rules {
+            if rule.name.trim().is_empty() {
+                return Err(anyhow!("Rule name cannot be empty"));
+            }
+            if rule.app_patterns.iter().all(|p| p.trim().is_empty()) {
+                return Err(anyhow!("Rule '{}' needs at least one app name", rule.name));
+            }
+        }
+        Ok(())
+    }
+
+    /// First rule matching one of `apps`, with the app it matched
+    pub fn find_rule<'a>(&'a self, apps: &[String]) -> Option<(&'a AutoRecordRule, String)> {
+        self.rules
+            .iter()
+            .find_map(|rule| rule.matching_app(apps).map(|app| (rule, app.clone())))
+    }
+}
+
+/// A meeting app the recorder reacted to
+#[derive(Debug, Clone, Serialize)]
+pub struct MeetingDetection {
+    pub rule_name: String,
+    pub app_name: String,
+    pub meeting_name: String,
+}
+
+impl MeetingDetection {
+    fn new(rule: &AutoRecordRule, app_name: String) -> Self {
+        Self {
+            rule_name: rule.name.clone(),
+            app_name,
+            meeting_name: format!(
+                "{} meeting {}",
+                rule.name,
+                chrono::Local::now().format("%Y-%m-%d %H:%M")
+            ),
+        }
+    }
+}
+
+/// A recording started by the recorder
+struct AutoSession {
+    detection: MeetingDetection,
+    requested_at: Instant,
+    recording_seen: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AutoRecorderStatus {
+    pub enabled: bool,
+    pub detecting: bool,
+    pub pending_prompt: Option<MeetingDetection>,
+    pub active_recording: Option<MeetingDetection>,
+    /// Seconds without speech in the automatic recording
+    pub silent_secs: Option<f64>,
+}
+
+/// VAD over the system audio of an automatic recording
+struct SilenceMonitor {
+    vad: Option<ContinuousVadProcessor>,
+    clock: SilenceClock,
+    last_observed: Instant,
+}
+
+impl SilenceMonitor {
+    fn new() -> Self {
+        Self {
+            vad: None,
+            clock: SilenceClock::default(),
+            last_observed: Instant::now(),
+        }
+    }
+
+    fn observe(&mut self, samples: &[f32], sample_rate: u32) {
+        if self.vad.is_none() {
+            match ContinuousVadProcessor::new(sample_rate, SILENCE_VAD_REDEMPTION_MS) {
+                Ok(vad) => self.vad = Some(vad),
+                Err(e) => {
+                    warn!("Auto-recorder could not create its VAD: {}", e);
+                    return;
+                }
+            }
+        }
+        let Some(vad) = self.vad.as_mut() else {
+            return;
+        };
+        let speech = match vad.process_audio(samples) {
+            Ok(segments) => !segments.is_empty() || vad.pending_speech_start_ms().is_some(),
+            Err(_) => false,
+        };
+        self.clock.observe(samples.len(), sample_rate, speech);
+        self.last_observed = Instant::now();
+    }
+
+    /// Silence in the audio seen so far, plus any time no audio arrived at all
+    fn silent_secs(&self) -> f64 {
+        self.clock.silent_secs() + self.last_observed.elapsed().as_secs_f64()
+    }
+}
+
+/// Feed system audio from the recording pipeline. Cheap no-op unless an
+/// automatic recording is running.
+pub fn observe_system_audio(samples: &[f32], sample_rate: u32) {
+    if !MONITORING.load(Ordering::Relaxed) || samples.is_empty() {
+        return;
+    }
+    if let Ok(mut monitor) = SILENCE_MONITOR.lock() {
+        if let Some(monitor) = monitor.as_mut() {
+            monitor.observe(samples, sample_rate);
+        }
+    }
+}
+
+fn start_silence_monitor() {
+    if let Ok(mut monitor) = SILENCE_MONITOR.lock() {
+        *monitor = Some(SilenceMonitor::new());
+    }
+    MONITORING.store(true, Ordering::SeqCst);
+}
+
+fn stop_silence_monitor() {
+    MONITORING.store(false, Ordering::SeqCst);
+    if let Ok(mut monitor) = SILENCE_MONITOR.lock() {
+        *monitor = None;
+    }
+}
+
+fn silent_secs() -> Option<f64> {
+    SILENCE_MONITOR
+        .lock()
+        .ok()
+        .and_then(|monitor| monitor.as_ref().map(SilenceMonitor::silent_secs))
+}
+
+fn settings_path() -> Result<PathBuf> {
+    let mut path = dirs::config_dir().ok_or_else(|| anyhow!("Could not find config directory"))?;
+    path.push("meetily");
+    path.push("auto_recorder.json");
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    Ok(path)
+}
+
+/// Current settings, loaded from disk on first use
+pub async fn settings() -> AutoRecorderSettings {
+    if let Some(settings) = SETTINGS.lock().ok().and_then(|s| s.clone()) {
+        return settings;
+    }
+
+    let loaded = match load_settings().await {
+        Ok(settings) => settings,
+        Err(e) => {
+            warn!("Failed to load auto-recorder settings, using defaults: {}", e);
+            AutoRecorderSettings::default()
+        }
+    };
+    if let Ok(mut current) = SETTINGS.lock() {
+        *current = Some(loaded.clone());
+    }
+    loaded
+}
+
+async fn load_settings() -> Result<AutoRecorderSettings> {
+    let path = settings_path()?;
+    if !path.exists() {
+        return Ok(AutoRecorderSettings::default());
+    }
+    let content = tokio::fs::read_to_string(&path).await?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Validate, store and apply new settings
+pub async fn save_settings<R: Runtime>(app: &AppHandle<R>, settings: AutoRecorderSettings) -> Result<()> {
+    settings.validate()?;
+    let content = serde_json::to_string_pretty(&settings)?;
+    tokio::fs::write(settings_path()?, content).await?;
+
+    if let Ok(mut current) = SETTINGS.lock() {
+        *current = Some(settings.clone());
+    }
+    info!(
+        "Saved auto-recorder settings: enabled={}, {} rule(s)",
+        settings.enabled,
+        settings.rules.len()
+    );
+    apply_settings(app, &settings);
+    Ok(())
+}
+
+/// Start or stop meeting detection to match `settings`
+fn apply_settings<R: Runtime>(app: &AppHandle<R>, settings: &AutoRecorderSettings) {
+    let Ok(mut detector) = DETECTOR.lock() else {
+        return;
+    };
+
+    if !settings.enabled {
+        if let Some(mut running) = detector.take() {
+            running.stop();
+            info!("Auto-recorder meeting detection stopped");
+        }
+        clear_prompt();
+        return;
+    }
+
+    if detector.is_none() {
+        let app = app.clone();
+        let mut new_detector = SystemAudioDetector::new();
+        new_detector.start(new_system_audio_callback(move |event| {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                handle_event(&app, event).await;
+            });
+        }));
+        *detector = Some(new_detector);
+        info!("✅ Auto-recorder meeting detection started");
+    }
+}
+
+/// Load settings and start detection if enabled (app startup)
+pub async fn init<R: Runtime>(app: &AppHandle<R>) {
+    let settings = settings().await;
+    apply_settings(app, &settings);
+}
+
+pub fn is_detecting() -> bool {
+    DETECTOR.lock().map(|d| d.is_some()).unwrap_or(false)
+}
+
+pub async fn status() -> AutoRecorderStatus {
+    let settings = settings().await;
+    let active_recording = ACTIVE_SESSION
+        .lock()
+        .ok()
+        .and_then(|s| s.as_ref().map(|s| s.detection.clone()));
+    AutoRecorderStatus {
+        enabled: settings.enabled,
+        detecting: is_detecting(),
+        pending_prompt: PENDING_PROMPT.lock().ok().and_then(|p| p.clone()),
+        silent_secs: active_recording.as_ref().and_then(|_| silent_secs()),
+        active_recording,
+    }
+}
+
+async fn handle_event<R: Runtime>(app: &AppHandle<R>, event: SystemAudioEvent) {
+    match event {
+        SystemAudioEvent::SystemAudioStarted(apps) => {
+            let settings = settings().await;
+            if !settings.enabled || crate::audio::recording_commands::is_recording().await {
+                return;
+            }
+            let Some((rule, app_name)) = settings.find_rule(&apps) else {
+                return;
+            };
+            let detection = MeetingDetection::new(rule, app_name);
+
+            match rule.policy {
+                AutoRecordPolicy::Ignore => {
+                    info!("Auto-recorder ignoring {} ({})", detection.app_name, rule.name);
+                }
+                AutoRecordPolicy::AutoStart => {
+                    start_recording_for(app, detection).await;
+                }
+                AutoRecordPolicy::Prompt => {
+                    let dismissed = DISMISSED_RULE
+                        .lock()
+                        .map(|d| d.as_deref() == Some(rule.name.as_str()))
+                        .unwrap_or(false);
+                    let already_prompted = PENDING_PROMPT
+                        .lock()
+                        .map(|p| p.as_ref().map_or(false, |p| p.rule_name == rule.name))
+                        .unwrap_or(false);
+                    if dismissed || already_prompted {
+                        return;
+                    }
+
+                    info!("Auto-recorder offering to record {}", detection.app_name);
+                    if let Ok(mut pending) = PENDING_PROMPT.lock() {
+                        *pending = Some(detection.clone());
+                    }
+                    notify(app, &detection.rule_name, false).await;
+                    let _ = app.emit("auto-recorder-prompt", &detection);
+                }
+            }
+        }
+        SystemAudioEvent::SystemAudioStopped => {
+            // The call app went quiet: forget dismissals and stale prompts.
+            // Running recordings stop on VAD silence, not here.
+            if let Ok(mut dismissed) = DISMISSED_RULE.lock() {
+                *dismissed = None;
+            }
+            clear_prompt();
+        }
+    }
+}
+
+fn clear_prompt() {
+    if let Ok(mut pending) = PENDING_PROMPT.lock() {
+        *pending = None;
+    }
+}
+
+async fn notify<R: Runtime>(app: &AppHandle<R>, rule_name: &str, auto_started: bool) {
+    let Some(manager_state) = app.try_state::<NotificationManagerState<R>>() else {
+        return;
+    };
+    let manager = manager_state.read().await;
+    if let Some(manager) = manager.as_ref() {
+        if let Err(e) = manager.show_meeting_detected(rule_name, auto_started).await {
+            warn!("Failed to show meeting detected notification: {}", e);
+        }
+    }
+}
+
+/// Accept the pending prompt. Returns false if there was none.
+pub async fn accept_prompt<R: Runtime>(app: &AppHandle<R>) -> bool {
+    let detection = PENDING_PROMPT.lock().ok().and_then(|mut p| p.take());
+    match detection {
+        Some(detection) => {
+            start_recording_for(app, detection).await;
+            true
+        }
+        None => false,
+    }
+}
+
+/// Dismiss the pending prompt until its app stops using audio
+pub fn dismiss_prompt() {
+    let detection = PENDING_PROMPT.lock().ok().and_then(|mut p| p.take());
+    if let Some(detection) = detection {
+        info!("Auto-recorder prompt for {} dismissed", detection.app_name);
+        if let Ok(mut dismissed) = DISMISSED_RULE.lock() {
+            *dismissed = Some(detection.rule_name);
+        }
+    }
+}
+
+/// Ask the main window to start a recording named after the meeting app,
+/// the same way the tray menu does
+async fn start_recording_for<R: Runtime>(app: &AppHandle<R>, detection: MeetingDetection) {
+    if crate::audio::recording_commands::is_recording().await {
+        return;
+    }
+    let Some(window) = app.get_webview_window("main") else {
+        warn!("Auto-recorder cannot start recording: main window not found");
+        return;
+    };
+
+    info!("🎙️ Auto-recorder starting recording '{}'", detection.meeting_name);
+    let meeting_name = serde_json::to_string(&detection.meeting_name).unwrap_or_default();
+    let _ = window.eval(&format!(
+        "sessionStorage.setItem('autoStartMeetingName', {})",
+        meeting_name
+    ));
+    let _ = window.eval("sessionStorage.setItem('autoStartRecording', 'true')");
+    let _ = window.eval("window.location.assign('/')");
+
+    notify(app, &detection.rule_name, true).await;
+    let _ = app.emit("auto-recorder-started", &detection);
+
+    let auto_stop = settings().await.auto_stop;
+    if let Ok(mut session) = ACTIVE_SESSION.lock() {
+        *session = Some(AutoSession {
+            detection,
+            requested_at: Instant::now(),
+            recording_seen: false,
+        });
+    }
+    if auto_stop {
+        start_silence_monitor();
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            watch_silence(app).await;
+        });
+    }
+}
+
+fn end_session() {
+    if let Ok(mut session) = ACTIVE_SESSION.lock() {
+        *session = None;
+    }
+    stop_silence_monitor();
+}
+
+/// Stop the automatic recording once it has been silent long enough. Also
+/// ends the session if the recording never started or was stopped by hand.
+async fn watch_silence<R: Runtime>(app: AppHandle<R>) {
+    let mut ticker = tokio::time::interval(WATCH_INTERVAL);
+    loop {
+        ticker.tick().await;
+
+        let recording = crate::audio::recording_commands::is_recording().await;
+        let (requested_at, recording_seen) = match ACTIVE_SESSION.lock() {
+            Ok(mut session) => match session.as_mut() {
+                Some(session) => {
+                    session.recording_seen |= recording;
+                    (session.requested_at, session.recording_seen)
+                }
+                None => break,
+            },
+            Err(_) => break,
+        };
+
+        if !recording {
+            if recording_seen || requested_at.elapsed() > START_GRACE {
+                info!("Auto-recorder session ended without auto-stop");
+                end_session();
+                break;
+            }
+            continue;
+        }
+        if crate::audio::recording_commands::is_recording_paused().await {
+            continue;
+        }
+
+        let timeout = settings().await.silence_timeout_secs as f64;
+        let silent = silent_secs().unwrap_or(0.0);
+        if silent >= timeout {
+            info!("🛑 Auto-recorder stopping recording after {:.0}s of silence", silent);
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.eval("window.handleRecordingStop()");
+            }
+            let detection = ACTIVE_SESSION
+                .lock()
+                .ok()
+                .and_then(|s| s.as_ref().map(|s| s.detection.clone()));
+            let _ = app.emit("auto-recorder-stopped", &detection);
+            end_session();
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apps(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn test_rules_match_in_order() {
+        let mut settings = AutoRecorderSettings::default();
+        let (rule, app) = settings.find_rule(&apps(&["Spotify", "zoom.us"])).unwrap();
+        assert_eq!(rule.name, "Zoom");
+        assert_eq!(app, "zoom.us");
+        assert!(settings.find_rule(&apps(&["Spotify"])).is_none());
+
+        // An ignore rule listed first shadows the rules below it
+        settings
+            .rules
+            .insert(0, AutoRecordRule::new("Zoom webinars", &["ZOOM"], AutoRecordPolicy::Ignore));
+        let (rule, _) = settings.find_rule(&apps(&["zoom.us"])).unwrap();
+        assert_eq!(rule.policy, AutoRecordPolicy::Ignore);
+    }
+
+    #[test]
+    fn test_blank_patterns_never_match() {
+        let rule = AutoRecordRule::new("Empty", &["  "], AutoRecordPolicy::AutoStart);
+        assert!(rule.matching_app(&apps(&["anything"])).is_none());
+    }
+
+    #[test]
+    fn test_settings_validation() {
+        assert!(AutoRecorderSettings::default().validate().is_ok());
+
+        let mut settings = AutoRecorderSettings::default();
+        settings.silence_timeout_secs = 5;
+        assert!(settings.validate().is_err());
+
+        let mut settings = AutoRecorderSettings::default();
+        settings.rules.push(AutoRecordRule::new("Blank", &[""], AutoRecordPolicy::Prompt));
+        assert!(settings.validate().is_err());
+    }
+
+    #[test]
+    fn test_policy_serialization() {
+        assert_eq!(
+            serde_json::to_string(&AutoRecordPolicy::AutoStart).unwrap(),
+            "\"auto-start\""
+        );
+    }
+
+    #[test]
+    fn test_silence_clock() {
+        let mut clock = SilenceClock::default();
+        assert_eq!(clock.silent_secs(), 0.0);
+
+        clock.observe(16000, 16000, true);
+        assert_eq!(clock.silent_secs(), 0.0);
+        clock.observe(32000, 16000, false);
+        assert_eq!(clock.silent_secs(), 2.0);
+        clock.observe(8000, 16000, true);
+        assert_eq!(clock.silent_secs(), 0.0);
+    }
+}
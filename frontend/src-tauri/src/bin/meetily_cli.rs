@@ -0,0 +1,8 @@
+// Headless entry point: `meetily-cli --help` (see src/cli)
+
+fn main() {
+    // Keep the console quiet unless RUST_LOG asks for more; logs go to stderr
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn")).init();
+
+    std::process::exit(app_lib::cli::run());
+}
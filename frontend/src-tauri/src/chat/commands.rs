@@ -5,7 +5,12 @@ use crate::database::repositories::{
     transcript_chunk::TranscriptChunksRepository,
 };
 use crate::state::AppState;
-use crate::summary::llm_client::{ChatMessage, ChatTemplateKwargs, stream_chat, LLMProvider};
+use crate::summary::backends::BackendConfig;
+use crate::summary::failover::{self, ChainLink, LlmChain};
+use crate::summary::usage::UsageRecorder;
+use crate::summary::llm_client::{
+    ChatMessage, ChatTemplateKwargs, CompletionParams, stream_chat, LLMProvider,
+};
 use log::{error as log_error, info as log_info};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
@@ -63,18 +68,38 @@ pub async fn api_chat_send_message<R: Runtime>(
         .ok_or_else(|| format!("Meeting not found: {}", request.meeting_id))?;
 
     // Parse provider
-    let provider = match request.provider.as_str() {
-        "openai" => LLMProvider::OpenAI,
-        "claude" => LLMProvider::Claude,
-        "groq" => LLMProvider::Groq,
-        "ollama" => LLMProvider::Ollama,
-        "openrouter" => LLMProvider::OpenRouter,
-        "openai-compatible" => LLMProvider::OpenAICompatible,
-        _ => return Err(format!("Unsupported provider: {}", request.provider)),
+    let provider = LLMProvider::from_str(&request.provider)
+        .map_err(|_| format!("Unsupported provider: {}", request.provider))?;
+    let capabilities = provider.backend().capabilities();
+
+    // Get API key from request; optional for local backends
+    let api_key = match request.api_key.clone() {
+        Some(key) => key,
+        None if !capabilities.requires_api_key => String::new(),
+        None => return Err("API key is required for this provider".to_string()),
     };
 
-    // Get endpoint (for Ollama and OpenAI-compatible)
-    let endpoint = request.endpoint.clone();
+    // Endpoint only applies to backends that accept one (Ollama, llama.cpp, ...)
+    let endpoint = request
+        .endpoint
+        .clone()
+        .filter(|_| capabilities.custom_endpoint);
+    let config = BackendConfig::new(api_key, endpoint);
+
+    // The requested model first, then the fallbacks configured for summaries
+    let primary = ChainLink {
+        provider: provider.clone(),
+        model: request.model.clone(),
+        config: config.clone(),
+    };
+    let fallbacks = failover::resolve_fallbacks(pool, &primary).await;
+    let chain = LlmChain::new(primary, fallbacks).with_usage(UsageRecorder {
+        pool: pool.clone(),
+        meeting_id: Some(request.meeting_id.clone()),
+    });
+
+    // Create HTTP client
+    let client = Client::new();
 
     // Get transcript text - prefer transcript_chunks for KV cache compatibility with summary
     // Fallback to combining transcripts table if transcript_chunks doesn't exist
@@ -105,32 +130,16 @@ pub async fn api_chat_send_message<R: Runtime>(
         }
     };
 
-    // Get endpoint (for Ollama and OpenAI-compatible)
-    let endpoint = request.endpoint.clone();
-
-    // Separate endpoints for Ollama and OpenAI-compatible
-    let ollama_endpoint = if provider == LLMProvider::Ollama {
-        endpoint.as_deref()
-    } else {
-        None
-    };
-
-    let openai_compatible_endpoint = if provider == LLMProvider::OpenAICompatible {
-        endpoint.as_deref()
-    } else {
-        None
-    };
-
     // Build complete message history with system prompt
     // Chunking logic is handled inside processor::build_chat_messages
     // This may return an error if transcript exceeds single-chunk limit
     let messages = processor::build_chat_messages(
         &meeting,
         &transcript_text,
+        &client,
         &provider,
         &request.model,
-        ollama_endpoint,
-        openai_compatible_endpoint,
+        &config,
         request.user_messages.clone(),
         &request.current_message,
     )
@@ -144,60 +153,30 @@ pub async fn api_chat_send_message<R: Runtime>(
         messages.len()
     );
 
-    // Get API key from request or settings
-    let api_key = if let Some(key) = request.api_key {
-        key
-    } else {
-        // For Ollama, API key is optional
-        if matches!(provider, LLMProvider::Ollama) {
-            String::new()
-        } else {
-            return Err("API key is required for this provider".to_string());
-        }
-    };
-
     // Use meeting_id as request_id for streaming events
     let request_id = request.meeting_id.clone();
 
-    // Set default parameters if not provided
-    let temperature = request.temperature;
-    let top_p = request.top_p;
-    let max_tokens = request.max_tokens;
-    let repeat_penalty = request.repeat_penalty;
-    let repeat_last_n = request.repeat_last_n;
-    let chat_template_kwargs = request.chat_template_kwargs.clone();
-
-    // Create HTTP client
-    let client = Client::new();
+    // Parameters left unset are omitted from the request
+    let completion_params = CompletionParams {
+        temperature: request.temperature,
+        top_p: request.top_p,
+        max_tokens: request.max_tokens,
+        repeat_penalty: request.repeat_penalty,
+        repeat_last_n: request.repeat_last_n,
+        chat_template_kwargs: request.chat_template_kwargs.clone(),
+    };
 
     // Spawn background task for streaming
-    // Clone endpoints for move into async block
-    let ollama_endpoint_clone = ollama_endpoint.map(|s| s.to_string());
-    let openai_compatible_endpoint_clone = openai_compatible_endpoint.map(|s| s.to_string());
-
     tauri::async_runtime::spawn(async move {
         log_info!("Starting streaming chat for request_id: {}", request_id);
 
-        // Use cloned endpoints
-        let ollama_endpoint = ollama_endpoint_clone.as_deref();
-        let openai_compatible_endpoint = openai_compatible_endpoint_clone.as_deref();
-
         match stream_chat(
             app.clone(),
             &client,
-            &provider,
-            &request.model,
-            &api_key,
+            &chain,
             messages,
             request_id.clone(),
-            ollama_endpoint,
-            openai_compatible_endpoint,
-            temperature,
-            top_p,
-            max_tokens,
-            repeat_penalty,
-            repeat_last_n,
-            chat_template_kwargs,
+            completion_params,
         )
         .await
         {
@@ -1,83 +1,73 @@
 use crate::api::MeetingDetails;
-use crate::ollama::metadata::ModelMetadataCache;
+use crate::summary::backends::BackendConfig;
+use crate::summary::context_window::{context_window, source_token_budget};
 use crate::summary::llm_client::{ChatMessage, LLMProvider};
-use crate::summary::processor::{chunk_text, rough_token_count};
-use once_cell::sync::Lazy;
-use std::time::Duration;
+use crate::summary::processor::{chunk_transcript, rough_token_count};
+use reqwest::Client;
 use tracing::{info, warn};
 
-// Global cache for model metadata (5 minute TTL) - same as summary service
-static METADATA_CACHE: Lazy<ModelMetadataCache> = Lazy::new(|| {
-    ModelMetadataCache::new(Duration::from_secs(300))
-});
+/// Token threshold above which summary/processor chunks the transcript
+///
+/// Only local backends are chunked for chat: hosted providers get the full
+/// transcript as before, and their prompt caches don't depend on the layout.
+async fn chat_token_threshold(
+    client: &Client,
+    provider: &LLMProvider,
+    model_name: &str,
+    config: &BackendConfig,
+) -> Option<usize> {
+    if !provider.backend().capabilities().local {
+        return None;
+    }
+    let context_size = context_window(client, provider, model_name, config).await;
+    let threshold = source_token_budget(context_size, None);
+    info!(
+        "✓ Using context window for {}: {} tokens (chunk size: {})",
+        model_name, context_size, threshold
+    );
+    Some(threshold)
+}
 
 /// Gets the first chunk of transcript using the same chunking logic as summary/processor
 /// This ensures KV cache compatibility - chat and summary will use the same first chunk
 ///
 /// # Arguments
 /// * `text` - Full transcript text
+/// * `client` - Reqwest HTTP client (for the context window lookup)
 /// * `provider` - LLM provider
-/// * `model_name` - Model name (for Ollama context size lookup)
-/// * `ollama_endpoint` - Optional Ollama endpoint
-/// * `openai_compatible_endpoint` - Optional OpenAI-compatible endpoint
+/// * `model_name` - Model name (for the context window lookup)
+/// * `config` - API key and endpoint for the provider
 ///
 /// # Returns
 /// First chunk of transcript (or full text if no chunking needed)
 pub async fn get_transcript_chunk_for_chat(
     text: &str,
+    client: &Client,
     provider: &LLMProvider,
     model_name: &str,
-    ollama_endpoint: Option<&str>,
-    _openai_compatible_endpoint: Option<&str>,
+    config: &BackendConfig,
 ) -> String {
-    // Calculate token threshold - same logic as summary/processor
-    // Only Ollama uses endpoint for metadata lookup, but we accept openai_compatible_endpoint
-    // for consistency with summary/processor signature
-    let token_threshold = if provider == &LLMProvider::Ollama {
-        match METADATA_CACHE.get_or_fetch(model_name, ollama_endpoint).await {
-            Ok(metadata) => {
-                // Reserve 300 tokens for prompt overhead (same as summary)
-                let optimal = metadata.context_size.saturating_sub(300);
-                info!(
-                    "✓ Using dynamic context for {}: {} tokens (chunk size: {})",
-                    model_name, metadata.context_size, optimal
-                );
-                optimal
-            }
-            Err(e) => {
-                warn!(
-                    "⚠️ Failed to fetch context for {}: {}. Using default 4000",
-                    model_name, e
-                );
-                4000  // Fallback to safe default
-            }
+    let total_tokens = rough_token_count(text);
+    let token_threshold = match chat_token_threshold(client, provider, model_name, config).await {
+        Some(threshold) if total_tokens > threshold => threshold,
+        _ => {
+            // Single-pass: use full transcript (no chunking needed)
+            info!("Using full transcript (tokens: {})", total_tokens);
+            return text.to_string();
         }
-    } else {
-        // Cloud providers (OpenAI, Claude, Groq, OpenAI-compatible) handle large contexts automatically
-        100000  // Effectively unlimited for single-pass processing
     };
 
-    // Chunk transcript using the same logic as summary/processor
-    let total_tokens = rough_token_count(text);
-    info!("Transcript length: {} tokens, threshold: {}", total_tokens, token_threshold);
-
-    if provider != &LLMProvider::Ollama || total_tokens < token_threshold {
-        // Single-pass: use full transcript (no chunking needed)
-        info!("Using full transcript (tokens: {} < threshold: {})", total_tokens, token_threshold);
-        text.to_string()
-    } else {
-        // Multi-level: chunk and use first chunk (same as summary's first chunk)
-        info!(
-            "Chunking transcript (tokens: {} >= threshold: {}), using first chunk",
-            total_tokens, token_threshold
-        );
-        let chunks = chunk_text(text, token_threshold - 300, 100);
-        if chunks.is_empty() {
+    // Multi-level: chunk and use first chunk (same as summary's first chunk)
+    info!(
+        "Chunking transcript (tokens: {} > threshold: {}), using first chunk",
+        total_tokens, token_threshold
+    );
+    let chunks = chunk_transcript(text, token_threshold);
+    match chunks.into_iter().next() {
+        Some(first_chunk) => first_chunk,
+        None => {
             warn!("Chunking resulted in empty chunks, using full transcript");
             text.to_string()
-        } else {
-            info!("Using first chunk of {} chunks (length: {} chars)", chunks.len(), chunks[0].len());
-            chunks[0].clone()
         }
     }
 }
@@ -104,32 +94,25 @@ Please answer the user's questions based on the {} above. Be concise and accurat
 /// # Arguments
 /// * `meeting` - MeetingDetails object with title and date
 /// * `transcript_text` - Full transcript text (will be chunked if needed)
+/// * `client` - Reqwest HTTP client (for the context window lookup)
 /// * `provider` - LLM provider
-/// * `model_name` - Model name (for Ollama context size lookup)
-/// * `ollama_endpoint` - Optional Ollama endpoint
-/// * `openai_compatible_endpoint` - Optional OpenAI-compatible endpoint
+/// * `model_name` - Model name (for the context window lookup)
+/// * `config` - API key and endpoint for the provider
 ///
 /// # Returns
 /// Ok(String) with formatted system prompt, or Err(String) if transcript exceeds single-chunk limit
 pub async fn build_chat_system_prompt(
     meeting: &MeetingDetails,
     transcript_text: &str,
+    client: &Client,
     provider: &LLMProvider,
     model_name: &str,
-    ollama_endpoint: Option<&str>,
-    _openai_compatible_endpoint: Option<&str>,
+    config: &BackendConfig,
 ) -> Result<String, String> {
     info!("Building chat system prompt for meeting: {}", meeting.id);
 
     // Calculate token threshold (same logic as summary/processor)
-    let token_threshold = if provider == &LLMProvider::Ollama {
-        match METADATA_CACHE.get_or_fetch(model_name, ollama_endpoint).await {
-            Ok(metadata) => metadata.context_size.saturating_sub(300),
-            Err(_) => 4000,
-        }
-    } else {
-        100000
-    };
+    let token_threshold = chat_token_threshold(client, provider, model_name, config).await;
 
     let total_tokens = rough_token_count(transcript_text);
 
@@ -137,9 +120,9 @@ pub async fn build_chat_system_prompt(
     let formatted_date = &meeting.created_at;
 
     // Use different prompt format based on token threshold to match summary/processor's KV cache
-    let system_prompt = if provider != &LLMProvider::Ollama || total_tokens < token_threshold {
-        // Token < threshold: Match summary's final prompt format (uses full transcript with <transcript_chunks>)
-        info!("Chat using full transcript (tokens: {} < threshold: {}) - matching summary's final prompt", total_tokens, token_threshold);
+    let system_prompt = if token_threshold.map_or(true, |threshold| total_tokens <= threshold) {
+        // Token <= threshold: Match summary's final prompt format (uses full transcript with <transcript_chunks>)
+        info!("Chat using full transcript (tokens: {}, threshold: {:?}) - matching summary's final prompt", total_tokens, token_threshold);
         let prompt_suffix = build_chat_prompt_suffix(&meeting.title, formatted_date, "transcript");
         format!(
             r#"<transcript_chunks>
@@ -149,9 +132,10 @@ pub async fn build_chat_system_prompt(
             prompt_suffix
         )
     } else {
-        // Token >= threshold: Match summary's chunk prompt format (uses first chunk with <transcript_chunk>)
-        info!("Chat using first chunk (tokens: {} >= threshold: {}) - matching summary's chunk prompt", total_tokens, token_threshold);
-        let chunks = chunk_text(transcript_text, token_threshold - 300, 100);
+        let token_threshold = token_threshold.unwrap_or_default();
+        // Token > threshold: Match summary's chunk prompt format (uses first chunk with <transcript_chunk>)
+        info!("Chat using first chunk (tokens: {} > threshold: {}) - matching summary's chunk prompt", total_tokens, token_threshold);
+        let chunks = chunk_transcript(transcript_text, token_threshold);
 
         // Safety check: If chunking results in multiple chunks, chat cannot handle it
         if chunks.len() > 1 {
@@ -191,10 +175,10 @@ pub async fn build_chat_system_prompt(
 /// # Arguments
 /// * `meeting` - MeetingDetails object for context
 /// * `transcript_text` - Full transcript text (will be chunked if needed)
+/// * `client` - Reqwest HTTP client (for the context window lookup)
 /// * `provider` - LLM provider
-/// * `model_name` - Model name (for Ollama context size lookup)
-/// * `ollama_endpoint` - Optional Ollama endpoint
-/// * `openai_compatible_endpoint` - Optional OpenAI-compatible endpoint
+/// * `model_name` - Model name (for the context window lookup)
+/// * `config` - API key and endpoint for the provider
 /// * `user_messages` - Previous chat messages (user and assistant)
 /// * `current_message` - Current user message
 ///
@@ -203,10 +187,10 @@ pub async fn build_chat_system_prompt(
 pub async fn build_chat_messages(
     meeting: &MeetingDetails,
     transcript_text: &str,
+    client: &Client,
     provider: &LLMProvider,
     model_name: &str,
-    ollama_endpoint: Option<&str>,
-    openai_compatible_endpoint: Option<&str>,
+    config: &BackendConfig,
     user_messages: Vec<ChatMessage>,
     current_message: &str,
 ) -> Result<Vec<ChatMessage>, String> {
@@ -219,10 +203,10 @@ pub async fn build_chat_messages(
         content: build_chat_system_prompt(
             meeting,
             transcript_text,
+            client,
             provider,
             model_name,
-            ollama_endpoint,
-            openai_compatible_endpoint,
+            config,
         )
         .await?,
     });
@@ -0,0 +1,623 @@
+use super::{
+    Cli, Command, Engine, ExportArgs, MeetingsCommand, ModelsCommand, Output, SummarizeArgs,
+    TranscribeArgs,
+};
+use crate::api::{SearchFilters, TranscriptSegment};
+use crate::audio::transcription::TranscriptionEngine;
+use crate::database::manager::DatabaseManager;
+use crate::database::repositories::{
+    action_item::ActionItemsRepository, meeting::MeetingsRepository, search::SearchRepository,
+    setting::SettingsRepository, summary::SummaryProcessesRepository,
+    transcript::TranscriptsRepository, transcript_chunk::TranscriptChunksRepository,
+};
+use crate::hooks::{self, HookContext, HookEvent};
+use crate::media_import::commands::{
+    decode_for_transcription, detect_speech_segments, transcribe_segments,
+};
+use crate::media_import::IMPORT_SAMPLE_RATE;
+use crate::parakeet_engine::ParakeetEngine;
+use crate::summary::llm_client::CompletionParams;
+use crate::summary::SummaryService;
+use crate::whisper_engine::WhisperEngine;
+use chrono::Utc;
+use log::{info as log_info, warn as log_warn};
+use serde_json::json;
+use sqlx::SqlitePool;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
+
+pub(super) async fn dispatch(cli: Cli, data_dir: PathBuf) -> Result<Output, String> {
+    let models_dir = data_dir.join("models");
+
+    match cli.command {
+        // Model management doesn't touch the database
+        Command::Models(command) => models(&models_dir, command).await,
+        Command::Templates => templates(),
+        command => {
+            let db = open_database(&data_dir).await?;
+            let pool = db.pool();
+            match command {
+                Command::Transcribe(args) => transcribe(pool, &models_dir, args).await,
+                Command::Summarize(args) => summarize(pool, args).await,
+                Command::Meetings(command) => meetings(pool, command).await,
+                Command::Export(args) => export(pool, args).await,
+                Command::Models(_) | Command::Templates => unreachable!(),
+            }
+        }
+    }
+}
+
+async fn open_database(data_dir: &Path) -> Result<DatabaseManager, String> {
+    std::fs::create_dir_all(data_dir)
+        .map_err(|e| format!("Failed to create {}: {}", data_dir.display(), e))?;
+    let tauri_db_path = data_dir.join("meeting_minutes.sqlite");
+    let backend_db_path = data_dir.join("meeting_minutes.db");
+    DatabaseManager::new(
+        &tauri_db_path.to_string_lossy(),
+        &backend_db_path.to_string_lossy(),
+    )
+    .await
+    .map_err(|e| format!("Failed to open database: {}", e))
+}
+
+async fn transcribe(
+    pool: &SqlitePool,
+    models_dir: &Path,
+    args: TranscribeArgs,
+) -> Result<Output, String> {
+    if !args.file.is_file() {
+        return Err(format!("File not found: {}", args.file.display()));
+    }
+    if let Some(language) = args.language {
+        crate::set_language_preference_internal(language);
+    }
+    if let Err(e) = crate::transcript_rules::reload_active_rules(pool).await {
+        log_warn!("Failed to load transcript replacement rules: {}", e);
+    }
+
+    // Fall back to the engine and model picked in the app's settings
+    let configured = SettingsRepository::get_transcript_config(pool)
+        .await
+        .map_err(|e| format!("Failed to read transcript settings: {}", e))?;
+    let configured_engine = match configured.as_ref().map(|c| c.provider.as_str()) {
+        Some("parakeet") => Engine::Parakeet,
+        _ => Engine::Whisper,
+    };
+    let engine_kind = args.engine.unwrap_or(configured_engine);
+    let configured_model = configured
+        .filter(|c| match engine_kind {
+            Engine::Whisper => c.provider == "localWhisper",
+            Engine::Parakeet => c.provider == "parakeet",
+        })
+        .map(|c| c.model);
+    let (engine, model_name) =
+        load_engine(models_dir, engine_kind, args.model.or(configured_model)).await?;
+    log_info!("Transcribing {} with {}", args.file.display(), model_name);
+
+    let samples = decode_for_transcription(&args.file).await?;
+    let duration = samples.len() as f64 / IMPORT_SAMPLE_RATE as f64;
+    let segments = detect_speech_segments(samples, 0.0).await?;
+    let transcripts = transcribe_segments(&engine, segments, Utc::now(), |i, total| {
+        log_info!("Transcribing segment {}/{}", i + 1, total);
+        Ok(())
+    })
+    .await?;
+
+    let meeting_id = if args.save {
+        let title = args
+            .title
+            .filter(|t| !t.trim().is_empty())
+            .or_else(|| {
+                args.file
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .map(|s| s.to_string())
+            })
+            .unwrap_or_else(|| format!("Import {}", Utc::now().format("%Y-%m-%d %H:%M:%S")));
+        let meeting_id = TranscriptsRepository::save_transcript(pool, &title, &transcripts, None)
+            .await
+            .map_err(|e| format!("Failed to save meeting: {}", e))?;
+        log_info!("Saved transcript as meeting {}", meeting_id);
+        hooks::dispatch_once(
+            pool,
+            HookEvent::TranscriptFinalized,
+            HookContext::meeting(&meeting_id),
+        )
+        .await;
+        Some(meeting_id)
+    } else {
+        None
+    };
+
+    let mut text = transcript_text(&transcripts);
+    if let Some(id) = &meeting_id {
+        text.push_str(&format!("\n\nSaved as meeting {}", id));
+    }
+    Output::new(
+        json!({
+            "file": args.file,
+            "engine": engine.provider_name(),
+            "model": model_name,
+            "duration_seconds": duration,
+            "meeting_id": meeting_id,
+            "segments": transcripts,
+        }),
+        text,
+    )
+}
+
+/// Load `model` (or the first downloaded model) into a fresh engine
+async fn load_engine(
+    models_dir: &Path,
+    kind: Engine,
+    model: Option<String>,
+) -> Result<(TranscriptionEngine, String), String> {
+    match kind {
+        Engine::Whisper => {
+            let engine = whisper_engine(models_dir)?;
+            let models = engine
+                .discover_models()
+                .await
+                .map_err(|e| format!("Failed to discover Whisper models: {}", e))?;
+            let name = pick_model(
+                model,
+                models
+                    .iter()
+                    .filter(|m| matches!(m.status, crate::whisper_engine::ModelStatus::Available))
+                    .map(|m| m.name.clone()),
+            )?;
+            engine
+                .load_model(&name)
+                .await
+                .map_err(|e| format!("Failed to load model {}: {}", name, e))?;
+            Ok((TranscriptionEngine::Whisper(Arc::new(engine)), name))
+        }
+        Engine::Parakeet => {
+            let engine = parakeet_engine(models_dir)?;
+            let models = engine
+                .discover_models()
+                .await
+                .map_err(|e| format!("Failed to discover Parakeet models: {}", e))?;
+            let name = pick_model(
+                model,
+                models
+                    .iter()
+                    .filter(|m| matches!(m.status, crate::parakeet_engine::ModelStatus::Available))
+                    .map(|m| m.name.clone()),
+            )?;
+            engine
+                .load_model(&name)
+                .await
+                .map_err(|e| format!("Failed to load model {}: {}", name, e))?;
+            Ok((TranscriptionEngine::Parakeet(Arc::new(engine)), name))
+        }
+    }
+}
+
+fn pick_model(
+    requested: Option<String>,
+    mut downloaded: impl Iterator<Item = String>,
+) -> Result<String, String> {
+    requested.or_else(|| downloaded.next()).ok_or_else(|| {
+        "No downloaded model found, run `meetily-cli models download <name>` first".to_string()
+    })
+}
+
+fn whisper_engine(models_dir: &Path) -> Result<WhisperEngine, String> {
+    WhisperEngine::new_with_models_dir(Some(models_dir.to_path_buf()))
+        .map_err(|e| format!("Failed to initialize Whisper engine: {}", e))
+}
+
+fn parakeet_engine(models_dir: &Path) -> Result<ParakeetEngine, String> {
+    ParakeetEngine::new_with_models_dir(Some(models_dir.to_path_buf()))
+        .map_err(|e| format!("Failed to initialize Parakeet engine: {}", e))
+}
+
+/// One line per segment: "[mm:ss] text"
+fn transcript_text(transcripts: &[TranscriptSegment]) -> String {
+    transcripts
+        .iter()
+        .map(|t| {
+            let secs = t.audio_start_time.unwrap_or(0.0).max(0.0) as u64;
+            format!("[{:02}:{:02}] {}", secs / 60, secs % 60, t.text)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+async fn summarize(pool: &SqlitePool, args: SummarizeArgs) -> Result<Output, String> {
+    crate::summary::templates::get_template(&args.template)?;
+
+    let config = SettingsRepository::get_model_config(pool)
+        .await
+        .map_err(|e| format!("Failed to read model settings: {}", e))?;
+    let completion_params = config
+        .as_ref()
+        .and_then(|c| c.completion_params.as_deref())
+        .and_then(|params| serde_json::from_str::<CompletionParams>(params).ok());
+    let provider = args
+        .provider
+        .or_else(|| config.as_ref().map(|c| c.provider.clone()))
+        .ok_or("No summary provider configured, pass --provider")?;
+    let model = args
+        .model
+        .or_else(|| config.map(|c| c.model))
+        .ok_or("No summary model configured, pass --model")?;
+
+    if let Some(path) = args.file {
+        let text = tokio::fs::read_to_string(&path)
+            .await
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let summary = SummaryService::generate(
+            pool,
+            &provider,
+            &model,
+            &text,
+            &args.prompt,
+            &args.template,
+            &args.language,
+            completion_params,
+        )
+        .await?;
+        return Output::new(
+            json!({
+                "file": path,
+                "provider": provider,
+                "model": model,
+                "template": args.template,
+                "markdown": summary.markdown,
+                "structured": summary.structured,
+                "chunk_count": summary.num_chunks,
+                "total_time_us": summary.total_time_us,
+            }),
+            summary.markdown,
+        );
+    }
+
+    let meeting_id = args.meeting.ok_or("Pass --meeting or --file")?;
+    let meeting = MeetingsRepository::get_meeting(pool, &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load meeting: {}", e))?
+        .ok_or_else(|| format!("Meeting not found: {}", meeting_id))?;
+    let text = meeting
+        .transcripts
+        .iter()
+        .map(|t| t.text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n");
+    if text.trim().is_empty() {
+        return Err(format!("Meeting {} has no transcript", meeting_id));
+    }
+
+    // Same bookkeeping as api_process_transcript, but waiting for the result
+    SummaryProcessesRepository::create_or_reset_process(pool, &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to initialize process: {}", e))?;
+    TranscriptChunksRepository::save_transcript_data(
+        pool,
+        &meeting_id,
+        &text,
+        &provider,
+        &model,
+        40000,
+        1000,
+    )
+    .await
+    .map_err(|e| format!("Failed to save transcript data: {}", e))?;
+
+    let result = SummaryService::process_transcript(
+        pool,
+        &meeting_id,
+        &text,
+        &provider,
+        &model,
+        &args.prompt,
+        &args.template,
+        &args.language,
+        completion_params,
+        None,
+    )
+    .await;
+
+    // Run the hooks here: the process exits as soon as we return. Failed
+    // deliveries are left for the app to retry.
+    match &result {
+        Ok(()) => {
+            hooks::dispatch_once(
+                pool,
+                HookEvent::SummaryCompleted,
+                HookContext::meeting(&meeting_id),
+            )
+            .await
+        }
+        Err(e) => {
+            let context = HookContext {
+                error: Some(e.clone()),
+                ..HookContext::meeting(&meeting_id)
+            };
+            hooks::dispatch_once(pool, HookEvent::SummaryFailed, context).await
+        }
+    }
+    result?;
+
+    let process = SummaryProcessesRepository::get_summary_data(pool, &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load summary: {}", e))?
+        .ok_or_else(|| format!("No summary process for meeting {}", meeting_id))?;
+    let result: serde_json::Value = process
+        .result
+        .as_deref()
+        .and_then(|r| serde_json::from_str(r).ok())
+        .unwrap_or_default();
+    let markdown = result["markdown"].as_str().unwrap_or_default().to_string();
+
+    Output::new(
+        json!({
+            "meeting_id": meeting_id,
+            "provider": provider,
+            "model": model,
+            "template": args.template,
+            "markdown": markdown,
+            "structured": result.get("structured"),
+            "chunk_count": process.chunk_count,
+            "processing_time": process.processing_time,
+        }),
+        markdown,
+    )
+}
+
+async fn meetings(pool: &SqlitePool, command: MeetingsCommand) -> Result<Output, String> {
+    match command {
+        MeetingsCommand::List => {
+            let meetings = MeetingsRepository::get_meetings(pool)
+                .await
+                .map_err(|e| format!("Failed to get meetings: {}", e))?;
+            let text = meetings
+                .iter()
+                .map(|m| {
+                    format!(
+                        "{}  {}  {}",
+                        m.id,
+                        m.created_at.0.format("%Y-%m-%d %H:%M"),
+                        m.title
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            let meetings: Vec<_> = meetings
+                .into_iter()
+                .map(|m| {
+                    json!({
+                        "id": m.id,
+                        "title": m.title,
+                        "created_at": m.created_at.0.to_rfc3339(),
+                        "updated_at": m.updated_at.0.to_rfc3339(),
+                        "folder_path": m.folder_path,
+                    })
+                })
+                .collect();
+            Output::new(meetings, text)
+        }
+        MeetingsCommand::Search {
+            query,
+            meeting,
+            sources,
+            from,
+            to,
+            limit,
+        } => {
+            let filters = SearchFilters {
+                meeting_id: meeting,
+                source_types: sources,
+                from_date: from,
+                to_date: to,
+                limit,
+            };
+            let results = SearchRepository::search(pool, &query, &filters)
+                .await
+                .map_err(|e| format!("Failed to search: {}", e))?;
+            let text = results
+                .iter()
+                .map(|r| {
+                    let snippet = r.snippet.replace("<mark>", "").replace("</mark>", "");
+                    format!(
+                        "{} [{}] {}\n    {}",
+                        r.meeting_id, r.source_type, r.meeting_title, snippet
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            Output::new(results, text)
+        }
+        MeetingsCommand::ActionItems { meeting: None } => {
+            let items = ActionItemsRepository::list_open(pool)
+                .await
+                .map_err(|e| format!("Failed to load action items: {}", e))?;
+            let text = items
+                .iter()
+                .map(|entry| {
+                    format!(
+                        "{}  {}  {}  ({})",
+                        entry.item.due_date.as_deref().unwrap_or("----------"),
+                        entry.item.owner.as_deref().unwrap_or("-"),
+                        entry.item.text,
+                        entry.meeting_title
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            Output::new(items, text)
+        }
+        MeetingsCommand::ActionItems {
+            meeting: Some(meeting_id),
+        } => {
+            let items = ActionItemsRepository::list_for_meeting(pool, &meeting_id)
+                .await
+                .map_err(|e| format!("Failed to load action items: {}", e))?;
+            let text = items
+                .iter()
+                .map(|item| {
+                    format!(
+                        "[{}] {}  {}  {}",
+                        if item.status == "done" { "x" } else { " " },
+                        item.due_date.as_deref().unwrap_or("----------"),
+                        item.owner.as_deref().unwrap_or("-"),
+                        item.text
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            Output::new(items, text)
+        }
+    }
+}
+
+async fn export(pool: &SqlitePool, args: ExportArgs) -> Result<Output, String> {
+    let path = crate::export::commands::export_meeting(
+        pool,
+        &args.meeting_id,
+        args.format,
+        &args.output.to_string_lossy(),
+    )
+    .await?;
+    Output::new(
+        json!({ "meeting_id": args.meeting_id, "format": args.format, "path": path }),
+        format!("Exported {} to {}", args.meeting_id, path),
+    )
+}
+
+fn templates() -> Result<Output, String> {
+    let templates: Vec<_> = crate::summary::templates::list_templates()
+        .into_iter()
+        .map(
+            |(id, name, description)| json!({ "id": id, "name": name, "description": description }),
+        )
+        .collect();
+    let text = templates
+        .iter()
+        .map(|t| {
+            format!(
+                "{:<24} {}",
+                t["id"].as_str().unwrap_or_default(),
+                t["name"].as_str().unwrap_or_default()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    Output::new(templates, text)
+}
+
+async fn models(models_dir: &Path, command: ModelsCommand) -> Result<Output, String> {
+    match command {
+        ModelsCommand::List {
+            engine: Engine::Whisper,
+        } => {
+            let models = whisper_engine(models_dir)?
+                .discover_models()
+                .await
+                .map_err(|e| format!("Failed to discover Whisper models: {}", e))?;
+            let text = models
+                .iter()
+                .map(|m| format!("{:<32} {:>6} MB  {:?}", m.name, m.size_mb, m.status))
+                .collect::<Vec<_>>()
+                .join("\n");
+            Output::new(models, text)
+        }
+        ModelsCommand::List {
+            engine: Engine::Parakeet,
+        } => {
+            let models = parakeet_engine(models_dir)?
+                .discover_models()
+                .await
+                .map_err(|e| format!("Failed to discover Parakeet models: {}", e))?;
+            let text = models
+                .iter()
+                .map(|m| format!("{:<32} {:>6} MB  {:?}", m.name, m.size_mb, m.status))
+                .collect::<Vec<_>>()
+                .join("\n");
+            Output::new(models, text)
+        }
+        ModelsCommand::Download { name, engine } => {
+            let result = match engine {
+                Engine::Whisper => {
+                    let engine = whisper_engine(models_dir)?;
+                    engine
+                        .discover_models()
+                        .await
+                        .map_err(|e| format!("Failed to discover Whisper models: {}", e))?;
+                    engine
+                        .download_model(&name, Some(progress_printer(&name)))
+                        .await
+                }
+                Engine::Parakeet => {
+                    let engine = parakeet_engine(models_dir)?;
+                    engine
+                        .discover_models()
+                        .await
+                        .map_err(|e| format!("Failed to discover Parakeet models: {}", e))?;
+                    engine
+                        .download_model(&name, Some(progress_printer(&name)))
+                        .await
+                }
+            };
+            result.map_err(|e| format!("Failed to download {}: {}", name, e))?;
+            Output::new(
+                json!({ "model": name, "status": "downloaded" }),
+                format!("Downloaded {}", name),
+            )
+        }
+        ModelsCommand::Delete { name, engine } => {
+            let message = match engine {
+                Engine::Whisper => {
+                    let engine = whisper_engine(models_dir)?;
+                    engine
+                        .discover_models()
+                        .await
+                        .map_err(|e| format!("Failed to discover Whisper models: {}", e))?;
+                    engine.delete_model(&name).await
+                }
+                Engine::Parakeet => {
+                    let engine = parakeet_engine(models_dir)?;
+                    engine
+                        .discover_models()
+                        .await
+                        .map_err(|e| format!("Failed to discover Parakeet models: {}", e))?;
+                    engine.delete_model(&name).await
+                }
+            }
+            .map_err(|e| format!("Failed to delete {}: {}", name, e))?;
+            Output::new(json!({ "model": name, "status": "deleted" }), message)
+        }
+    }
+}
+
+/// Download progress on stderr, so it never mixes with `--json` output
+fn progress_printer(name: &str) -> Box<dyn Fn(u8) + Send> {
+    let name = name.to_string();
+    let last = AtomicU8::new(u8::MAX);
+    Box::new(move |progress| {
+        if last.swap(progress, Ordering::Relaxed) != progress {
+            eprint!("\rDownloading {}: {:>3}%", name, progress);
+            if progress >= 100 {
+                eprintln!();
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pick_model() {
+        let downloaded = || vec!["base".to_string(), "small".to_string()].into_iter();
+        assert_eq!(
+            pick_model(Some("tiny".to_string()), downloaded()).unwrap(),
+            "tiny"
+        );
+        assert_eq!(pick_model(None, downloaded()).unwrap(), "base");
+        assert!(pick_model(None, std::iter::empty()).is_err());
+    }
+}
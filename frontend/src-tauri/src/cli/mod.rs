@@ -0,0 +1,361 @@
+/// Headless Command Line Interface
+///
+/// The `meetily-cli` binary runs the app's transcription, summary, export and
+/// model management code without a window, for batch jobs on servers. It
+/// opens the same SQLite database and models directory as the desktop app
+/// (`--data-dir` points it elsewhere) and reuses the LLM and transcription
+/// settings stored there.
+///
+/// Every command prints plain text by default and a single JSON document with
+/// `--json`. Failures exit with status 1; with `--json` the error is printed
+/// as `{"error": "..."}` on stdout as well.
+
+mod handlers;
+
+use crate::export::ExportFormat;
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Tauri bundle identifier, which names the app data directory
+const APP_IDENTIFIER: &str = "com.meetily.ai";
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "meetily-cli",
+    version,
+    about = "Batch transcription, summaries and exports without the desktop app"
+)]
+pub struct Cli {
+    /// Print JSON instead of text
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    /// App data directory with the database and models [default: the desktop app's]
+    #[arg(long, global = true, value_name = "DIR")]
+    pub data_dir: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Transcribe an audio or video file, optionally saving it as a meeting
+    Transcribe(TranscribeArgs),
+    /// Summarize a saved meeting or a plain-text transcript
+    Summarize(SummarizeArgs),
+    /// List or search saved meetings and their action items
+    #[command(subcommand)]
+    Meetings(MeetingsCommand),
+    /// Export a meeting to a file
+    Export(ExportArgs),
+    /// List the available summary templates
+    Templates,
+    /// Manage local transcription models
+    #[command(subcommand)]
+    Models(ModelsCommand),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Engine {
+    Whisper,
+    Parakeet,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct TranscribeArgs {
+    /// Audio or video file (anything the media import accepts)
+    pub file: PathBuf,
+
+    /// Transcription engine [default: the one configured in the app]
+    #[arg(long, value_enum)]
+    pub engine: Option<Engine>,
+
+    /// Model name [default: the configured model, else the first downloaded one]
+    #[arg(long)]
+    pub model: Option<String>,
+
+    /// Spoken language code, "auto" or "auto-translate"
+    #[arg(long)]
+    pub language: Option<String>,
+
+    /// Save the transcript as a new meeting
+    #[arg(long)]
+    pub save: bool,
+
+    /// Title of the saved meeting [default: the file name]
+    #[arg(long, requires = "save")]
+    pub title: Option<String>,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct SummarizeArgs {
+    /// Meeting to summarize; the summary is stored with the meeting
+    #[arg(long, conflicts_with = "file", required_unless_present = "file")]
+    pub meeting: Option<String>,
+
+    /// Plain-text transcript to summarize; nothing is stored
+    #[arg(long)]
+    pub file: Option<PathBuf>,
+
+    /// Summary template id (see `templates`)
+    #[arg(long, default_value = "daily_standup")]
+    pub template: String,
+
+    /// LLM provider, e.g. "ollama" or "openai" [default: the app's]
+    #[arg(long)]
+    pub provider: Option<String>,
+
+    /// LLM model name [default: the app's]
+    #[arg(long)]
+    pub model: Option<String>,
+
+    /// Summary language code
+    #[arg(long, default_value = "en")]
+    pub language: String,
+
+    /// Extra context for the summary prompt
+    #[arg(long, default_value = "")]
+    pub prompt: String,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum MeetingsCommand {
+    /// List all meetings, newest first
+    List,
+    /// Full-text search over transcripts, summaries and chat
+    Search {
+        query: String,
+
+        /// Only search this meeting
+        #[arg(long)]
+        meeting: Option<String>,
+
+        /// Comma-separated sources: transcript, summary, chat
+        #[arg(long, value_delimiter = ',')]
+        sources: Option<Vec<String>>,
+
+        /// Meetings created on or after this date (YYYY-MM-DD)
+        #[arg(long)]
+        from: Option<String>,
+
+        /// Meetings created on or before this date (YYYY-MM-DD)
+        #[arg(long)]
+        to: Option<String>,
+
+        #[arg(long)]
+        limit: Option<i64>,
+    },
+    /// List open action items of all meetings, or every item of one meeting
+    ActionItems {
+        #[arg(long)]
+        meeting: Option<String>,
+    },
+}
+
+#[derive(Debug, clap::Args)]
+pub struct ExportArgs {
+    pub meeting_id: String,
+
+    #[arg(long, value_enum)]
+    pub format: ExportFormat,
+
+    /// Output file; the format's extension is added if missing
+    #[arg(long, short)]
+    pub output: PathBuf,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ModelsCommand {
+    /// List known models and whether they are downloaded
+    List {
+        #[arg(long, value_enum, default_value = "whisper")]
+        engine: Engine,
+    },
+    /// Download a model
+    Download {
+        name: String,
+
+        #[arg(long, value_enum, default_value = "whisper")]
+        engine: Engine,
+    },
+    /// Delete a downloaded model
+    Delete {
+        name: String,
+
+        #[arg(long, value_enum, default_value = "whisper")]
+        engine: Engine,
+    },
+}
+
+/// Command output: `json` for `--json`, `text` otherwise
+pub(crate) struct Output {
+    pub json: serde_json::Value,
+    pub text: String,
+}
+
+impl Output {
+    pub fn new(value: impl Serialize, text: impl Into<String>) -> Result<Self, String> {
+        Ok(Self {
+            json: serde_json::to_value(value)
+                .map_err(|e| format!("Failed to serialize output: {}", e))?,
+            text: text.into(),
+        })
+    }
+}
+
+fn default_data_dir() -> Result<PathBuf, String> {
+    dirs::data_dir()
+        .map(|dir| dir.join(APP_IDENTIFIER))
+        .ok_or_else(|| "Could not find the data directory, pass --data-dir".to_string())
+}
+
+/// Entry point of the `meetily-cli` binary. Returns the process exit code.
+pub fn run() -> i32 {
+    let cli = Cli::parse();
+    let json = cli.json;
+
+    let result = match cli
+        .data_dir
+        .clone()
+        .map(Ok)
+        .unwrap_or_else(default_data_dir)
+    {
+        Ok(data_dir) => tauri::async_runtime::block_on(handlers::dispatch(cli, data_dir)),
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(output) if json => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&output.json).unwrap_or_default()
+            );
+            0
+        }
+        Ok(output) => {
+            if !output.text.is_empty() {
+                println!("{}", output.text);
+            }
+            0
+        }
+        Err(e) => {
+            if json {
+                println!("{}", serde_json::json!({ "error": e }));
+            }
+            eprintln!("Error: {}", e);
+            1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_cli_definition() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_parse_transcribe() {
+        let cli = Cli::try_parse_from([
+            "meetily-cli",
+            "transcribe",
+            "call.mp4",
+            "--engine",
+            "parakeet",
+            "--save",
+            "--title",
+            "Weekly sync",
+            "--json",
+        ])
+        .unwrap();
+        assert!(cli.json);
+        match cli.command {
+            Command::Transcribe(args) => {
+                assert_eq!(args.file, PathBuf::from("call.mp4"));
+                assert_eq!(args.engine, Some(Engine::Parakeet));
+                assert!(args.save);
+                assert_eq!(args.title.as_deref(), Some("Weekly sync"));
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+
+        // A title only makes sense for a saved meeting
+        assert!(
+            Cli::try_parse_from(["meetily-cli", "transcribe", "a.wav", "--title", "x"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_summarize_requires_one_source() {
+        assert!(Cli::try_parse_from(["meetily-cli", "summarize"]).is_err());
+        assert!(Cli::try_parse_from([
+            "meetily-cli",
+            "summarize",
+            "--meeting",
+            "meeting-1",
+            "--file",
+            "notes.txt",
+        ])
+        .is_err());
+
+        let cli = Cli::try_parse_from(["meetily-cli", "summarize", "--file", "notes.txt"]).unwrap();
+        match cli.command {
+            Command::Summarize(args) => {
+                assert_eq!(args.template, "daily_standup");
+                assert_eq!(args.language, "en");
+                assert!(args.meeting.is_none());
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_search_and_export() {
+        let cli = Cli::try_parse_from([
+            "meetily-cli",
+            "meetings",
+            "search",
+            "budget",
+            "--sources",
+            "transcript,summary",
+        ])
+        .unwrap();
+        match cli.command {
+            Command::Meetings(MeetingsCommand::Search { query, sources, .. }) => {
+                assert_eq!(query, "budget");
+                assert_eq!(
+                    sources,
+                    Some(vec!["transcript".to_string(), "summary".to_string()])
+                );
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+
+        let cli = Cli::try_parse_from(["meetily-cli", "meetings", "action-items"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::Meetings(MeetingsCommand::ActionItems { meeting: None })
+        ));
+
+        let cli = Cli::try_parse_from([
+            "meetily-cli",
+            "export",
+            "meeting-1",
+            "--format",
+            "markdown",
+            "-o",
+            "out.md",
+        ])
+        .unwrap();
+        match cli.command {
+            Command::Export(args) => assert_eq!(args.format, ExportFormat::Markdown),
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+}
@@ -35,6 +35,10 @@ pub struct Transcript {
     pub audio_start_time: Option<f64>,
     pub audio_end_time: Option<f64>,
     pub duration: Option<f64>,
+    // Diarization label, unique within the meeting
+    pub speaker_id: Option<String>,
+    // "mic" / "system" for per-channel recordings
+    pub channel: Option<String>,
 }
 
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
@@ -50,6 +54,9 @@ pub struct SummaryProcess {
     pub chunk_count: i64,
     pub processing_time: f64,
     pub metadata: Option<String>, // JSON
+    pub current_version_id: Option<String>,
+    pub job: Option<String>, // JSON
+    pub attempts: i64,
 }
 
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
@@ -74,6 +81,156 @@ pub struct ChatMessageModel {
     pub ttft_us: Option<i64>,
 }
 
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct GlossaryTerm {
+    pub id: String,
+    /// None for terms that apply to every meeting
+    pub meeting_id: Option<String>,
+    pub term: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ReplacementRule {
+    pub id: String,
+    pub pattern: String,
+    pub replacement: String,
+    pub is_regex: bool,
+    pub case_sensitive: bool,
+    pub whole_word: bool,
+    pub preserve_case: bool,
+    pub enabled: bool,
+    pub position: i64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct Hook {
+    pub id: String,
+    pub name: String,
+    pub event: String,
+    /// "webhook" or "command"
+    pub kind: String,
+    /// URL for webhooks, shell command line for commands
+    pub target: String,
+    pub headers: Option<String>, // JSON object
+    pub payload_template: Option<String>,
+    pub max_attempts: i64,
+    pub enabled: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct HookDelivery {
+    pub id: String,
+    pub hook_id: String,
+    pub event: String,
+    pub meeting_id: Option<String>,
+    /// "pending", "retrying", "queued" (waiting for the app to retry),
+    /// "succeeded" or "failed"
+    pub status: String,
+    pub attempts: i64,
+    /// HTTP status for webhooks, exit code for commands
+    pub response_code: Option<i64>,
+    pub last_error: Option<String>,
+    pub payload: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ActionItem {
+    pub id: String,
+    pub meeting_id: String,
+    pub text: String,
+    pub owner: Option<String>,
+    pub due_date: Option<String>, // YYYY-MM-DD
+    /// Transcript segment the item was found in
+    pub transcript_id: Option<String>,
+    pub confidence: Option<f64>,
+    /// "open" or "done"
+    pub status: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+    pub completed_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Action item with the title of its meeting, for cross-meeting lists
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ActionItemWithMeeting {
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub item: ActionItem,
+    pub meeting_title: String,
+}
+
+/// A saved summary, as generated or after a manual edit
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct SummaryVersion {
+    pub id: String,
+    pub meeting_id: String,
+    /// The version that was current when this one was made
+    pub parent_id: Option<String>,
+    /// "generated" or "edited"
+    pub source: String,
+    pub result: String, // JSON, as in summary_processes.result
+    pub provider: Option<String>,
+    pub model: Option<String>,
+    pub template_id: Option<String>,
+    pub custom_prompt: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// One LLM call, for cost accounting
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct LlmUsage {
+    pub id: i64,
+    pub meeting_id: Option<String>,
+    /// Step that made the call (e.g. "chunk 2/5", "final", "chat")
+    pub stage: String,
+    pub provider: String,
+    pub model: String,
+    pub local: bool,
+    pub prompt_tokens: i64,
+    pub completion_tokens: i64,
+    /// The provider reported no token counts, so they were estimated
+    pub estimated: bool,
+    pub ttft_us: Option<i64>,
+    pub total_time_us: i64,
+    /// None when no price was known for the model
+    pub cost_usd: Option<f64>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// LLM spend summed per meeting, model or month
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct LlmSpend {
+    /// Meeting id, "provider/model" or "YYYY-MM" (UTC), depending on the grouping
+    pub key: String,
+    /// Meeting title when grouped per meeting
+    pub label: Option<String>,
+    pub calls: i64,
+    pub prompt_tokens: i64,
+    pub completion_tokens: i64,
+    pub cost_usd: f64,
+    /// Calls left out of cost_usd because no price was known
+    pub unpriced_calls: i64,
+}
+
+/// Price of a model in USD per million tokens
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct LlmPrice {
+    pub provider: String,
+    pub model: String,
+    pub prompt_usd_per_mtok: f64,
+    pub completion_usd_per_mtok: f64,
+    /// "user" or "openrouter"
+    pub source: String,
+    pub updated_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct Setting {
     pub id: String,
@@ -0,0 +1,143 @@
+use crate::database::models::{ActionItem, ActionItemWithMeeting};
+use chrono::Utc;
+use sqlx::{Connection, Error as SqlxError, SqlitePool};
+use std::collections::HashSet;
+use tracing::info;
+use uuid::Uuid;
+
+/// An extracted action item that hasn't been stored yet
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewActionItem {
+    pub text: String,
+    pub owner: Option<String>,
+    pub due_date: Option<String>,
+    pub transcript_id: Option<String>,
+    pub confidence: Option<f64>,
+}
+
+pub struct ActionItemsRepository;
+
+impl ActionItemsRepository {
+    /// A meeting's items in extraction order
+    pub async fn list_for_meeting(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Vec<ActionItem>, SqlxError> {
+        sqlx::query_as::<_, ActionItem>(
+            "SELECT * FROM action_items WHERE meeting_id = ? ORDER BY created_at ASC, rowid ASC",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Open items of every meeting, earliest due date first (undated last)
+    pub async fn list_open(pool: &SqlitePool) -> Result<Vec<ActionItemWithMeeting>, SqlxError> {
+        sqlx::query_as::<_, ActionItemWithMeeting>(
+            "SELECT a.*, m.title AS meeting_title
+             FROM action_items a
+             JOIN meetings m ON m.id = a.meeting_id
+             WHERE a.status = 'open'
+             ORDER BY a.due_date IS NULL, a.due_date ASC, m.created_at DESC, a.rowid ASC",
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn get_item(
+        pool: &SqlitePool,
+        item_id: &str,
+    ) -> Result<Option<ActionItem>, SqlxError> {
+        sqlx::query_as::<_, ActionItem>("SELECT * FROM action_items WHERE id = ?")
+            .bind(item_id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Replaces the meeting's open items with a fresh extraction. Items already
+    /// marked done are kept, and new items with the same text are skipped so a
+    /// re-run doesn't reopen them.
+    pub async fn replace_extracted(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        items: &[NewActionItem],
+    ) -> Result<Vec<ActionItem>, SqlxError> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+
+        sqlx::query("DELETE FROM action_items WHERE meeting_id = ? AND status = 'open'")
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        let done: HashSet<String> = sqlx::query_scalar::<_, String>(
+            "SELECT LOWER(text) FROM action_items WHERE meeting_id = ?",
+        )
+        .bind(meeting_id)
+        .fetch_all(&mut *transaction)
+        .await?
+        .into_iter()
+        .collect();
+
+        let now = Utc::now();
+        let mut inserted = 0;
+        for item in items
+            .iter()
+            .filter(|item| !done.contains(&item.text.to_lowercase()))
+        {
+            sqlx::query(
+                "INSERT INTO action_items
+                    (id, meeting_id, text, owner, due_date, transcript_id, confidence, status,
+                     created_at, updated_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, 'open', ?, ?)",
+            )
+            .bind(format!("action-{}", Uuid::new_v4()))
+            .bind(meeting_id)
+            .bind(&item.text)
+            .bind(&item.owner)
+            .bind(&item.due_date)
+            .bind(&item.transcript_id)
+            .bind(item.confidence)
+            .bind(now)
+            .bind(now)
+            .execute(&mut *transaction)
+            .await?;
+            inserted += 1;
+        }
+
+        transaction.commit().await?;
+        info!(
+            "Stored {} action items for meeting {} ({} already done)",
+            inserted,
+            meeting_id,
+            done.len()
+        );
+
+        Self::list_for_meeting(pool, meeting_id).await
+    }
+
+    /// Marks an item open or done. Returns None if it doesn't exist.
+    pub async fn set_status(
+        pool: &SqlitePool,
+        item_id: &str,
+        status: &str,
+    ) -> Result<Option<ActionItem>, SqlxError> {
+        let now = Utc::now();
+        let completed_at = (status == "done").then_some(now);
+
+        let result = sqlx::query(
+            "UPDATE action_items SET status = ?, completed_at = ?, updated_at = ? WHERE id = ?",
+        )
+        .bind(status)
+        .bind(completed_at)
+        .bind(now)
+        .bind(item_id)
+        .execute(pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+        Self::get_item(pool, item_id).await
+    }
+}
@@ -0,0 +1,98 @@
+use crate::database::models::GlossaryTerm;
+use chrono::Utc;
+use sqlx::{Error as SqlxError, SqlitePool};
+use tracing::info;
+use uuid::Uuid;
+
+pub struct GlossaryRepository;
+
+impl GlossaryRepository {
+    /// Terms of one scope: user-wide terms for `None`, otherwise only the
+    /// meeting's own terms
+    pub async fn list_terms(
+        pool: &SqlitePool,
+        meeting_id: Option<&str>,
+    ) -> Result<Vec<GlossaryTerm>, SqlxError> {
+        sqlx::query_as::<_, GlossaryTerm>(
+            "SELECT id, meeting_id, term, created_at FROM glossary_terms
+             WHERE meeting_id IS ?
+             ORDER BY created_at ASC",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Terms that apply when transcribing: user-wide terms plus the meeting's
+    /// terms, without case-insensitive duplicates
+    pub async fn get_active_terms(
+        pool: &SqlitePool,
+        meeting_id: Option<&str>,
+    ) -> Result<Vec<String>, SqlxError> {
+        sqlx::query_scalar::<_, String>(
+            "SELECT term FROM glossary_terms
+             WHERE meeting_id IS NULL OR meeting_id = ?
+             GROUP BY LOWER(term)
+             ORDER BY MIN(created_at) ASC",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Add a term to a scope. Adding a term the scope already has
+    /// (ignoring case) returns the existing entry.
+    pub async fn add_term(
+        pool: &SqlitePool,
+        meeting_id: Option<&str>,
+        term: &str,
+    ) -> Result<GlossaryTerm, SqlxError> {
+        let term = term.trim();
+
+        let existing = sqlx::query_as::<_, GlossaryTerm>(
+            "SELECT id, meeting_id, term, created_at FROM glossary_terms
+             WHERE meeting_id IS ? AND LOWER(term) = LOWER(?)",
+        )
+        .bind(meeting_id)
+        .bind(term)
+        .fetch_optional(pool)
+        .await?;
+        if let Some(existing) = existing {
+            return Ok(existing);
+        }
+
+        let glossary_term = GlossaryTerm {
+            id: format!("glossary-{}", Uuid::new_v4()),
+            meeting_id: meeting_id.map(str::to_string),
+            term: term.to_string(),
+            created_at: Utc::now(),
+        };
+
+        sqlx::query(
+            "INSERT INTO glossary_terms (id, meeting_id, term, created_at)
+             VALUES (?, ?, ?, ?)",
+        )
+        .bind(&glossary_term.id)
+        .bind(&glossary_term.meeting_id)
+        .bind(&glossary_term.term)
+        .bind(glossary_term.created_at)
+        .execute(pool)
+        .await?;
+
+        info!(
+            "Added glossary term '{}' ({})",
+            glossary_term.term,
+            meeting_id.unwrap_or("all meetings")
+        );
+        Ok(glossary_term)
+    }
+
+    /// Returns false if the term didn't exist
+    pub async fn delete_term(pool: &SqlitePool, term_id: &str) -> Result<bool, SqlxError> {
+        let result = sqlx::query("DELETE FROM glossary_terms WHERE id = ?")
+            .bind(term_id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,270 @@
+use crate::database::models::{Hook, HookDelivery};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sqlx::{Connection, Error as SqlxError, SqlitePool};
+use tracing::info;
+use uuid::Uuid;
+
+/// Deliveries kept in the log; older ones are dropped as new ones arrive
+const MAX_DELIVERIES: i64 = 1000;
+
+/// Editable fields of a hook
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HookInput {
+    pub name: String,
+    pub event: String,
+    pub kind: String,
+    pub target: String,
+    pub headers: Option<String>,
+    pub payload_template: Option<String>,
+    pub max_attempts: i64,
+    pub enabled: bool,
+}
+
+pub struct HooksRepository;
+
+impl HooksRepository {
+    pub async fn list_hooks(pool: &SqlitePool) -> Result<Vec<Hook>, SqlxError> {
+        sqlx::query_as::<_, Hook>("SELECT * FROM hooks ORDER BY created_at ASC")
+            .fetch_all(pool)
+            .await
+    }
+
+    pub async fn get_hook(pool: &SqlitePool, hook_id: &str) -> Result<Option<Hook>, SqlxError> {
+        sqlx::query_as::<_, Hook>("SELECT * FROM hooks WHERE id = ?")
+            .bind(hook_id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    pub async fn get_enabled_hooks(pool: &SqlitePool, event: &str) -> Result<Vec<Hook>, SqlxError> {
+        sqlx::query_as::<_, Hook>(
+            "SELECT * FROM hooks WHERE event = ? AND enabled = 1 ORDER BY created_at ASC",
+        )
+        .bind(event)
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create_hook(pool: &SqlitePool, input: &HookInput) -> Result<Hook, SqlxError> {
+        let id = format!("hook-{}", Uuid::new_v4());
+        let now = Utc::now();
+
+        sqlx::query(
+            "INSERT INTO hooks
+                (id, name, event, kind, target, headers, payload_template, max_attempts,
+                 enabled, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&id)
+        .bind(&input.name)
+        .bind(&input.event)
+        .bind(&input.kind)
+        .bind(&input.target)
+        .bind(&input.headers)
+        .bind(&input.payload_template)
+        .bind(input.max_attempts)
+        .bind(input.enabled)
+        .bind(now)
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+        info!("Created {} hook {} for '{}'", input.kind, id, input.event);
+
+        sqlx::query_as::<_, Hook>("SELECT * FROM hooks WHERE id = ?")
+            .bind(&id)
+            .fetch_one(pool)
+            .await
+    }
+
+    /// Returns None if the hook doesn't exist
+    pub async fn update_hook(
+        pool: &SqlitePool,
+        hook_id: &str,
+        input: &HookInput,
+    ) -> Result<Option<Hook>, SqlxError> {
+        let result = sqlx::query(
+            "UPDATE hooks SET
+                name = ?, event = ?, kind = ?, target = ?, headers = ?, payload_template = ?,
+                max_attempts = ?, enabled = ?, updated_at = ?
+             WHERE id = ?",
+        )
+        .bind(&input.name)
+        .bind(&input.event)
+        .bind(&input.kind)
+        .bind(&input.target)
+        .bind(&input.headers)
+        .bind(&input.payload_template)
+        .bind(input.max_attempts)
+        .bind(input.enabled)
+        .bind(Utc::now())
+        .bind(hook_id)
+        .execute(pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+        Self::get_hook(pool, hook_id).await
+    }
+
+    /// Deletes the hook and its delivery log. Returns false if it didn't exist.
+    pub async fn delete_hook(pool: &SqlitePool, hook_id: &str) -> Result<bool, SqlxError> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+
+        sqlx::query("DELETE FROM hook_deliveries WHERE hook_id = ?")
+            .bind(hook_id)
+            .execute(&mut *transaction)
+            .await?;
+        let result = sqlx::query("DELETE FROM hooks WHERE id = ?")
+            .bind(hook_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        transaction.commit().await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Starts a delivery log entry in the 'pending' state
+    pub async fn create_delivery(
+        pool: &SqlitePool,
+        hook_id: &str,
+        event: &str,
+        meeting_id: Option<&str>,
+        payload: &str,
+    ) -> Result<String, SqlxError> {
+        let id = format!("delivery-{}", Uuid::new_v4());
+        let now = Utc::now();
+
+        sqlx::query(
+            "INSERT INTO hook_deliveries
+                (id, hook_id, event, meeting_id, status, attempts, payload, created_at, updated_at)
+             VALUES (?, ?, ?, ?, 'pending', 0, ?, ?, ?)",
+        )
+        .bind(&id)
+        .bind(hook_id)
+        .bind(event)
+        .bind(meeting_id)
+        .bind(payload)
+        .bind(now)
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+        sqlx::query(
+            "DELETE FROM hook_deliveries WHERE id NOT IN
+                (SELECT id FROM hook_deliveries ORDER BY created_at DESC LIMIT ?)",
+        )
+        .bind(MAX_DELIVERIES)
+        .execute(pool)
+        .await?;
+
+        Ok(id)
+    }
+
+    /// Records the outcome of an attempt
+    pub async fn update_delivery(
+        pool: &SqlitePool,
+        delivery_id: &str,
+        status: &str,
+        attempts: i64,
+        response_code: Option<i64>,
+        last_error: Option<&str>,
+    ) -> Result<(), SqlxError> {
+        sqlx::query(
+            "UPDATE hook_deliveries SET
+                status = ?, attempts = ?, response_code = ?, last_error = ?, updated_at = ?
+             WHERE id = ?",
+        )
+        .bind(status)
+        .bind(attempts)
+        .bind(response_code)
+        .bind(last_error)
+        .bind(Utc::now())
+        .bind(delivery_id)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Deliveries left for the app to retry, oldest first
+    ///
+    /// A delivery is 'queued' when a process that doesn't wait out retries
+    /// (the CLI) failed its first attempt, or when the app was closed while
+    /// it was waiting for the next attempt.
+    pub async fn list_queued_deliveries(
+        pool: &SqlitePool,
+    ) -> Result<Vec<HookDelivery>, SqlxError> {
+        sqlx::query_as::<_, HookDelivery>(
+            "SELECT * FROM hook_deliveries WHERE status = 'queued' ORDER BY created_at",
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Moves a queued delivery to 'retrying', keeping `updated_at` so the
+    /// backoff counts from the last attempt
+    ///
+    /// # Returns
+    /// False if the delivery was no longer queued
+    pub async fn claim_queued_delivery(
+        pool: &SqlitePool,
+        delivery_id: &str,
+    ) -> Result<bool, SqlxError> {
+        let result = sqlx::query(
+            "UPDATE hook_deliveries SET status = 'retrying' WHERE id = ? AND status = 'queued'",
+        )
+        .bind(delivery_id)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Queues deliveries that were waiting for a retry when the app last
+    /// stopped
+    pub async fn requeue_interrupted_deliveries(pool: &SqlitePool) -> Result<u64, SqlxError> {
+        let result = sqlx::query(
+            "UPDATE hook_deliveries SET status = 'queued' WHERE status = 'retrying'",
+        )
+        .execute(pool)
+        .await?;
+        if result.rows_affected() > 0 {
+            info!(
+                "Queued {} hook deliveries interrupted by the last shutdown",
+                result.rows_affected()
+            );
+        }
+        Ok(result.rows_affected())
+    }
+
+    pub async fn get_delivery(
+        pool: &SqlitePool,
+        delivery_id: &str,
+    ) -> Result<Option<HookDelivery>, SqlxError> {
+        sqlx::query_as::<_, HookDelivery>("SELECT * FROM hook_deliveries WHERE id = ?")
+            .bind(delivery_id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Newest first, optionally for one hook
+    pub async fn list_deliveries(
+        pool: &SqlitePool,
+        hook_id: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<HookDelivery>, SqlxError> {
+        sqlx::query_as::<_, HookDelivery>(
+            "SELECT * FROM hook_deliveries
+             WHERE (? IS NULL OR hook_id = ?)
+             ORDER BY created_at DESC
+             LIMIT ?",
+        )
+        .bind(hook_id)
+        .bind(hook_id)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -0,0 +1,250 @@
+use crate::database::models::{LlmPrice, LlmSpend, LlmUsage};
+use chrono::{DateTime, Utc};
+use sqlx::{Connection, Error as SqlxError, SqlitePool};
+
+/// A finished LLM call that hasn't been stored yet
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewLlmUsage {
+    pub meeting_id: Option<String>,
+    pub stage: String,
+    pub provider: String,
+    pub model: String,
+    pub local: bool,
+    pub prompt_tokens: i64,
+    pub completion_tokens: i64,
+    pub estimated: bool,
+    pub ttft_us: Option<i64>,
+    pub total_time_us: i64,
+    pub cost_usd: Option<f64>,
+}
+
+/// Columns shared by the spend queries; `cost_usd` of unpriced calls counts as 0
+const SPEND_COLUMNS: &str = "COUNT(*) AS calls,
+        COALESCE(SUM(u.prompt_tokens), 0) AS prompt_tokens,
+        COALESCE(SUM(u.completion_tokens), 0) AS completion_tokens,
+        COALESCE(SUM(u.cost_usd), 0.0) AS cost_usd,
+        COALESCE(SUM(u.cost_usd IS NULL), 0) AS unpriced_calls";
+
+pub struct LlmUsageRepository;
+
+impl LlmUsageRepository {
+    pub async fn insert(pool: &SqlitePool, usage: &NewLlmUsage) -> Result<(), SqlxError> {
+        sqlx::query(
+            "INSERT INTO llm_usage
+                (meeting_id, stage, provider, model, local, prompt_tokens, completion_tokens,
+                 estimated, ttft_us, total_time_us, cost_usd, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&usage.meeting_id)
+        .bind(&usage.stage)
+        .bind(&usage.provider)
+        .bind(&usage.model)
+        .bind(usage.local)
+        .bind(usage.prompt_tokens)
+        .bind(usage.completion_tokens)
+        .bind(usage.estimated)
+        .bind(usage.ttft_us)
+        .bind(usage.total_time_us)
+        .bind(usage.cost_usd)
+        .bind(Utc::now())
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// A meeting's calls, oldest first
+    pub async fn list_for_meeting(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Vec<LlmUsage>, SqlxError> {
+        sqlx::query_as::<_, LlmUsage>(
+            "SELECT * FROM llm_usage WHERE meeting_id = ? ORDER BY created_at ASC, id ASC",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Spend per meeting since `since` (all time if None), most expensive first
+    pub async fn spend_by_meeting(
+        pool: &SqlitePool,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<LlmSpend>, SqlxError> {
+        let query = format!(
+            "SELECT u.meeting_id AS key, m.title AS label, {}
+             FROM llm_usage u
+             LEFT JOIN meetings m ON m.id = u.meeting_id
+             WHERE u.meeting_id IS NOT NULL AND (? IS NULL OR u.created_at >= ?)
+             GROUP BY u.meeting_id
+             ORDER BY cost_usd DESC, key ASC",
+            SPEND_COLUMNS
+        );
+        sqlx::query_as::<_, LlmSpend>(&query)
+            .bind(since)
+            .bind(since)
+            .fetch_all(pool)
+            .await
+    }
+
+    /// Spend per provider and model since `since` (all time if None), most
+    /// expensive first
+    pub async fn spend_by_model(
+        pool: &SqlitePool,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<LlmSpend>, SqlxError> {
+        let query = format!(
+            "SELECT u.provider || '/' || u.model AS key, NULL AS label, {}
+             FROM llm_usage u
+             WHERE ? IS NULL OR u.created_at >= ?
+             GROUP BY u.provider, u.model
+             ORDER BY cost_usd DESC, key ASC",
+            SPEND_COLUMNS
+        );
+        sqlx::query_as::<_, LlmSpend>(&query)
+            .bind(since)
+            .bind(since)
+            .fetch_all(pool)
+            .await
+    }
+
+    /// Spend per calendar month (UTC), latest first
+    pub async fn spend_by_month(pool: &SqlitePool) -> Result<Vec<LlmSpend>, SqlxError> {
+        let query = format!(
+            "SELECT substr(u.created_at, 1, 7) AS key, NULL AS label, {}
+             FROM llm_usage u
+             GROUP BY key
+             ORDER BY key DESC",
+            SPEND_COLUMNS
+        );
+        sqlx::query_as::<_, LlmSpend>(&query).fetch_all(pool).await
+    }
+
+    /// Cost of cloud calls since `since`, for budget caps
+    pub async fn cloud_spend_since(
+        pool: &SqlitePool,
+        since: DateTime<Utc>,
+    ) -> Result<f64, SqlxError> {
+        sqlx::query_scalar::<_, f64>(
+            "SELECT COALESCE(SUM(cost_usd), 0.0) FROM llm_usage WHERE local = 0 AND created_at >= ?",
+        )
+        .bind(since)
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Cost of a meeting's cloud calls, for budget caps
+    pub async fn meeting_cloud_spend(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<f64, SqlxError> {
+        sqlx::query_scalar::<_, f64>(
+            "SELECT COALESCE(SUM(cost_usd), 0.0) FROM llm_usage WHERE local = 0 AND meeting_id = ?",
+        )
+        .bind(meeting_id)
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn list_prices(pool: &SqlitePool) -> Result<Vec<LlmPrice>, SqlxError> {
+        sqlx::query_as::<_, LlmPrice>("SELECT * FROM llm_prices ORDER BY provider, model")
+            .fetch_all(pool)
+            .await
+    }
+
+    pub async fn find_price(
+        pool: &SqlitePool,
+        provider: &str,
+        model: &str,
+    ) -> Result<Option<LlmPrice>, SqlxError> {
+        sqlx::query_as::<_, LlmPrice>("SELECT * FROM llm_prices WHERE provider = ? AND model = ?")
+            .bind(provider)
+            .bind(model)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Sets a user price, replacing a synced one for the same model
+    pub async fn save_price(
+        pool: &SqlitePool,
+        provider: &str,
+        model: &str,
+        prompt_usd_per_mtok: f64,
+        completion_usd_per_mtok: f64,
+    ) -> Result<Option<LlmPrice>, SqlxError> {
+        sqlx::query(
+            "INSERT INTO llm_prices
+                (provider, model, prompt_usd_per_mtok, completion_usd_per_mtok, source, updated_at)
+             VALUES (?, ?, ?, ?, 'user', ?)
+             ON CONFLICT(provider, model) DO UPDATE SET
+                prompt_usd_per_mtok = excluded.prompt_usd_per_mtok,
+                completion_usd_per_mtok = excluded.completion_usd_per_mtok,
+                source = 'user',
+                updated_at = excluded.updated_at",
+        )
+        .bind(provider)
+        .bind(model)
+        .bind(prompt_usd_per_mtok)
+        .bind(completion_usd_per_mtok)
+        .bind(Utc::now())
+        .execute(pool)
+        .await?;
+
+        Self::find_price(pool, provider, model).await
+    }
+
+    /// Returns false if there was no such price
+    pub async fn delete_price(
+        pool: &SqlitePool,
+        provider: &str,
+        model: &str,
+    ) -> Result<bool, SqlxError> {
+        let result = sqlx::query("DELETE FROM llm_prices WHERE provider = ? AND model = ?")
+            .bind(provider)
+            .bind(model)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Stores OpenRouter's prices as `(model, prompt, completion)` in USD per
+    /// million tokens. Prices the user entered are left alone.
+    pub async fn save_synced_prices(
+        pool: &SqlitePool,
+        prices: &[(String, f64, f64)],
+    ) -> Result<usize, SqlxError> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+        let now = Utc::now();
+        let mut saved = 0;
+        for (model, prompt, completion) in prices {
+            let result = sqlx::query(
+                "INSERT INTO llm_prices
+                    (provider, model, prompt_usd_per_mtok, completion_usd_per_mtok, source, updated_at)
+                 VALUES ('openrouter', ?, ?, ?, 'openrouter', ?)
+                 ON CONFLICT(provider, model) DO UPDATE SET
+                    prompt_usd_per_mtok = excluded.prompt_usd_per_mtok,
+                    completion_usd_per_mtok = excluded.completion_usd_per_mtok,
+                    updated_at = excluded.updated_at
+                 WHERE llm_prices.source = 'openrouter'",
+            )
+            .bind(model)
+            .bind(prompt)
+            .bind(completion)
+            .bind(now)
+            .execute(&mut *transaction)
+            .await?;
+            saved += result.rows_affected() as usize;
+        }
+        transaction.commit().await?;
+        Ok(saved)
+    }
+
+    /// When OpenRouter's prices were last synced
+    pub async fn last_price_sync(pool: &SqlitePool) -> Result<Option<DateTime<Utc>>, SqlxError> {
+        sqlx::query_scalar::<_, Option<DateTime<Utc>>>(
+            "SELECT MAX(updated_at) FROM llm_prices WHERE source = 'openrouter'",
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
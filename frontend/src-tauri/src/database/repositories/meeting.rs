@@ -1,7 +1,12 @@
 use crate::api::{MeetingDetails, MeetingTranscript};
+use crate::audio::TranscriptChannel;
 use crate::database::models::{MeetingModel, Transcript};
-use chrono::Utc;
+use crate::database::repositories::speaker::SpeakersRepository;
+use crate::database::repositories::transcript_word::TranscriptWordsRepository;
+use crate::diarization::default_speaker_name;
+use chrono::{DateTime, Utc};
 use sqlx::{Connection, Error as SqlxError, SqliteConnection, SqlitePool};
+use std::collections::HashMap;
 use tracing::{error, info};
 
 pub struct MeetingsRepository;
@@ -47,6 +52,46 @@ impl MeetingsRepository {
         }
     }
 
+    /// Gets the meeting row itself (including folder_path), without transcripts
+    pub async fn get_meeting_model(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Option<MeetingModel>, SqlxError> {
+        sqlx::query_as::<_, MeetingModel>(
+            "SELECT id, title, created_at, updated_at, folder_path FROM meetings WHERE id = ?",
+        )
+        .bind(meeting_id)
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Finds the meeting saved from a recordings folder, if any
+    pub async fn get_meeting_by_folder_path(
+        pool: &SqlitePool,
+        folder_path: &str,
+    ) -> Result<Option<MeetingModel>, SqlxError> {
+        sqlx::query_as::<_, MeetingModel>(
+            "SELECT id, title, created_at, updated_at, folder_path FROM meetings WHERE folder_path = ? LIMIT 1",
+        )
+        .bind(folder_path)
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Sets the meeting's creation time, e.g. to the start of a recovered recording
+    pub async fn update_meeting_created_at(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        created_at: DateTime<Utc>,
+    ) -> Result<bool, SqlxError> {
+        let result = sqlx::query("UPDATE meetings SET created_at = ? WHERE id = ?")
+            .bind(created_at)
+            .bind(meeting_id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
     pub async fn get_meeting(
         pool: &SqlitePool,
         meeting_id: &str,
@@ -73,19 +118,49 @@ impl MeetingsRepository {
         }
 
         if let Some(meeting) = meeting {
-            // Get all transcripts for this meeting
-            let transcripts =
-                sqlx::query_as::<_, Transcript>("SELECT * FROM transcripts WHERE meeting_id = ?")
-                    .bind(meeting_id)
-                    .fetch_all(&mut *transaction)
-                    .await?;
+            // Get all transcripts for this meeting, in recording order
+            // (per-channel recordings interleave mic and system segments)
+            let transcripts = sqlx::query_as::<_, Transcript>(
+                "SELECT * FROM transcripts WHERE meeting_id = ?
+                 ORDER BY COALESCE(audio_start_time, 0) ASC, rowid ASC",
+            )
+            .bind(meeting_id)
+            .fetch_all(&mut *transaction)
+            .await?;
 
             transaction.commit().await?;
 
+            // Word timings are optional - a failed lookup shouldn't hide the transcript
+            let mut words = TranscriptWordsRepository::get_words_for_meeting(pool, meeting_id)
+                .await
+                .unwrap_or_else(|e| {
+                    error!("Failed to load word timings for meeting {}: {}", meeting_id, e);
+                    Default::default()
+                });
+
+            let speaker_names: HashMap<String, String> =
+                SpeakersRepository::get_speaker_names(pool, meeting_id)
+                    .await
+                    .unwrap_or_else(|e| {
+                        error!("Failed to load speaker names for meeting {}: {}", meeting_id, e);
+                        Default::default()
+                    })
+                    .into_iter()
+                    .collect();
+
             // Convert Transcript to MeetingTranscript
             let meeting_transcripts = transcripts
                 .into_iter()
                 .map(|t| MeetingTranscript {
+                    words: words.remove(&t.id).unwrap_or_default(),
+                    speaker_name: t.speaker_id.as_ref().map(|id| {
+                        speaker_names
+                            .get(id)
+                            .cloned()
+                            .unwrap_or_else(|| default_speaker_name(id))
+                    }),
+                    speaker_id: t.speaker_id,
+                    channel: t.channel.as_deref().and_then(TranscriptChannel::parse),
                     id: t.id,
                     text: t.transcript,
                     timestamp: t.timestamp,
@@ -173,7 +248,7 @@ impl MeetingsRepository {
     }
 }
 
-async fn delete_meeting_with_transaction(
+pub(crate) async fn delete_meeting_with_transaction(
     transaction: &mut SqliteConnection,
     meeting_id: &str,
 ) -> Result<bool, SqlxError> {
@@ -201,7 +276,9 @@ async fn delete_meeting_with_transaction(
         .execute(&mut *transaction)
         .await?;
 
-    // 3. Delete from transcripts
+    // 3. Delete word timings, then transcripts
+    TranscriptWordsRepository::delete_for_meeting(&mut *transaction, meeting_id).await?;
+
     sqlx::query("DELETE FROM transcripts WHERE meeting_id = ?")
         .bind(meeting_id)
         .execute(&mut *transaction)
@@ -1,6 +1,15 @@
+pub mod action_item;
 pub mod chat_message;
+pub mod glossary;
+pub mod hook;
+pub mod llm_usage;
 pub mod meeting;
+pub mod replacement_rule;
+pub mod search;
 pub mod setting;
+pub mod speaker;
 pub mod summary;
+pub mod summary_version;
 pub mod transcript;
 pub mod transcript_chunk;
+pub mod transcript_word;
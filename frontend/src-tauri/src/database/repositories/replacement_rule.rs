@@ -0,0 +1,149 @@
+use crate::database::models::ReplacementRule;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sqlx::{Connection, Error as SqlxError, SqlitePool};
+use tracing::info;
+use uuid::Uuid;
+
+/// Editable fields of a rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplacementRuleInput {
+    pub pattern: String,
+    pub replacement: String,
+    pub is_regex: bool,
+    pub case_sensitive: bool,
+    pub whole_word: bool,
+    pub preserve_case: bool,
+    pub enabled: bool,
+}
+
+impl From<&ReplacementRule> for ReplacementRuleInput {
+    fn from(rule: &ReplacementRule) -> Self {
+        Self {
+            pattern: rule.pattern.clone(),
+            replacement: rule.replacement.clone(),
+            is_regex: rule.is_regex,
+            case_sensitive: rule.case_sensitive,
+            whole_word: rule.whole_word,
+            preserve_case: rule.preserve_case,
+            enabled: rule.enabled,
+        }
+    }
+}
+
+pub struct ReplacementRulesRepository;
+
+impl ReplacementRulesRepository {
+    /// All rules in the order they are applied
+    pub async fn list_rules(pool: &SqlitePool) -> Result<Vec<ReplacementRule>, SqlxError> {
+        sqlx::query_as::<_, ReplacementRule>(
+            "SELECT * FROM replacement_rules ORDER BY position ASC, created_at ASC",
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn get_enabled_rules(pool: &SqlitePool) -> Result<Vec<ReplacementRule>, SqlxError> {
+        sqlx::query_as::<_, ReplacementRule>(
+            "SELECT * FROM replacement_rules WHERE enabled = 1 ORDER BY position ASC, created_at ASC",
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Adds a rule after the existing ones
+    pub async fn create_rule(
+        pool: &SqlitePool,
+        input: &ReplacementRuleInput,
+    ) -> Result<ReplacementRule, SqlxError> {
+        let id = format!("rule-{}", Uuid::new_v4());
+        let now = Utc::now();
+
+        sqlx::query(
+            "INSERT INTO replacement_rules
+                (id, pattern, replacement, is_regex, case_sensitive, whole_word, preserve_case,
+                 enabled, position, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?,
+                 (SELECT COALESCE(MAX(position), -1) + 1 FROM replacement_rules), ?, ?)",
+        )
+        .bind(&id)
+        .bind(&input.pattern)
+        .bind(&input.replacement)
+        .bind(input.is_regex)
+        .bind(input.case_sensitive)
+        .bind(input.whole_word)
+        .bind(input.preserve_case)
+        .bind(input.enabled)
+        .bind(now)
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+        info!("Created replacement rule {} for '{}'", id, input.pattern);
+
+        sqlx::query_as::<_, ReplacementRule>("SELECT * FROM replacement_rules WHERE id = ?")
+            .bind(&id)
+            .fetch_one(pool)
+            .await
+    }
+
+    /// Returns None if the rule doesn't exist
+    pub async fn update_rule(
+        pool: &SqlitePool,
+        rule_id: &str,
+        input: &ReplacementRuleInput,
+    ) -> Result<Option<ReplacementRule>, SqlxError> {
+        let result = sqlx::query(
+            "UPDATE replacement_rules SET
+                pattern = ?, replacement = ?, is_regex = ?, case_sensitive = ?,
+                whole_word = ?, preserve_case = ?, enabled = ?, updated_at = ?
+             WHERE id = ?",
+        )
+        .bind(&input.pattern)
+        .bind(&input.replacement)
+        .bind(input.is_regex)
+        .bind(input.case_sensitive)
+        .bind(input.whole_word)
+        .bind(input.preserve_case)
+        .bind(input.enabled)
+        .bind(Utc::now())
+        .bind(rule_id)
+        .execute(pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        sqlx::query_as::<_, ReplacementRule>("SELECT * FROM replacement_rules WHERE id = ?")
+            .bind(rule_id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Returns false if the rule didn't exist
+    pub async fn delete_rule(pool: &SqlitePool, rule_id: &str) -> Result<bool, SqlxError> {
+        let result = sqlx::query("DELETE FROM replacement_rules WHERE id = ?")
+            .bind(rule_id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Sets the application order to the order of `rule_ids`
+    pub async fn reorder_rules(pool: &SqlitePool, rule_ids: &[String]) -> Result<(), SqlxError> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+
+        for (position, rule_id) in rule_ids.iter().enumerate() {
+            sqlx::query("UPDATE replacement_rules SET position = ? WHERE id = ?")
+                .bind(position as i64)
+                .bind(rule_id)
+                .execute(&mut *transaction)
+                .await?;
+        }
+
+        transaction.commit().await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,261 @@
+use crate::api::{SearchFilters, SearchResult};
+use crate::database::models::DateTimeUtc;
+use sqlx::{Error as SqlxError, FromRow, SqlitePool};
+use tracing::info;
+
+const DEFAULT_SEARCH_LIMIT: i64 = 50;
+const MAX_SEARCH_LIMIT: i64 = 500;
+
+/// Source types stored in the `search_index` FTS5 table
+pub const SEARCH_SOURCE_TYPES: &[&str] = &["transcript", "summary", "chat"];
+
+#[derive(Debug, FromRow)]
+struct SearchRow {
+    meeting_id: String,
+    meeting_title: String,
+    meeting_created_at: DateTimeUtc,
+    source_type: String,
+    source_id: String,
+    snippet: String,
+    rank: f64,
+    timestamp: Option<String>,
+    audio_start_time: Option<f64>,
+}
+
+pub struct SearchRepository;
+
+impl SearchRepository {
+    /// Full-text search over transcripts, summaries and chat messages.
+    /// Results are ranked by bm25 (best match first) with highlighted snippets.
+    pub async fn search(
+        pool: &SqlitePool,
+        query: &str,
+        filters: &SearchFilters,
+    ) -> Result<Vec<SearchResult>, SqlxError> {
+        let match_expr = match build_fts_query(query) {
+            Some(expr) => expr,
+            None => return Ok(Vec::new()),
+        };
+
+        let mut sql = String::from(
+            "SELECT search_index.meeting_id AS meeting_id,
+                    m.title AS meeting_title,
+                    m.created_at AS meeting_created_at,
+                    search_index.source_type AS source_type,
+                    search_index.source_id AS source_id,
+                    snippet(search_index, 0, '<mark>', '</mark>', '…', 24) AS snippet,
+                    bm25(search_index) AS rank,
+                    t.timestamp AS timestamp,
+                    t.audio_start_time AS audio_start_time
+             FROM search_index
+             JOIN meetings m ON m.id = search_index.meeting_id
+             LEFT JOIN transcripts t
+                 ON search_index.source_type = 'transcript' AND t.id = search_index.source_id
+             WHERE search_index MATCH ?",
+        );
+        let mut binds: Vec<String> = vec![match_expr];
+
+        if let Some(meeting_id) = filters.meeting_id.as_ref().filter(|m| !m.is_empty()) {
+            sql.push_str(" AND search_index.meeting_id = ?");
+            binds.push(meeting_id.clone());
+        }
+
+        if let Some(source_types) = &filters.source_types {
+            let valid: Vec<&String> = source_types
+                .iter()
+                .filter(|t| SEARCH_SOURCE_TYPES.contains(&t.as_str()))
+                .collect();
+            if !valid.is_empty() {
+                let placeholders = vec!["?"; valid.len()].join(", ");
+                sql.push_str(&format!(" AND search_index.source_type IN ({})", placeholders));
+                binds.extend(valid.into_iter().cloned());
+            }
+        }
+
+        // created_at is stored as text starting with YYYY-MM-DD, so prefix comparison works
+        if let Some(from_date) = filters.from_date.as_ref().filter(|d| !d.is_empty()) {
+            sql.push_str(" AND substr(m.created_at, 1, 10) >= ?");
+            binds.push(from_date.clone());
+        }
+        if let Some(to_date) = filters.to_date.as_ref().filter(|d| !d.is_empty()) {
+            sql.push_str(" AND substr(m.created_at, 1, 10) <= ?");
+            binds.push(to_date.clone());
+        }
+
+        sql.push_str(" ORDER BY rank LIMIT ?");
+        let limit = filters
+            .limit
+            .unwrap_or(DEFAULT_SEARCH_LIMIT)
+            .clamp(1, MAX_SEARCH_LIMIT);
+
+        let mut query_builder = sqlx::query_as::<_, SearchRow>(&sql);
+        for bind in &binds {
+            query_builder = query_builder.bind(bind);
+        }
+        let rows = query_builder.bind(limit).fetch_all(pool).await?;
+
+        info!("Full-text search for '{}' returned {} results", query, rows.len());
+
+        Ok(rows
+            .into_iter()
+            .map(|row| SearchResult {
+                meeting_id: row.meeting_id,
+                meeting_title: row.meeting_title,
+                meeting_created_at: row.meeting_created_at.0.to_rfc3339(),
+                source_type: row.source_type,
+                source_id: row.source_id,
+                snippet: row.snippet,
+                rank: row.rank,
+                timestamp: row.timestamp,
+                audio_start_time: row.audio_start_time,
+            })
+            .collect())
+    }
+}
+
+/// Converts user input into an FTS5 MATCH expression.
+///
+/// - `"exact phrase"` is kept as a phrase query
+/// - `term*` (or `"phrase"*`) becomes a prefix query
+/// - `AND` / `OR` / `NOT` between terms are passed through as operators
+///
+/// Every term is quoted, so FTS5 syntax characters in user input (`-`, `:`, `(`)
+/// can't produce a syntax error. Returns None if there is nothing to search for.
+pub fn build_fts_query(input: &str) -> Option<String> {
+    let mut parts: Vec<String> = Vec::new();
+    let mut last_was_operator = true; // Operators can't lead the expression
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        let (text, quoted) = if c == '"' {
+            chars.next();
+            let mut phrase = String::new();
+            for ch in chars.by_ref() {
+                if ch == '"' {
+                    break;
+                }
+                phrase.push(ch);
+            }
+            (phrase, true)
+        } else {
+            let mut term = String::new();
+            while let Some(&ch) = chars.peek() {
+                if ch.is_whitespace() || ch == '"' {
+                    break;
+                }
+                term.push(ch);
+                chars.next();
+            }
+            (term, false)
+        };
+
+        let mut prefix = false;
+        let mut text = text;
+        if quoted {
+            if chars.peek() == Some(&'*') {
+                chars.next();
+                prefix = true;
+            }
+        } else if !text.is_empty() && matches!(text.as_str(), "AND" | "OR" | "NOT") {
+            if !last_was_operator {
+                parts.push(text);
+                last_was_operator = true;
+            }
+            continue;
+        } else {
+            while text.ends_with('*') {
+                text.pop();
+                prefix = true;
+            }
+        }
+
+        let text = text.trim();
+        if text.is_empty() {
+            continue;
+        }
+
+        let mut part = format!("\"{}\"", text.replace('"', "\"\""));
+        if prefix {
+            part.push('*');
+        }
+        parts.push(part);
+        last_was_operator = false;
+    }
+
+    // Drop a dangling trailing operator
+    if last_was_operator {
+        parts.pop();
+    }
+
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_terms_are_quoted() {
+        assert_eq!(
+            build_fts_query("budget review"),
+            Some("\"budget\" \"review\"".to_string())
+        );
+    }
+
+    #[test]
+    fn test_phrase_and_prefix() {
+        assert_eq!(
+            build_fts_query("\"action items\" budg*"),
+            Some("\"action items\" \"budg\"*".to_string())
+        );
+        assert_eq!(
+            build_fts_query("\"next quar\"*"),
+            Some("\"next quar\"*".to_string())
+        );
+    }
+
+    #[test]
+    fn test_operators() {
+        assert_eq!(
+            build_fts_query("hiring OR budget NOT travel"),
+            Some("\"hiring\" OR \"budget\" NOT \"travel\"".to_string())
+        );
+        // Leading/trailing/repeated operators are dropped
+        assert_eq!(
+            build_fts_query("OR hiring AND AND"),
+            Some("\"hiring\"".to_string())
+        );
+    }
+
+    #[test]
+    fn test_special_characters_are_escaped() {
+        assert_eq!(
+            build_fts_query("c++ -x:y (z)"),
+            Some("\"c++\" \"-x:y\" \"(z)\"".to_string())
+        );
+    }
+
+    #[test]
+    fn test_empty_queries() {
+        assert_eq!(build_fts_query(""), None);
+        assert_eq!(build_fts_query("   \"\" * "), None);
+        assert_eq!(build_fts_query("OR"), None);
+    }
+
+    #[test]
+    fn test_unicode_terms() {
+        assert_eq!(
+            build_fts_query("réunion 会議*"),
+            Some("\"réunion\" \"会議\"*".to_string())
+        );
+    }
+}
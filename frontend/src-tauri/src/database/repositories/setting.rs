@@ -24,7 +24,8 @@ pub struct SaveTranscriptConfigRequest {
 pub struct SettingsRepository;
 
 // Transcript providers: localWhisper, deepgram, elevenLabs, groq, openai
-// Summary providers: openai, claude, ollama, groq, added openrouter
+// Summary providers: openai, claude, ollama, groq, added openrouter, openai-compatible,
+// gemini, azure-openai, mistral, llama-cpp
 // NOTE: Handle data exclusion in the higher layer as this is database abstraction layer(using SELECT *)
 
 impl SettingsRepository {
@@ -100,6 +101,10 @@ impl SettingsRepository {
             "groq" => "groqApiKey",
             "openrouter" => "openRouterApiKey",
             "openai-compatible" => "openaiCompatibleApiKey",
+            "gemini" => "geminiApiKey",
+            "azure-openai" => "azureOpenaiApiKey",
+            "mistral" => "mistralApiKey",
+            "llama-cpp" => "llamaCppApiKey",
             _ => {
                 return Err(sqlx::Error::Protocol(
                     format!("Invalid provider: {}", provider).into(),
@@ -132,6 +137,10 @@ impl SettingsRepository {
             "claude" => "anthropicApiKey",
             "openrouter" => "openRouterApiKey",
             "openai-compatible" => "openaiCompatibleApiKey",
+            "gemini" => "geminiApiKey",
+            "azure-openai" => "azureOpenaiApiKey",
+            "mistral" => "mistralApiKey",
+            "llama-cpp" => "llamaCppApiKey",
             _ => {
                 return Err(sqlx::Error::Protocol(
                     format!("Invalid provider: {}", provider).into(),
@@ -147,6 +156,155 @@ impl SettingsRepository {
         Ok(api_key)
     }
 
+    /// Endpoint stored for a summary provider; None for providers without one
+    pub async fn get_endpoint(
+        pool: &SqlitePool,
+        provider: &str,
+    ) -> std::result::Result<Option<String>, sqlx::Error> {
+        let Some(endpoint_column) = Self::endpoint_column(provider) else {
+            return Ok(None);
+        };
+
+        let query = format!(
+            "SELECT {} FROM settings WHERE id = '1' LIMIT 1",
+            endpoint_column
+        );
+        let endpoint = sqlx::query_scalar::<_, Option<String>>(&query)
+            .fetch_optional(pool)
+            .await?;
+        Ok(endpoint.flatten())
+    }
+
+    pub async fn save_endpoint(
+        pool: &SqlitePool,
+        provider: &str,
+        endpoint: Option<&str>,
+    ) -> std::result::Result<(), sqlx::Error> {
+        let Some(endpoint_column) = Self::endpoint_column(provider) else {
+            return Err(sqlx::Error::Protocol(
+                format!("Provider {} has no configurable endpoint", provider).into(),
+            ));
+        };
+
+        let query = format!(
+            r#"
+            INSERT INTO settings (id, provider, model, whisperModel, "{}")
+            VALUES ('1', 'openai', 'gpt-4o-2024-11-20', 'large-v3', $1)
+            ON CONFLICT(id) DO UPDATE SET
+                "{}" = $1
+            "#,
+            endpoint_column, endpoint_column
+        );
+        sqlx::query(&query).bind(endpoint).execute(pool).await?;
+
+        Ok(())
+    }
+
+    pub async fn get_failover_chain(
+        pool: &SqlitePool,
+    ) -> std::result::Result<Option<String>, sqlx::Error> {
+        let chain = sqlx::query_scalar::<_, Option<String>>(
+            "SELECT llmFailoverChain FROM settings WHERE id = '1' LIMIT 1",
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(chain.flatten())
+    }
+
+    pub async fn save_failover_chain(
+        pool: &SqlitePool,
+        failover_chain: Option<&str>, // JSON string
+    ) -> std::result::Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO settings (id, provider, model, whisperModel, llmFailoverChain)
+            VALUES ('1', 'openai', 'gpt-4o-2024-11-20', 'large-v3', $1)
+            ON CONFLICT(id) DO UPDATE SET
+                llmFailoverChain = $1
+            "#,
+        )
+        .bind(failover_chain)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Monthly and per-meeting LLM budget caps in USD
+    pub async fn get_llm_budget(
+        pool: &SqlitePool,
+    ) -> std::result::Result<(Option<f64>, Option<f64>), sqlx::Error> {
+        let budget = sqlx::query_as::<_, (Option<f64>, Option<f64>)>(
+            "SELECT llmMonthlyBudget, llmMeetingBudget FROM settings WHERE id = '1' LIMIT 1",
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(budget.unwrap_or_default())
+    }
+
+    pub async fn save_llm_budget(
+        pool: &SqlitePool,
+        monthly_budget: Option<f64>,
+        meeting_budget: Option<f64>,
+    ) -> std::result::Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO settings (id, provider, model, whisperModel, llmMonthlyBudget, llmMeetingBudget)
+            VALUES ('1', 'openai', 'gpt-4o-2024-11-20', 'large-v3', $1, $2)
+            ON CONFLICT(id) DO UPDATE SET
+                llmMonthlyBudget = $1,
+                llmMeetingBudget = $2
+            "#,
+        )
+        .bind(monthly_budget)
+        .bind(meeting_budget)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Speaker diarization on/off, None if never saved
+    pub async fn get_diarization_enabled(
+        pool: &SqlitePool,
+    ) -> std::result::Result<Option<bool>, sqlx::Error> {
+        let enabled = sqlx::query_scalar::<_, Option<bool>>(
+            "SELECT diarizationEnabled FROM settings WHERE id = '1' LIMIT 1",
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(enabled.flatten())
+    }
+
+    pub async fn save_diarization_enabled(
+        pool: &SqlitePool,
+        enabled: bool,
+    ) -> std::result::Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO settings (id, provider, model, whisperModel, diarizationEnabled)
+            VALUES ('1', 'openai', 'gpt-4o-2024-11-20', 'large-v3', $1)
+            ON CONFLICT(id) DO UPDATE SET
+                diarizationEnabled = $1
+            "#,
+        )
+        .bind(enabled)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    fn endpoint_column(provider: &str) -> Option<&'static str> {
+        match provider {
+            "ollama" => Some("ollamaEndpoint"),
+            "openai-compatible" => Some("openaiCompatibleEndpoint"),
+            "azure-openai" => Some("azureOpenaiEndpoint"),
+            "llama-cpp" => Some("llamaCppEndpoint"),
+            _ => None,
+        }
+    }
+
     pub async fn get_transcript_config(
         pool: &SqlitePool,
     ) -> std::result::Result<Option<TranscriptSetting>, sqlx::Error> {
@@ -252,6 +410,10 @@ impl SettingsRepository {
             "claude" => "anthropicApiKey",
             "openrouter" => "openRouterApiKey",
             "openai-compatible" => "openaiCompatibleApiKey",
+            "gemini" => "geminiApiKey",
+            "azure-openai" => "azureOpenaiApiKey",
+            "mistral" => "mistralApiKey",
+            "llama-cpp" => "llamaCppApiKey",
             _ => {
                 return Err(sqlx::Error::Protocol(
                     format!("Invalid provider: {}", provider).into(),
@@ -0,0 +1,135 @@
+use crate::diarization::default_speaker_name;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sqlx::{Connection, Error as SqlxError, FromRow, SqlitePool};
+use tracing::info;
+
+/// A speaker in a meeting with the name shown for it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeetingSpeaker {
+    pub speaker_id: String,
+    pub display_name: String,
+    /// true if the user renamed the speaker
+    pub is_custom_name: bool,
+    pub segment_count: i64,
+}
+
+#[derive(Debug, FromRow)]
+struct SpeakerRow {
+    speaker_id: String,
+    display_name: Option<String>,
+    segment_count: i64,
+}
+
+pub struct SpeakersRepository;
+
+impl SpeakersRepository {
+    /// Speakers that appear in the meeting's transcripts, with their names
+    pub async fn get_meeting_speakers(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Vec<MeetingSpeaker>, SqlxError> {
+        let rows = sqlx::query_as::<_, SpeakerRow>(
+            "SELECT t.speaker_id AS speaker_id,
+                    ms.display_name AS display_name,
+                    COUNT(*) AS segment_count
+             FROM transcripts t
+             LEFT JOIN meeting_speakers ms
+                 ON ms.meeting_id = t.meeting_id AND ms.speaker_id = t.speaker_id
+             WHERE t.meeting_id = ? AND t.speaker_id IS NOT NULL
+             GROUP BY t.speaker_id
+             ORDER BY MIN(COALESCE(t.audio_start_time, 0))",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| MeetingSpeaker {
+                is_custom_name: row.display_name.is_some(),
+                display_name: row
+                    .display_name
+                    .unwrap_or_else(|| default_speaker_name(&row.speaker_id)),
+                speaker_id: row.speaker_id,
+                segment_count: row.segment_count,
+            })
+            .collect())
+    }
+
+    /// Name a speaker for one meeting. An empty name restores the default.
+    pub async fn rename_speaker(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        speaker_id: &str,
+        display_name: &str,
+    ) -> Result<(), SqlxError> {
+        let display_name = display_name.trim();
+        if display_name.is_empty() {
+            sqlx::query("DELETE FROM meeting_speakers WHERE meeting_id = ? AND speaker_id = ?")
+                .bind(meeting_id)
+                .bind(speaker_id)
+                .execute(pool)
+                .await?;
+            return Ok(());
+        }
+
+        sqlx::query(
+            "INSERT INTO meeting_speakers (meeting_id, speaker_id, display_name, updated_at)
+             VALUES (?, ?, ?, ?)
+             ON CONFLICT(meeting_id, speaker_id) DO UPDATE SET
+                display_name = excluded.display_name,
+                updated_at = excluded.updated_at",
+        )
+        .bind(meeting_id)
+        .bind(speaker_id)
+        .bind(display_name)
+        .bind(Utc::now())
+        .execute(pool)
+        .await?;
+
+        info!("Renamed {} in meeting {} to '{}'", speaker_id, meeting_id, display_name);
+        Ok(())
+    }
+
+    /// Replace the speaker labels of a meeting's transcripts.
+    /// Custom names are dropped since the old ids no longer mean the same people.
+    pub async fn replace_transcript_speakers(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        assignments: &[(String, Option<String>)],
+    ) -> Result<(), SqlxError> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+
+        sqlx::query("DELETE FROM meeting_speakers WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        for (transcript_id, speaker_id) in assignments {
+            sqlx::query("UPDATE transcripts SET speaker_id = ? WHERE id = ? AND meeting_id = ?")
+                .bind(speaker_id)
+                .bind(transcript_id)
+                .bind(meeting_id)
+                .execute(&mut *transaction)
+                .await?;
+        }
+
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    /// Speaker names for a meeting, keyed by speaker id
+    pub async fn get_speaker_names(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Vec<(String, String)>, SqlxError> {
+        sqlx::query_as::<_, (String, String)>(
+            "SELECT speaker_id, display_name FROM meeting_speakers WHERE meeting_id = ?",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await
+    }
+}
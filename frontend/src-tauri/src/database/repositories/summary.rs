@@ -1,4 +1,5 @@
 use crate::database::models::SummaryProcess;
+use crate::database::repositories::summary_version::{SummaryVersionsRepository, VersionOrigin};
 use chrono::Utc;
 use serde_json::Value;
 use sqlx::SqlitePool;
@@ -18,6 +19,7 @@ impl SummaryProcessesRepository {
             .await
     }
 
+    /// Saves a manual edit, keeping it as a new summary version
     pub async fn update_meeting_summary(
         pool: &SqlitePool,
         meeting_id: &str,
@@ -46,15 +48,24 @@ impl SummaryProcessesRepository {
             transaction.rollback().await?;
             return Ok(false);
         }
+        let result_json = result_json.unwrap();
         let now = Utc::now();
 
         sqlx::query("UPDATE summary_processes SET result = ?, updated_at = ? WHERE meeting_id = ?")
-            .bind(&result_json.unwrap())
+            .bind(&result_json)
             .bind(now)
             .bind(meeting_id)
             .execute(&mut *transaction)
             .await?;
 
+        SummaryVersionsRepository::record(
+            &mut *transaction,
+            meeting_id,
+            &result_json,
+            &VersionOrigin::edited(),
+        )
+        .await?;
+
         sqlx::query("UPDATE meetings SET updated_at = ? WHERE id = ?")
             .bind(now)
             .bind(meeting_id)
@@ -82,6 +93,10 @@ impl SummaryProcessesRepository {
         .await
     }
 
+    /// Starts a new run for the meeting
+    ///
+    /// The stored summary and its current version are kept, so a run that
+    /// fails or is cancelled leaves the previous summary in place.
     pub async fn create_or_reset_process(
         pool: &SqlitePool,
         meeting_id: &str,
@@ -93,14 +108,17 @@ impl SummaryProcessesRepository {
         let now = Utc::now();
         sqlx::query(
             r#"
-            INSERT INTO summary_processes (meeting_id, status, created_at, updated_at, start_time, result, error)
-            VALUES (?, 'PENDING', ?, ?, ?, NULL, NULL)
+            INSERT INTO summary_processes (meeting_id, status, created_at, updated_at, start_time, result, error, metadata, job, attempts)
+            VALUES (?, 'PENDING', ?, ?, ?, NULL, NULL, NULL, NULL, 0)
             ON CONFLICT(meeting_id) DO UPDATE SET
                 status = 'PENDING',
                 updated_at = excluded.updated_at,
                 start_time = excluded.start_time,
-                result = NULL,
-                error = NULL
+                end_time = NULL,
+                error = NULL,
+                metadata = NULL,
+                job = NULL,
+                attempts = 0
             "#
         )
         .bind(meeting_id)
@@ -112,16 +130,19 @@ impl SummaryProcessesRepository {
         Ok(())
     }
 
+    /// Saves a generated summary, keeping it as a new summary version
     pub async fn update_process_completed(
         pool: &SqlitePool,
         meeting_id: &str,
         result: Value, // Keep this as Value to handle both old and new formats if needed
         chunk_count: i64,
         processing_time: f64,
+        origin: &VersionOrigin,
     ) -> Result<(), sqlx::Error> {
         let now = Utc::now();
         let result_str = serde_json::to_string(&result)
             .map_err(|e| sqlx::Error::Protocol(format!("Failed to serialize result: {}", e)))?;
+        let mut transaction = pool.begin().await?;
 
         sqlx::query(
             r#"
@@ -130,14 +151,19 @@ impl SummaryProcessesRepository {
             WHERE meeting_id = ?
             "#
         )
-        .bind(result_str)
+        .bind(&result_str)
         .bind(now)
         .bind(now)
         .bind(chunk_count)
         .bind(processing_time)
         .bind(meeting_id)
-        .execute(pool)
+        .execute(&mut *transaction)
         .await?;
+
+        SummaryVersionsRepository::record(&mut *transaction, meeting_id, &result_str, origin)
+            .await?;
+
+        transaction.commit().await?;
         Ok(())
     }
 
@@ -162,4 +188,114 @@ impl SummaryProcessesRepository {
         .await?;
         Ok(())
     }
+
+    /// Records run details, such as every LLM attempt and the backend that
+    /// produced the summary, in the process's `metadata` column
+    pub async fn update_process_metadata(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        metadata: &Value,
+    ) -> Result<(), sqlx::Error> {
+        let metadata_str = serde_json::to_string(metadata)
+            .map_err(|e| sqlx::Error::Protocol(format!("Failed to serialize metadata: {}", e)))?;
+
+        sqlx::query("UPDATE summary_processes SET metadata = ? WHERE meeting_id = ?")
+            .bind(metadata_str)
+            .bind(meeting_id)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Stores the request of a queued job, so it can be resumed after a restart
+    pub async fn set_process_job(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        job: &Value,
+    ) -> Result<(), sqlx::Error> {
+        let job_str = serde_json::to_string(job)
+            .map_err(|e| sqlx::Error::Protocol(format!("Failed to serialize job: {}", e)))?;
+
+        sqlx::query("UPDATE summary_processes SET job = ? WHERE meeting_id = ?")
+            .bind(job_str)
+            .bind(meeting_id)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Marks a queued job as started
+    pub async fn update_process_running(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<(), sqlx::Error> {
+        let now = Utc::now();
+        sqlx::query(
+            r#"
+            UPDATE summary_processes
+            SET status = 'processing', start_time = ?, updated_at = ?, attempts = attempts + 1
+            WHERE meeting_id = ?
+            "#,
+        )
+        .bind(now)
+        .bind(now)
+        .bind(meeting_id)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Puts an interrupted job back in the queue
+    pub async fn update_process_queued(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE summary_processes SET status = 'PENDING', updated_at = ? WHERE meeting_id = ?",
+        )
+        .bind(Utc::now())
+        .bind(meeting_id)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Marks a queued or running job as cancelled
+    ///
+    /// Returns false if the job had already finished.
+    pub async fn update_process_cancelled(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let now = Utc::now();
+        let result = sqlx::query(
+            r#"
+            UPDATE summary_processes
+            SET status = 'cancelled', error = 'Cancelled', updated_at = ?, end_time = ?
+            WHERE meeting_id = ? AND status IN ('PENDING', 'processing')
+            "#,
+        )
+        .bind(now)
+        .bind(now)
+        .bind(meeting_id)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Jobs that were queued or running, interrupted ones first, then in the
+    /// order they were queued
+    pub async fn get_unfinished_processes(
+        pool: &SqlitePool,
+    ) -> Result<Vec<SummaryProcess>, sqlx::Error> {
+        sqlx::query_as::<_, SummaryProcess>(
+            r#"
+            SELECT * FROM summary_processes
+            WHERE status IN ('PENDING', 'processing')
+            ORDER BY status = 'processing' DESC, start_time ASC
+            "#,
+        )
+        .fetch_all(pool)
+        .await
+    }
 }
@@ -0,0 +1,204 @@
+use crate::database::models::SummaryVersion;
+use chrono::Utc;
+use sqlx::{Error as SqlxError, SqliteConnection, SqlitePool};
+use tracing::info;
+use uuid::Uuid;
+
+/// How a summary version came about
+#[derive(Debug, Clone, PartialEq)]
+pub struct VersionOrigin {
+    /// "generated" or "edited"
+    pub source: &'static str,
+    pub provider: Option<String>,
+    pub model: Option<String>,
+    pub template_id: Option<String>,
+    pub custom_prompt: Option<String>,
+}
+
+impl VersionOrigin {
+    pub fn generated(provider: &str, model: &str, template_id: &str, custom_prompt: &str) -> Self {
+        Self {
+            source: "generated",
+            provider: Some(provider.to_string()),
+            model: Some(model.to_string()),
+            template_id: Some(template_id.to_string()),
+            custom_prompt: Some(custom_prompt.to_string()).filter(|p| !p.trim().is_empty()),
+        }
+    }
+
+    pub fn edited() -> Self {
+        Self {
+            source: "edited",
+            provider: None,
+            model: None,
+            template_id: None,
+            custom_prompt: None,
+        }
+    }
+}
+
+pub struct SummaryVersionsRepository;
+
+impl SummaryVersionsRepository {
+    /// A meeting's versions, newest first
+    pub async fn list_for_meeting(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Vec<SummaryVersion>, SqlxError> {
+        sqlx::query_as::<_, SummaryVersion>(
+            "SELECT * FROM summary_versions WHERE meeting_id = ? ORDER BY created_at DESC, rowid DESC",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn get_version(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        version_id: &str,
+    ) -> Result<Option<SummaryVersion>, SqlxError> {
+        sqlx::query_as::<_, SummaryVersion>(
+            "SELECT * FROM summary_versions WHERE id = ? AND meeting_id = ?",
+        )
+        .bind(version_id)
+        .bind(meeting_id)
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Stores `result` as the meeting's current version, with the previous
+    /// current version as its parent
+    ///
+    /// Runs on the caller's transaction, so the version is only kept if the
+    /// summary itself is saved. A result identical to the current version
+    /// adds nothing.
+    ///
+    /// # Returns
+    /// The id of the current version
+    pub async fn record(
+        conn: &mut SqliteConnection,
+        meeting_id: &str,
+        result: &str,
+        origin: &VersionOrigin,
+    ) -> Result<String, SqlxError> {
+        let current = sqlx::query_as::<_, (String, String)>(
+            "SELECT v.id, v.result FROM summary_processes p
+             JOIN summary_versions v ON v.id = p.current_version_id
+             WHERE p.meeting_id = ?",
+        )
+        .bind(meeting_id)
+        .fetch_optional(&mut *conn)
+        .await?;
+        if let Some((current_id, current_result)) = &current {
+            if current_result == result {
+                return Ok(current_id.clone());
+            }
+        }
+
+        let id = format!("summary-version-{}", Uuid::new_v4());
+        sqlx::query(
+            "INSERT INTO summary_versions
+                (id, meeting_id, parent_id, source, result, provider, model, template_id,
+                 custom_prompt, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&id)
+        .bind(meeting_id)
+        .bind(current.map(|(current_id, _)| current_id))
+        .bind(origin.source)
+        .bind(result)
+        .bind(&origin.provider)
+        .bind(&origin.model)
+        .bind(&origin.template_id)
+        .bind(&origin.custom_prompt)
+        .bind(Utc::now())
+        .execute(&mut *conn)
+        .await?;
+
+        sqlx::query("UPDATE summary_processes SET current_version_id = ? WHERE meeting_id = ?")
+            .bind(&id)
+            .bind(meeting_id)
+            .execute(&mut *conn)
+            .await?;
+
+        info!(
+            "Recorded {} summary version {} for meeting_id: {}",
+            origin.source, id, meeting_id
+        );
+        Ok(id)
+    }
+
+    /// Makes an earlier version the meeting's summary again
+    ///
+    /// No new version is added: later versions stay in the history, and the
+    /// next generation or edit takes the restored version as its parent.
+    ///
+    /// # Returns
+    /// The restored version, or None if the meeting has no such version
+    pub async fn restore(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        version_id: &str,
+    ) -> Result<Option<SummaryVersion>, SqlxError> {
+        let mut transaction = pool.begin().await?;
+
+        let version = sqlx::query_as::<_, SummaryVersion>(
+            "SELECT * FROM summary_versions WHERE id = ? AND meeting_id = ?",
+        )
+        .bind(version_id)
+        .bind(meeting_id)
+        .fetch_optional(&mut *transaction)
+        .await?;
+        let Some(version) = version else {
+            transaction.rollback().await?;
+            return Ok(None);
+        };
+
+        let now = Utc::now();
+        sqlx::query(
+            r#"
+            UPDATE summary_processes
+            SET status = 'completed', result = ?, error = NULL, current_version_id = ?, updated_at = ?
+            WHERE meeting_id = ?
+            "#,
+        )
+        .bind(&version.result)
+        .bind(&version.id)
+        .bind(now)
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+
+        sqlx::query("UPDATE meetings SET updated_at = ? WHERE id = ?")
+            .bind(now)
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        transaction.commit().await?;
+
+        info!(
+            "Restored summary version {} for meeting_id: {}",
+            version_id, meeting_id
+        );
+        Ok(Some(version))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_origin() {
+        let origin = VersionOrigin::generated("ollama", "llama3", "daily_standup", "  ");
+        assert_eq!(origin.source, "generated");
+        assert_eq!(origin.template_id.as_deref(), Some("daily_standup"));
+        assert_eq!(origin.custom_prompt, None);
+
+        let origin = VersionOrigin::edited();
+        assert_eq!(origin.source, "edited");
+        assert_eq!(origin.model, None);
+    }
+}
@@ -1,4 +1,7 @@
-use crate::api::{TranscriptSearchResult, TranscriptSegment};
+use crate::api::{SearchFilters, TranscriptSearchResult, TranscriptSegment};
+use crate::audio::transcription::WordTimestamp;
+use crate::database::repositories::search::SearchRepository;
+use crate::database::repositories::transcript_word::TranscriptWordsRepository;
 use chrono::Utc;
 use sqlx::{Connection, Error as SqlxError, SqlitePool};
 use tracing::{error, info};
@@ -47,8 +50,8 @@ impl TranscriptsRepository {
         for segment in transcripts {
             let transcript_id = format!("transcript-{}", Uuid::new_v4());
             let result = sqlx::query(
-                "INSERT INTO transcripts (id, meeting_id, transcript, timestamp, audio_start_time, audio_end_time, duration)
-                 VALUES (?, ?, ?, ?, ?, ?, ?)"
+                "INSERT INTO transcripts (id, meeting_id, transcript, timestamp, audio_start_time, audio_end_time, duration, speaker_id, channel)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"
             )
             .bind(&transcript_id)
             .bind(&meeting_id)
@@ -57,6 +60,8 @@ impl TranscriptsRepository {
             .bind(segment.audio_start_time)
             .bind(segment.audio_end_time)
             .bind(segment.duration)
+            .bind(&segment.speaker_id)
+            .bind(segment.channel.map(|c| c.as_str()))
             .execute(&mut *transaction)
             .await;
 
@@ -68,6 +73,23 @@ impl TranscriptsRepository {
                 transaction.rollback().await?;
                 return Err(e);
             }
+
+            if !segment.words.is_empty() {
+                if let Err(e) = TranscriptWordsRepository::save_words(
+                    &mut *transaction,
+                    &transcript_id,
+                    &segment.words,
+                )
+                .await
+                {
+                    error!(
+                        "Failed to save word timings for meeting {}: {}",
+                        meeting_id, e
+                    );
+                    transaction.rollback().await?;
+                    return Err(e);
+                }
+            }
         }
 
         info!(
@@ -82,8 +104,92 @@ impl TranscriptsRepository {
         Ok(meeting_id)
     }
 
+    /// Rewrites the text of existing transcript segments of a meeting.
+    /// Each change is (transcript id, new text, new word timings if they changed).
+    pub async fn update_transcript_texts(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        changes: &[(String, String, Option<Vec<WordTimestamp>>)],
+    ) -> Result<(), SqlxError> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+
+        for (transcript_id, text, words) in changes {
+            sqlx::query("UPDATE transcripts SET transcript = ? WHERE id = ? AND meeting_id = ?")
+                .bind(text)
+                .bind(transcript_id)
+                .bind(meeting_id)
+                .execute(&mut *transaction)
+                .await?;
+
+            if let Some(words) = words {
+                TranscriptWordsRepository::save_words(&mut *transaction, transcript_id, words)
+                    .await?;
+            }
+        }
+
+        sqlx::query("UPDATE meetings SET updated_at = ? WHERE id = ?")
+            .bind(Utc::now())
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        transaction.commit().await?;
+        info!(
+            "Updated {} transcript segment(s) of meeting {}",
+            changes.len(),
+            meeting_id
+        );
+        Ok(())
+    }
+
+    /// Moves the audio timings of a meeting's transcripts (and their words)
+    /// `offset` seconds earlier, e.g. after leading silence was cut from the
+    /// recording. Times never go below zero.
+    pub async fn shift_audio_times(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        offset: f64,
+    ) -> Result<u64, SqlxError> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+
+        let shifted = sqlx::query(
+            "UPDATE transcripts
+             SET audio_start_time = MAX(audio_start_time - ?, 0),
+                 audio_end_time = MAX(audio_end_time - ?, 0)
+             WHERE meeting_id = ? AND audio_start_time IS NOT NULL",
+        )
+        .bind(offset)
+        .bind(offset)
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?
+        .rows_affected();
+
+        sqlx::query(
+            "UPDATE transcript_words
+             SET start_time = MAX(start_time - ?, 0),
+                 end_time = MAX(end_time - ?, 0)
+             WHERE transcript_id IN (SELECT id FROM transcripts WHERE meeting_id = ?)",
+        )
+        .bind(offset)
+        .bind(offset)
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+        info!(
+            "Shifted audio times of {} transcript segment(s) of meeting {} by -{:.2}s",
+            shifted, meeting_id, offset
+        );
+        Ok(shifted)
+    }
+
     /// Searches for a query string within the transcripts.
-    /// It returns a list of matching transcripts with context.
+    /// Uses the FTS5 search index (bm25-ranked); falls back to a LIKE scan if the
+    /// index query fails.
     pub async fn search_transcripts(
         pool: &SqlitePool,
         query: &str,
@@ -92,6 +198,33 @@ impl TranscriptsRepository {
             return Ok(Vec::new());
         }
 
+        let filters = SearchFilters {
+            source_types: Some(vec!["transcript".to_string()]),
+            ..Default::default()
+        };
+
+        match SearchRepository::search(pool, query, &filters).await {
+            Ok(results) => Ok(results
+                .into_iter()
+                .map(|r| TranscriptSearchResult {
+                    id: r.meeting_id,
+                    title: r.meeting_title,
+                    match_context: r.snippet.replace("<mark>", "").replace("</mark>", ""),
+                    timestamp: r.timestamp.unwrap_or_default(),
+                })
+                .collect()),
+            Err(e) => {
+                error!("Full-text search failed, falling back to LIKE search: {}", e);
+                Self::search_transcripts_like(pool, query).await
+            }
+        }
+    }
+
+    /// Legacy substring search, used when the FTS index is unavailable
+    async fn search_transcripts_like(
+        pool: &SqlitePool,
+        query: &str,
+    ) -> Result<Vec<TranscriptSearchResult>, SqlxError> {
         let search_query = format!("%{}%", query.to_lowercase());
 
         let rows = sqlx::query_as::<_, (String, String, String, String)>(
@@ -121,21 +254,28 @@ impl TranscriptsRepository {
     }
 
     /// Helper function to extract a snippet of text around the first match of a query.
+    /// Works in characters rather than bytes so multi-byte text is never split.
     fn get_match_context(transcript: &str, query: &str) -> String {
+        const CONTEXT_CHARS: usize = 100;
+
         let transcript_lower = transcript.to_lowercase();
         let query_lower = query.to_lowercase();
 
         match transcript_lower.find(&query_lower) {
             Some(match_index) => {
-                let start_index = match_index.saturating_sub(100);
-                let end_index = (match_index + query.len() + 100).min(transcript.len());
+                let chars: Vec<char> = transcript.chars().collect();
+                // Lowercasing can change lengths for a few scripts - clamp to be safe
+                let match_char = transcript_lower[..match_index].chars().count().min(chars.len());
+                let start_index = match_char.saturating_sub(CONTEXT_CHARS);
+                let end_index =
+                    (match_char + query.chars().count() + CONTEXT_CHARS).min(chars.len());
 
                 let mut context = String::new();
                 if start_index > 0 {
                     context.push_str("...");
                 }
-                context.push_str(&transcript[start_index..end_index]);
-                if end_index < transcript.len() {
+                context.extend(&chars[start_index..end_index]);
+                if end_index < chars.len() {
                     context.push_str("...");
                 }
                 context
@@ -144,3 +284,26 @@ impl TranscriptsRepository {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_context_multibyte() {
+        let transcript = format!("{}預算會議{}", "會".repeat(150), "議".repeat(150));
+        let context = TranscriptsRepository::get_match_context(&transcript, "預算");
+
+        assert!(context.starts_with("..."));
+        assert!(context.ends_with("..."));
+        assert!(context.contains("預算會議"));
+        // 100 chars before + 2 matched + 100 after, plus the two ellipses
+        assert_eq!(context.chars().count(), 202 + 6);
+    }
+
+    #[test]
+    fn test_match_context_short_text() {
+        let context = TranscriptsRepository::get_match_context("Café budget", "BUDGET");
+        assert_eq!(context, "Café budget");
+    }
+}
@@ -0,0 +1,78 @@
+use crate::audio::transcription::WordTimestamp;
+use sqlx::{Error as SqlxError, SqliteConnection, SqlitePool};
+use std::collections::HashMap;
+
+pub struct TranscriptWordsRepository;
+
+impl TranscriptWordsRepository {
+    /// Replaces the word timings of a transcript segment.
+    /// Takes a connection so callers can run it inside their own transaction.
+    pub async fn save_words(
+        conn: &mut SqliteConnection,
+        transcript_id: &str,
+        words: &[WordTimestamp],
+    ) -> Result<(), SqlxError> {
+        sqlx::query("DELETE FROM transcript_words WHERE transcript_id = ?")
+            .bind(transcript_id)
+            .execute(&mut *conn)
+            .await?;
+
+        for (index, word) in words.iter().enumerate() {
+            sqlx::query(
+                "INSERT INTO transcript_words (transcript_id, word_index, word, start_time, end_time)
+                 VALUES (?, ?, ?, ?, ?)",
+            )
+            .bind(transcript_id)
+            .bind(index as i64)
+            .bind(&word.word)
+            .bind(word.start)
+            .bind(word.end)
+            .execute(&mut *conn)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Gets all word timings for a meeting, grouped by transcript id
+    pub async fn get_words_for_meeting(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<HashMap<String, Vec<WordTimestamp>>, SqlxError> {
+        let rows = sqlx::query_as::<_, (String, String, f64, f64)>(
+            "SELECT w.transcript_id, w.word, w.start_time, w.end_time
+             FROM transcript_words w
+             JOIN transcripts t ON t.id = w.transcript_id
+             WHERE t.meeting_id = ?
+             ORDER BY w.transcript_id, w.word_index",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await?;
+
+        let mut words: HashMap<String, Vec<WordTimestamp>> = HashMap::new();
+        for (transcript_id, word, start, end) in rows {
+            words
+                .entry(transcript_id)
+                .or_default()
+                .push(WordTimestamp { word, start, end });
+        }
+
+        Ok(words)
+    }
+
+    /// Deletes word timings for every transcript of a meeting
+    pub async fn delete_for_meeting(
+        conn: &mut SqliteConnection,
+        meeting_id: &str,
+    ) -> Result<(), SqlxError> {
+        sqlx::query(
+            "DELETE FROM transcript_words
+             WHERE transcript_id IN (SELECT id FROM transcripts WHERE meeting_id = ?)",
+        )
+        .bind(meeting_id)
+        .execute(&mut *conn)
+        .await?;
+        Ok(())
+    }
+}
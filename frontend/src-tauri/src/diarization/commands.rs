@@ -0,0 +1,196 @@
+use super::embedding::{DiarizationError, MIN_EMBEDDING_SECONDS};
+use super::fbank::FBANK_SAMPLE_RATE;
+use super::{cluster_embeddings, speaker_id, DEFAULT_SIMILARITY_THRESHOLD};
+use crate::database::repositories::meeting::MeetingsRepository;
+use crate::database::repositories::setting::SettingsRepository;
+use crate::database::repositories::speaker::{MeetingSpeaker, SpeakersRepository};
+use crate::export::bundle::find_meeting_audio;
+use crate::media_import::commands::decode_for_transcription;
+use crate::state::AppState;
+use log::{error as log_error, info as log_info, warn as log_warn};
+use serde::Serialize;
+use std::path::Path;
+use tauri::{AppHandle, Emitter, Runtime, State};
+
+#[derive(Debug, Serialize)]
+pub struct DiarizationStatus {
+    pub enabled: bool,
+    pub model_available: bool,
+    pub model_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiarizationProgress {
+    pub meeting_id: String,
+    pub current: usize,
+    pub total: usize,
+}
+
+#[tauri::command]
+pub async fn api_get_diarization_status() -> Result<DiarizationStatus, String> {
+    Ok(DiarizationStatus {
+        enabled: super::is_enabled(),
+        model_available: super::is_model_available(),
+        model_path: super::model_path().map(|p| p.to_string_lossy().to_string()),
+    })
+}
+
+#[tauri::command]
+pub async fn api_set_diarization_enabled(
+    state: State<'_, AppState>,
+    enabled: bool,
+) -> Result<(), String> {
+    SettingsRepository::save_diarization_enabled(state.db_manager.pool(), enabled)
+        .await
+        .map_err(|e| {
+            log_error!("Failed to save diarization setting: {}", e);
+            format!("Failed to save diarization setting: {}", e)
+        })?;
+    super::set_enabled(enabled);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn api_get_meeting_speakers(
+    state: State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Vec<MeetingSpeaker>, String> {
+    SpeakersRepository::get_meeting_speakers(state.db_manager.pool(), &meeting_id)
+        .await
+        .map_err(|e| {
+            log_error!("Failed to load speakers for meeting {}: {}", meeting_id, e);
+            format!("Failed to load speakers: {}", e)
+        })
+}
+
+/// Rename a speaker for one meeting. An empty name restores "Speaker N".
+#[tauri::command]
+pub async fn api_rename_speaker(
+    state: State<'_, AppState>,
+    meeting_id: String,
+    speaker_id: String,
+    display_name: String,
+) -> Result<(), String> {
+    log_info!(
+        "api_rename_speaker called for meeting_id: {}, speaker_id: {}",
+        meeting_id,
+        speaker_id
+    );
+
+    SpeakersRepository::rename_speaker(
+        state.db_manager.pool(),
+        &meeting_id,
+        &speaker_id,
+        &display_name,
+    )
+    .await
+    .map_err(|e| {
+        log_error!("Failed to rename speaker {}: {}", speaker_id, e);
+        format!("Failed to rename speaker: {}", e)
+    })
+}
+
+/// Diarize a saved meeting from its recording.
+///
+/// Every transcript row with audio timings gets an embedding of its audio
+/// range, and all embeddings are clustered together. This replaces any
+/// labels from the live pass and resets custom speaker names.
+/// Returns the number of speakers found.
+#[tauri::command]
+pub async fn api_diarize_meeting<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, AppState>,
+    meeting_id: String,
+) -> Result<usize, String> {
+    log_info!("api_diarize_meeting called for meeting_id: {}", meeting_id);
+
+    if !super::is_model_available() {
+        return Err("Speaker embedding model is not installed".to_string());
+    }
+
+    let pool = state.db_manager.pool();
+    let meeting = MeetingsRepository::get_meeting(pool, &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load meeting: {}", e))?
+        .ok_or_else(|| format!("Meeting not found: {}", meeting_id))?;
+    let folder_path = MeetingsRepository::get_meeting_model(pool, &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load meeting: {}", e))?
+        .and_then(|m| m.folder_path)
+        .ok_or_else(|| "Meeting has no recording folder".to_string())?;
+    let audio_path = find_meeting_audio(Path::new(&folder_path))
+        .ok_or_else(|| "No recording found for this meeting".to_string())?;
+
+    let samples = decode_for_transcription(&audio_path).await?;
+    let ranges: Vec<(String, Option<(f64, f64)>)> = meeting
+        .transcripts
+        .iter()
+        .map(|t| {
+            let range = match (t.audio_start_time, t.audio_end_time) {
+                (Some(start), Some(end)) if end - start >= MIN_EMBEDDING_SECONDS => Some((start, end)),
+                _ => None,
+            };
+            (t.id.clone(), range)
+        })
+        .collect();
+
+    let total = ranges.len();
+    let progress_app = app.clone();
+    let progress_meeting_id = meeting_id.clone();
+    let range_list: Vec<Option<(f64, f64)>> = ranges.iter().map(|(_, r)| *r).collect();
+    let embeddings = tokio::task::spawn_blocking(move || {
+        let mut embeddings = Vec::with_capacity(range_list.len());
+        for (i, range) in range_list.into_iter().enumerate() {
+            let embedding = range.and_then(|(start, end)| {
+                let from = ((start * FBANK_SAMPLE_RATE as f64) as usize).min(samples.len());
+                let to = ((end * FBANK_SAMPLE_RATE as f64) as usize).min(samples.len());
+                match super::embed(&samples[from..to]) {
+                    Ok(embedding) => Some(embedding),
+                    Err(DiarizationError::AudioTooShort(_)) => None,
+                    Err(e) => {
+                        log_warn!("Speaker embedding failed at {:.1}s: {}", start, e);
+                        None
+                    }
+                }
+            });
+            embeddings.push(embedding);
+
+            if (i + 1) % 10 == 0 || i + 1 == total {
+                let _ = progress_app.emit(
+                    "diarization-progress",
+                    DiarizationProgress {
+                        meeting_id: progress_meeting_id.clone(),
+                        current: i + 1,
+                        total,
+                    },
+                );
+            }
+        }
+        embeddings
+    })
+    .await
+    .map_err(|e| format!("Diarization task failed: {}", e))?;
+
+    let labels = cluster_embeddings(&embeddings, DEFAULT_SIMILARITY_THRESHOLD);
+    let num_speakers = labels.iter().flatten().max().map_or(0, |max| max + 1);
+    let assignments: Vec<(String, Option<String>)> = ranges
+        .into_iter()
+        .zip(labels)
+        .map(|((transcript_id, _), label)| (transcript_id, label.map(speaker_id)))
+        .collect();
+
+    SpeakersRepository::replace_transcript_speakers(pool, &meeting_id, &assignments)
+        .await
+        .map_err(|e| {
+            log_error!("Failed to save speaker labels for meeting {}: {}", meeting_id, e);
+            format!("Failed to save speaker labels: {}", e)
+        })?;
+
+    log_info!(
+        "✅ Diarized meeting {}: {} speakers across {} segments",
+        meeting_id,
+        num_speakers,
+        total
+    );
+    Ok(num_speakers)
+}
@@ -0,0 +1,101 @@
+// diarization/embedding.rs
+//
+// Speaker-embedding model (ONNX) run through ort.
+// Expects a WeSpeaker / 3D-Speaker style model: input `[1, frames, 80]` fbank
+// features, output `[1, dim]` embedding.
+
+use super::fbank::{FbankExtractor, FBANK_SAMPLE_RATE};
+use ort::execution_providers::CPUExecutionProvider;
+use ort::inputs;
+use ort::session::builder::GraphOptimizationLevel;
+use ort::session::Session;
+use ort::value::TensorRef;
+use std::path::Path;
+
+/// Segments shorter than this don't carry enough voice to identify a speaker
+pub const MIN_EMBEDDING_SECONDS: f64 = 1.0;
+
+#[derive(thiserror::Error, Debug)]
+pub enum DiarizationError {
+    #[error("ORT error")]
+    Ort(#[from] ort::Error),
+    #[error("I/O error")]
+    Io(#[from] std::io::Error),
+    #[error("ndarray shape error")]
+    Shape(#[from] ndarray::ShapeError),
+    #[error("Speaker embedding model not found: {0}")]
+    ModelNotFound(String),
+    #[error("Model has no {0}")]
+    InvalidModel(String),
+    #[error("Audio too short for a speaker embedding: {0:.2}s")]
+    AudioTooShort(f64),
+}
+
+pub struct SpeakerEmbeddingModel {
+    session: Session,
+    input_name: String,
+    output_name: String,
+    fbank: FbankExtractor,
+}
+
+impl SpeakerEmbeddingModel {
+    pub fn load(model_path: &Path) -> Result<Self, DiarizationError> {
+        if !model_path.is_file() {
+            return Err(DiarizationError::ModelNotFound(
+                model_path.display().to_string(),
+            ));
+        }
+
+        log::info!("Loading speaker embedding model from {}...", model_path.display());
+        let session = Session::builder()?
+            .with_optimization_level(GraphOptimizationLevel::Level3)?
+            .with_execution_providers(vec![CPUExecutionProvider::default().build()])?
+            .with_intra_threads(1)?
+            .commit_from_file(model_path)?;
+
+        let input_name = session
+            .inputs
+            .first()
+            .map(|input| input.name.clone())
+            .ok_or_else(|| DiarizationError::InvalidModel("inputs".to_string()))?;
+        let output_name = session
+            .outputs
+            .first()
+            .map(|output| output.name.clone())
+            .ok_or_else(|| DiarizationError::InvalidModel("outputs".to_string()))?;
+
+        log::info!(
+            "Speaker embedding model loaded: input={}, output={}",
+            input_name,
+            output_name
+        );
+
+        Ok(Self {
+            session,
+            input_name,
+            output_name,
+            fbank: FbankExtractor::new(),
+        })
+    }
+
+    /// L2-normalized embedding for 16kHz mono speech
+    pub fn embed(&mut self, samples_16k: &[f32]) -> Result<Vec<f32>, DiarizationError> {
+        let seconds = samples_16k.len() as f64 / FBANK_SAMPLE_RATE as f64;
+        if seconds < MIN_EMBEDDING_SECONDS {
+            return Err(DiarizationError::AudioTooShort(seconds));
+        }
+
+        let features = self.fbank.compute(samples_16k);
+        let features = features.insert_axis(ndarray::Axis(0)); // [1, frames, 80]
+
+        let outputs = self.session.run(inputs![
+            self.input_name.as_str() => TensorRef::from_array_view(features.view())?,
+        ])?;
+        let embedding = outputs
+            .get(self.output_name.as_str())
+            .ok_or_else(|| DiarizationError::InvalidModel(self.output_name.clone()))?
+            .try_extract_array::<f32>()?;
+
+        Ok(super::normalize(embedding.iter().copied().collect()))
+    }
+}
@@ -0,0 +1,194 @@
+// diarization/fbank.rs
+//
+// Kaldi-compatible log mel filterbank features, the input format of common
+// speaker-embedding models (WeSpeaker, 3D-Speaker).
+
+use ndarray::Array2;
+use realfft::{RealFftPlanner, RealToComplex};
+use std::sync::Arc;
+
+pub const FBANK_SAMPLE_RATE: u32 = 16000;
+pub const NUM_MEL_BINS: usize = 80;
+
+const FRAME_LENGTH: usize = 400; // 25ms
+const FRAME_SHIFT: usize = 160; // 10ms
+const FFT_SIZE: usize = 512;
+const PREEMPHASIS: f32 = 0.97;
+const LOW_FREQ: f32 = 20.0;
+
+/// Models are trained on int16-range audio
+const WAVEFORM_SCALE: f32 = 32768.0;
+
+fn mel_scale(freq: f32) -> f32 {
+    1127.0 * (1.0 + freq / 700.0).ln()
+}
+
+pub struct FbankExtractor {
+    fft: Arc<dyn RealToComplex<f32>>,
+    window: Vec<f32>,
+    /// Per mel bin: first FFT bin and its triangular weights
+    mel_banks: Vec<(usize, Vec<f32>)>,
+}
+
+impl FbankExtractor {
+    pub fn new() -> Self {
+        let fft = RealFftPlanner::<f32>::new().plan_fft_forward(FFT_SIZE);
+
+        // Povey window (Kaldi default)
+        let window = (0..FRAME_LENGTH)
+            .map(|i| {
+                let hann = 0.5
+                    - 0.5 * (2.0 * std::f32::consts::PI * i as f32 / (FRAME_LENGTH - 1) as f32).cos();
+                hann.powf(0.85)
+            })
+            .collect();
+
+        let nyquist = FBANK_SAMPLE_RATE as f32 / 2.0;
+        let mel_low = mel_scale(LOW_FREQ);
+        let mel_high = mel_scale(nyquist);
+        let mel_delta = (mel_high - mel_low) / (NUM_MEL_BINS + 1) as f32;
+        let fft_bin_width = FBANK_SAMPLE_RATE as f32 / FFT_SIZE as f32;
+
+        let mel_banks = (0..NUM_MEL_BINS)
+            .map(|m| {
+                let left = mel_low + m as f32 * mel_delta;
+                let center = left + mel_delta;
+                let right = center + mel_delta;
+
+                let mut first_bin = None;
+                let mut weights = Vec::new();
+                for bin in 0..FFT_SIZE / 2 {
+                    let mel = mel_scale(bin as f32 * fft_bin_width);
+                    if mel > left && mel < right {
+                        let weight = if mel <= center {
+                            (mel - left) / (center - left)
+                        } else {
+                            (right - mel) / (right - center)
+                        };
+                        first_bin.get_or_insert(bin);
+                        weights.push(weight);
+                    } else if first_bin.is_some() {
+                        break;
+                    }
+                }
+                (first_bin.unwrap_or(0), weights)
+            })
+            .collect();
+
+        Self {
+            fft,
+            window,
+            mel_banks,
+        }
+    }
+
+    /// Number of frames produced for `num_samples` of 16kHz audio
+    pub fn num_frames(num_samples: usize) -> usize {
+        if num_samples < FRAME_LENGTH {
+            0
+        } else {
+            1 + (num_samples - FRAME_LENGTH) / FRAME_SHIFT
+        }
+    }
+
+    /// Compute `[frames, NUM_MEL_BINS]` log mel energies with per-utterance
+    /// mean normalization (CMN) applied.
+    pub fn compute(&self, samples_16k: &[f32]) -> Array2<f32> {
+        let num_frames = Self::num_frames(samples_16k.len());
+        let mut features = Array2::<f32>::zeros((num_frames, NUM_MEL_BINS));
+        if num_frames == 0 {
+            return features;
+        }
+
+        let mut frame = vec![0.0f32; FFT_SIZE];
+        let mut spectrum = self.fft.make_output_vec();
+
+        for f in 0..num_frames {
+            let start = f * FRAME_SHIFT;
+            let input = &samples_16k[start..start + FRAME_LENGTH];
+
+            // Remove DC offset
+            let mean = input.iter().sum::<f32>() / FRAME_LENGTH as f32;
+            for (dst, &src) in frame.iter_mut().zip(input) {
+                *dst = (src - mean) * WAVEFORM_SCALE;
+            }
+
+            // Pre-emphasis (Kaldi style, first sample uses itself)
+            for i in (1..FRAME_LENGTH).rev() {
+                frame[i] -= PREEMPHASIS * frame[i - 1];
+            }
+            frame[0] -= PREEMPHASIS * frame[0];
+
+            for (sample, w) in frame.iter_mut().zip(&self.window) {
+                *sample *= w;
+            }
+            frame[FRAME_LENGTH..].iter_mut().for_each(|s| *s = 0.0);
+
+            if self.fft.process(&mut frame, &mut spectrum).is_err() {
+                continue;
+            }
+
+            for (m, (first_bin, weights)) in self.mel_banks.iter().enumerate() {
+                let energy: f32 = weights
+                    .iter()
+                    .enumerate()
+                    .map(|(i, w)| w * spectrum[first_bin + i].norm_sqr())
+                    .sum();
+                features[[f, m]] = energy.max(f32::EPSILON).ln();
+            }
+        }
+
+        // Cepstral mean normalization
+        if let Some(mean) = features.mean_axis(ndarray::Axis(0)) {
+            features -= &mean;
+        }
+
+        features
+    }
+}
+
+impl Default for FbankExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(freq: f32, seconds: f32) -> Vec<f32> {
+        (0..(seconds * FBANK_SAMPLE_RATE as f32) as usize)
+            .map(|i| 0.3 * (2.0 * std::f32::consts::PI * freq * i as f32 / FBANK_SAMPLE_RATE as f32).sin())
+            .collect()
+    }
+
+    #[test]
+    fn test_frame_count() {
+        assert_eq!(FbankExtractor::num_frames(399), 0);
+        assert_eq!(FbankExtractor::num_frames(400), 1);
+        assert_eq!(FbankExtractor::num_frames(16000), 98);
+
+        let features = FbankExtractor::new().compute(&sine(440.0, 1.0));
+        assert_eq!(features.shape(), &[98, NUM_MEL_BINS]);
+    }
+
+    #[test]
+    fn test_features_are_mean_normalized() {
+        let features = FbankExtractor::new().compute(&sine(1000.0, 0.5));
+        let mean = features.mean_axis(ndarray::Axis(0)).unwrap();
+        assert!(mean.iter().all(|m| m.abs() < 1e-3));
+    }
+
+    #[test]
+    fn test_mel_banks_cover_spectrum() {
+        let extractor = FbankExtractor::new();
+        assert_eq!(extractor.mel_banks.len(), NUM_MEL_BINS);
+        assert!(extractor.mel_banks.iter().all(|(_, weights)| !weights.is_empty()));
+        // Banks are ordered by frequency
+        assert!(extractor
+            .mel_banks
+            .windows(2)
+            .all(|pair| pair[0].0 <= pair[1].0));
+    }
+}
@@ -0,0 +1,306 @@
+/// Speaker Diarization Module
+///
+/// Labels transcript segments with the person speaking. Each VAD segment from
+/// the live pipeline (or each saved transcript row, for existing meetings) is
+/// turned into a speaker embedding by an ONNX model, and embeddings are
+/// clustered into per-meeting speaker ids ("speaker_1", "speaker_2", ...).
+/// Ids are numbered in order of first appearance, so they stay stable for the
+/// whole meeting; users can give them names per meeting.
+///
+/// The model is optional: place a WeSpeaker / 3D-Speaker ONNX model at
+/// `<app data>/models/speaker-embedding/model.onnx` to enable diarization.
+
+pub mod commands;
+pub mod embedding;
+pub mod fbank;
+
+use crate::database::repositories::setting::SettingsRepository;
+use crate::state::AppState;
+use embedding::{DiarizationError, SpeakerEmbeddingModel};
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, Runtime};
+
+pub const SPEAKER_MODEL_DIR: &str = "speaker-embedding";
+pub const SPEAKER_MODEL_FILE: &str = "model.onnx";
+
+/// Cosine similarity above which a segment joins an existing speaker
+pub const DEFAULT_SIMILARITY_THRESHOLD: f32 = 0.5;
+
+/// Upper bound on speakers per meeting; further voices join the closest speaker
+pub const MAX_SPEAKERS: usize = 12;
+
+static MODEL_PATH: Mutex<Option<PathBuf>> = Mutex::new(None);
+static EMBEDDING_MODEL: Mutex<Option<SpeakerEmbeddingModel>> = Mutex::new(None);
+static DIARIZATION_ENABLED: AtomicBool = AtomicBool::new(true);
+
+// Speaker clusters of the recording in progress
+static LIVE_CLUSTERER: Lazy<Mutex<SpeakerClusterer>> =
+    Lazy::new(|| Mutex::new(SpeakerClusterer::new(DEFAULT_SIMILARITY_THRESHOLD)));
+
+/// Speaker id for a 0-based cluster index
+pub fn speaker_id(index: usize) -> String {
+    format!("speaker_{}", index + 1)
+}
+
+/// Default display name for a speaker id ("speaker_2" -> "Speaker 2")
+pub fn default_speaker_name(speaker_id: &str) -> String {
+    match speaker_id.strip_prefix("speaker_") {
+        Some(number) => format!("Speaker {}", number),
+        None => speaker_id.to_string(),
+    }
+}
+
+pub fn normalize(mut vector: Vec<f32>) -> Vec<f32> {
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > f32::EPSILON {
+        vector.iter_mut().for_each(|v| *v /= norm);
+    }
+    vector
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    // Inputs are normalized
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// Online clustering of speaker embeddings.
+///
+/// Each embedding joins the most similar speaker centroid if the similarity
+/// passes the threshold, otherwise it starts a new speaker. Centroids are
+/// running means, so a speaker's profile improves as they talk.
+#[derive(Debug, Clone)]
+pub struct SpeakerClusterer {
+    centroids: Vec<(Vec<f32>, usize)>,
+    threshold: f32,
+}
+
+impl SpeakerClusterer {
+    pub fn new(threshold: f32) -> Self {
+        Self {
+            centroids: Vec::new(),
+            threshold,
+        }
+    }
+
+    pub fn num_speakers(&self) -> usize {
+        self.centroids.len()
+    }
+
+    fn closest(&self, embedding: &[f32]) -> Option<(usize, f32)> {
+        self.centroids
+            .iter()
+            .enumerate()
+            .map(|(i, (centroid, _))| (i, cosine_similarity(centroid, embedding)))
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+    }
+
+    /// Assign an embedding to a speaker and return the speaker's index
+    pub fn assign(&mut self, embedding: &[f32]) -> usize {
+        let embedding = normalize(embedding.to_vec());
+
+        match self.closest(&embedding) {
+            Some((index, similarity))
+                if similarity >= self.threshold || self.centroids.len() >= MAX_SPEAKERS =>
+            {
+                let (centroid, count) = &mut self.centroids[index];
+                let n = *count as f32;
+                for (c, e) in centroid.iter_mut().zip(&embedding) {
+                    *c = (*c * n + e) / (n + 1.0);
+                }
+                *centroid = normalize(std::mem::take(centroid));
+                *count += 1;
+                index
+            }
+            _ => {
+                self.centroids.push((embedding, 1));
+                self.centroids.len() - 1
+            }
+        }
+    }
+
+    /// Index of the closest existing speaker, without updating it
+    pub fn nearest(&self, embedding: &[f32]) -> Option<usize> {
+        self.closest(&normalize(embedding.to_vec())).map(|(i, _)| i)
+    }
+}
+
+/// Cluster a whole meeting's embeddings at once.
+///
+/// A first online pass finds the speakers, a second pass re-assigns every
+/// segment to its closest final centroid (early segments were assigned
+/// against immature centroids). Indices are renumbered by first appearance.
+/// `None` embeddings (segments too short to embed) stay unassigned.
+pub fn cluster_embeddings(embeddings: &[Option<Vec<f32>>], threshold: f32) -> Vec<Option<usize>> {
+    let mut clusterer = SpeakerClusterer::new(threshold);
+    for embedding in embeddings.iter().flatten() {
+        clusterer.assign(embedding);
+    }
+
+    let assigned: Vec<Option<usize>> = embeddings
+        .iter()
+        .map(|e| e.as_ref().and_then(|e| clusterer.nearest(e)))
+        .collect();
+
+    // Renumber by first appearance so speaker_1 is whoever spoke first
+    let mut order: Vec<usize> = Vec::new();
+    assigned
+        .into_iter()
+        .map(|index| {
+            index.map(|i| match order.iter().position(|&o| o == i) {
+                Some(position) => position,
+                None => {
+                    order.push(i);
+                    order.len() - 1
+                }
+            })
+        })
+        .collect()
+}
+
+/// Set the model location using app_data_dir (same models folder as Parakeet)
+pub fn set_models_directory<R: Runtime>(app: &AppHandle<R>) {
+    match app.path().app_data_dir() {
+        Ok(app_data_dir) => {
+            let path = app_data_dir
+                .join("models")
+                .join(SPEAKER_MODEL_DIR)
+                .join(SPEAKER_MODEL_FILE);
+            info!("Speaker embedding model path set to: {}", path.display());
+            *MODEL_PATH.lock().unwrap() = Some(path);
+        }
+        Err(e) => warn!("Failed to resolve app data dir for speaker model: {}", e),
+    }
+}
+
+pub fn model_path() -> Option<PathBuf> {
+    MODEL_PATH.lock().unwrap().clone()
+}
+
+pub fn is_model_available() -> bool {
+    model_path().map_or(false, |p| p.is_file())
+}
+
+pub fn is_enabled() -> bool {
+    DIARIZATION_ENABLED.load(Ordering::SeqCst)
+}
+
+pub fn set_enabled(enabled: bool) {
+    DIARIZATION_ENABLED.store(enabled, Ordering::SeqCst);
+    info!("Speaker diarization {}", if enabled { "enabled" } else { "disabled" });
+}
+
+/// Restore the on/off setting saved in the database
+pub async fn init<R: Runtime>(app: &AppHandle<R>) {
+    let Some(state) = app.try_state::<AppState>() else {
+        warn!("Database not ready, using default diarization setting");
+        return;
+    };
+    match SettingsRepository::get_diarization_enabled(state.db_manager.pool()).await {
+        Ok(Some(enabled)) => set_enabled(enabled),
+        Ok(None) => {}
+        Err(e) => warn!("Failed to load diarization setting: {}", e),
+    }
+}
+
+/// Diarization runs only when enabled and a model is installed
+pub fn is_active() -> bool {
+    is_enabled() && is_model_available()
+}
+
+/// Compute an embedding with the shared model, loading it on first use.
+/// Blocking - call from spawn_blocking.
+pub fn embed(samples_16k: &[f32]) -> Result<Vec<f32>, DiarizationError> {
+    let mut guard = EMBEDDING_MODEL.lock().unwrap();
+    if guard.is_none() {
+        let path = model_path()
+            .ok_or_else(|| DiarizationError::ModelNotFound("models directory not set".to_string()))?;
+        *guard = Some(SpeakerEmbeddingModel::load(&path)?);
+    }
+    guard.as_mut().unwrap().embed(samples_16k)
+}
+
+/// Start a new recording: forget the previous meeting's speakers
+pub fn reset_live_session() {
+    if let Ok(mut clusterer) = LIVE_CLUSTERER.lock() {
+        *clusterer = SpeakerClusterer::new(DEFAULT_SIMILARITY_THRESHOLD);
+    }
+}
+
+/// Speaker id for a live VAD segment. None when diarization is inactive or
+/// the segment is too short to identify. Blocking - call from spawn_blocking.
+pub fn assign_live_speaker(samples_16k: &[f32]) -> Option<String> {
+    if !is_active() {
+        return None;
+    }
+
+    match embed(samples_16k) {
+        Ok(embedding) => {
+            let mut clusterer = LIVE_CLUSTERER.lock().ok()?;
+            Some(speaker_id(clusterer.assign(&embedding)))
+        }
+        Err(DiarizationError::AudioTooShort(_)) => None,
+        Err(e) => {
+            warn!("Speaker embedding failed: {}", e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn voice(direction: usize, noise: f32) -> Vec<f32> {
+        let mut v = vec![noise; 8];
+        v[direction] = 1.0;
+        v
+    }
+
+    #[test]
+    fn test_online_clustering() {
+        let mut clusterer = SpeakerClusterer::new(0.7);
+        assert_eq!(clusterer.assign(&voice(0, 0.1)), 0);
+        assert_eq!(clusterer.assign(&voice(1, 0.1)), 1);
+        assert_eq!(clusterer.assign(&voice(0, 0.2)), 0);
+        assert_eq!(clusterer.assign(&voice(1, 0.05)), 1);
+        assert_eq!(clusterer.assign(&voice(2, 0.0)), 2);
+        assert_eq!(clusterer.num_speakers(), 3);
+    }
+
+    #[test]
+    fn test_max_speakers() {
+        let mut clusterer = SpeakerClusterer::new(0.99);
+        for i in 0..MAX_SPEAKERS + 3 {
+            let mut v = vec![0.0; MAX_SPEAKERS + 3];
+            v[i] = 1.0;
+            clusterer.assign(&v);
+        }
+        assert_eq!(clusterer.num_speakers(), MAX_SPEAKERS);
+    }
+
+    #[test]
+    fn test_offline_clustering_renumbers_by_first_appearance() {
+        let embeddings = vec![
+            Some(voice(3, 0.1)),
+            None,
+            Some(voice(5, 0.1)),
+            Some(voice(3, 0.15)),
+            Some(voice(5, 0.0)),
+        ];
+        assert_eq!(
+            cluster_embeddings(&embeddings, 0.7),
+            vec![Some(0), None, Some(1), Some(0), Some(1)]
+        );
+    }
+
+    #[test]
+    fn test_speaker_names() {
+        assert_eq!(speaker_id(0), "speaker_1");
+        assert_eq!(default_speaker_name("speaker_3"), "Speaker 3");
+        assert_eq!(default_speaker_name("guest"), "guest");
+    }
+}
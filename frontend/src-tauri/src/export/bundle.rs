@@ -0,0 +1,328 @@
+// export/bundle.rs
+//
+// Portable `.meetily` bundle: a zip archive holding everything needed to
+// recreate a meeting on another machine.
+//
+// Layout:
+//   metadata.json     - BundleMetadata
+//   transcripts.json  - Vec<MeetingTranscript> (with word timings)
+//   summary.json      - stored summary result JSON (optional)
+//   chat.json         - Vec<ChatMessageModel>
+//   audio/<file>      - meeting recording (optional)
+
+use crate::api::MeetingTranscript;
+use crate::database::models::ChatMessageModel;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use zip::result::ZipError;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+pub const BUNDLE_EXTENSION: &str = "meetily";
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+pub const METADATA_FILE: &str = "metadata.json";
+pub const TRANSCRIPTS_FILE: &str = "transcripts.json";
+pub const SUMMARY_FILE: &str = "summary.json";
+pub const CHAT_FILE: &str = "chat.json";
+pub const AUDIO_DIR: &str = "audio/";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleMetadata {
+    pub format_version: u32,
+    pub app_version: String,
+    pub meeting_id: String,
+    pub title: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub exported_at: String,
+    /// Path of the audio file inside the archive, e.g. "audio/audio.mp4"
+    pub audio_file: Option<String>,
+}
+
+/// Everything stored in a bundle except the audio bytes
+#[derive(Debug)]
+pub struct MeetingBundle {
+    pub metadata: BundleMetadata,
+    pub transcripts: Vec<MeetingTranscript>,
+    pub summary: Option<serde_json::Value>,
+    pub chat_messages: Vec<ChatMessageModel>,
+}
+
+/// Find the recording inside a meeting folder.
+/// Live recordings produce `audio.mp4`; imported media may keep its original extension.
+pub fn find_meeting_audio(folder: &Path) -> Option<PathBuf> {
+    let preferred = folder.join("audio.mp4");
+    if preferred.is_file() {
+        return Some(preferred);
+    }
+
+    std::fs::read_dir(folder)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .find(|path| {
+            path.is_file()
+                && path
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .map(|s| s == "audio")
+                    .unwrap_or(false)
+        })
+}
+
+/// Write a bundle to `path`.
+///
+/// The archive is written to a temporary file first and renamed into place,
+/// so a failed export never leaves a truncated bundle behind.
+pub fn write_bundle(path: &Path, bundle: &MeetingBundle, audio_path: Option<&Path>) -> Result<()> {
+    let tmp_path = path.with_extension(format!("{}.tmp", BUNDLE_EXTENSION));
+
+    let result = write_bundle_to(&tmp_path, bundle, audio_path);
+    if let Err(e) = result {
+        let _ = std::fs::remove_file(&tmp_path);
+        return Err(e);
+    }
+
+    std::fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+fn write_bundle_to(path: &Path, bundle: &MeetingBundle, audio_path: Option<&Path>) -> Result<()> {
+    let file = File::create(path)?;
+    let mut zip = ZipWriter::new(file);
+    let json_options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let mut metadata = bundle.metadata.clone();
+    metadata.audio_file = None;
+
+    // Audio first so metadata only references it once it is actually in the archive
+    if let Some(audio_path) = audio_path {
+        let file_name = audio_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .ok_or_else(|| anyhow!("Invalid audio file name: {}", audio_path.display()))?;
+        let entry_name = format!("{}{}", AUDIO_DIR, file_name);
+
+        // Audio is already compressed - store it as-is
+        let audio_options = SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Stored)
+            .large_file(true);
+        zip.start_file(entry_name.as_str(), audio_options)?;
+        let mut audio = File::open(audio_path)?;
+        std::io::copy(&mut audio, &mut zip)?;
+
+        metadata.audio_file = Some(entry_name);
+    }
+
+    zip.start_file(TRANSCRIPTS_FILE, json_options)?;
+    zip.write_all(&serde_json::to_vec_pretty(&bundle.transcripts)?)?;
+
+    if let Some(summary) = &bundle.summary {
+        zip.start_file(SUMMARY_FILE, json_options)?;
+        zip.write_all(&serde_json::to_vec_pretty(summary)?)?;
+    }
+
+    zip.start_file(CHAT_FILE, json_options)?;
+    zip.write_all(&serde_json::to_vec_pretty(&bundle.chat_messages)?)?;
+
+    zip.start_file(METADATA_FILE, json_options)?;
+    zip.write_all(&serde_json::to_vec_pretty(&metadata)?)?;
+
+    zip.finish()?;
+    Ok(())
+}
+
+/// Read the JSON parts of a bundle. Audio stays in the archive until
+/// `extract_bundle_audio` is called.
+pub fn read_bundle(path: &Path) -> Result<MeetingBundle> {
+    let mut archive = ZipArchive::new(File::open(path)?)
+        .map_err(|e| anyhow!("Not a valid .{} bundle: {}", BUNDLE_EXTENSION, e))?;
+
+    let metadata: BundleMetadata = serde_json::from_str(
+        &read_entry(&mut archive, METADATA_FILE)?
+            .ok_or_else(|| anyhow!("Bundle is missing {}", METADATA_FILE))?,
+    )?;
+    if metadata.format_version > BUNDLE_FORMAT_VERSION {
+        return Err(anyhow!(
+            "Bundle format version {} is newer than supported version {}. Please update the app.",
+            metadata.format_version,
+            BUNDLE_FORMAT_VERSION
+        ));
+    }
+
+    let transcripts = match read_entry(&mut archive, TRANSCRIPTS_FILE)? {
+        Some(json) => serde_json::from_str(&json)?,
+        None => Vec::new(),
+    };
+    let summary = match read_entry(&mut archive, SUMMARY_FILE)? {
+        Some(json) => Some(serde_json::from_str(&json)?),
+        None => None,
+    };
+    let chat_messages = match read_entry(&mut archive, CHAT_FILE)? {
+        Some(json) => serde_json::from_str(&json)?,
+        None => Vec::new(),
+    };
+
+    Ok(MeetingBundle {
+        metadata,
+        transcripts,
+        summary,
+        chat_messages,
+    })
+}
+
+fn read_entry(archive: &mut ZipArchive<File>, name: &str) -> Result<Option<String>> {
+    match archive.by_name(name) {
+        Ok(mut entry) => {
+            let mut content = String::new();
+            entry.read_to_string(&mut content)?;
+            Ok(Some(content))
+        }
+        Err(ZipError::FileNotFound) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Extract the bundle's audio into `dest_folder`, keeping only the file name
+/// from the archive entry (entries can't escape the destination folder).
+pub fn extract_bundle_audio(
+    bundle_path: &Path,
+    metadata: &BundleMetadata,
+    dest_folder: &Path,
+) -> Result<Option<PathBuf>> {
+    let entry_name = match &metadata.audio_file {
+        Some(name) => name,
+        None => return Ok(None),
+    };
+    let file_name = Path::new(entry_name)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .filter(|n| !n.is_empty())
+        .ok_or_else(|| anyhow!("Invalid audio entry in bundle: {}", entry_name))?;
+
+    let mut archive = ZipArchive::new(File::open(bundle_path)?)?;
+    let mut entry = match archive.by_name(entry_name) {
+        Ok(entry) => entry,
+        Err(ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    let target = dest_folder.join(file_name);
+    let mut output = File::create(&target)?;
+    std::io::copy(&mut entry, &mut output)?;
+    Ok(Some(target))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn sample_bundle() -> MeetingBundle {
+        MeetingBundle {
+            metadata: BundleMetadata {
+                format_version: BUNDLE_FORMAT_VERSION,
+                app_version: "test".to_string(),
+                meeting_id: "meeting-1".to_string(),
+                title: "Standup".to_string(),
+                created_at: "2025-12-01T10:00:00+00:00".to_string(),
+                updated_at: "2025-12-01T10:30:00+00:00".to_string(),
+                exported_at: "2025-12-02T09:00:00+00:00".to_string(),
+                audio_file: None,
+            },
+            transcripts: vec![MeetingTranscript {
+                id: "transcript-1".to_string(),
+                text: "Hello".to_string(),
+                timestamp: "10:00:01".to_string(),
+                audio_start_time: Some(1.0),
+                audio_end_time: Some(2.0),
+                duration: Some(1.0),
+                words: Vec::new(),
+                speaker_id: Some("speaker_1".to_string()),
+                speaker_name: Some("Alice".to_string()),
+                channel: None,
+            }],
+            summary: Some(serde_json::json!({ "markdown": "# Notes" })),
+            chat_messages: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_write_bundle_with_audio() {
+        let temp_dir = tempdir().unwrap();
+        let dir = temp_dir.path();
+        let audio = dir.join("audio.mp4");
+        std::fs::write(&audio, b"fake audio").unwrap();
+        let bundle_path = dir.join("standup.meetily");
+
+        write_bundle(&bundle_path, &sample_bundle(), Some(&audio)).unwrap();
+
+        let mut archive = zip::ZipArchive::new(File::open(&bundle_path).unwrap()).unwrap();
+        let mut metadata_json = String::new();
+        archive
+            .by_name(METADATA_FILE)
+            .unwrap()
+            .read_to_string(&mut metadata_json)
+            .unwrap();
+        let metadata: BundleMetadata = serde_json::from_str(&metadata_json).unwrap();
+        assert_eq!(metadata.audio_file.as_deref(), Some("audio/audio.mp4"));
+        assert!(archive.by_name("audio/audio.mp4").is_ok());
+        assert!(archive.by_name(TRANSCRIPTS_FILE).is_ok());
+        assert!(archive.by_name(SUMMARY_FILE).is_ok());
+        assert!(!bundle_path.with_extension("meetily.tmp").exists());
+    }
+
+    #[test]
+    fn test_read_bundle_round_trip() {
+        let temp_dir = tempdir().unwrap();
+        let dir = temp_dir.path();
+        let audio = dir.join("audio.mp4");
+        std::fs::write(&audio, b"fake audio").unwrap();
+        let bundle_path = dir.join("standup.meetily");
+        write_bundle(&bundle_path, &sample_bundle(), Some(&audio)).unwrap();
+
+        let bundle = read_bundle(&bundle_path).unwrap();
+        assert_eq!(bundle.metadata.meeting_id, "meeting-1");
+        assert_eq!(bundle.transcripts.len(), 1);
+        assert_eq!(bundle.transcripts[0].audio_start_time, Some(1.0));
+        assert_eq!(bundle.transcripts[0].speaker_name.as_deref(), Some("Alice"));
+        assert!(bundle.summary.is_some());
+
+        let restore_dir = dir.join("restored");
+        std::fs::create_dir_all(&restore_dir).unwrap();
+        let extracted = extract_bundle_audio(&bundle_path, &bundle.metadata, &restore_dir)
+            .unwrap()
+            .unwrap();
+        assert_eq!(extracted, restore_dir.join("audio.mp4"));
+        assert_eq!(std::fs::read(&extracted).unwrap(), b"fake audio");
+    }
+
+    #[test]
+    fn test_read_bundle_rejects_newer_format() {
+        let temp_dir = tempdir().unwrap();
+        let dir = temp_dir.path();
+        let bundle_path = dir.join("future.meetily");
+        let mut bundle = sample_bundle();
+        bundle.metadata.format_version = BUNDLE_FORMAT_VERSION + 1;
+        write_bundle(&bundle_path, &bundle, None).unwrap();
+
+        assert!(read_bundle(&bundle_path).is_err());
+    }
+
+    #[test]
+    fn test_find_meeting_audio() {
+        let temp_dir = tempdir().unwrap();
+        let dir = temp_dir.path();
+        assert!(find_meeting_audio(dir).is_none());
+
+        std::fs::write(dir.join("audio.m4a"), b"x").unwrap();
+        assert_eq!(find_meeting_audio(dir), Some(dir.join("audio.m4a")));
+
+        std::fs::write(dir.join("audio.mp4"), b"x").unwrap();
+        assert_eq!(find_meeting_audio(dir), Some(dir.join("audio.mp4")));
+    }
+}
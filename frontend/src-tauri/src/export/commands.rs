@@ -0,0 +1,336 @@
+use super::bundle::{
+    extract_bundle_audio, find_meeting_audio, read_bundle, write_bundle, BundleMetadata,
+    MeetingBundle, BUNDLE_FORMAT_VERSION,
+};
+use super::restore::{find_collision, restore_bundle, BundleCollision, ConflictStrategy, RestoreOptions};
+use super::{summary_markdown, to_lrc, to_markdown, to_srt, to_webvtt, ExportFormat};
+use crate::api::MeetingDetails;
+use crate::audio::audio_processing::create_meeting_folder;
+use crate::audio::recording_preferences::load_recording_preferences;
+use crate::database::repositories::{
+    chat_message::ChatMessagesRepository, meeting::MeetingsRepository,
+    summary::SummaryProcessesRepository,
+};
+use crate::recovery::CHECKPOINTS_DIR;
+use crate::state::AppState;
+use chrono::Utc;
+use log::{error as log_error, info as log_info, warn as log_warn};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Runtime, State};
+
+/// Export a meeting to `output_path` in the requested format.
+/// Returns the path that was written (an extension is added if missing).
+#[tauri::command]
+pub async fn api_export_meeting<R: Runtime>(
+    _app: AppHandle<R>,
+    state: State<'_, AppState>,
+    meeting_id: String,
+    format: ExportFormat,
+    output_path: String,
+) -> Result<String, String> {
+    log_info!(
+        "api_export_meeting called for meeting_id: {}, format: {:?}",
+        meeting_id,
+        format
+    );
+
+    export_meeting(state.db_manager.pool(), &meeting_id, format, &output_path).await
+}
+
+/// Write a meeting export without going through a Tauri command (used by the CLI)
+pub(crate) async fn export_meeting(
+    pool: &SqlitePool,
+    meeting_id: &str,
+    format: ExportFormat,
+    output_path: &str,
+) -> Result<String, String> {
+    let mut meeting = load_meeting(pool, meeting_id).await?;
+    sort_transcripts(&mut meeting);
+
+    let mut path = PathBuf::from(output_path);
+    if path.extension().is_none() {
+        path.set_extension(format.extension());
+    }
+
+    let summary = load_summary(pool, meeting_id).await;
+
+    let result = match format {
+        ExportFormat::Srt => std::fs::write(&path, to_srt(&meeting.transcripts)),
+        ExportFormat::Vtt => std::fs::write(&path, to_webvtt(&meeting.transcripts)),
+        ExportFormat::Lrc => std::fs::write(&path, to_lrc(&meeting.title, &meeting.transcripts)),
+        ExportFormat::Markdown => {
+            let summary_md = summary.as_ref().and_then(summary_markdown);
+            std::fs::write(
+                &path,
+                to_markdown(
+                    &meeting.title,
+                    &meeting.created_at,
+                    summary_md.as_deref(),
+                    &meeting.transcripts,
+                ),
+            )
+        }
+        ExportFormat::Bundle => {
+            return export_bundle(pool, meeting, summary, &path)
+                .await
+                .map(|_| path.to_string_lossy().to_string());
+        }
+    };
+
+    match result {
+        Ok(()) => {
+            log_info!("✅ Exported meeting {} to {}", meeting_id, path.display());
+            Ok(path.to_string_lossy().to_string())
+        }
+        Err(e) => {
+            log_error!("Failed to write export file {}: {}", path.display(), e);
+            Err(format!("Failed to write export file: {}", e))
+        }
+    }
+}
+
+async fn load_meeting(pool: &SqlitePool, meeting_id: &str) -> Result<MeetingDetails, String> {
+    match MeetingsRepository::get_meeting(pool, meeting_id).await {
+        Ok(Some(meeting)) => Ok(meeting),
+        Ok(None) | Err(sqlx::Error::RowNotFound) => {
+            Err(format!("Meeting not found: {}", meeting_id))
+        }
+        Err(e) => {
+            log_error!("Error retrieving meeting {}: {}", meeting_id, e);
+            Err(format!("Failed to retrieve meeting: {}", e))
+        }
+    }
+}
+
+/// Transcripts come back in insertion order - exports need playback order
+fn sort_transcripts(meeting: &mut MeetingDetails) {
+    meeting.transcripts.sort_by(|a, b| {
+        a.audio_start_time
+            .unwrap_or(0.0)
+            .partial_cmp(&b.audio_start_time.unwrap_or(0.0))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+}
+
+/// Stored summary result as JSON, if the meeting has one
+async fn load_summary(pool: &SqlitePool, meeting_id: &str) -> Option<serde_json::Value> {
+    match SummaryProcessesRepository::get_summary_data(pool, meeting_id).await {
+        Ok(Some(process)) => process.result.and_then(|result| {
+            serde_json::from_str(&result)
+                .map_err(|e| log_warn!("Stored summary for {} is not valid JSON: {}", meeting_id, e))
+                .ok()
+        }),
+        Ok(None) => None,
+        Err(e) => {
+            log_warn!("Failed to load summary for {}: {}", meeting_id, e);
+            None
+        }
+    }
+}
+
+async fn export_bundle(
+    pool: &SqlitePool,
+    meeting: MeetingDetails,
+    summary: Option<serde_json::Value>,
+    path: &Path,
+) -> Result<(), String> {
+    let chat_messages = ChatMessagesRepository::get_chat_history(pool, &meeting.id)
+        .await
+        .map_err(|e| format!("Failed to load chat history: {}", e))?;
+
+    let audio_path = match MeetingsRepository::get_meeting_model(pool, &meeting.id).await {
+        Ok(Some(model)) => model
+            .folder_path
+            .and_then(|folder| find_meeting_audio(Path::new(&folder))),
+        Ok(None) => None,
+        Err(e) => {
+            log_warn!("Failed to look up meeting folder for {}: {}", meeting.id, e);
+            None
+        }
+    };
+    if audio_path.is_none() {
+        log_info!("No audio file found for meeting {}, exporting without audio", meeting.id);
+    }
+
+    let bundle = MeetingBundle {
+        metadata: BundleMetadata {
+            format_version: BUNDLE_FORMAT_VERSION,
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            meeting_id: meeting.id.clone(),
+            title: meeting.title,
+            created_at: meeting.created_at,
+            updated_at: meeting.updated_at,
+            exported_at: Utc::now().to_rfc3339(),
+            audio_file: None, // Filled in by write_bundle once the audio is stored
+        },
+        transcripts: meeting.transcripts,
+        summary,
+        chat_messages,
+    };
+
+    let bundle_path = path.to_path_buf();
+    let meeting_id = meeting.id;
+    tokio::task::spawn_blocking(move || write_bundle(&bundle_path, &bundle, audio_path.as_deref()))
+        .await
+        .map_err(|e| format!("Bundle export task failed: {}", e))?
+        .map_err(|e| {
+            log_error!("Failed to write bundle for meeting {}: {}", meeting_id, e);
+            format!("Failed to write bundle: {}", e)
+        })?;
+
+    log_info!("✅ Exported meeting {} as bundle to {}", meeting_id, path.display());
+    Ok(())
+}
+
+/// What a bundle contains and whether it clashes with a local meeting
+#[derive(Debug, Serialize)]
+pub struct BundlePreview {
+    pub metadata: BundleMetadata,
+    pub transcript_count: usize,
+    pub chat_message_count: usize,
+    pub has_summary: bool,
+    pub has_audio: bool,
+    pub collision: Option<BundleCollision>,
+}
+
+async fn load_bundle(bundle_path: &str) -> Result<MeetingBundle, String> {
+    let path = PathBuf::from(bundle_path);
+    if !path.is_file() {
+        return Err(format!("Bundle not found: {}", bundle_path));
+    }
+    tokio::task::spawn_blocking(move || read_bundle(&path))
+        .await
+        .map_err(|e| format!("Bundle read task failed: {}", e))?
+        .map_err(|e| {
+            log_error!("Failed to read bundle {}: {}", bundle_path, e);
+            format!("Failed to read bundle: {}", e)
+        })
+}
+
+/// Read a `.meetily` bundle without importing it, so the UI can ask how to
+/// handle a collision before calling `api_import_meeting_bundle`.
+#[tauri::command]
+pub async fn api_inspect_meeting_bundle(
+    state: State<'_, AppState>,
+    bundle_path: String,
+) -> Result<BundlePreview, String> {
+    log_info!("api_inspect_meeting_bundle called for: {}", bundle_path);
+
+    let bundle = load_bundle(&bundle_path).await?;
+    let collision = find_collision(state.db_manager.pool(), &bundle)
+        .await
+        .map_err(|e| format!("Failed to check for existing meeting: {}", e))?;
+
+    Ok(BundlePreview {
+        transcript_count: bundle.transcripts.len(),
+        chat_message_count: bundle.chat_messages.len(),
+        has_summary: bundle.summary.is_some(),
+        has_audio: bundle.metadata.audio_file.is_some(),
+        metadata: bundle.metadata,
+        collision,
+    })
+}
+
+/// Import a `.meetily` bundle as a meeting.
+///
+/// `preserve_ids` (default true) keeps the original meeting, transcript and
+/// chat ids so the same meeting can move between machines. `on_conflict`
+/// decides what happens when the meeting already exists locally (default:
+/// fail). The audio is copied into the recordings folder.
+/// Returns the id of the imported meeting.
+#[tauri::command]
+pub async fn api_import_meeting_bundle<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, AppState>,
+    bundle_path: String,
+    preserve_ids: Option<bool>,
+    on_conflict: Option<ConflictStrategy>,
+) -> Result<String, String> {
+    log_info!(
+        "api_import_meeting_bundle called for: {}, preserve_ids: {:?}, on_conflict: {:?}",
+        bundle_path,
+        preserve_ids,
+        on_conflict
+    );
+
+    let bundle = load_bundle(&bundle_path).await?;
+    let pool = state.db_manager.pool();
+
+    let mut options = RestoreOptions {
+        preserve_ids: preserve_ids.unwrap_or(true),
+        ..Default::default()
+    };
+
+    let collision = find_collision(pool, &bundle)
+        .await
+        .map_err(|e| format!("Failed to check for existing meeting: {}", e))?;
+    if let Some(collision) = collision {
+        match on_conflict.unwrap_or_default() {
+            ConflictStrategy::Fail => {
+                return Err(format!(
+                    "Meeting '{}' already exists ({}). Import it as a copy or replace the existing meeting.",
+                    collision.title, collision.meeting_id
+                ));
+            }
+            ConflictStrategy::Copy => {
+                log_info!("Importing bundle as a copy of meeting {}", collision.meeting_id);
+                options.preserve_ids = false;
+                options.title = Some(format!("{} (copy)", bundle.metadata.title));
+            }
+            ConflictStrategy::Replace => {
+                log_info!("Bundle import will replace meeting {}", collision.meeting_id);
+                options.replace_meeting_id = Some(collision.meeting_id);
+            }
+        }
+    }
+
+    // Copy the audio next to the other recordings
+    let mut created_folder = None;
+    if bundle.metadata.audio_file.is_some() {
+        let title = options.title.as_deref().unwrap_or(&bundle.metadata.title);
+        match load_recording_preferences(&app).await {
+            Ok(prefs) => match create_meeting_folder(&prefs.save_folder, title) {
+                Ok(folder) => {
+                    // Imported meetings are complete - drop the empty dir so recovery won't flag it
+                    let _ = std::fs::remove_dir(folder.join(CHECKPOINTS_DIR));
+                    let source = PathBuf::from(&bundle_path);
+                    let metadata = bundle.metadata.clone();
+                    let target = folder.clone();
+                    match tokio::task::spawn_blocking(move || {
+                        extract_bundle_audio(&source, &metadata, &target)
+                    })
+                    .await
+                    {
+                        Ok(Ok(Some(audio))) => log_info!("Copied bundle audio to {}", audio.display()),
+                        Ok(Ok(None)) => log_warn!("Bundle lists audio but the archive has none"),
+                        Ok(Err(e)) => log_warn!("Failed to extract bundle audio: {}", e),
+                        Err(e) => log_warn!("Audio extract task failed: {}", e),
+                    }
+                    created_folder = Some(folder);
+                }
+                Err(e) => log_warn!("Failed to create meeting folder for bundle import: {}", e),
+            },
+            Err(e) => log_warn!("Failed to load recording preferences: {}", e),
+        }
+    }
+    options.folder_path = created_folder
+        .as_ref()
+        .map(|folder| folder.to_string_lossy().to_string());
+
+    match restore_bundle(pool, &bundle, &options).await {
+        Ok(meeting_id) => {
+            log_info!("✅ Imported bundle {} as meeting {}", bundle_path, meeting_id);
+            Ok(meeting_id)
+        }
+        Err(e) => {
+            log_error!("Failed to import bundle {}: {}", bundle_path, e);
+            // Don't leave an orphaned recording folder behind
+            if let Some(folder) = created_folder {
+                let _ = std::fs::remove_dir_all(folder);
+            }
+            Err(format!("Failed to import meeting: {}", e))
+        }
+    }
+}
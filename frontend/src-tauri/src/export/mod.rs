@@ -0,0 +1,399 @@
+/// Meeting Export Module
+///
+/// Converts a stored meeting into files that can be shared outside the app:
+/// - SRT / WebVTT subtitles from the recording-relative transcript timings
+/// - LRC (the reverse of `lrc::parse_lrc`)
+/// - Markdown with the summary and a timestamped transcript
+/// - A portable `.meetily` zip bundle (see `bundle`), which can be imported
+///   again on another machine (see `restore`)
+
+pub mod bundle;
+pub mod commands;
+pub mod restore;
+
+use crate::api::MeetingTranscript;
+use serde::{Deserialize, Serialize};
+
+/// Fallback length for the last segment when no end time is stored
+const DEFAULT_SEGMENT_SECONDS: f64 = 5.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Srt,
+    Vtt,
+    Lrc,
+    Markdown,
+    Bundle,
+}
+
+impl ExportFormat {
+    /// File extension used when the caller doesn't provide one
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Srt => "srt",
+            Self::Vtt => "vtt",
+            Self::Lrc => "lrc",
+            Self::Markdown => "md",
+            Self::Bundle => bundle::BUNDLE_EXTENSION,
+        }
+    }
+}
+
+/// Resolve start/end times (seconds) for every segment.
+///
+/// Older meetings may lack `audio_start_time`/`audio_end_time`; missing starts
+/// continue from the previous segment and missing ends use the duration, the
+/// next segment's start or a 5 second default.
+pub fn segment_times(segments: &[MeetingTranscript]) -> Vec<(f64, f64)> {
+    let mut times = Vec::with_capacity(segments.len());
+    let mut previous_end = 0.0;
+
+    for (i, segment) in segments.iter().enumerate() {
+        let start = segment.audio_start_time.unwrap_or(previous_end).max(0.0);
+        let end = segment
+            .audio_end_time
+            .or_else(|| segment.duration.map(|d| start + d))
+            .or_else(|| segments.get(i + 1).and_then(|next| next.audio_start_time))
+            .filter(|end| *end > start)
+            .unwrap_or(start + DEFAULT_SEGMENT_SECONDS);
+
+        times.push((start, end));
+        previous_end = end;
+    }
+
+    times
+}
+
+/// Split seconds into (hours, minutes, seconds, milliseconds)
+fn split_time(seconds: f64) -> (u64, u64, u64, u64) {
+    let total_ms = (seconds.max(0.0) * 1000.0).round() as u64;
+    (
+        total_ms / 3_600_000,
+        (total_ms / 60_000) % 60,
+        (total_ms / 1000) % 60,
+        total_ms % 1000,
+    )
+}
+
+/// Format as SRT timestamp: "HH:MM:SS,mmm"
+pub fn format_srt_time(seconds: f64) -> String {
+    let (h, m, s, ms) = split_time(seconds);
+    format!("{:02}:{:02}:{:02},{:03}", h, m, s, ms)
+}
+
+/// Format as WebVTT timestamp: "HH:MM:SS.mmm"
+pub fn format_vtt_time(seconds: f64) -> String {
+    let (h, m, s, ms) = split_time(seconds);
+    format!("{:02}:{:02}:{:02}.{:03}", h, m, s, ms)
+}
+
+/// Format as LRC timestamp: "[mm:ss.xx]" (minutes are not wrapped at 60)
+pub fn format_lrc_time(seconds: f64) -> String {
+    let total_cs = (seconds.max(0.0) * 100.0).round() as u64;
+    format!(
+        "[{:02}:{:02}.{:02}]",
+        total_cs / 6000,
+        (total_cs / 100) % 60,
+        total_cs % 100
+    )
+}
+
+/// Format for display in Markdown: "[MM:SS]" or "[H:MM:SS]" past one hour
+pub fn format_display_time(seconds: f64) -> String {
+    let (h, m, s, _) = split_time(seconds);
+    if h > 0 {
+        format!("[{}:{:02}:{:02}]", h, m, s)
+    } else {
+        format!("[{:02}:{:02}]", m, s)
+    }
+}
+
+/// Speaker name for a segment: the diarized name, else "Me" / "Remote" for
+/// per-channel recordings
+fn speaker_label(segment: &MeetingTranscript) -> Option<String> {
+    segment
+        .speaker_name
+        .as_deref()
+        .map(single_line)
+        .filter(|name| !name.is_empty())
+        .or_else(|| segment.channel.map(|c| c.label().to_string()))
+}
+
+/// Collapse newlines so one segment stays on one subtitle/LRC line; a blank
+/// line inside an SRT or WebVTT cue would end it
+fn single_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Escape the characters WebVTT cue text reserves for markup
+fn escape_vtt(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;")
+}
+
+pub fn to_srt(segments: &[MeetingTranscript]) -> String {
+    let mut output = String::new();
+    let times = segment_times(segments);
+
+    let mut index = 0;
+    for (segment, (start, end)) in segments.iter().zip(times) {
+        let text = single_line(&segment.text);
+        if text.is_empty() {
+            continue;
+        }
+        // Cue numbers must be sequential, so count only emitted cues
+        index += 1;
+        output.push_str(&format!(
+            "{}\n{} --> {}\n{}\n\n",
+            index,
+            format_srt_time(start),
+            format_srt_time(end),
+            text
+        ));
+    }
+
+    output
+}
+
+pub fn to_webvtt(segments: &[MeetingTranscript]) -> String {
+    let mut output = String::from("WEBVTT\n\n");
+    let times = segment_times(segments);
+
+    for (segment, (start, end)) in segments.iter().zip(times) {
+        let text = single_line(&segment.text);
+        if text.is_empty() {
+            continue;
+        }
+        // "-->" is not allowed inside a cue payload
+        let mut payload = escape_vtt(&text.replace("-->", "->"));
+        if let Some(speaker) = speaker_label(segment) {
+            let speaker = escape_vtt(&speaker.replace(['<', '>'], ""));
+            payload = format!("<v {}>{}", speaker, payload);
+        }
+        output.push_str(&format!(
+            "{} --> {}\n{}\n\n",
+            format_vtt_time(start),
+            format_vtt_time(end),
+            payload
+        ));
+    }
+
+    output
+}
+
+/// Produce LRC content that `lrc::parse_lrc` reads back into the same lines
+pub fn to_lrc(title: &str, segments: &[MeetingTranscript]) -> String {
+    let mut output = String::new();
+    let times = segment_times(segments);
+
+    let title = single_line(title);
+    if !title.is_empty() {
+        output.push_str(&format!("[ti:{}]\n", title));
+    }
+    if let Some((_, end)) = times.last() {
+        let total_seconds = end.max(0.0).round() as u64;
+        output.push_str(&format!(
+            "[length:{:02}:{:02}]\n",
+            total_seconds / 60,
+            total_seconds % 60
+        ));
+    }
+
+    for (segment, (start, _)) in segments.iter().zip(times) {
+        let text = single_line(&segment.text);
+        if text.is_empty() {
+            continue;
+        }
+        output.push_str(&format!("{}{}\n", format_lrc_time(start), text));
+    }
+
+    output
+}
+
+/// Markdown document with an optional summary and the timestamped transcript.
+/// Sticks to headings, emphasis and paragraphs so it converts cleanly to DOCX.
+pub fn to_markdown(
+    title: &str,
+    created_at: &str,
+    summary_markdown: Option<&str>,
+    segments: &[MeetingTranscript],
+) -> String {
+    let mut output = format!("# {}\n\n", single_line(title));
+    if !created_at.is_empty() {
+        output.push_str(&format!("*Date: {}*\n\n", created_at));
+    }
+
+    if let Some(summary) = summary_markdown.map(str::trim).filter(|s| !s.is_empty()) {
+        output.push_str("## Summary\n\n");
+        output.push_str(summary);
+        output.push_str("\n\n");
+    }
+
+    output.push_str("## Transcript\n\n");
+    let times = segment_times(segments);
+    for (segment, (start, _)) in segments.iter().zip(times) {
+        let text = single_line(&segment.text);
+        if text.is_empty() {
+            continue;
+        }
+        match speaker_label(segment) {
+            Some(speaker) => output.push_str(&format!(
+                "**{} {}:** {}\n\n",
+                format_display_time(start),
+                speaker,
+                text
+            )),
+            None => output.push_str(&format!("**{}** {}\n\n", format_display_time(start), text)),
+        }
+    }
+
+    output
+}
+
+/// Pull the markdown out of a stored summary result.
+/// Results are saved as `{ "markdown": "...", "summary_json": [...] }`.
+pub fn summary_markdown(result: &serde_json::Value) -> Option<String> {
+    match result {
+        serde_json::Value::String(text) => Some(text.clone()),
+        serde_json::Value::Object(map) => map
+            .get("markdown")
+            .and_then(|m| m.as_str())
+            .map(|m| m.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::TranscriptChannel;
+    use crate::lrc::parse_lrc;
+
+    fn segment(text: &str, start: Option<f64>, end: Option<f64>) -> MeetingTranscript {
+        MeetingTranscript {
+            id: format!("t-{}", text),
+            text: text.to_string(),
+            timestamp: "10:00:00".to_string(),
+            audio_start_time: start,
+            audio_end_time: end,
+            duration: None,
+            words: Vec::new(),
+            speaker_id: None,
+            speaker_name: None,
+            channel: None,
+        }
+    }
+
+    #[test]
+    fn test_time_formats() {
+        assert_eq!(format_srt_time(3725.5), "01:02:05,500");
+        assert_eq!(format_vtt_time(0.0456), "00:00:00.046");
+        assert_eq!(format_lrc_time(75.25), "[01:15.25]");
+        assert_eq!(format_lrc_time(3725.5), "[62:05.50]");
+        assert_eq!(format_display_time(135.0), "[02:15]");
+        assert_eq!(format_display_time(3725.0), "[1:02:05]");
+    }
+
+    #[test]
+    fn test_segment_times_fill_gaps() {
+        let segments = vec![
+            segment("a", Some(1.0), Some(3.0)),
+            segment("b", None, None),
+            segment("c", Some(20.0), None),
+        ];
+        let times = segment_times(&segments);
+        assert_eq!(times[0], (1.0, 3.0));
+        // Missing start continues from previous end; end falls back to next start
+        assert_eq!(times[1], (3.0, 20.0));
+        assert_eq!(times[2], (20.0, 25.0));
+    }
+
+    #[test]
+    fn test_srt_output() {
+        let segments = vec![
+            segment("Hello everyone", Some(0.5), Some(2.0)),
+            segment("Let's begin", Some(2.0), Some(4.25)),
+        ];
+        let srt = to_srt(&segments);
+        assert_eq!(
+            srt,
+            "1\n00:00:00,500 --> 00:00:02,000\nHello everyone\n\n2\n00:00:02,000 --> 00:00:04,250\nLet's begin\n\n"
+        );
+    }
+
+    #[test]
+    fn test_cues_collapse_blank_lines() {
+        let segments = vec![segment("First part\n\n second part", Some(0.0), Some(1.0))];
+        assert!(to_srt(&segments).contains("00:00:01,000\nFirst part second part\n\n"));
+        assert!(to_webvtt(&segments).contains("00:00:01.000\nFirst part second part\n\n"));
+    }
+
+    #[test]
+    fn test_webvtt_output() {
+        let vtt = to_webvtt(&[
+            segment("a --> b", Some(1.0), Some(2.0)),
+            segment("R&D says x < y", Some(2.0), Some(3.0)),
+        ]);
+        assert!(vtt.starts_with("WEBVTT\n\n"));
+        assert!(vtt.contains("00:00:01.000 --> 00:00:02.000\na -> b\n"));
+        assert!(vtt.contains("00:00:02.000 --> 00:00:03.000\nR&amp;D says x &lt; y\n"));
+    }
+
+    #[test]
+    fn test_lrc_round_trip() {
+        let segments = vec![
+            segment("First line", Some(12.0), Some(15.5)),
+            segment("Second\nline", Some(15.5), Some(20.3)),
+            segment("Third line", Some(3725.3), Some(3730.0)),
+        ];
+        let lrc = to_lrc("Weekly Sync", &segments);
+        let parsed = parse_lrc(&lrc).unwrap();
+
+        assert_eq!(parsed.metadata.title, Some("Weekly Sync".to_string()));
+        assert_eq!(parsed.lines.len(), 3);
+        assert_eq!(parsed.lines[0].time_seconds, 12.0);
+        assert_eq!(parsed.lines[1].text, "Second line");
+        assert!((parsed.lines[2].time_seconds - 3725.3).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_markdown_output() {
+        let md = to_markdown(
+            "Planning",
+            "2025-12-01",
+            Some("- Ship v2\n"),
+            &[segment("Kickoff", Some(65.0), Some(70.0))],
+        );
+        assert!(md.starts_with("# Planning\n\n*Date: 2025-12-01*\n\n## Summary\n\n- Ship v2\n\n"));
+        assert!(md.contains("## Transcript\n\n**[01:05]** Kickoff\n\n"));
+    }
+
+    #[test]
+    fn test_speaker_labels() {
+        let mut kickoff = segment("Kickoff", Some(65.0), Some(70.0));
+        kickoff.speaker_id = Some("speaker_1".to_string());
+        kickoff.speaker_name = Some("Alice".to_string());
+        let segments = [kickoff, segment("Thanks", Some(70.0), Some(72.0))];
+
+        let md = to_markdown("Planning", "", None, &segments);
+        assert!(md.contains("**[01:05] Alice:** Kickoff\n\n**[01:10]** Thanks\n\n"));
+
+        let vtt = to_webvtt(&segments);
+        assert!(vtt.contains("00:01:05.000 --> 00:01:10.000\n<v Alice>Kickoff\n\n"));
+
+        let mut qa = segment("Q&A", Some(0.0), Some(1.0));
+        qa.speaker_name = Some("Ops & <Infra>".to_string());
+        assert!(to_webvtt(&[qa]).contains("<v Ops &amp; Infra>Q&amp;A\n"));
+
+        let mut remote = segment("Hi all", Some(0.0), Some(1.0));
+        remote.channel = Some(TranscriptChannel::System);
+        assert!(to_markdown("Sync", "", None, &[remote]).contains("**[00:00] Remote:** Hi all"));
+    }
+
+    #[test]
+    fn test_summary_markdown_extraction() {
+        let result = serde_json::json!({ "markdown": "# Notes", "summary_json": [] });
+        assert_eq!(summary_markdown(&result), Some("# Notes".to_string()));
+        assert_eq!(summary_markdown(&serde_json::json!({ "other": 1 })), None);
+    }
+}
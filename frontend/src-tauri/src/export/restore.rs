@@ -0,0 +1,380 @@
+// export/restore.rs
+//
+// Recreates a meeting in the database from a `.meetily` bundle.
+
+use super::bundle::MeetingBundle;
+use crate::database::repositories::meeting::delete_meeting_with_transaction;
+use crate::database::repositories::summary_version::{SummaryVersionsRepository, VersionOrigin};
+use crate::database::repositories::transcript_word::TranscriptWordsRepository;
+use crate::diarization::default_speaker_name;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Connection, Error as SqlxError, SqlitePool};
+use tracing::{error, info};
+use uuid::Uuid;
+
+/// What to do when the bundle's meeting already exists locally
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictStrategy {
+    /// Abort the import
+    #[default]
+    Fail,
+    /// Import alongside the existing meeting with fresh ids
+    Copy,
+    /// Delete the existing meeting and import in its place
+    Replace,
+}
+
+/// An existing meeting that matches the bundle
+#[derive(Debug, Clone, Serialize)]
+pub struct BundleCollision {
+    pub meeting_id: String,
+    pub title: String,
+    pub created_at: String,
+    /// true if the ids match, false if only title and creation time match
+    /// (a bundle that was imported earlier with new ids)
+    pub same_id: bool,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct CollisionRow {
+    id: String,
+    title: String,
+    created_at: crate::database::models::DateTimeUtc,
+}
+
+/// Parse a bundle timestamp, falling back to now for anything unreadable
+pub fn parse_bundle_time(value: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|_| Utc::now())
+}
+
+/// Look for a local meeting with the bundle's id, or with the same title and
+/// creation time.
+pub async fn find_collision(
+    pool: &SqlitePool,
+    bundle: &MeetingBundle,
+) -> Result<Option<BundleCollision>, SqlxError> {
+    let metadata = &bundle.metadata;
+
+    let by_id: Option<CollisionRow> =
+        sqlx::query_as("SELECT id, title, created_at FROM meetings WHERE id = ?")
+            .bind(&metadata.meeting_id)
+            .fetch_optional(pool)
+            .await?;
+    if let Some(row) = by_id {
+        return Ok(Some(BundleCollision {
+            meeting_id: row.id,
+            title: row.title,
+            created_at: row.created_at.0.to_rfc3339(),
+            same_id: true,
+        }));
+    }
+
+    let created_at = match DateTime::parse_from_rfc3339(&metadata.created_at) {
+        Ok(dt) => dt.with_timezone(&Utc),
+        Err(_) => return Ok(None),
+    };
+    let by_content: Option<CollisionRow> = sqlx::query_as(
+        "SELECT id, title, created_at FROM meetings WHERE title = ? AND created_at = ? LIMIT 1",
+    )
+    .bind(&metadata.title)
+    .bind(created_at)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(by_content.map(|row| BundleCollision {
+        meeting_id: row.id,
+        title: row.title,
+        created_at: row.created_at.0.to_rfc3339(),
+        same_id: false,
+    }))
+}
+
+/// Origin of a bundle's summary: summaries saved from the editor carry
+/// `summary_json`, anything else came straight from a model we no longer know
+fn imported_origin(summary: &serde_json::Value) -> VersionOrigin {
+    if summary.get("summary_json").is_some() {
+        VersionOrigin::edited()
+    } else {
+        VersionOrigin {
+            source: "generated",
+            provider: None,
+            model: None,
+            template_id: None,
+            custom_prompt: None,
+        }
+    }
+}
+
+/// Options for `restore_bundle`
+#[derive(Debug, Clone, Default)]
+pub struct RestoreOptions {
+    /// Keep the meeting, transcript and chat message ids from the bundle
+    pub preserve_ids: bool,
+    /// Title to use instead of the bundle's title
+    pub title: Option<String>,
+    /// Folder the audio was copied into
+    pub folder_path: Option<String>,
+    /// Existing meeting to delete in the same transaction
+    pub replace_meeting_id: Option<String>,
+}
+
+/// Insert the meeting, transcripts (with word timings), summary and chat
+/// history from a bundle in one transaction. Returns the meeting id.
+pub async fn restore_bundle(
+    pool: &SqlitePool,
+    bundle: &MeetingBundle,
+    options: &RestoreOptions,
+) -> Result<String, SqlxError> {
+    let metadata = &bundle.metadata;
+    let meeting_id = if options.preserve_ids {
+        metadata.meeting_id.clone()
+    } else {
+        format!("meeting-{}", Uuid::new_v4())
+    };
+    let title = options.title.as_deref().unwrap_or(&metadata.title);
+    let created_at = parse_bundle_time(&metadata.created_at);
+    let updated_at = parse_bundle_time(&metadata.updated_at);
+
+    let mut conn = pool.acquire().await?;
+    let mut transaction = conn.begin().await?;
+
+    // 1. Remove the meeting being replaced
+    if let Some(existing_id) = &options.replace_meeting_id {
+        delete_meeting_with_transaction(&mut *transaction, existing_id).await?;
+        info!("Replacing existing meeting {} with bundle import", existing_id);
+    }
+
+    // 2. Meeting row with the original timestamps
+    if let Err(e) = sqlx::query(
+        "INSERT INTO meetings (id, title, created_at, updated_at, folder_path) VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(&meeting_id)
+    .bind(title)
+    .bind(created_at)
+    .bind(updated_at)
+    .bind(&options.folder_path)
+    .execute(&mut *transaction)
+    .await
+    {
+        error!("Failed to create meeting '{}' from bundle: {}", title, e);
+        transaction.rollback().await?;
+        return Err(e);
+    }
+
+    // 3. Transcripts and word timings
+    for segment in &bundle.transcripts {
+        let transcript_id = if options.preserve_ids {
+            segment.id.clone()
+        } else {
+            format!("transcript-{}", Uuid::new_v4())
+        };
+
+        if let Err(e) = sqlx::query(
+            "INSERT INTO transcripts (id, meeting_id, transcript, timestamp, audio_start_time, audio_end_time, duration, speaker_id, channel)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&transcript_id)
+        .bind(&meeting_id)
+        .bind(&segment.text)
+        .bind(&segment.timestamp)
+        .bind(segment.audio_start_time)
+        .bind(segment.audio_end_time)
+        .bind(segment.duration)
+        .bind(&segment.speaker_id)
+        .bind(segment.channel.map(|c| c.as_str()))
+        .execute(&mut *transaction)
+        .await
+        {
+            error!("Failed to restore transcript {} for meeting {}: {}", segment.id, meeting_id, e);
+            transaction.rollback().await?;
+            return Err(e);
+        }
+
+        if let Err(e) =
+            TranscriptWordsRepository::save_words(&mut *transaction, &transcript_id, &segment.words)
+                .await
+        {
+            error!("Failed to restore word timings for {}: {}", transcript_id, e);
+            transaction.rollback().await?;
+            return Err(e);
+        }
+    }
+
+    // Custom speaker names travel with the transcripts
+    let mut named_speakers: Vec<(&str, &str)> = bundle
+        .transcripts
+        .iter()
+        .filter_map(|t| Some((t.speaker_id.as_deref()?, t.speaker_name.as_deref()?)))
+        .filter(|(id, name)| *name != default_speaker_name(id))
+        .collect();
+    named_speakers.sort();
+    named_speakers.dedup_by(|a, b| a.0 == b.0);
+    for (speaker_id, display_name) in named_speakers {
+        if let Err(e) = sqlx::query(
+            "INSERT INTO meeting_speakers (meeting_id, speaker_id, display_name, updated_at)
+             VALUES (?, ?, ?, ?)",
+        )
+        .bind(&meeting_id)
+        .bind(speaker_id)
+        .bind(display_name)
+        .bind(updated_at)
+        .execute(&mut *transaction)
+        .await
+        {
+            error!("Failed to restore speaker name for meeting {}: {}", meeting_id, e);
+            transaction.rollback().await?;
+            return Err(e);
+        }
+    }
+
+    // 4. Summary, stored as completed and recorded as the first version so a
+    // later regeneration keeps it in the history
+    if let Some(summary) = &bundle.summary {
+        let result = summary.to_string();
+        if let Err(e) = sqlx::query(
+            "INSERT INTO summary_processes (meeting_id, status, created_at, updated_at, result, start_time, end_time, chunk_count, processing_time)
+             VALUES (?, 'completed', ?, ?, ?, ?, ?, 0, 0.0)",
+        )
+        .bind(&meeting_id)
+        .bind(created_at)
+        .bind(updated_at)
+        .bind(&result)
+        .bind(created_at)
+        .bind(updated_at)
+        .execute(&mut *transaction)
+        .await
+        {
+            error!("Failed to restore summary for meeting {}: {}", meeting_id, e);
+            transaction.rollback().await?;
+            return Err(e);
+        }
+
+        if let Err(e) = SummaryVersionsRepository::record(
+            &mut *transaction,
+            &meeting_id,
+            &result,
+            &imported_origin(summary),
+        )
+        .await
+        {
+            error!("Failed to record summary version for meeting {}: {}", meeting_id, e);
+            transaction.rollback().await?;
+            return Err(e);
+        }
+    }
+
+    // 5. Chat history
+    for message in &bundle.chat_messages {
+        let message_id = if options.preserve_ids {
+            message.id.clone()
+        } else {
+            format!("chat-msg-{}", Uuid::new_v4())
+        };
+
+        if let Err(e) = sqlx::query(
+            "INSERT INTO chat_messages (id, meeting_id, role, content, created_at, ttft_us)
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&message_id)
+        .bind(&meeting_id)
+        .bind(&message.role)
+        .bind(&message.content)
+        .bind(message.created_at)
+        .bind(message.ttft_us)
+        .execute(&mut *transaction)
+        .await
+        {
+            error!("Failed to restore chat message for meeting {}: {}", meeting_id, e);
+            transaction.rollback().await?;
+            return Err(e);
+        }
+    }
+
+    transaction.commit().await?;
+    info!(
+        "Restored meeting {} from bundle ({} transcripts, {} chat messages)",
+        meeting_id,
+        bundle.transcripts.len(),
+        bundle.chat_messages.len()
+    );
+
+    Ok(meeting_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::bundle::{BundleMetadata, BUNDLE_FORMAT_VERSION};
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn test_pool() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        pool
+    }
+
+    fn bundle_with_summary(summary: serde_json::Value) -> MeetingBundle {
+        MeetingBundle {
+            metadata: BundleMetadata {
+                format_version: BUNDLE_FORMAT_VERSION,
+                app_version: "test".to_string(),
+                meeting_id: "meeting-1".to_string(),
+                title: "Standup".to_string(),
+                created_at: "2025-12-01T10:00:00+00:00".to_string(),
+                updated_at: "2025-12-01T10:30:00+00:00".to_string(),
+                exported_at: "2025-12-02T09:00:00+00:00".to_string(),
+                audio_file: None,
+            },
+            transcripts: Vec::new(),
+            summary: Some(summary),
+            chat_messages: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_restore_records_summary_version() {
+        let pool = test_pool().await;
+        let summary = serde_json::json!({ "markdown": "# Notes", "summary_json": [] });
+
+        let meeting_id = restore_bundle(
+            &pool,
+            &bundle_with_summary(summary.clone()),
+            &RestoreOptions::default(),
+        )
+        .await
+        .unwrap();
+
+        let versions = SummaryVersionsRepository::list_for_meeting(&pool, &meeting_id)
+            .await
+            .unwrap();
+        assert_eq!(versions.len(), 1);
+        assert_eq!(versions[0].source, "edited");
+        assert_eq!(versions[0].result, summary.to_string());
+
+        let current: (Option<String>,) =
+            sqlx::query_as("SELECT current_version_id FROM summary_processes WHERE meeting_id = ?")
+                .bind(&meeting_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(current.0.as_deref(), Some(versions[0].id.as_str()));
+    }
+
+    #[test]
+    fn test_imported_origin() {
+        let edited = imported_origin(&serde_json::json!({ "markdown": "", "summary_json": [] }));
+        assert_eq!(edited.source, "edited");
+
+        let generated = imported_origin(&serde_json::json!({ "MeetingName": "Standup" }));
+        assert_eq!(generated.source, "generated");
+        assert_eq!(generated.model, None);
+    }
+}
@@ -0,0 +1,98 @@
+use crate::database::models::GlossaryTerm;
+use crate::database::repositories::glossary::GlossaryRepository;
+use crate::state::AppState;
+use log::{error as log_error, info as log_info, warn as log_warn};
+use sqlx::SqlitePool;
+use tauri::State;
+
+/// Longest term accepted; glossary entries are words and short phrases
+const MAX_TERM_CHARS: usize = 100;
+
+async fn reload_after_change(pool: &SqlitePool) {
+    if let Err(e) = super::reload_active_terms(pool).await {
+        log_warn!("Failed to reload glossary after change: {}", e);
+    }
+}
+
+/// Glossary entries of one scope. Without `meeting_id`, the user-wide terms.
+#[tauri::command]
+pub async fn api_list_glossary_terms(
+    state: State<'_, AppState>,
+    meeting_id: Option<String>,
+) -> Result<Vec<GlossaryTerm>, String> {
+    GlossaryRepository::list_terms(state.db_manager.pool(), meeting_id.as_deref())
+        .await
+        .map_err(|e| {
+            log_error!("Failed to load glossary terms: {}", e);
+            format!("Failed to load glossary terms: {}", e)
+        })
+}
+
+/// Add a term for all meetings, or only for `meeting_id`
+#[tauri::command]
+pub async fn api_add_glossary_term(
+    state: State<'_, AppState>,
+    term: String,
+    meeting_id: Option<String>,
+) -> Result<GlossaryTerm, String> {
+    log_info!("api_add_glossary_term called for meeting_id: {:?}", meeting_id);
+
+    let term = term.trim();
+    if term.is_empty() {
+        return Err("Glossary term cannot be empty".to_string());
+    }
+    if term.chars().count() > MAX_TERM_CHARS {
+        return Err(format!(
+            "Glossary term is too long (max {} characters)",
+            MAX_TERM_CHARS
+        ));
+    }
+
+    let pool = state.db_manager.pool();
+    let added = GlossaryRepository::add_term(pool, meeting_id.as_deref(), term)
+        .await
+        .map_err(|e| {
+            log_error!("Failed to add glossary term: {}", e);
+            format!("Failed to add glossary term: {}", e)
+        })?;
+    reload_after_change(pool).await;
+    Ok(added)
+}
+
+#[tauri::command]
+pub async fn api_delete_glossary_term(
+    state: State<'_, AppState>,
+    term_id: String,
+) -> Result<bool, String> {
+    log_info!("api_delete_glossary_term called for term_id: {}", term_id);
+
+    let pool = state.db_manager.pool();
+    let deleted = GlossaryRepository::delete_term(pool, &term_id)
+        .await
+        .map_err(|e| {
+            log_error!("Failed to delete glossary term {}: {}", term_id, e);
+            format!("Failed to delete glossary term: {}", e)
+        })?;
+    if deleted {
+        reload_after_change(pool).await;
+    }
+    Ok(deleted)
+}
+
+/// Choose the meeting whose terms are used for the next transcriptions, e.g.
+/// before recording a follow-up of an existing meeting. `None` goes back to
+/// the user-wide terms only. Returns the number of active terms.
+#[tauri::command]
+pub async fn api_set_active_glossary_meeting(
+    state: State<'_, AppState>,
+    meeting_id: Option<String>,
+) -> Result<usize, String> {
+    log_info!("api_set_active_glossary_meeting called for meeting_id: {:?}", meeting_id);
+
+    super::set_active_meeting(state.db_manager.pool(), meeting_id)
+        .await
+        .map_err(|e| {
+            log_error!("Failed to load glossary terms: {}", e);
+            format!("Failed to load glossary terms: {}", e)
+        })
+}
@@ -0,0 +1,215 @@
+/// Custom Vocabulary Module
+///
+/// Keeps the glossary (product names, people, acronyms) that transcription
+/// engines are biased towards. Terms are stored per user (all meetings) or per
+/// meeting; the active set is loaded into memory so the engines can read it on
+/// every chunk without touching the database.
+///
+/// Whisper gets the terms plus the last words of the transcript as its initial
+/// prompt, so spelling and context carry over between chunks. Parakeet has no
+/// prompt; it boosts the terms' tokens while decoding (see
+/// `parakeet_engine::hotwords`).
+
+pub mod commands;
+
+use crate::database::repositories::glossary::GlossaryRepository;
+use crate::state::AppState;
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use sqlx::SqlitePool;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, RwLock};
+use tauri::{AppHandle, Manager, Runtime};
+
+/// Words of recent transcript carried into the next Whisper prompt
+pub const CONTEXT_WORDS: usize = 40;
+
+/// Budget for the term list in the prompt. Whisper keeps only the last ~224
+/// prompt tokens, so terms and context together must stay under that.
+pub const MAX_GLOSSARY_PROMPT_CHARS: usize = 400;
+
+// Meeting whose terms are active in addition to the user-wide ones
+static ACTIVE_MEETING: Mutex<Option<String>> = Mutex::new(None);
+static ACTIVE_TERMS: RwLock<Vec<String>> = RwLock::new(Vec::new());
+// Bumped whenever ACTIVE_TERMS changes so engines can rebuild derived state
+static TERMS_VERSION: AtomicU64 = AtomicU64::new(0);
+static RECENT_WORDS: Lazy<Mutex<VecDeque<String>>> =
+    Lazy::new(|| Mutex::new(VecDeque::with_capacity(CONTEXT_WORDS)));
+
+pub fn active_terms() -> Vec<String> {
+    ACTIVE_TERMS.read().map(|terms| terms.clone()).unwrap_or_default()
+}
+
+pub fn terms_version() -> u64 {
+    TERMS_VERSION.load(Ordering::SeqCst)
+}
+
+pub fn set_active_terms(terms: Vec<String>) {
+    if let Ok(mut active) = ACTIVE_TERMS.write() {
+        *active = terms;
+    }
+    TERMS_VERSION.fetch_add(1, Ordering::SeqCst);
+}
+
+pub fn active_meeting() -> Option<String> {
+    ACTIVE_MEETING.lock().ok().and_then(|meeting| meeting.clone())
+}
+
+/// Make a meeting's terms active (None = user-wide terms only) and load them
+pub async fn set_active_meeting(
+    pool: &SqlitePool,
+    meeting_id: Option<String>,
+) -> Result<usize, sqlx::Error> {
+    if let Ok(mut active) = ACTIVE_MEETING.lock() {
+        *active = meeting_id;
+    }
+    reload_active_terms(pool).await
+}
+
+/// Reload the active terms from the database. Returns how many are active.
+pub async fn reload_active_terms(pool: &SqlitePool) -> Result<usize, sqlx::Error> {
+    let meeting_id = active_meeting();
+    let terms = GlossaryRepository::get_active_terms(pool, meeting_id.as_deref()).await?;
+    let count = terms.len();
+    set_active_terms(terms);
+    info!(
+        "Loaded {} glossary term(s) for {}",
+        count,
+        meeting_id.as_deref().unwrap_or("all meetings")
+    );
+    Ok(count)
+}
+
+/// Prepare for a new transcript: reload the terms (they may have been edited
+/// since the last load) and clear the carried-over context
+pub async fn start_session<R: Runtime>(app: &AppHandle<R>) {
+    reset_context();
+    let Some(state) = app.try_state::<AppState>() else {
+        warn!("Database not ready, transcribing without glossary reload");
+        return;
+    };
+    if let Err(e) = reload_active_terms(state.db_manager.pool()).await {
+        warn!("Failed to load glossary terms: {}", e);
+    }
+}
+
+/// Start a new transcript: forget the words carried over from the last one
+pub fn reset_context() {
+    if let Ok(mut words) = RECENT_WORDS.lock() {
+        words.clear();
+    }
+}
+
+/// Remember the end of a transcribed chunk for the next prompt
+pub fn push_context(text: &str) {
+    if let Ok(mut words) = RECENT_WORDS.lock() {
+        for word in text.split_whitespace() {
+            if words.len() == CONTEXT_WORDS {
+                words.pop_front();
+            }
+            words.push_back(word.to_string());
+        }
+    }
+}
+
+pub fn recent_context() -> String {
+    RECENT_WORDS
+        .lock()
+        .map(|words| words.iter().map(String::as_str).collect::<Vec<_>>().join(" "))
+        .unwrap_or_default()
+}
+
+/// Whisper initial prompt from the active terms and the recent transcript
+pub fn initial_prompt() -> Option<String> {
+    build_initial_prompt(&active_terms(), &recent_context())
+}
+
+/// Terms come first and the context last, since Whisper treats the prompt as
+/// the text preceding the audio. Terms past the budget are left out.
+pub fn build_initial_prompt(terms: &[String], context: &str) -> Option<String> {
+    let mut glossary = String::new();
+    for term in terms.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+        let extra = if glossary.is_empty() { term.len() } else { term.len() + 2 };
+        if glossary.len() + extra > MAX_GLOSSARY_PROMPT_CHARS {
+            break;
+        }
+        if !glossary.is_empty() {
+            glossary.push_str(", ");
+        }
+        glossary.push_str(term);
+    }
+
+    let mut prompt = String::new();
+    if !glossary.is_empty() {
+        prompt.push_str("Glossary: ");
+        prompt.push_str(&glossary);
+        prompt.push('.');
+    }
+    let context = context.trim();
+    if !context.is_empty() {
+        if !prompt.is_empty() {
+            prompt.push(' ');
+        }
+        prompt.push_str(context);
+    }
+
+    // whisper.cpp takes a C string
+    let prompt = prompt.replace('\0', "");
+    if prompt.is_empty() {
+        None
+    } else {
+        Some(prompt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terms(list: &[&str]) -> Vec<String> {
+        list.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn test_prompt_from_terms_and_context() {
+        assert_eq!(build_initial_prompt(&[], ""), None);
+        assert_eq!(
+            build_initial_prompt(&terms(&["Meetily", " Tauri ", ""]), ""),
+            Some("Glossary: Meetily, Tauri.".to_string())
+        );
+        assert_eq!(
+            build_initial_prompt(&terms(&["OKR"]), "we shipped the beta"),
+            Some("Glossary: OKR. we shipped the beta".to_string())
+        );
+        assert_eq!(
+            build_initial_prompt(&[], " so anyway "),
+            Some("so anyway".to_string())
+        );
+    }
+
+    #[test]
+    fn test_prompt_term_budget() {
+        let many: Vec<String> = (0..200).map(|i| format!("Term{}", i)).collect();
+        let prompt = build_initial_prompt(&many, "").unwrap();
+        assert!(prompt.len() <= MAX_GLOSSARY_PROMPT_CHARS + "Glossary: .".len());
+        assert!(prompt.starts_with("Glossary: Term0, Term1,"));
+        assert!(!prompt.contains("Term199"));
+    }
+
+    #[test]
+    fn test_context_keeps_last_words() {
+        reset_context();
+        let text: Vec<String> = (0..CONTEXT_WORDS + 5).map(|i| format!("w{}", i)).collect();
+        push_context(&text[..10].join(" "));
+        push_context(&text[10..].join(" "));
+
+        let context = recent_context();
+        assert_eq!(context.split(' ').count(), CONTEXT_WORDS);
+        assert!(context.starts_with("w5 "));
+        assert!(context.ends_with(&format!("w{}", CONTEXT_WORDS + 4)));
+
+        reset_context();
+        assert_eq!(recent_context(), "");
+    }
+}
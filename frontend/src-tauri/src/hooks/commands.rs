@@ -0,0 +1,89 @@
+use crate::database::models::{Hook, HookDelivery};
+use crate::database::repositories::hook::{HookInput, HooksRepository};
+use crate::state::AppState;
+use log::{error as log_error, info as log_info};
+use tauri::State;
+
+/// Deliveries returned when the caller doesn't pass a limit
+const DEFAULT_DELIVERY_LIMIT: i64 = 100;
+
+#[tauri::command]
+pub async fn api_list_hooks(state: State<'_, AppState>) -> Result<Vec<Hook>, String> {
+    HooksRepository::list_hooks(state.db_manager.pool())
+        .await
+        .map_err(|e| {
+            log_error!("Failed to load hooks: {}", e);
+            format!("Failed to load hooks: {}", e)
+        })
+}
+
+#[tauri::command]
+pub async fn api_create_hook(state: State<'_, AppState>, hook: HookInput) -> Result<Hook, String> {
+    log_info!("api_create_hook called: {} on {}", hook.kind, hook.event);
+    super::validate(&hook)?;
+
+    HooksRepository::create_hook(state.db_manager.pool(), &hook)
+        .await
+        .map_err(|e| {
+            log_error!("Failed to create hook: {}", e);
+            format!("Failed to create hook: {}", e)
+        })
+}
+
+#[tauri::command]
+pub async fn api_update_hook(
+    state: State<'_, AppState>,
+    hook_id: String,
+    hook: HookInput,
+) -> Result<Hook, String> {
+    log_info!("api_update_hook called for hook_id: {}", hook_id);
+    super::validate(&hook)?;
+
+    HooksRepository::update_hook(state.db_manager.pool(), &hook_id, &hook)
+        .await
+        .map_err(|e| {
+            log_error!("Failed to update hook {}: {}", hook_id, e);
+            format!("Failed to update hook: {}", e)
+        })?
+        .ok_or_else(|| format!("Hook not found: {}", hook_id))
+}
+
+#[tauri::command]
+pub async fn api_delete_hook(state: State<'_, AppState>, hook_id: String) -> Result<bool, String> {
+    log_info!("api_delete_hook called for hook_id: {}", hook_id);
+    HooksRepository::delete_hook(state.db_manager.pool(), &hook_id)
+        .await
+        .map_err(|e| {
+            log_error!("Failed to delete hook {}: {}", hook_id, e);
+            format!("Failed to delete hook: {}", e)
+        })
+}
+
+/// Delivery log, newest first, for one hook or all of them
+#[tauri::command]
+pub async fn api_list_hook_deliveries(
+    state: State<'_, AppState>,
+    hook_id: Option<String>,
+    limit: Option<i64>,
+) -> Result<Vec<HookDelivery>, String> {
+    HooksRepository::list_deliveries(
+        state.db_manager.pool(),
+        hook_id.as_deref(),
+        limit.unwrap_or(DEFAULT_DELIVERY_LIMIT),
+    )
+    .await
+    .map_err(|e| {
+        log_error!("Failed to load hook deliveries: {}", e);
+        format!("Failed to load hook deliveries: {}", e)
+    })
+}
+
+/// Run a hook once with a sample payload and return the logged delivery
+#[tauri::command]
+pub async fn api_test_hook(
+    state: State<'_, AppState>,
+    hook_id: String,
+) -> Result<HookDelivery, String> {
+    log_info!("api_test_hook called for hook_id: {}", hook_id);
+    super::test_hook(state.db_manager.pool(), &hook_id).await
+}
@@ -0,0 +1,924 @@
+/// Automation Hooks
+///
+/// User-registered webhooks (HTTP POST) and local shell commands that run when
+/// a meeting event happens: recording stopped, transcript finalized, summary
+/// completed or summary failed. Each run gets a JSON payload with the meeting,
+/// its summary markdown, the action items found in it and the meeting files.
+///
+/// A hook can replace the default body with a template. `{{path}}` is replaced
+/// by the payload value at that dotted path (`{{meeting.title}}`,
+/// `{{action_items}}`). Written as a whole JSON string (`"{{action_items}}"`)
+/// the value is inserted as JSON; inside longer text it is inserted as escaped
+/// text, with missing values left empty.
+///
+/// Failed runs are retried with exponential backoff, and every run is
+/// recorded in `hook_deliveries`. The CLI runs each hook once and leaves
+/// failed runs queued for the app to retry. Commands get the body on stdin
+/// and the event in `MEETILY_EVENT`, `MEETILY_MEETING_ID` and
+/// `MEETILY_FOLDER`.
+
+pub mod commands;
+
+use crate::database::models::{Hook, HookDelivery};
+use crate::database::repositories::hook::{HookInput, HooksRepository};
+use crate::database::repositories::meeting::MeetingsRepository;
+use crate::database::repositories::summary::SummaryProcessesRepository;
+use crate::state::AppState;
+use chrono::Utc;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+use std::path::Path;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, Runtime};
+
+/// Upper bound for a hook's `max_attempts`
+pub const MAX_ATTEMPTS_LIMIT: i64 = 10;
+
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(5);
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(300);
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(30);
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(120);
+/// How often the app looks for deliveries queued by the CLI
+const QUEUE_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Response bodies and stderr are cut to this length in the delivery log
+const MAX_ERROR_CHARS: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookEvent {
+    RecordingStopped,
+    TranscriptFinalized,
+    SummaryCompleted,
+    SummaryFailed,
+}
+
+impl HookEvent {
+    pub const ALL: [HookEvent; 4] = [
+        HookEvent::RecordingStopped,
+        HookEvent::TranscriptFinalized,
+        HookEvent::SummaryCompleted,
+        HookEvent::SummaryFailed,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::RecordingStopped => "recording_stopped",
+            Self::TranscriptFinalized => "transcript_finalized",
+            Self::SummaryCompleted => "summary_completed",
+            Self::SummaryFailed => "summary_failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|event| event.as_str() == value)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookKind {
+    Webhook,
+    Command,
+}
+
+impl HookKind {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "webhook" => Some(Self::Webhook),
+            "command" => Some(Self::Command),
+            _ => None,
+        }
+    }
+}
+
+/// What an event is about. Fields left empty are filled in from the meeting.
+#[derive(Debug, Clone, Default)]
+pub struct HookContext {
+    pub meeting_id: Option<String>,
+    pub title: Option<String>,
+    pub folder_path: Option<String>,
+    pub error: Option<String>,
+}
+
+impl HookContext {
+    pub fn meeting(meeting_id: impl Into<String>) -> Self {
+        Self {
+            meeting_id: Some(meeting_id.into()),
+            ..Default::default()
+        }
+    }
+}
+
+/// A failed attempt: HTTP status or exit code (if any) and a message
+struct Failure {
+    code: Option<i64>,
+    message: String,
+}
+
+/// Check a hook before it is stored
+pub fn validate(input: &HookInput) -> Result<(), String> {
+    if input.name.trim().is_empty() {
+        return Err("Hook name cannot be empty".to_string());
+    }
+    let event = HookEvent::parse(&input.event)
+        .ok_or_else(|| format!("Unknown hook event: {}", input.event))?;
+    let kind =
+        HookKind::parse(&input.kind).ok_or_else(|| format!("Unknown hook kind: {}", input.kind))?;
+    if !(1..=MAX_ATTEMPTS_LIMIT).contains(&input.max_attempts) {
+        return Err(format!(
+            "Attempts must be between 1 and {}",
+            MAX_ATTEMPTS_LIMIT
+        ));
+    }
+
+    match kind {
+        HookKind::Webhook => {
+            let url = url::Url::parse(input.target.trim())
+                .map_err(|e| format!("Invalid webhook URL: {}", e))?;
+            if url.scheme() != "http" && url.scheme() != "https" {
+                return Err("Webhook URL must start with http:// or https://".to_string());
+            }
+            parse_headers(input.headers.as_deref())?;
+        }
+        HookKind::Command => {
+            if input.target.trim().is_empty() {
+                return Err("Command cannot be empty".to_string());
+            }
+        }
+    }
+
+    // Webhooks send JSON, so the template has to render to JSON
+    if let (HookKind::Webhook, Some(template)) = (kind, non_empty(&input.payload_template)) {
+        let rendered = render_template(template, &sample_payload(event));
+        serde_json::from_str::<Value>(&rendered)
+            .map_err(|e| format!("Payload template does not render to valid JSON: {}", e))?;
+    }
+    Ok(())
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().filter(|v| !v.trim().is_empty())
+}
+
+/// Extra webhook headers, stored as a JSON object of strings
+fn parse_headers(headers: Option<&str>) -> Result<Vec<(String, String)>, String> {
+    let Some(headers) = headers.filter(|h| !h.trim().is_empty()) else {
+        return Ok(Vec::new());
+    };
+    let map: serde_json::Map<String, Value> = serde_json::from_str(headers)
+        .map_err(|e| format!("Headers must be a JSON object: {}", e))?;
+    map.into_iter()
+        .map(|(name, value)| match value {
+            Value::String(value) => Ok((name, value)),
+            _ => Err(format!("Header '{}' must be a string", name)),
+        })
+        .collect()
+}
+
+/// Fill `{{path}}` placeholders from `payload` (see the module docs)
+pub fn render_template(template: &str, payload: &Value) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    let mut in_string = false;
+    let mut escaped = false;
+    // Length of `out` right after the quote that opened the current string
+    let mut string_start = 0;
+
+    while !rest.is_empty() {
+        if rest.starts_with("{{") {
+            if let Some(end) = rest.find("}}") {
+                let value = lookup(payload, rest[2..end].trim());
+                let after = &rest[end + 2..];
+
+                if !in_string {
+                    out.push_str(&json_text(value));
+                } else if out.len() == string_start && after.starts_with('"') {
+                    // The placeholder is the whole JSON string: insert the value itself
+                    out.pop();
+                    out.push_str(&json_text(value));
+                    in_string = false;
+                    rest = &after[1..];
+                    continue;
+                } else {
+                    out.push_str(&escaped_text(value));
+                }
+                rest = after;
+                continue;
+            }
+        }
+
+        let c = rest.chars().next().unwrap_or_default();
+        let opens_string = c == '"' && !in_string;
+        if escaped {
+            escaped = false;
+        } else if c == '\\' && in_string {
+            escaped = true;
+        } else if c == '"' {
+            in_string = !in_string;
+        }
+        out.push(c);
+        if opens_string {
+            string_start = out.len();
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+    out
+}
+
+fn lookup<'a>(payload: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(payload, |value, key| match value {
+        Value::Array(items) => key.parse::<usize>().ok().and_then(|i| items.get(i)),
+        _ => value.get(key),
+    })
+}
+
+fn json_text(value: Option<&Value>) -> String {
+    value
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| "null".to_string())
+}
+
+/// Value as the inside of a JSON string
+fn escaped_text(value: Option<&Value>) -> String {
+    let text = match value {
+        None | Some(Value::Null) => return String::new(),
+        Some(Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+    };
+    let quoted = Value::String(text).to_string();
+    quoted[1..quoted.len() - 1].to_string()
+}
+
+/// List items under an "Action Items" heading of a summary
+pub fn extract_action_items(markdown: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut in_section = false;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+        let is_heading = trimmed.starts_with('#')
+            || (trimmed.len() > 4 && trimmed.starts_with("**") && trimmed.ends_with("**"));
+        if is_heading {
+            let heading = trimmed.trim_start_matches('#').trim().trim_matches('*');
+            in_section = heading.to_lowercase().contains("action item");
+            continue;
+        }
+        if in_section {
+            if let Some(item) = list_item(trimmed) {
+                items.push(item.to_string());
+            }
+        }
+    }
+    items
+}
+
+fn list_item(line: &str) -> Option<&str> {
+    let text = if let Some(rest) = line.strip_prefix(['-', '*', '+']) {
+        rest.trim_start()
+    } else {
+        let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+        let rest = &line[digits..];
+        if digits == 0 || !(rest.starts_with(". ") || rest.starts_with(") ")) {
+            return None;
+        }
+        rest[1..].trim_start()
+    };
+    let text = ["[ ]", "[x]", "[X]"]
+        .iter()
+        .find_map(|box_| text.strip_prefix(box_))
+        .unwrap_or(text)
+        .trim();
+    (!text.is_empty()).then_some(text)
+}
+
+/// Wait before retry `attempt` (1-based): 5s, 10s, 20s, ... capped at 5 minutes
+pub fn retry_delay(attempt: u32) -> Duration {
+    RETRY_BASE_DELAY
+        .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+        .min(RETRY_MAX_DELAY)
+}
+
+fn compose_payload(
+    event: HookEvent,
+    meeting: Value,
+    summary_markdown: Option<String>,
+    files: Value,
+    error: Option<String>,
+) -> Value {
+    let action_items = summary_markdown
+        .as_deref()
+        .map(extract_action_items)
+        .unwrap_or_default();
+    json!({
+        "event": event.as_str(),
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+        "meeting": meeting,
+        "summary_markdown": summary_markdown,
+        "action_items": action_items,
+        "files": files,
+        "error": error,
+    })
+}
+
+/// Payload with made-up data, for template checks and test runs
+pub fn sample_payload(event: HookEvent) -> Value {
+    compose_payload(
+        event,
+        json!({
+            "id": "meeting-00000000-0000-0000-0000-000000000000",
+            "title": "Test meeting",
+            "created_at": chrono::Utc::now().to_rfc3339(),
+        }),
+        Some(
+            "## Summary\nA test run of this hook.\n\n## Action Items\n- Check the hook output"
+                .to_string(),
+        ),
+        json!({ "folder": null, "audio": null, "transcript": null }),
+        (event == HookEvent::SummaryFailed).then(|| "Test failure".to_string()),
+    )
+}
+
+async fn build_payload(pool: &SqlitePool, event: HookEvent, context: &HookContext) -> Value {
+    let mut title = context.title.clone();
+    let mut folder = context.folder_path.clone();
+    let mut created_at = None;
+    let mut summary = None;
+
+    if let Some(meeting_id) = &context.meeting_id {
+        match MeetingsRepository::get_meeting_model(pool, meeting_id).await {
+            Ok(Some(meeting)) => {
+                title = title.or(Some(meeting.title));
+                folder = folder.or(meeting.folder_path);
+                created_at = Some(meeting.created_at.0.to_rfc3339());
+            }
+            Ok(None) => warn!("Hook payload: meeting {} not found", meeting_id),
+            Err(e) => warn!("Hook payload: failed to load meeting {}: {}", meeting_id, e),
+        }
+
+        if let Ok(Some(process)) =
+            SummaryProcessesRepository::get_summary_data(pool, meeting_id).await
+        {
+            summary = process
+                .result
+                .as_deref()
+                .and_then(|result| serde_json::from_str::<Value>(result).ok())
+                .and_then(|result| crate::export::summary_markdown(&result));
+        }
+    }
+
+    let folder_path = folder.as_deref().map(Path::new);
+    let existing = |name: &str| {
+        folder_path
+            .map(|f| f.join(name))
+            .filter(|p| p.is_file())
+            .map(|p| p.to_string_lossy().to_string())
+    };
+    let files = json!({
+        "folder": folder,
+        "audio": folder_path
+            .and_then(crate::export::bundle::find_meeting_audio)
+            .map(|p| p.to_string_lossy().to_string()),
+        "transcript": existing("transcripts.json"),
+    });
+
+    compose_payload(
+        event,
+        json!({ "id": context.meeting_id, "title": title, "created_at": created_at }),
+        summary,
+        files,
+        context.error.clone(),
+    )
+}
+
+/// Run the hooks for `event` in the background
+pub fn fire(pool: &SqlitePool, event: HookEvent, context: HookContext) {
+    let pool = pool.clone();
+    tauri::async_runtime::spawn(async move {
+        dispatch(&pool, event, context).await;
+    });
+}
+
+/// Run the hooks for `event` and wait until every delivery has finished
+pub async fn dispatch(pool: &SqlitePool, event: HookEvent, context: HookContext) {
+    run_hooks(pool, event, context, true).await;
+}
+
+/// Run each hook for `event` once and queue failed deliveries for the app to
+/// retry, for processes that exit when their work is done (the CLI)
+pub async fn dispatch_once(pool: &SqlitePool, event: HookEvent, context: HookContext) {
+    run_hooks(pool, event, context, false).await;
+}
+
+async fn run_hooks(
+    pool: &SqlitePool,
+    event: HookEvent,
+    context: HookContext,
+    wait_for_retries: bool,
+) {
+    let hooks = match HooksRepository::get_enabled_hooks(pool, event.as_str()).await {
+        Ok(hooks) => hooks,
+        Err(e) => {
+            warn!("Failed to load hooks for {}: {}", event.as_str(), e);
+            return;
+        }
+    };
+    if hooks.is_empty() {
+        return;
+    }
+
+    info!("Running {} hook(s) for {}", hooks.len(), event.as_str());
+    let payload = build_payload(pool, event, &context).await;
+    let deliveries = hooks.iter().map(|hook| {
+        deliver(
+            pool,
+            hook,
+            event,
+            context.meeting_id.as_deref(),
+            &payload,
+            wait_for_retries,
+        )
+    });
+    for result in futures_util::future::join_all(deliveries).await {
+        if let Err(e) = result {
+            warn!("Hook delivery could not be recorded: {}", e);
+        }
+    }
+}
+
+/// One logged run of a hook
+struct Delivery<'a> {
+    id: &'a str,
+    hook: &'a Hook,
+    event: HookEvent,
+    meeting_id: Option<&'a str>,
+    /// Meeting folder, passed to commands in `MEETILY_FOLDER`
+    folder: &'a str,
+    body: &'a str,
+}
+
+/// Log and run one hook. Returns the delivery id.
+async fn deliver(
+    pool: &SqlitePool,
+    hook: &Hook,
+    event: HookEvent,
+    meeting_id: Option<&str>,
+    payload: &Value,
+    wait_for_retries: bool,
+) -> Result<String, String> {
+    let body = match non_empty(&hook.payload_template) {
+        Some(template) => render_template(template, payload),
+        None => serde_json::to_string_pretty(payload).unwrap_or_default(),
+    };
+    let delivery_id =
+        HooksRepository::create_delivery(pool, &hook.id, event.as_str(), meeting_id, &body)
+            .await
+            .map_err(|e| format!("Failed to log hook delivery: {}", e))?;
+
+    let delivery = Delivery {
+        id: &delivery_id,
+        hook,
+        event,
+        meeting_id,
+        folder: payload["files"]["folder"].as_str().unwrap_or_default(),
+        body: &body,
+    };
+    run_attempts(pool, &delivery, 1, wait_for_retries).await;
+    Ok(delivery_id)
+}
+
+/// Try a delivery from attempt `first` on, logging each attempt
+///
+/// Failed attempts are retried after `retry_delay`, or queued for the app to
+/// retry when `wait_for_retries` is false.
+async fn run_attempts(
+    pool: &SqlitePool,
+    delivery: &Delivery<'_>,
+    first: u32,
+    wait_for_retries: bool,
+) {
+    let hook = delivery.hook;
+    let event = delivery.event;
+    // A hook edited down to fewer attempts still gets the one it was queued for
+    let max_attempts = (hook.max_attempts.clamp(1, MAX_ATTEMPTS_LIMIT) as u32).max(first);
+    for attempt in first..=max_attempts {
+        let (status, code, error) = match send(
+            hook,
+            event,
+            delivery.meeting_id,
+            delivery.folder,
+            delivery.body,
+        )
+        .await
+        {
+            Ok(code) => ("succeeded", code, None),
+            Err(failure) if attempt == max_attempts => {
+                ("failed", failure.code, Some(failure.message))
+            }
+            Err(failure) if !wait_for_retries => ("queued", failure.code, Some(failure.message)),
+            Err(failure) => ("retrying", failure.code, Some(failure.message)),
+        };
+        if let Err(e) = HooksRepository::update_delivery(
+            pool,
+            delivery.id,
+            status,
+            attempt as i64,
+            code,
+            error.as_deref(),
+        )
+        .await
+        {
+            warn!("Failed to update hook delivery {}: {}", delivery.id, e);
+        }
+
+        match status {
+            "succeeded" => {
+                info!(
+                    "Hook '{}' delivered {} (attempt {})",
+                    hook.name,
+                    event.as_str(),
+                    attempt
+                );
+                break;
+            }
+            "failed" => {
+                warn!(
+                    "Hook '{}' failed for {} after {} attempt(s): {}",
+                    hook.name,
+                    event.as_str(),
+                    attempt,
+                    error.unwrap_or_default()
+                );
+            }
+            "queued" => {
+                warn!(
+                    "Hook '{}' failed for {}, queued for retry by the app: {}",
+                    hook.name,
+                    event.as_str(),
+                    error.unwrap_or_default()
+                );
+                break;
+            }
+            _ => tokio::time::sleep(retry_delay(attempt)).await,
+        }
+    }
+}
+
+/// Retry queued deliveries: ones left by the CLI, and ones interrupted by
+/// the last shutdown. Checks for new ones every `QUEUE_POLL_INTERVAL`.
+pub async fn init<R: Runtime>(app: &AppHandle<R>) {
+    let Some(state) = app.try_state::<AppState>() else {
+        warn!("Database not ready, queued hook deliveries not retried");
+        return;
+    };
+    let pool = state.db_manager.pool().clone();
+    if let Err(e) = HooksRepository::requeue_interrupted_deliveries(&pool).await {
+        warn!("Failed to queue interrupted hook deliveries: {}", e);
+    }
+    tauri::async_runtime::spawn(async move {
+        loop {
+            retry_queued(&pool).await;
+            tokio::time::sleep(QUEUE_POLL_INTERVAL).await;
+        }
+    });
+}
+
+async fn retry_queued(pool: &SqlitePool) {
+    let deliveries = match HooksRepository::list_queued_deliveries(pool).await {
+        Ok(deliveries) => deliveries,
+        Err(e) => {
+            warn!("Failed to load queued hook deliveries: {}", e);
+            return;
+        }
+    };
+    for delivery in deliveries {
+        match HooksRepository::claim_queued_delivery(pool, &delivery.id).await {
+            Ok(true) => {
+                let pool = pool.clone();
+                tauri::async_runtime::spawn(async move {
+                    resume_delivery(&pool, delivery).await;
+                });
+            }
+            Ok(false) => {}
+            Err(e) => warn!("Failed to claim hook delivery {}: {}", delivery.id, e),
+        }
+    }
+}
+
+/// Continue a queued delivery after the backoff since its last attempt
+async fn resume_delivery(pool: &SqlitePool, record: HookDelivery) {
+    let hook = match HooksRepository::get_hook(pool, &record.hook_id).await {
+        Ok(Some(hook)) if hook.enabled => hook,
+        Ok(Some(_)) => return drop_delivery(pool, &record, "Hook was disabled".into()).await,
+        Ok(None) => return drop_delivery(pool, &record, "Hook was deleted".into()).await,
+        Err(e) => return drop_delivery(pool, &record, format!("Failed to load hook: {}", e)).await,
+    };
+    let Some(event) = HookEvent::parse(&record.event) else {
+        let message = format!("Unknown hook event: {}", record.event);
+        return drop_delivery(pool, &record, message).await;
+    };
+
+    let folder = match &record.meeting_id {
+        Some(meeting_id) => MeetingsRepository::get_meeting_model(pool, meeting_id)
+            .await
+            .ok()
+            .flatten()
+            .and_then(|meeting| meeting.folder_path),
+        None => None,
+    };
+
+    let attempts = record.attempts.max(0) as u32;
+    let since_last = (Utc::now() - record.updated_at)
+        .to_std()
+        .unwrap_or_default();
+    if let Some(wait) = retry_delay(attempts).checked_sub(since_last) {
+        tokio::time::sleep(wait).await;
+    }
+
+    let delivery = Delivery {
+        id: &record.id,
+        hook: &hook,
+        event,
+        meeting_id: record.meeting_id.as_deref(),
+        folder: folder.as_deref().unwrap_or_default(),
+        body: &record.payload,
+    };
+    run_attempts(pool, &delivery, attempts + 1, true).await;
+}
+
+/// Mark a queued delivery failed without running it
+async fn drop_delivery(pool: &SqlitePool, record: &HookDelivery, message: String) {
+    warn!("Dropping queued hook delivery {}: {}", record.id, message);
+    if let Err(e) = HooksRepository::update_delivery(
+        pool,
+        &record.id,
+        "failed",
+        record.attempts,
+        record.response_code,
+        Some(&message),
+    )
+    .await
+    {
+        warn!("Failed to update hook delivery {}: {}", record.id, e);
+    }
+}
+
+async fn send(
+    hook: &Hook,
+    event: HookEvent,
+    meeting_id: Option<&str>,
+    folder: &str,
+    body: &str,
+) -> Result<Option<i64>, Failure> {
+    match HookKind::parse(&hook.kind) {
+        Some(HookKind::Webhook) => post_webhook(hook, event, body).await,
+        Some(HookKind::Command) => run_command(hook, event, meeting_id, folder, body).await,
+        None => Err(Failure {
+            code: None,
+            message: format!("Unknown hook kind: {}", hook.kind),
+        }),
+    }
+}
+
+async fn post_webhook(hook: &Hook, event: HookEvent, body: &str) -> Result<Option<i64>, Failure> {
+    let failure = |code, message| Failure { code, message };
+    let headers = parse_headers(hook.headers.as_deref()).map_err(|e| failure(None, e))?;
+
+    let client = reqwest::Client::builder()
+        .timeout(WEBHOOK_TIMEOUT)
+        .build()
+        .map_err(|e| failure(None, format!("Failed to create HTTP client: {}", e)))?;
+    let mut request = client
+        .post(hook.target.trim())
+        .header("Content-Type", "application/json")
+        .header("User-Agent", concat!("Meetily/", env!("CARGO_PKG_VERSION")))
+        .header("X-Meetily-Event", event.as_str())
+        .body(body.to_string());
+    for (name, value) in headers {
+        request = request.header(name, value);
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| failure(None, format!("Request failed: {}", e)))?;
+    let status = response.status();
+    let code = Some(status.as_u16() as i64);
+    if status.is_success() {
+        return Ok(code);
+    }
+    let text = response.text().await.unwrap_or_default();
+    Err(failure(
+        code,
+        format!("HTTP {}: {}", status, truncate(&text)),
+    ))
+}
+
+async fn run_command(
+    hook: &Hook,
+    event: HookEvent,
+    meeting_id: Option<&str>,
+    folder: &str,
+    body: &str,
+) -> Result<Option<i64>, Failure> {
+    use tokio::io::AsyncWriteExt;
+
+    let failure = |code, message| Failure { code, message };
+
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut command = tokio::process::Command::new("cmd");
+        command.arg("/C").arg(&hook.target);
+        command
+    };
+    #[cfg(not(target_os = "windows"))]
+    let mut command = {
+        let mut command = tokio::process::Command::new("sh");
+        command.arg("-c").arg(&hook.target);
+        command
+    };
+
+    let mut child = command
+        .env("MEETILY_EVENT", event.as_str())
+        .env("MEETILY_MEETING_ID", meeting_id.unwrap_or_default())
+        .env("MEETILY_FOLDER", folder)
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| failure(None, format!("Failed to start command: {}", e)))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // A command that ignores stdin may close it early; that's not an error
+        let _ = stdin.write_all(body.as_bytes()).await;
+    }
+
+    let output = tokio::time::timeout(COMMAND_TIMEOUT, child.wait_with_output())
+        .await
+        .map_err(|_| {
+            failure(
+                None,
+                format!("Command timed out after {:?}", COMMAND_TIMEOUT),
+            )
+        })?
+        .map_err(|e| failure(None, format!("Command failed: {}", e)))?;
+
+    let code = output.status.code().map(i64::from);
+    if output.status.success() {
+        return Ok(code);
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    Err(failure(
+        code,
+        format!(
+            "Command exited with {}: {}",
+            output.status,
+            truncate(stderr.trim())
+        ),
+    ))
+}
+
+fn truncate(text: &str) -> String {
+    match text.char_indices().nth(MAX_ERROR_CHARS) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+/// Run a hook once with the sample payload and return the delivery
+pub async fn test_hook(pool: &SqlitePool, hook_id: &str) -> Result<HookDelivery, String> {
+    let mut hook = HooksRepository::get_hook(pool, hook_id)
+        .await
+        .map_err(|e| format!("Failed to load hook: {}", e))?
+        .ok_or_else(|| format!("Hook not found: {}", hook_id))?;
+    let event = HookEvent::parse(&hook.event)
+        .ok_or_else(|| format!("Unknown hook event: {}", hook.event))?;
+
+    // No retries: the caller is waiting for the result
+    hook.max_attempts = 1;
+    let delivery_id = deliver(pool, &hook, event, None, &sample_payload(event), true).await?;
+    HooksRepository::get_delivery(pool, &delivery_id)
+        .await
+        .map_err(|e| format!("Failed to load delivery: {}", e))?
+        .ok_or_else(|| "Delivery log entry disappeared".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(kind: &str, target: &str) -> HookInput {
+        HookInput {
+            name: "Wiki".to_string(),
+            event: "summary_completed".to_string(),
+            kind: kind.to_string(),
+            target: target.to_string(),
+            headers: None,
+            payload_template: None,
+            max_attempts: 3,
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn test_event_names_round_trip() {
+        for event in HookEvent::ALL {
+            assert_eq!(HookEvent::parse(event.as_str()), Some(event));
+            assert_eq!(
+                serde_json::to_value(event).unwrap(),
+                Value::String(event.as_str().to_string())
+            );
+        }
+        assert_eq!(HookEvent::parse("meeting_started"), None);
+    }
+
+    #[test]
+    fn test_render_template() {
+        let payload = json!({
+            "meeting": { "id": "m1", "title": "Q3 \"plan\"" },
+            "action_items": ["Ship it", "Write notes"],
+            "summary_markdown": null,
+        });
+
+        // Whole-string placeholders keep the JSON type
+        assert_eq!(
+            render_template(
+                r#"{"title": "{{meeting.title}}", "todo": "{{action_items}}"}"#,
+                &payload
+            ),
+            r#"{"title": "Q3 \"plan\"", "todo": ["Ship it","Write notes"]}"#
+        );
+        // Inside text values are escaped, missing and null values are empty
+        assert_eq!(
+            render_template(
+                r#"{"text": "Minutes for {{ meeting.title }}{{summary_markdown}}{{nope}}"}"#,
+                &payload
+            ),
+            r#"{"text": "Minutes for Q3 \"plan\""}"#
+        );
+        // Outside strings the JSON value is inserted as is
+        assert_eq!(
+            render_template(
+                r#"{"first": {{action_items.0}}, "x": {{missing}}}"#,
+                &payload
+            ),
+            r#"{"first": "Ship it", "x": null}"#
+        );
+        // Escaped quotes don't end the string
+        assert_eq!(
+            render_template(r#"{"a": "say \"{{meeting.id}}\""}"#, &payload),
+            r#"{"a": "say \"m1\""}"#
+        );
+        assert_eq!(
+            render_template("no placeholders {{", &payload),
+            "no placeholders {{"
+        );
+    }
+
+    #[test]
+    fn test_extract_action_items() {
+        let markdown = "## Summary\n- Not an action\n\n## Action Items\n- [ ] Send the deck\n* Book the room\n1. Follow up with legal\nSome prose\n\n**Decisions**\n- Keep the date";
+        assert_eq!(
+            extract_action_items(markdown),
+            vec!["Send the deck", "Book the room", "Follow up with legal"]
+        );
+        assert!(extract_action_items("## Summary\n- Nothing to do").is_empty());
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        assert_eq!(retry_delay(1), Duration::from_secs(5));
+        assert_eq!(retry_delay(2), Duration::from_secs(10));
+        assert_eq!(retry_delay(3), Duration::from_secs(20));
+        assert_eq!(retry_delay(10), RETRY_MAX_DELAY);
+        assert_eq!(retry_delay(40), RETRY_MAX_DELAY);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(validate(&input("webhook", "https://wiki.example.com/hooks/minutes")).is_ok());
+        assert!(validate(&input("command", "/usr/local/bin/push-minutes")).is_ok());
+        assert!(validate(&input("webhook", "ftp://example.com")).is_err());
+        assert!(validate(&input("command", "  ")).is_err());
+        assert!(validate(&input("email", "a@example.com")).is_err());
+
+        let mut hook = input("webhook", "http://127.0.0.1:8080/");
+        hook.headers = Some(r#"{"Authorization": "Bearer abc"}"#.to_string());
+        assert!(validate(&hook).is_ok());
+        hook.headers = Some(r#"{"X-Retries": 3}"#.to_string());
+        assert!(validate(&hook).is_err());
+
+        let mut hook = input("webhook", "http://127.0.0.1:8080/");
+        hook.payload_template =
+            Some(r#"{"text": "{{summary_markdown}}", "items": "{{action_items}}"}"#.to_string());
+        assert!(validate(&hook).is_ok());
+        hook.payload_template = Some(r#"{"text": {{meeting.title}"#.to_string());
+        assert!(validate(&hook).is_err());
+
+        let mut hook = input("webhook", "http://127.0.0.1:8080/");
+        hook.max_attempts = 0;
+        assert!(validate(&hook).is_err());
+    }
+}
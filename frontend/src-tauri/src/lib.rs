@@ -38,16 +38,26 @@ pub(crate) use perf_trace;
 pub mod analytics;
 pub mod api;
 pub mod audio;
+pub mod auto_recorder;
 pub mod chat;
+pub mod cli;
 pub mod console_utils;
 pub mod database;
+pub mod diarization;
+pub mod export;
+pub mod glossary;
+pub mod hooks;
+pub mod local_api;
 pub mod lrc;
+pub mod media_import;
 pub mod notifications;
 pub mod ollama;
 pub mod openrouter;
 pub mod parakeet_engine;
+pub mod recovery;
 pub mod state;
 pub mod summary;
+pub mod transcript_rules;
 pub mod tray;
 pub mod utils;
 pub mod whisper_engine;
@@ -311,6 +321,7 @@ async fn start_recording_with_devices_and_meeting<R: Runtime>(
                 "microphone-only" => Some(audio::recording_commands::RecordingMode::MicrophoneOnly),
                 "system-audio-only" => Some(audio::recording_commands::RecordingMode::SystemAudioOnly),
                 "mixed" => Some(audio::recording_commands::RecordingMode::Mixed),
+                "per-channel" => Some(audio::recording_commands::RecordingMode::PerChannel),
                 _ => None
             }
         });
@@ -407,6 +418,13 @@ pub fn get_language_preference_internal() -> Option<String> {
     LANGUAGE_PREFERENCE.lock().ok().map(|lang| lang.clone())
 }
 
+// Internal helper to set the language preference without the frontend (CLI)
+pub fn set_language_preference_internal(language: String) {
+    if let Ok(mut lang_pref) = LANGUAGE_PREFERENCE.lock() {
+        *lang_pref = language;
+    }
+}
+
 pub fn run() {
     log::set_max_level(log::LevelFilter::Info);
 
@@ -466,6 +484,9 @@ pub fn run() {
             // Set Parakeet models directory
             parakeet_engine::commands::set_models_directory(&_app.handle());
 
+            // Set speaker embedding model location (diarization stays off until a model is installed)
+            diarization::set_models_directory(&_app.handle());
+
             // Initialize Parakeet engine on startup
             tauri::async_runtime::spawn(async {
                 if let Err(e) = parakeet_engine::commands::parakeet_init().await {
@@ -489,6 +510,42 @@ pub fn run() {
             })
             .expect("Failed to initialize database");
 
+            // Look for recordings interrupted by a crash so the UI can offer recovery
+            let app_for_recovery = _app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                recovery::commands::check_for_interrupted_recordings(app_for_recovery).await;
+            });
+
+            // Restore the saved speaker diarization setting
+            let app_for_diarization = _app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                diarization::init(&app_for_diarization).await;
+            });
+
+            // Resume summary jobs left unfinished by the last run
+            let app_for_summary_queue = _app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                summary::queue::init(&app_for_summary_queue).await;
+            });
+
+            // Retry hook deliveries queued by the CLI or the last run
+            let app_for_hooks = _app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                hooks::init(&app_for_hooks).await;
+            });
+
+            // Start meeting detection if the auto-recorder is enabled
+            let app_for_auto_recorder = _app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                auto_recorder::init(&app_for_auto_recorder).await;
+            });
+
+            // Start the localhost automation API if the user enabled it
+            let app_for_local_api = _app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                local_api::init(&app_for_local_api).await;
+            });
+
             // Initialize bundled templates directory for dynamic template discovery
             log::info!("Initializing bundled templates directory...");
             if let Ok(resource_path) = _app.handle().path().resource_dir() {
@@ -592,6 +649,8 @@ pub fn run() {
             audio::recording_commands::attempt_device_reconnect,
             // Playback device detection (Bluetooth warning)
             audio::recording_commands::get_active_audio_output,
+            audio::recording_commands::set_file_capture_speed,
+            audio::recording_commands::get_file_capture_status,
             console_utils::show_console,
             console_utils::hide_console,
             console_utils::toggle_console,
@@ -601,6 +660,7 @@ pub fn run() {
             ollama::get_ollama_model_context,
             api::api_get_meetings,
             api::api_search_transcripts,
+            api::api_search,
             api::api_get_profile,
             api::api_save_profile,
             api::api_update_profile,
@@ -627,10 +687,78 @@ pub fn run() {
             api::open_external_url,
             // LRC import command
             lrc::commands::api_import_lrc,
+            media_import::commands::api_import_media_file,
+            media_import::commands::api_cancel_media_import,
+            recovery::commands::api_scan_recovery_sessions,
+            recovery::commands::api_recover_session,
+            recovery::commands::api_discard_recovery_session,
+            export::commands::api_export_meeting,
+            export::commands::api_inspect_meeting_bundle,
+            export::commands::api_import_meeting_bundle,
+            diarization::commands::api_get_diarization_status,
+            diarization::commands::api_set_diarization_enabled,
+            diarization::commands::api_get_meeting_speakers,
+            diarization::commands::api_rename_speaker,
+            diarization::commands::api_diarize_meeting,
+            glossary::commands::api_list_glossary_terms,
+            glossary::commands::api_add_glossary_term,
+            glossary::commands::api_delete_glossary_term,
+            glossary::commands::api_set_active_glossary_meeting,
+            transcript_rules::commands::api_list_replacement_rules,
+            transcript_rules::commands::api_create_replacement_rule,
+            transcript_rules::commands::api_update_replacement_rule,
+            transcript_rules::commands::api_delete_replacement_rule,
+            transcript_rules::commands::api_reorder_replacement_rules,
+            transcript_rules::commands::api_preview_replacement_rules,
+            transcript_rules::commands::api_apply_replacement_rules,
+            // Automatic meeting recording commands
+            auto_recorder::commands::get_auto_recorder_settings,
+            auto_recorder::commands::set_auto_recorder_settings,
+            auto_recorder::commands::get_auto_recorder_status,
+            auto_recorder::commands::accept_auto_recording_prompt,
+            auto_recorder::commands::dismiss_auto_recording_prompt,
+            local_api::commands::get_local_api_settings,
+            local_api::commands::set_local_api_settings,
+            local_api::commands::regenerate_local_api_token,
+            local_api::commands::get_local_api_status,
+            hooks::commands::api_list_hooks,
+            hooks::commands::api_create_hook,
+            hooks::commands::api_update_hook,
+            hooks::commands::api_delete_hook,
+            hooks::commands::api_list_hook_deliveries,
+            hooks::commands::api_test_hook,
             // Summary commands
             summary::api_process_transcript,
             summary::api_get_summary,
             summary::api_save_meeting_summary,
+            summary::api_cancel_summary_job,
+            summary::api_list_summary_jobs,
+            // Summary version commands
+            summary::api_list_summary_versions,
+            summary::api_restore_summary_version,
+            summary::api_diff_summary_versions,
+            // Action item commands
+            summary::api_list_open_action_items,
+            summary::api_get_meeting_action_items,
+            summary::api_set_action_item_status,
+            summary::api_extract_action_items,
+            // LLM backend commands
+            summary::api_list_llm_backends,
+            summary::api_list_llm_models,
+            summary::api_save_llm_endpoint,
+            summary::api_get_llm_failover_chain,
+            summary::api_save_llm_failover_chain,
+            // LLM usage and cost commands
+            summary::api_get_llm_spend_by_meeting,
+            summary::api_get_llm_spend_by_model,
+            summary::api_get_llm_spend_by_month,
+            summary::api_get_meeting_llm_usage,
+            summary::api_list_llm_prices,
+            summary::api_save_llm_price,
+            summary::api_delete_llm_price,
+            summary::api_sync_openrouter_prices,
+            summary::api_get_llm_budget,
+            summary::api_save_llm_budget,
             // Template commands
             summary::api_list_templates,
             summary::api_get_template_details,
@@ -676,6 +804,13 @@ pub fn run() {
             audio::system_audio_commands::start_system_audio_monitoring,
             audio::system_audio_commands::stop_system_audio_monitoring,
             audio::system_audio_commands::get_system_audio_monitoring_status,
+            audio::system_audio_commands::list_linux_audio_applications,
+            audio::system_audio_commands::set_linux_capture_applications,
+            audio::system_audio_commands::get_linux_capture_applications,
+            // Idle recording policy commands
+            audio::idle_commands::get_idle_policy,
+            audio::idle_commands::set_idle_policy,
+            audio::idle_commands::get_idle_status,
             // Screen Recording permission commands
             audio::permissions::check_screen_recording_permission_command,
             audio::permissions::request_screen_recording_permission_command,
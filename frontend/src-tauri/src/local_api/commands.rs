@@ -0,0 +1,41 @@
+use super::{LocalApiSettings, LocalApiStatus};
+use log::{error as log_error, info as log_info};
+use tauri::{AppHandle, Runtime};
+
+#[tauri::command]
+pub async fn get_local_api_settings() -> Result<LocalApiSettings, String> {
+    Ok(super::settings().await)
+}
+
+/// Save the settings; starts, restarts or stops the server to match
+#[tauri::command]
+pub async fn set_local_api_settings<R: Runtime>(
+    app: AppHandle<R>,
+    settings: LocalApiSettings,
+) -> Result<(), String> {
+    log_info!(
+        "set_local_api_settings called: enabled={}, port={}",
+        settings.enabled,
+        settings.port
+    );
+    super::save_settings(&app, settings).await.map_err(|e| {
+        log_error!("Failed to apply local API settings: {}", e);
+        format!("Failed to apply local API settings: {}", e)
+    })
+}
+
+/// Issue a new API token, invalidating the old one
+#[tauri::command]
+pub async fn regenerate_local_api_token<R: Runtime>(app: AppHandle<R>) -> Result<String, String> {
+    let token = super::regenerate_token(&app).await.map_err(|e| {
+        log_error!("Failed to regenerate local API token: {}", e);
+        format!("Failed to regenerate local API token: {}", e)
+    })?;
+    log_info!("✅ Local API token regenerated");
+    Ok(token)
+}
+
+#[tauri::command]
+pub async fn get_local_api_status() -> Result<LocalApiStatus, String> {
+    Ok(super::status().await)
+}
@@ -0,0 +1,291 @@
+/// Local Automation API
+///
+/// An opt-in HTTP server on 127.0.0.1 for scripts and editor plugins. It
+/// serves meetings, transcripts, summaries and search from the local database,
+/// controls recording, and streams live transcript updates over a WebSocket.
+/// Every request must carry the API token, either as
+/// `Authorization: Bearer <token>` or as a `?token=` query parameter (for
+/// WebSocket clients that can't set headers).
+///
+/// Settings (enabled, port, token) live in `local_api.json` next to the other
+/// settings files; the token is generated on first use.
+
+pub mod commands;
+mod routes;
+
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::net::{Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, EventId, Listener, Runtime};
+use tokio::sync::{broadcast, watch};
+
+/// Event forwarded to WebSocket clients
+const TRANSCRIPT_EVENT: &str = "transcript-update";
+
+/// Live transcript updates buffered per WebSocket client before it lags
+const TRANSCRIPT_CHANNEL_CAPACITY: usize = 256;
+
+/// Retries when the port is still held by a server that is shutting down
+const BIND_RETRIES: u32 = 5;
+const BIND_RETRY_DELAY: Duration = Duration::from_millis(200);
+
+static SETTINGS: Lazy<Mutex<Option<LocalApiSettings>>> = Lazy::new(|| Mutex::new(None));
+static SERVER: Mutex<Option<RunningServer>> = Mutex::new(None);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LocalApiSettings {
+    pub enabled: bool,
+    pub port: u16,
+    pub token: String,
+}
+
+impl Default for LocalApiSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 5168,
+            token: generate_token(),
+        }
+    }
+}
+
+impl LocalApiSettings {
+    pub fn validate(&self) -> Result<()> {
+        if self.port < 1024 {
+            return Err(anyhow!("Port must be 1024 or higher"));
+        }
+        if self.token.trim().len() < 16 {
+            return Err(anyhow!("API token must be at least 16 characters"));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LocalApiStatus {
+    pub enabled: bool,
+    pub running: bool,
+    /// Base URL while running ("http://127.0.0.1:5168")
+    pub url: Option<String>,
+}
+
+struct RunningServer {
+    addr: SocketAddr,
+    shutdown: watch::Sender<bool>,
+    transcript_listener: EventId,
+}
+
+fn generate_token() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+/// Compare tokens without stopping at the first differing byte
+pub(crate) fn tokens_match(expected: &str, given: &str) -> bool {
+    let (expected, given) = (expected.as_bytes(), given.as_bytes());
+    expected.len() == given.len()
+        && expected
+            .iter()
+            .zip(given)
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+fn settings_path() -> Result<PathBuf> {
+    let mut path = dirs::config_dir().ok_or_else(|| anyhow!("Could not find config directory"))?;
+    path.push("meetily");
+    path.push("local_api.json");
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    Ok(path)
+}
+
+/// Current settings, loaded from disk on first use. A generated token is
+/// written back so it stays the same across restarts.
+pub async fn settings() -> LocalApiSettings {
+    if let Some(settings) = SETTINGS.lock().ok().and_then(|s| s.clone()) {
+        return settings;
+    }
+
+    let loaded = match load_settings().await {
+        Ok(settings) => settings,
+        Err(e) => {
+            warn!("Failed to load local API settings, using defaults: {}", e);
+            LocalApiSettings::default()
+        }
+    };
+    if let Ok(mut current) = SETTINGS.lock() {
+        *current = Some(loaded.clone());
+    }
+    loaded
+}
+
+async fn load_settings() -> Result<LocalApiSettings> {
+    let path = settings_path()?;
+    if !path.exists() {
+        let settings = LocalApiSettings::default();
+        write_settings(&settings).await?;
+        return Ok(settings);
+    }
+    let content = tokio::fs::read_to_string(&path).await?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+async fn write_settings(settings: &LocalApiSettings) -> Result<()> {
+    let content = serde_json::to_string_pretty(settings)?;
+    tokio::fs::write(settings_path()?, content).await?;
+    Ok(())
+}
+
+/// Validate, store and apply new settings (restarts the server if it runs)
+pub async fn save_settings<R: Runtime>(app: &AppHandle<R>, settings: LocalApiSettings) -> Result<()> {
+    settings.validate()?;
+    write_settings(&settings).await?;
+
+    if let Ok(mut current) = SETTINGS.lock() {
+        *current = Some(settings.clone());
+    }
+    info!(
+        "Saved local API settings: enabled={}, port={}",
+        settings.enabled, settings.port
+    );
+    apply_settings(app, &settings).await
+}
+
+/// Replace the token; clients using the old one are rejected from now on
+pub async fn regenerate_token<R: Runtime>(app: &AppHandle<R>) -> Result<String> {
+    let mut settings = settings().await;
+    settings.token = generate_token();
+    save_settings(app, settings.clone()).await?;
+    Ok(settings.token)
+}
+
+/// Start the server if enabled (app startup)
+pub async fn init<R: Runtime>(app: &AppHandle<R>) {
+    let settings = settings().await;
+    if let Err(e) = apply_settings(app, &settings).await {
+        warn!("Failed to start local API: {}", e);
+    }
+}
+
+async fn apply_settings<R: Runtime>(app: &AppHandle<R>, settings: &LocalApiSettings) -> Result<()> {
+    stop_server(app);
+    if settings.enabled {
+        start_server(app, settings).await?;
+    }
+    Ok(())
+}
+
+async fn start_server<R: Runtime>(app: &AppHandle<R>, settings: &LocalApiSettings) -> Result<()> {
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, settings.port));
+    let listener = bind(addr).await?;
+
+    let (shutdown, shutdown_rx) = watch::channel(false);
+    let (transcripts, _) = broadcast::channel(TRANSCRIPT_CHANNEL_CAPACITY);
+
+    let forward = transcripts.clone();
+    let transcript_listener = app.listen_any(TRANSCRIPT_EVENT, move |event| {
+        // No receivers just means no WebSocket client is connected
+        let _ = forward.send(event.payload().to_string());
+    });
+
+    let router = routes::router(routes::ApiState {
+        app: app.clone(),
+        token: settings.token.clone(),
+        transcripts,
+        shutdown: shutdown_rx.clone(),
+    });
+
+    let mut server_shutdown = shutdown_rx;
+    tauri::async_runtime::spawn(async move {
+        let served = axum::serve(listener, router)
+            .with_graceful_shutdown(async move {
+                let _ = server_shutdown.changed().await;
+            })
+            .await;
+        if let Err(e) = served {
+            warn!("Local API server stopped with error: {}", e);
+        }
+    });
+
+    if let Ok(mut server) = SERVER.lock() {
+        *server = Some(RunningServer {
+            addr,
+            shutdown,
+            transcript_listener,
+        });
+    }
+    info!("✅ Local API listening on http://{}", addr);
+    Ok(())
+}
+
+/// Bind `addr`, retrying briefly while a server we just stopped releases it
+async fn bind(addr: SocketAddr) -> Result<tokio::net::TcpListener> {
+    let mut attempts = 0;
+    loop {
+        match tokio::net::TcpListener::bind(addr).await {
+            Ok(listener) => return Ok(listener),
+            Err(e) if attempts < BIND_RETRIES => {
+                attempts += 1;
+                warn!("Local API could not listen on {} yet ({}), retrying", addr, e);
+                tokio::time::sleep(BIND_RETRY_DELAY).await;
+            }
+            Err(e) => return Err(anyhow!("Could not listen on {}: {}", addr, e)),
+        }
+    }
+}
+
+fn stop_server<R: Runtime>(app: &AppHandle<R>) {
+    let running = SERVER.lock().ok().and_then(|mut s| s.take());
+    if let Some(server) = running {
+        app.unlisten(server.transcript_listener);
+        let _ = server.shutdown.send(true);
+        info!("Local API on {} stopped", server.addr);
+    }
+}
+
+pub async fn status() -> LocalApiStatus {
+    let url = SERVER
+        .lock()
+        .ok()
+        .and_then(|s| s.as_ref().map(|s| format!("http://{}", s.addr)));
+    LocalApiStatus {
+        enabled: settings().await.enabled,
+        running: url.is_some(),
+        url,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens_match() {
+        assert!(tokens_match("abcdef0123456789", "abcdef0123456789"));
+        assert!(!tokens_match("abcdef0123456789", "abcdef012345678"));
+        assert!(!tokens_match("abcdef0123456789", "abcdef0123456780"));
+        assert!(!tokens_match("abcdef0123456789", ""));
+    }
+
+    #[test]
+    fn test_settings_validation() {
+        let settings = LocalApiSettings::default();
+        assert!(!settings.enabled);
+        assert!(settings.validate().is_ok());
+
+        let mut settings = LocalApiSettings::default();
+        settings.port = 80;
+        assert!(settings.validate().is_err());
+
+        let mut settings = LocalApiSettings::default();
+        settings.token = "short".to_string();
+        assert!(settings.validate().is_err());
+    }
+}
@@ -0,0 +1,288 @@
+use super::tokens_match;
+use crate::api::SearchFilters;
+use crate::database::repositories::{meeting::MeetingsRepository, search::SearchRepository};
+use crate::state::AppState;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Path, Query, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use log::{info, warn};
+use serde::Deserialize;
+use serde_json::json;
+use tauri::{AppHandle, Manager, Runtime};
+use tokio::sync::{broadcast, watch};
+
+pub(super) struct ApiState<R: Runtime> {
+    pub app: AppHandle<R>,
+    pub token: String,
+    /// Raw `transcript-update` payloads
+    pub transcripts: broadcast::Sender<String>,
+    /// Flips to true when the server stops, to close WebSockets
+    pub shutdown: watch::Receiver<bool>,
+}
+
+// Manual impl: derive would require `R: Clone`
+impl<R: Runtime> Clone for ApiState<R> {
+    fn clone(&self) -> Self {
+        Self {
+            app: self.app.clone(),
+            token: self.token.clone(),
+            transcripts: self.transcripts.clone(),
+            shutdown: self.shutdown.clone(),
+        }
+    }
+}
+
+/// Error response: `{"error": "..."}` with a status code
+struct ApiError(StatusCode, String);
+
+impl ApiError {
+    fn internal(message: impl Into<String>) -> Self {
+        Self(StatusCode::INTERNAL_SERVER_ERROR, message.into())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(json!({ "error": self.1 }))).into_response()
+    }
+}
+
+type ApiResult = Result<Response, ApiError>;
+
+pub(super) fn router<R: Runtime>(state: ApiState<R>) -> Router {
+    Router::new()
+        .route("/api/v1/meetings", get(list_meetings::<R>))
+        .route("/api/v1/meetings/:id", get(get_meeting::<R>))
+        .route("/api/v1/meetings/:id/summary", get(get_summary::<R>))
+        .route("/api/v1/search", get(search::<R>))
+        .route("/api/v1/recording", get(recording_state))
+        .route("/api/v1/recording/start", post(start_recording::<R>))
+        .route("/api/v1/recording/stop", post(stop_recording::<R>))
+        .route("/api/v1/recording/pause", post(pause_recording::<R>))
+        .route("/api/v1/recording/resume", post(resume_recording::<R>))
+        .route("/api/v1/transcripts/live", get(live_transcripts::<R>))
+        .route_layer(middleware::from_fn_with_state(state.clone(), require_token::<R>))
+        .with_state(state)
+}
+
+/// Reject requests without the API token (Bearer header or `token` query parameter)
+async fn require_token<R: Runtime>(
+    State(state): State<ApiState<R>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let bearer = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(|token| token.trim().to_string());
+    let query_token = request.uri().query().and_then(|query| {
+        url::form_urlencoded::parse(query.as_bytes())
+            .find(|(key, _)| key == "token")
+            .map(|(_, value)| value.into_owned())
+    });
+
+    match bearer.or(query_token) {
+        Some(token) if tokens_match(&state.token, &token) => next.run(request).await,
+        _ => ApiError(StatusCode::UNAUTHORIZED, "Missing or invalid API token".to_string())
+            .into_response(),
+    }
+}
+
+async fn list_meetings<R: Runtime>(State(state): State<ApiState<R>>) -> ApiResult {
+    let app_state = state.app.state::<AppState>();
+    let meetings = MeetingsRepository::get_meetings(app_state.db_manager.pool())
+        .await
+        .map_err(|e| ApiError::internal(format!("Failed to get meetings: {}", e)))?;
+
+    let meetings: Vec<_> = meetings
+        .into_iter()
+        .map(|m| {
+            json!({
+                "id": m.id,
+                "title": m.title,
+                "created_at": m.created_at.0.to_rfc3339(),
+                "updated_at": m.updated_at.0.to_rfc3339(),
+            })
+        })
+        .collect();
+    Ok(Json(meetings).into_response())
+}
+
+/// Meeting details with its transcript segments
+async fn get_meeting<R: Runtime>(
+    State(state): State<ApiState<R>>,
+    Path(meeting_id): Path<String>,
+) -> ApiResult {
+    let app_state = state.app.state::<AppState>();
+    match MeetingsRepository::get_meeting(app_state.db_manager.pool(), &meeting_id).await {
+        Ok(Some(meeting)) => Ok(Json(meeting).into_response()),
+        Ok(None) => Err(ApiError(
+            StatusCode::NOT_FOUND,
+            format!("Meeting not found: {}", meeting_id),
+        )),
+        Err(e) => Err(ApiError::internal(format!("Failed to get meeting: {}", e))),
+    }
+}
+
+async fn get_summary<R: Runtime>(
+    State(state): State<ApiState<R>>,
+    Path(meeting_id): Path<String>,
+) -> ApiResult {
+    let summary = crate::summary::commands::api_get_summary(
+        state.app.clone(),
+        state.app.state::<AppState>(),
+        meeting_id,
+        None,
+    )
+    .await
+    .map_err(ApiError::internal)?;
+    Ok(Json(summary).into_response())
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchParams {
+    q: String,
+    meeting_id: Option<String>,
+    /// Comma-separated: transcript, summary, chat
+    sources: Option<String>,
+    from_date: Option<String>,
+    to_date: Option<String>,
+    limit: Option<i64>,
+}
+
+async fn search<R: Runtime>(
+    State(state): State<ApiState<R>>,
+    Query(params): Query<SearchParams>,
+) -> ApiResult {
+    let filters = SearchFilters {
+        meeting_id: params.meeting_id,
+        source_types: params.sources.map(|sources| {
+            sources
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect()
+        }),
+        from_date: params.from_date,
+        to_date: params.to_date,
+        limit: params.limit,
+    };
+
+    let app_state = state.app.state::<AppState>();
+    let results = SearchRepository::search(app_state.db_manager.pool(), &params.q, &filters)
+        .await
+        .map_err(|e| ApiError::internal(format!("Failed to search: {}", e)))?;
+    Ok(Json(results).into_response())
+}
+
+async fn recording_state() -> ApiResult {
+    Ok(Json(crate::audio::recording_commands::get_recording_state().await).into_response())
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct StartRecordingBody {
+    meeting_name: Option<String>,
+}
+
+/// Start a recording through the main window, the way the tray menu does, so
+/// the meeting is created and saved by the usual flow
+async fn start_recording<R: Runtime>(
+    State(state): State<ApiState<R>>,
+    body: Option<Json<StartRecordingBody>>,
+) -> ApiResult {
+    if crate::audio::recording_commands::is_recording().await {
+        return Err(ApiError(StatusCode::CONFLICT, "Already recording".to_string()));
+    }
+    let window = state
+        .app
+        .get_webview_window("main")
+        .ok_or_else(|| ApiError::internal("Main window not found"))?;
+
+    let body = body.map(|Json(body)| body).unwrap_or_default();
+    if let Some(name) = body.meeting_name.filter(|n| !n.trim().is_empty()) {
+        let name = serde_json::to_string(&name).unwrap_or_default();
+        let _ = window.eval(&format!(
+            "sessionStorage.setItem('autoStartMeetingName', {})",
+            name
+        ));
+    }
+    let _ = window.eval("sessionStorage.setItem('autoStartRecording', 'true')");
+    let _ = window.eval("window.location.assign('/')");
+
+    info!("Recording start requested through the local API");
+    Ok((StatusCode::ACCEPTED, Json(json!({ "status": "starting" }))).into_response())
+}
+
+async fn stop_recording<R: Runtime>(State(state): State<ApiState<R>>) -> ApiResult {
+    if !crate::audio::recording_commands::is_recording().await {
+        return Err(ApiError(StatusCode::CONFLICT, "No recording is active".to_string()));
+    }
+    let window = state
+        .app
+        .get_webview_window("main")
+        .ok_or_else(|| ApiError::internal("Main window not found"))?;
+    let _ = window.eval("window.handleRecordingStop()");
+
+    info!("Recording stop requested through the local API");
+    Ok((StatusCode::ACCEPTED, Json(json!({ "status": "stopping" }))).into_response())
+}
+
+async fn pause_recording<R: Runtime>(State(state): State<ApiState<R>>) -> ApiResult {
+    crate::audio::recording_commands::pause_recording(state.app.clone())
+        .await
+        .map_err(|e| ApiError(StatusCode::CONFLICT, e))?;
+    Ok(Json(json!({ "status": "paused" })).into_response())
+}
+
+async fn resume_recording<R: Runtime>(State(state): State<ApiState<R>>) -> ApiResult {
+    crate::audio::recording_commands::resume_recording(state.app.clone())
+        .await
+        .map_err(|e| ApiError(StatusCode::CONFLICT, e))?;
+    Ok(Json(json!({ "status": "recording" })).into_response())
+}
+
+/// WebSocket streaming every live `TranscriptUpdate` as a JSON text message
+async fn live_transcripts<R: Runtime>(
+    State(state): State<ApiState<R>>,
+    ws: WebSocketUpgrade,
+) -> Response {
+    let updates = state.transcripts.subscribe();
+    let shutdown = state.shutdown.clone();
+    ws.on_upgrade(move |socket| stream_transcripts(socket, updates, shutdown))
+}
+
+async fn stream_transcripts(
+    mut socket: WebSocket,
+    mut updates: broadcast::Receiver<String>,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    info!("Local API transcript stream connected");
+    loop {
+        tokio::select! {
+            update = updates.recv() => match update {
+                Ok(payload) => {
+                    if socket.send(Message::Text(payload)).await.is_err() {
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("Local API transcript stream lagging, skipped {} update(s)", skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {} // Clients don't send anything we act on
+            },
+            _ = shutdown.changed() => break,
+        }
+    }
+    let _ = socket.send(Message::Close(None)).await;
+    info!("Local API transcript stream closed");
+}
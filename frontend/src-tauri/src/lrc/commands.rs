@@ -61,6 +61,9 @@ pub async fn api_import_lrc<R: Runtime>(
             audio_start_time: Some(audio_start_time),
             audio_end_time: Some(audio_end_time),
             duration: Some(duration),
+            words: Vec::new(),
+            speaker_id: None,
+            channel: None,
         });
     }
 
@@ -0,0 +1,330 @@
+use super::{
+    cancel_import, is_supported_file, register_import, split_speech_segments,
+    store_meeting_audio, unregister_import, ImportProgress, ImportSegment, ImportStage,
+    IMPORT_SAMPLE_RATE, MAX_SEGMENT_SECONDS,
+};
+use crate::api::TranscriptSegment;
+use crate::audio::audio_processing::{create_meeting_folder, resample};
+use crate::audio::decoder::decode_audio_file;
+use crate::audio::recording_preferences::load_recording_preferences;
+use crate::audio::transcription::{
+    get_or_init_transcription_engine, offset_words, validate_transcription_model_ready,
+    TranscriptionEngine, TranscriptionError,
+};
+use crate::audio::vad::get_speech_chunks;
+use crate::database::repositories::transcript::TranscriptsRepository;
+use crate::recovery::CHECKPOINTS_DIR;
+use crate::state::AppState;
+use chrono::{DateTime, Duration, Utc};
+use log::{error as log_error, info as log_info, warn as log_warn};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Emitter, Runtime, State};
+use uuid::Uuid;
+
+const CANCELLED_MESSAGE: &str = "Import cancelled";
+
+/// VAD redemption time - same value the live pipeline uses
+const VAD_REDEMPTION_MS: u32 = 400;
+
+fn emit_progress<R: Runtime>(
+    app: &AppHandle<R>,
+    import_id: &str,
+    stage: ImportStage,
+    progress: u32,
+    message: impl Into<String>,
+    segments_done: usize,
+    segments_total: usize,
+) {
+    let payload = ImportProgress {
+        import_id: import_id.to_string(),
+        stage,
+        progress: progress.min(100),
+        message: message.into(),
+        segments_done,
+        segments_total,
+    };
+    if let Err(e) = app.emit("media-import-progress", &payload) {
+        log_warn!("Failed to emit media-import-progress: {}", e);
+    }
+}
+
+fn check_cancelled(cancel_flag: &AtomicBool) -> Result<(), String> {
+    if cancel_flag.load(Ordering::SeqCst) {
+        Err(CANCELLED_MESSAGE.to_string())
+    } else {
+        Ok(())
+    }
+}
+
+/// Import an audio/video file (WAV, MP3, M4A, MP4) and create a new meeting
+/// with an offline transcription of it.
+///
+/// Progress is reported through `media-import-progress` events tagged with
+/// `import_id`; pass the same id to `api_cancel_media_import` to abort.
+#[tauri::command]
+pub async fn api_import_media_file<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, AppState>,
+    file_path: String,
+    title: Option<String>,
+    import_id: Option<String>,
+) -> Result<String, String> {
+    log_info!("api_import_media_file called for: {}", file_path);
+
+    let path = PathBuf::from(&file_path);
+    if !is_supported_file(&path) {
+        return Err(format!(
+            "Unsupported file type. Supported formats: {}",
+            super::SUPPORTED_EXTENSIONS.join(", ")
+        ));
+    }
+    if !path.exists() {
+        return Err(format!("File not found: {}", file_path));
+    }
+
+    let import_id = import_id.unwrap_or_else(|| format!("import-{}", Uuid::new_v4()));
+    let cancel_flag = register_import(&import_id);
+
+    let result = run_import(&app, &state, &path, title, &import_id, &cancel_flag).await;
+    unregister_import(&import_id);
+
+    match &result {
+        Ok(meeting_id) => {
+            log_info!("✅ Media import {} completed as meeting {}", import_id, meeting_id);
+            emit_progress(&app, &import_id, ImportStage::Completed, 100, meeting_id.clone(), 0, 0);
+            crate::hooks::fire(
+                state.db_manager.pool(),
+                crate::hooks::HookEvent::TranscriptFinalized,
+                crate::hooks::HookContext::meeting(meeting_id),
+            );
+        }
+        Err(_) if cancel_flag.load(Ordering::SeqCst) => {
+            log_info!("Media import {} cancelled", import_id);
+            emit_progress(&app, &import_id, ImportStage::Cancelled, 0, CANCELLED_MESSAGE, 0, 0);
+        }
+        Err(e) => {
+            log_error!("Media import {} failed: {}", import_id, e);
+            emit_progress(&app, &import_id, ImportStage::Failed, 0, e.clone(), 0, 0);
+        }
+    }
+
+    result
+}
+
+/// Cancel a running media import. Returns false if the import is not running.
+#[tauri::command]
+pub async fn api_cancel_media_import(import_id: String) -> Result<bool, String> {
+    log_info!("api_cancel_media_import called for: {}", import_id);
+    Ok(cancel_import(&import_id))
+}
+
+async fn run_import<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &State<'_, AppState>,
+    path: &Path,
+    title: Option<String>,
+    import_id: &str,
+    cancel_flag: &AtomicBool,
+) -> Result<String, String> {
+    // Fail fast before decoding if no transcription model is available
+    validate_transcription_model_ready(app).await?;
+    let engine = get_or_init_transcription_engine(app).await?;
+    log_info!("🎙️ Importing with {}", engine.provider_name());
+
+    let meeting_title = title
+        .filter(|t| !t.trim().is_empty())
+        .or_else(|| {
+            path.file_stem()
+                .and_then(|s| s.to_str())
+                .map(|s| s.to_string())
+        })
+        .unwrap_or_else(|| format!("Import {}", Utc::now().format("%Y-%m-%d %H:%M:%S")));
+
+    // 1. Decode and resample to 16kHz for VAD and transcription
+    emit_progress(app, import_id, ImportStage::Decoding, 0, "Decoding audio", 0, 0);
+    let samples_16k = decode_for_transcription(path).await?;
+    let total_duration = samples_16k.len() as f64 / IMPORT_SAMPLE_RATE as f64;
+    log_info!("Decoded {:.1}s of audio for import {}", total_duration, import_id);
+    check_cancelled(cancel_flag)?;
+
+    // 2. Speech segmentation
+    emit_progress(app, import_id, ImportStage::DetectingSpeech, 10, "Detecting speech", 0, 0);
+    let segments = detect_speech_segments(samples_16k, 0.0).await?;
+    if segments.is_empty() {
+        return Err("No speech detected in file".to_string());
+    }
+    check_cancelled(cancel_flag)?;
+
+    // 3. Transcribe segment by segment
+    crate::glossary::start_session(app).await;
+    crate::transcript_rules::start_session(app).await;
+    let total = segments.len();
+    let transcript_segments =
+        transcribe_segments(&engine, segments, Utc::now(), |i, total| {
+            check_cancelled(cancel_flag)?;
+            emit_progress(
+                app,
+                import_id,
+                ImportStage::Transcribing,
+                20 + (i * 70 / total) as u32,
+                format!("Transcribing segment {} of {}", i + 1, total),
+                i,
+                total,
+            );
+            Ok(())
+        })
+        .await?;
+    check_cancelled(cancel_flag)?;
+
+    if transcript_segments.is_empty() {
+        return Err("Transcription produced no text".to_string());
+    }
+
+    // 4. Save audio next to the meeting and persist transcripts
+    emit_progress(app, import_id, ImportStage::Saving, 90, "Saving meeting", total, total);
+
+    let folder_path = match load_recording_preferences(app).await {
+        Ok(prefs) => match create_meeting_folder(&prefs.save_folder, &meeting_title) {
+            Ok(folder) => {
+                // Imports never checkpoint - drop the empty dir so recovery won't flag it
+                let _ = std::fs::remove_dir(folder.join(CHECKPOINTS_DIR));
+                let source = path.to_path_buf();
+                let target = folder.clone();
+                match tokio::task::spawn_blocking(move || store_meeting_audio(&source, &target))
+                    .await
+                {
+                    Ok(Ok(_)) => Some(folder.to_string_lossy().to_string()),
+                    Ok(Err(e)) => {
+                        log_warn!("Failed to store imported audio: {}", e);
+                        Some(folder.to_string_lossy().to_string())
+                    }
+                    Err(e) => {
+                        log_warn!("Audio store task failed: {}", e);
+                        Some(folder.to_string_lossy().to_string())
+                    }
+                }
+            }
+            Err(e) => {
+                log_warn!("Failed to create meeting folder for import: {}", e);
+                None
+            }
+        },
+        Err(e) => {
+            log_warn!("Failed to load recording preferences: {}", e);
+            None
+        }
+    };
+
+    let pool = state.db_manager.pool();
+    TranscriptsRepository::save_transcript(pool, &meeting_title, &transcript_segments, folder_path)
+        .await
+        .map_err(|e| format!("Failed to save imported meeting: {}", e))
+}
+
+/// Decode a media file and resample it to 16kHz mono
+pub(crate) async fn decode_for_transcription(path: &Path) -> Result<Vec<f32>, String> {
+    let decode_path = path.to_path_buf();
+    let decoded = tokio::task::spawn_blocking(move || decode_audio_file(&decode_path))
+        .await
+        .map_err(|e| format!("Decode task failed: {}", e))?
+        .map_err(|e| format!("Failed to decode file: {}", e))?;
+
+    if decoded.sample_rate == IMPORT_SAMPLE_RATE {
+        return Ok(decoded.samples);
+    }
+
+    let from_rate = decoded.sample_rate;
+    tokio::task::spawn_blocking(move || resample(&decoded.samples, from_rate, IMPORT_SAMPLE_RATE))
+        .await
+        .map_err(|e| format!("Resample task failed: {}", e))?
+        .map_err(|e| format!("Failed to resample audio: {}", e))
+}
+
+/// Run VAD over 16kHz samples and split speech into engine-sized segments.
+/// `offset` (seconds) is added to every segment start.
+pub(crate) async fn detect_speech_segments(
+    samples_16k: Vec<f32>,
+    offset: f64,
+) -> Result<Vec<ImportSegment>, String> {
+    let speech_segments =
+        tokio::task::spawn_blocking(move || get_speech_chunks(&samples_16k, VAD_REDEMPTION_MS))
+            .await
+            .map_err(|e| format!("VAD task failed: {}", e))?
+            .map_err(|e| format!("Speech detection failed: {}", e))?;
+
+    let mut segments = split_speech_segments(
+        speech_segments,
+        (MAX_SEGMENT_SECONDS * IMPORT_SAMPLE_RATE as f64) as usize,
+    );
+    for segment in &mut segments {
+        segment.start_time += offset;
+    }
+    Ok(segments)
+}
+
+/// Transcribe segments one by one with the engine's confidence threshold.
+///
+/// `on_segment(index, total)` runs before each segment; returning an error
+/// (e.g. on cancellation) aborts the loop. `base_time` is the wall-clock time
+/// of offset 0, used for the display timestamps.
+pub(crate) async fn transcribe_segments<F>(
+    engine: &TranscriptionEngine,
+    segments: Vec<ImportSegment>,
+    base_time: DateTime<Utc>,
+    mut on_segment: F,
+) -> Result<Vec<TranscriptSegment>, String>
+where
+    F: FnMut(usize, usize) -> Result<(), String>,
+{
+    let total = segments.len();
+    let language = crate::get_language_preference_internal();
+    let confidence_threshold = match engine {
+        TranscriptionEngine::Whisper(_) | TranscriptionEngine::Provider(_) => 0.3,
+        TranscriptionEngine::Parakeet(_) => 0.0,
+    };
+    let rules = crate::transcript_rules::active_rules();
+    let mut transcript_segments = Vec::new();
+
+    for (i, segment) in segments.into_iter().enumerate() {
+        on_segment(i, total)?;
+
+        let start_time = segment.start_time;
+        let end_time = segment.end_time();
+
+        match engine.transcribe(segment.samples, language.clone()).await {
+            Ok(result) => {
+                if result.text.is_empty()
+                    || result.confidence.map_or(false, |c| c < confidence_threshold)
+                {
+                    continue;
+                }
+                let text = rules.apply(&result.text);
+                crate::glossary::push_context(&text);
+                let timestamp = (base_time + Duration::milliseconds((start_time * 1000.0) as i64))
+                    .format("%H:%M:%S")
+                    .to_string();
+                transcript_segments.push(TranscriptSegment {
+                    id: format!("import-{}", i),
+                    text,
+                    timestamp,
+                    audio_start_time: Some(start_time),
+                    audio_end_time: Some(end_time),
+                    duration: Some(end_time - start_time),
+                    words: offset_words(rules.apply_words(result.words), start_time),
+                    speaker_id: None,
+                    channel: None,
+                });
+            }
+            Err(TranscriptionError::AudioTooShort { .. }) => continue,
+            Err(TranscriptionError::ModelNotLoaded) => {
+                return Err("Transcription model was unloaded during transcription".to_string());
+            }
+            Err(e) => {
+                log_warn!("Segment {} at {:.1}s failed to transcribe: {}", i, start_time, e);
+            }
+        }
+    }
+
+    Ok(transcript_segments)
+}
@@ -0,0 +1,264 @@
+/// Media Import Module
+///
+/// Imports audio/video files recorded outside the app (phone recordings, exported
+/// call recordings) as meetings with a full offline transcription.
+/// Flow: decode -> resample to 16kHz -> VAD segmentation -> transcribe -> save
+
+pub mod commands;
+
+use crate::audio::ffmpeg::find_ffmpeg_path;
+use crate::audio::vad::SpeechSegment;
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// File extensions accepted by the importer
+pub const SUPPORTED_EXTENSIONS: &[&str] = &["wav", "mp3", "m4a", "mp4"];
+
+/// Sample rate used for VAD and transcription
+pub const IMPORT_SAMPLE_RATE: u32 = 16000;
+
+/// Longest segment handed to the engine in one call (Whisper's 30s window)
+pub const MAX_SEGMENT_SECONDS: f64 = 30.0;
+
+/// Minimum segment length (50ms at 16kHz) - matches the live pipeline filter
+const MIN_SEGMENT_SAMPLES: usize = 800;
+
+// Cancellation flags for in-flight imports, keyed by import_id
+static ACTIVE_IMPORTS: Lazy<Mutex<HashMap<String, Arc<AtomicBool>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Stage reported in `media-import-progress` events
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportStage {
+    Decoding,
+    DetectingSpeech,
+    Transcribing,
+    Saving,
+    Completed,
+    Cancelled,
+    Failed,
+}
+
+/// Payload of the `media-import-progress` event
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportProgress {
+    pub import_id: String,
+    pub stage: ImportStage,
+    pub progress: u32,
+    pub message: String,
+    pub segments_done: usize,
+    pub segments_total: usize,
+}
+
+/// A speech segment ready for transcription
+#[derive(Debug, Clone)]
+pub struct ImportSegment {
+    /// Seconds from the start of the file
+    pub start_time: f64,
+    /// 16kHz mono samples
+    pub samples: Vec<f32>,
+}
+
+impl ImportSegment {
+    pub fn end_time(&self) -> f64 {
+        self.start_time + self.samples.len() as f64 / IMPORT_SAMPLE_RATE as f64
+    }
+}
+
+/// Check whether a file has one of the supported extensions
+pub fn is_supported_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|ext| SUPPORTED_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+/// Convert VAD segments into transcription-sized segments.
+///
+/// Drops fragments shorter than 50ms and splits anything longer than
+/// `max_samples` so long monologues still fit in a single engine call.
+pub fn split_speech_segments(
+    segments: Vec<SpeechSegment>,
+    max_samples: usize,
+) -> Vec<ImportSegment> {
+    let max_samples = max_samples.max(MIN_SEGMENT_SAMPLES);
+    let mut result = Vec::new();
+
+    for segment in segments {
+        if segment.samples.len() < MIN_SEGMENT_SAMPLES {
+            continue;
+        }
+
+        let segment_start = segment.start_timestamp_ms / 1000.0;
+        for (i, part) in segment.samples.chunks(max_samples).enumerate() {
+            if part.len() < MIN_SEGMENT_SAMPLES {
+                continue;
+            }
+            result.push(ImportSegment {
+                start_time: segment_start
+                    + (i * max_samples) as f64 / IMPORT_SAMPLE_RATE as f64,
+                samples: part.to_vec(),
+            });
+        }
+    }
+
+    result
+}
+
+/// Register a new import and return its cancellation flag
+pub fn register_import(import_id: &str) -> Arc<AtomicBool> {
+    let flag = Arc::new(AtomicBool::new(false));
+    if let Ok(mut imports) = ACTIVE_IMPORTS.lock() {
+        imports.insert(import_id.to_string(), flag.clone());
+    }
+    flag
+}
+
+/// Remove an import from the registry once it finishes
+pub fn unregister_import(import_id: &str) {
+    if let Ok(mut imports) = ACTIVE_IMPORTS.lock() {
+        imports.remove(import_id);
+    }
+}
+
+/// Request cancellation of a running import. Returns false if no such import is running.
+pub fn cancel_import(import_id: &str) -> bool {
+    match ACTIVE_IMPORTS.lock() {
+        Ok(imports) => match imports.get(import_id) {
+            Some(flag) => {
+                flag.store(true, Ordering::SeqCst);
+                true
+            }
+            None => false,
+        },
+        Err(_) => false,
+    }
+}
+
+/// Store the imported media next to the meeting as `audio.mp4` for playback.
+///
+/// Transcodes through FFmpeg (same AAC settings as live recordings); if FFmpeg is
+/// unavailable the original file is copied unchanged and its path returned.
+pub fn store_meeting_audio(source: &Path, meeting_folder: &Path) -> Result<PathBuf> {
+    let target = meeting_folder.join("audio.mp4");
+
+    if let Some(ffmpeg_path) = find_ffmpeg_path() {
+        let mut command = std::process::Command::new(ffmpeg_path);
+        command.args([
+            "-nostdin",
+            "-i",
+            source
+                .to_str()
+                .ok_or_else(|| anyhow!("Invalid file path: {}", source.display()))?,
+            "-vn",
+            "-c:a",
+            "aac",
+            "-b:a",
+            "192k",
+            "-profile:a",
+            "aac_low",
+            "-movflags",
+            "+faststart",
+            "-y",
+            target
+                .to_str()
+                .ok_or_else(|| anyhow!("Invalid file path: {}", target.display()))?,
+        ]);
+
+        // Hide console window on Windows to prevent CMD popup during import
+        #[cfg(target_os = "windows")]
+        {
+            use std::os::windows::process::CommandExt;
+            const CREATE_NO_WINDOW: u32 = 0x08000000;
+            command.creation_flags(CREATE_NO_WINDOW);
+        }
+
+        match command.output() {
+            Ok(output) if output.status.success() && target.exists() => {
+                info!("Transcoded imported media to {}", target.display());
+                return Ok(target);
+            }
+            Ok(output) => {
+                warn!(
+                    "FFmpeg transcode failed, copying original file instead: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                );
+            }
+            Err(e) => warn!("Failed to run FFmpeg, copying original file instead: {}", e),
+        }
+    }
+
+    let extension = source
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("mp4")
+        .to_lowercase();
+    let copy_target = meeting_folder.join(format!("audio.{}", extension));
+    std::fs::copy(source, &copy_target)?;
+    info!("Copied imported media to {}", copy_target.display());
+    Ok(copy_target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn speech(start_ms: f64, samples: usize) -> SpeechSegment {
+        SpeechSegment {
+            samples: vec![0.1; samples],
+            start_timestamp_ms: start_ms,
+            end_timestamp_ms: start_ms + samples as f64 / 16.0,
+            confidence: 0.9,
+        }
+    }
+
+    #[test]
+    fn test_supported_extensions() {
+        assert!(is_supported_file(Path::new("/tmp/call.MP3")));
+        assert!(is_supported_file(Path::new("standup.m4a")));
+        assert!(!is_supported_file(Path::new("notes.txt")));
+        assert!(!is_supported_file(Path::new("no_extension")));
+    }
+
+    #[test]
+    fn test_split_keeps_short_segments_whole() {
+        let segments = split_speech_segments(vec![speech(2500.0, 16000)], 16000 * 30);
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].start_time, 2.5);
+        assert_eq!(segments[0].end_time(), 3.5);
+    }
+
+    #[test]
+    fn test_split_long_segment_offsets() {
+        // 70 seconds of speech starting at 10s, split into 30s pieces
+        let segments = split_speech_segments(vec![speech(10_000.0, 16000 * 70)], 16000 * 30);
+        assert_eq!(segments.len(), 3);
+        assert_eq!(segments[0].start_time, 10.0);
+        assert_eq!(segments[1].start_time, 40.0);
+        assert_eq!(segments[2].start_time, 70.0);
+        assert_eq!(segments[2].samples.len(), 16000 * 10);
+    }
+
+    #[test]
+    fn test_split_drops_tiny_fragments() {
+        let segments = split_speech_segments(vec![speech(0.0, 400)], 16000 * 30);
+        assert!(segments.is_empty());
+    }
+
+    #[test]
+    fn test_cancel_unknown_import() {
+        assert!(!cancel_import("import-does-not-exist"));
+        let flag = register_import("import-test");
+        assert!(cancel_import("import-test"));
+        assert!(flag.load(Ordering::SeqCst));
+        unregister_import("import-test");
+        assert!(!cancel_import("import-test"));
+    }
+}
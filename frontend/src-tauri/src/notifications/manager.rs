@@ -172,6 +172,28 @@ impl<R: Runtime> NotificationManager<R> {
         self.show_notification(notification).await
     }
 
+    /// Show a notification that the auto-recorder detected a meeting app
+    pub async fn show_meeting_detected(&self, app_name: &str, auto_started: bool) -> Result<()> {
+        let settings = self.settings.read().await;
+        if !settings.notification_preferences.show_meeting_reminders {
+            return Ok(());
+        }
+
+        let notification = Notification::meeting_detected(app_name, auto_started);
+        self.show_notification(notification).await
+    }
+
+    /// Show a warning that an idle recording is about to be paused and stopped
+    pub async fn show_recording_idle(&self, idle_minutes: u64, pause_in_secs: u64) -> Result<()> {
+        let settings = self.settings.read().await;
+        if !settings.notification_preferences.show_recording_paused {
+            return Ok(());
+        }
+
+        let notification = Notification::recording_idle(idle_minutes, pause_in_secs);
+        self.show_notification(notification).await
+    }
+
     /// Show a system error notification
     pub async fn show_system_error(&self, error: String) -> Result<()> {
         let settings = self.settings.read().await;
@@ -299,6 +321,8 @@ impl<R: Runtime> NotificationManager<R> {
             NotificationType::RecordingResumed => settings.notification_preferences.show_recording_resumed,
             NotificationType::TranscriptionComplete => settings.notification_preferences.show_transcription_complete,
             NotificationType::MeetingReminder(_) => settings.notification_preferences.show_meeting_reminders,
+            NotificationType::MeetingDetected(_) => settings.notification_preferences.show_meeting_reminders,
+            NotificationType::RecordingIdle(_) => settings.notification_preferences.show_recording_paused,
             NotificationType::SystemError(_) => settings.notification_preferences.show_system_errors,
             NotificationType::Test => true, // Always show test notifications
         }
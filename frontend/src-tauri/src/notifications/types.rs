@@ -21,6 +21,8 @@ pub enum NotificationType {
     RecordingResumed,
     TranscriptionComplete,
     MeetingReminder(u64), // Duration in minutes
+    MeetingDetected(String), // App that started a meeting
+    RecordingIdle(u64), // Minutes without speech
     SystemError(String),
     Test, // For testing notifications
 }
@@ -176,6 +178,31 @@ impl Notification {
             .with_timeout(NotificationTimeout::Seconds(10))
     }
 
+    pub fn meeting_detected(app_name: &str, auto_started: bool) -> Self {
+        let body = if auto_started {
+            format!("{} meeting detected. Recording started automatically.", app_name)
+        } else {
+            format!("{} meeting detected. Open Meetily to start recording.", app_name)
+        };
+
+        Notification::new("Meetily", body, NotificationType::MeetingDetected(app_name.to_string()))
+            .with_priority(NotificationPriority::High)
+            .with_timeout(NotificationTimeout::Seconds(10))
+    }
+
+    pub fn recording_idle(idle_minutes: u64, pause_in_secs: u64) -> Self {
+        Notification::new(
+            "Meetily",
+            format!(
+                "No speech for {} minutes. Recording will pause in {} seconds and then stop.",
+                idle_minutes, pause_in_secs
+            ),
+            NotificationType::RecordingIdle(idle_minutes)
+        )
+        .with_priority(NotificationPriority::High)
+        .with_timeout(NotificationTimeout::Seconds(10))
+    }
+
     pub fn system_error(error: impl Into<String>) -> Self {
         let error_string = error.into();
         Notification::new(
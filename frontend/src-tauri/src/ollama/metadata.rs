@@ -137,7 +137,7 @@ const ULTIMATE_FALLBACK: usize = 4000;
 ///
 /// # Returns
 /// ModelMetadata on success, error string on failure
-async fn fetch_model_info(
+pub async fn fetch_model_info(
     model_name: &str,
     endpoint: Option<&str>,
 ) -> Result<ModelMetadata, String> {
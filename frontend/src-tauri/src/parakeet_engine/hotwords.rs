@@ -0,0 +1,162 @@
+//! Hotword boosting for Parakeet greedy decoding.
+//!
+//! Parakeet has no text prompt, so glossary terms are applied while decoding:
+//! each term is split into vocabulary tokens, and at every step the token that
+//! would start or continue a term gets a bonus on its logit. The bonus only
+//! changes *which* token is emitted; whether a token is emitted at all is
+//! still decided by the unboosted logits (see `ParakeetModel::decode_sequence`).
+
+use std::collections::HashMap;
+
+/// Logit bonus for the first token of a term
+pub const START_BONUS: f32 = 1.5;
+
+/// Logit bonus for the next token of a partially decoded term
+pub const CONTINUATION_BONUS: f32 = 3.0;
+
+#[derive(Debug, Clone)]
+pub struct HotwordBooster {
+    sequences: Vec<Vec<i32>>,
+}
+
+impl HotwordBooster {
+    /// Tokenize `terms` with the model vocabulary (word-start tokens begin
+    /// with a space). Terms that can't be spelled with the vocabulary are
+    /// skipped. Returns None if no term is usable.
+    pub fn new(terms: &[String], vocab: &[String], blank_idx: i32) -> Option<Self> {
+        let mut token_ids: HashMap<&str, i32> = HashMap::new();
+        for (id, token) in vocab.iter().enumerate() {
+            let is_special = token.starts_with('<') && token.ends_with('>');
+            if id as i32 != blank_idx && !token.is_empty() && !is_special {
+                token_ids.entry(token.as_str()).or_insert(id as i32);
+            }
+        }
+        let max_token_len = token_ids.keys().map(|t| t.len()).max().unwrap_or(0);
+
+        let sequences: Vec<Vec<i32>> = terms
+            .iter()
+            .map(|term| term.split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter(|term| !term.is_empty())
+            .filter_map(|term| {
+                let sequence = tokenize(&format!(" {}", term), &token_ids, max_token_len);
+                if sequence.is_none() {
+                    log::debug!("Hotword '{}' has no spelling in the Parakeet vocabulary", term);
+                }
+                sequence
+            })
+            .collect();
+
+        if sequences.is_empty() {
+            None
+        } else {
+            Some(Self { sequences })
+        }
+    }
+
+    pub fn num_terms(&self) -> usize {
+        self.sequences.len()
+    }
+
+    /// Add term bonuses to `logits` given the tokens decoded so far.
+    /// A token shared by several terms gets the largest bonus, not the sum.
+    pub fn boost(&self, history: &[i32], logits: &mut [f32]) {
+        let mut bonuses: HashMap<usize, f32> = HashMap::new();
+
+        for sequence in &self.sequences {
+            // Longest proper prefix of the term that ends the history
+            let matched = (1..sequence.len())
+                .rev()
+                .find(|&len| history.ends_with(&sequence[..len]))
+                .unwrap_or(0);
+            let bonus = if matched > 0 { CONTINUATION_BONUS } else { START_BONUS };
+
+            let next = sequence[matched] as usize;
+            let entry = bonuses.entry(next).or_insert(0.0);
+            *entry = entry.max(bonus);
+        }
+
+        for (token, bonus) in bonuses {
+            if let Some(logit) = logits.get_mut(token) {
+                *logit += bonus;
+            }
+        }
+    }
+}
+
+/// Greedy longest-match tokenization
+fn tokenize(text: &str, token_ids: &HashMap<&str, i32>, max_token_len: usize) -> Option<Vec<i32>> {
+    let mut ids = Vec::new();
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        let mut end = rest.len().min(max_token_len);
+        loop {
+            if end == 0 {
+                return None;
+            }
+            if rest.is_char_boundary(end) {
+                if let Some(&id) = token_ids.get(&rest[..end]) {
+                    ids.push(id);
+                    rest = &rest[end..];
+                    break;
+                }
+            }
+            end -= 1;
+        }
+    }
+
+    Some(ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vocab() -> Vec<String> {
+        [" Meet", "ily", " T", "au", "ri", " the", "i", "l", "y", "<blk>", "<unk>"]
+            .iter()
+            .map(|t| t.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_tokenize_terms() {
+        let booster = HotwordBooster::new(
+            &["Meetily".to_string(), "Tauri".to_string(), "Zed".to_string()],
+            &vocab(),
+            9,
+        )
+        .unwrap();
+        // "Zed" can't be spelled with the vocabulary
+        assert_eq!(booster.num_terms(), 2);
+        assert_eq!(booster.sequences[0], vec![0, 1]);
+        assert_eq!(booster.sequences[1], vec![2, 3, 4]);
+
+        assert!(HotwordBooster::new(&["<unk>".to_string()], &vocab(), 9).is_none());
+        assert!(HotwordBooster::new(&[], &vocab(), 9).is_none());
+    }
+
+    #[test]
+    fn test_boost_start_and_continuation() {
+        let booster = HotwordBooster::new(&["Tauri".to_string()], &vocab(), 9).unwrap();
+
+        let mut logits = vec![0.0; vocab().len()];
+        booster.boost(&[5], &mut logits);
+        assert_eq!(logits[2], START_BONUS);
+        assert_eq!(logits.iter().filter(|&&l| l != 0.0).count(), 1);
+
+        let mut logits = vec![0.0; vocab().len()];
+        booster.boost(&[5, 2, 3], &mut logits);
+        assert_eq!(logits[4], CONTINUATION_BONUS);
+        assert_eq!(logits[9], 0.0); // blank is never boosted
+    }
+
+    #[test]
+    fn test_boost_takes_max_for_shared_tokens() {
+        let booster =
+            HotwordBooster::new(&["Meetily".to_string(), "Meet".to_string()], &vocab(), 9).unwrap();
+        let mut logits = vec![0.0; vocab().len()];
+        booster.boost(&[], &mut logits);
+        assert_eq!(logits[0], START_BONUS);
+    }
+}
@@ -15,10 +15,12 @@
 //!
 //! - `parakeet_engine`: Main engine implementation
 //! - `model`: ONNX model wrapper and inference logic
+//! - `hotwords`: Glossary term boosting during decoding
 //! - `commands`: Tauri command interface for frontend integration
 
 pub mod parakeet_engine;
 pub mod model;
+pub mod hotwords;
 pub mod commands;
 
 pub use parakeet_engine::{ParakeetEngine, ParakeetEngineError, QuantizationType, ModelInfo, ModelStatus};
@@ -10,6 +10,9 @@ use regex::Regex;
 use std::fs;
 use std::path::Path;
 
+use super::hotwords::HotwordBooster;
+use crate::audio::transcription::WordTimestamp;
+
 pub type DecoderState = (Array3<f32>, Array3<f32>);
 
 const SUBSAMPLING_FACTOR: usize = 8;
@@ -26,6 +29,48 @@ pub struct TimestampedResult {
     pub tokens: Vec<String>,
 }
 
+impl TimestampedResult {
+    /// Group subword tokens into words with start/end times.
+    ///
+    /// A token with a leading space (from the SentencePiece word marker) starts a new
+    /// word; punctuation and continuation pieces are appended to the current word.
+    /// A word ends where the next one starts; the last word ends one encoder frame
+    /// after its final token, capped at `audio_duration`.
+    pub fn word_timestamps(&self, audio_duration: f32) -> Vec<WordTimestamp> {
+        let frame_duration = WINDOW_SIZE * SUBSAMPLING_FACTOR as f32;
+        let mut words: Vec<(String, f32, f32)> = Vec::new();
+
+        for (token, &time) in self.tokens.iter().zip(self.timestamps.iter()) {
+            let starts_word = token.starts_with(' ') || words.is_empty();
+            if starts_word {
+                words.push((token.trim().to_string(), time, time));
+            } else if let Some(current) = words.last_mut() {
+                current.0.push_str(token.trim());
+                current.2 = time;
+            }
+        }
+
+        let mut result = Vec::with_capacity(words.len());
+        for i in 0..words.len() {
+            let (word, start, last_token_time) = &words[i];
+            if word.is_empty() {
+                continue;
+            }
+            let end = match words.get(i + 1) {
+                Some(next) => next.1,
+                None => (last_token_time + frame_duration).min(audio_duration.max(*start)),
+            };
+            result.push(WordTimestamp {
+                word: word.clone(),
+                start: *start as f64,
+                end: end as f64,
+            });
+        }
+
+        result
+    }
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum ParakeetError {
     #[error("ORT error")]
@@ -49,6 +94,8 @@ pub struct ParakeetModel {
     vocab: Vec<String>,
     blank_idx: i32,
     vocab_size: usize,
+    hotwords: Option<HotwordBooster>,
+    hotwords_version: Option<u64>,
 }
 
 impl Drop for ParakeetModel {
@@ -79,6 +126,8 @@ impl ParakeetModel {
             vocab,
             blank_idx,
             vocab_size,
+            hotwords: None,
+            hotwords_version: None,
         })
     }
 
@@ -382,12 +431,20 @@ impl ParakeetModel {
             };
 
             // Get argmax token from vocabulary logits only
-            let token = vocab_logits
-                .iter()
-                .enumerate()
-                .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
-                .map(|(idx, _)| idx as i32)
-                .unwrap_or(self.blank_idx);
+            let mut token = argmax(vocab_logits).unwrap_or(self.blank_idx);
+
+            // Hotwords pick between non-blank tokens; blank vs. speech is
+            // still decided by the model alone
+            if token != self.blank_idx {
+                if let Some(hotwords) = &self.hotwords {
+                    let mut boosted = vocab_logits.to_vec();
+                    hotwords.boost(&tokens, &mut boosted);
+                    if let Some(blank) = boosted.get_mut(self.blank_idx as usize) {
+                        *blank = f32::NEG_INFINITY;
+                    }
+                    token = argmax(&boosted).unwrap_or(token);
+                }
+            }
 
             if token != self.blank_idx {
                 prev_state = new_state;
@@ -452,6 +509,22 @@ impl ParakeetModel {
         }
     }
 
+    /// Glossary version the current hotwords were built from
+    pub fn hotwords_version(&self) -> Option<u64> {
+        self.hotwords_version
+    }
+
+    /// Boost `terms` while decoding; an empty list turns boosting off
+    pub fn set_hotwords(&mut self, terms: &[String], version: u64) {
+        self.hotwords = HotwordBooster::new(terms, &self.vocab, self.blank_idx);
+        self.hotwords_version = Some(version);
+        log::info!(
+            "Parakeet hotwords updated: {} of {} term(s) usable",
+            self.hotwords.as_ref().map_or(0, |h| h.num_terms()),
+            terms.len()
+        );
+    }
+
     pub fn transcribe_samples(
         &mut self,
         samples: Vec<f32>,
@@ -479,3 +552,53 @@ impl ParakeetModel {
         Ok(timestamped_result)
     }
 }
+
+fn argmax(logits: &[f32]) -> Option<i32> {
+    logits
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(idx, _)| idx as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(tokens: &[&str], timestamps: &[f32]) -> TimestampedResult {
+        TimestampedResult {
+            text: tokens.concat().trim().to_string(),
+            timestamps: timestamps.to_vec(),
+            tokens: tokens.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_word_timestamps_merges_subwords() {
+        let r = result(&[" hel", "lo", " wor", "ld", "."], &[0.0, 0.08, 0.4, 0.48, 0.56]);
+        let words = r.word_timestamps(2.0);
+
+        assert_eq!(words.len(), 2);
+        assert_eq!(words[0].word, "hello");
+        assert_eq!(words[0].start, 0.0);
+        assert!((words[0].end - 0.4).abs() < 1e-6);
+        assert_eq!(words[1].word, "world.");
+        assert!((words[1].start - 0.4).abs() < 1e-6);
+        assert!((words[1].end - 0.64).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_word_timestamps_capped_at_duration() {
+        let r = result(&[" hi"], &[0.96]);
+        let words = r.word_timestamps(1.0);
+
+        assert_eq!(words.len(), 1);
+        assert!((words[0].end - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_word_timestamps_empty() {
+        let r = result(&[], &[]);
+        assert!(r.word_timestamps(1.0).is_empty());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::parakeet_engine::model::ParakeetModel;
+use crate::parakeet_engine::model::{ParakeetModel, TimestampedResult};
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
@@ -319,6 +319,14 @@ impl ParakeetEngine {
 
     /// Transcribe audio samples using the loaded Parakeet model
     pub async fn transcribe_audio(&self, audio_data: Vec<f32>) -> Result<String> {
+        Ok(self.transcribe_audio_with_timestamps(audio_data).await?.text)
+    }
+
+    /// Transcribe audio and keep per-token timestamps (see `TimestampedResult::word_timestamps`)
+    pub async fn transcribe_audio_with_timestamps(
+        &self,
+        audio_data: Vec<f32>,
+    ) -> Result<TimestampedResult> {
         let mut model_guard = self.current_model.write().await;
         let model = model_guard
             .as_mut()
@@ -331,6 +339,12 @@ impl ParakeetEngine {
             duration_seconds
         );
 
+        // Pick up glossary edits since the last chunk
+        let glossary_version = crate::glossary::terms_version();
+        if model.hotwords_version() != Some(glossary_version) {
+            model.set_hotwords(&crate::glossary::active_terms(), glossary_version);
+        }
+
         // Transcribe using Parakeet model
         let result = model
             .transcribe_samples(audio_data)
@@ -338,7 +352,7 @@ impl ParakeetEngine {
 
         log::debug!("Parakeet transcription result: '{}'", result.text);
 
-        Ok(result.text)
+        Ok(result)
     }
 
     /// Get the models directory path
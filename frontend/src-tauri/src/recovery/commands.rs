@@ -0,0 +1,346 @@
+use super::{
+    inspect_session, merge_checkpoints, read_metadata, read_transcript_segments,
+    scan_for_sessions, to_api_segments, untranscribed_ranges, write_metadata,
+    RecoverableSession, CHECKPOINTS_DIR, DISCARDED_STATUS, MIN_UNTRANSCRIBED_SECONDS,
+};
+use crate::api::TranscriptSegment;
+use crate::audio::recording_commands::get_meeting_folder_path;
+use crate::audio::recording_preferences::{
+    get_default_recordings_folder, load_recording_preferences,
+};
+use crate::audio::transcription::{
+    get_or_init_transcription_engine, validate_transcription_model_ready,
+};
+use crate::database::repositories::{meeting::MeetingsRepository, transcript::TranscriptsRepository};
+use crate::media_import::commands::{
+    decode_for_transcription, detect_speech_segments, transcribe_segments,
+};
+use crate::media_import::IMPORT_SAMPLE_RATE;
+use crate::state::AppState;
+use chrono::{DateTime, Utc};
+use log::{error as log_error, info as log_info, warn as log_warn};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, Runtime, State};
+
+/// Stage reported in `recovery-progress` events
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecoveryStage {
+    MergingAudio,
+    Transcribing,
+    Saving,
+    Completed,
+    Failed,
+}
+
+/// Payload of the `recovery-progress` event
+#[derive(Debug, Clone, Serialize)]
+pub struct RecoveryProgress {
+    pub folder_path: String,
+    pub stage: RecoveryStage,
+    pub progress: u32,
+    pub message: String,
+}
+
+fn emit_progress<R: Runtime>(
+    app: &AppHandle<R>,
+    folder_path: &str,
+    stage: RecoveryStage,
+    progress: u32,
+    message: impl Into<String>,
+) {
+    let payload = RecoveryProgress {
+        folder_path: folder_path.to_string(),
+        stage,
+        progress: progress.min(100),
+        message: message.into(),
+    };
+    if let Err(e) = app.emit("recovery-progress", &payload) {
+        log_warn!("Failed to emit recovery-progress: {}", e);
+    }
+}
+
+/// Recordings folders to scan: the configured save folder and the default one
+async fn recordings_folders<R: Runtime>(app: &AppHandle<R>) -> Vec<PathBuf> {
+    let mut folders = Vec::new();
+    match load_recording_preferences(app).await {
+        Ok(prefs) => folders.push(prefs.save_folder),
+        Err(e) => log_warn!("Failed to load recording preferences: {}", e),
+    }
+    let default_folder = get_default_recordings_folder();
+    if !folders.contains(&default_folder) {
+        folders.push(default_folder);
+    }
+    folders
+}
+
+async fn find_sessions<R: Runtime>(app: &AppHandle<R>) -> Vec<RecoverableSession> {
+    let folders = recordings_folders(app).await;
+    // Never offer to recover the recording that is still running
+    let active_folder = get_meeting_folder_path().await.ok().flatten().map(PathBuf::from);
+
+    tokio::task::spawn_blocking(move || scan_for_sessions(&folders, active_folder.as_deref()))
+        .await
+        .unwrap_or_else(|e| {
+            log_error!("Recovery scan task failed: {}", e);
+            Vec::new()
+        })
+}
+
+/// Scan the recordings folders on startup and emit `recovery-sessions-found`
+/// so the UI can offer to recover them.
+pub async fn check_for_interrupted_recordings<R: Runtime>(app: AppHandle<R>) {
+    let sessions = find_sessions(&app).await;
+    if sessions.is_empty() {
+        log_info!("No interrupted recordings found");
+        return;
+    }
+
+    log_warn!("⚠️ Found {} interrupted recording(s) that can be recovered", sessions.len());
+    if let Err(e) = app.emit("recovery-sessions-found", &sessions) {
+        log_warn!("Failed to emit recovery-sessions-found: {}", e);
+    }
+}
+
+/// List recordings that were interrupted before they were finalized
+#[tauri::command]
+pub async fn api_scan_recovery_sessions<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<Vec<RecoverableSession>, String> {
+    log_info!("api_scan_recovery_sessions called");
+    Ok(find_sessions(&app).await)
+}
+
+/// Recover an interrupted recording as a meeting.
+///
+/// Merges leftover checkpoints into `audio.mp4`, rebuilds the meeting from the
+/// incremental `transcripts.json` and, if `retranscribe` is set, transcribes
+/// any audio that never got a transcript. Returns the meeting id.
+#[tauri::command]
+pub async fn api_recover_session<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, AppState>,
+    folder_path: String,
+    retranscribe: Option<bool>,
+) -> Result<String, String> {
+    log_info!(
+        "api_recover_session called for: {}, retranscribe: {:?}",
+        folder_path,
+        retranscribe
+    );
+
+    let result = run_recovery(&app, &state, &folder_path, retranscribe.unwrap_or(false)).await;
+    match &result {
+        Ok(meeting_id) => {
+            log_info!("✅ Recovered {} as meeting {}", folder_path, meeting_id);
+            emit_progress(&app, &folder_path, RecoveryStage::Completed, 100, meeting_id.clone());
+        }
+        Err(e) => {
+            log_error!("Recovery of {} failed: {}", folder_path, e);
+            emit_progress(&app, &folder_path, RecoveryStage::Failed, 0, e.clone());
+        }
+    }
+    result
+}
+
+async fn run_recovery<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &State<'_, AppState>,
+    folder_path: &str,
+    retranscribe: bool,
+) -> Result<String, String> {
+    let folder = PathBuf::from(folder_path);
+    if get_meeting_folder_path().await.ok().flatten().as_deref() == Some(folder_path) {
+        return Err("This recording is still in progress".to_string());
+    }
+    let session = inspect_session(&folder)
+        .ok_or_else(|| format!("No interrupted recording found in {}", folder_path))?;
+
+    let pool = state.db_manager.pool();
+    let existing = MeetingsRepository::get_meeting_by_folder_path(pool, folder_path)
+        .await
+        .map_err(|e| format!("Failed to look up meeting: {}", e))?;
+
+    // 1. Merge checkpoints into audio.mp4
+    let mut audio_path = folder.join("audio.mp4");
+    if session.checkpoint_count > 0 {
+        emit_progress(app, folder_path, RecoveryStage::MergingAudio, 0, "Merging audio checkpoints");
+        let merge_folder = folder.clone();
+        match tokio::task::spawn_blocking(move || merge_checkpoints(&merge_folder)).await {
+            Ok(Ok(path)) => audio_path = path,
+            Ok(Err(e)) => log_warn!("Failed to merge checkpoints for {}: {}", folder_path, e),
+            Err(e) => log_warn!("Checkpoint merge task failed: {}", e),
+        }
+    }
+    let has_audio = audio_path.is_file();
+
+    let mut metadata = read_metadata(&folder);
+    let created_at = metadata
+        .as_ref()
+        .and_then(|m| DateTime::parse_from_rfc3339(&m.created_at).ok())
+        .map(|dt| dt.with_timezone(&Utc));
+
+    // 2. Meeting already saved before the crash - only finalize the folder
+    let meeting_id = if let Some(meeting) = existing {
+        log_info!("Folder {} already belongs to meeting {}", folder_path, meeting.id);
+        meeting.id
+    } else {
+        // 3. Rebuild transcripts, filling gaps if requested
+        let base_time = created_at.unwrap_or_else(Utc::now);
+        let mut segments = to_api_segments(&read_transcript_segments(&folder), base_time);
+
+        if retranscribe && has_audio {
+            let recovered = transcribe_gaps(app, folder_path, &audio_path, &segments, base_time).await?;
+            segments.extend(recovered);
+            segments.sort_by(|a, b| {
+                a.audio_start_time
+                    .unwrap_or(0.0)
+                    .partial_cmp(&b.audio_start_time.unwrap_or(0.0))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+        } else if retranscribe {
+            log_warn!("No audio recovered for {}, skipping re-transcription", folder_path);
+        }
+
+        if segments.is_empty() && !has_audio {
+            return Err("Nothing to recover: no audio and no transcripts".to_string());
+        }
+
+        // 4. Save the meeting
+        emit_progress(app, folder_path, RecoveryStage::Saving, 90, "Saving meeting");
+        let meeting_id = TranscriptsRepository::save_transcript(
+            pool,
+            &session.meeting_name,
+            &segments,
+            Some(folder_path.to_string()),
+        )
+        .await
+        .map_err(|e| format!("Failed to save recovered meeting: {}", e))?;
+
+        if let Some(created_at) = created_at {
+            if let Err(e) = MeetingsRepository::update_meeting_created_at(pool, &meeting_id, created_at).await {
+                log_warn!("Failed to restore creation time for {}: {}", meeting_id, e);
+            }
+        }
+        meeting_id
+    };
+
+    // 5. Mark the folder as finalized so it isn't offered again
+    if let Some(metadata) = metadata.as_mut() {
+        metadata.status = "completed".to_string();
+        metadata.meeting_id = Some(meeting_id.clone());
+        metadata.completed_at = Some(Utc::now().to_rfc3339());
+        if metadata.duration_seconds.is_none() {
+            metadata.duration_seconds = session.transcribed_until;
+        }
+        if let Err(e) = write_metadata(&folder, metadata) {
+            log_warn!("Failed to update metadata for {}: {}", folder_path, e);
+        }
+    }
+
+    Ok(meeting_id)
+}
+
+/// Transcribe the parts of the recording that have no transcript
+async fn transcribe_gaps<R: Runtime>(
+    app: &AppHandle<R>,
+    folder_path: &str,
+    audio_path: &Path,
+    segments: &[TranscriptSegment],
+    base_time: DateTime<Utc>,
+) -> Result<Vec<TranscriptSegment>, String> {
+    validate_transcription_model_ready(app).await?;
+    let engine = get_or_init_transcription_engine(app).await?;
+
+    emit_progress(app, folder_path, RecoveryStage::Transcribing, 10, "Decoding recovered audio");
+    let samples_16k = decode_for_transcription(audio_path).await?;
+    let total_duration = samples_16k.len() as f64 / IMPORT_SAMPLE_RATE as f64;
+
+    let covered: Vec<(f64, f64)> = segments
+        .iter()
+        .filter_map(|s| Some((s.audio_start_time?, s.audio_end_time?)))
+        .collect();
+    let ranges = untranscribed_ranges(&covered, total_duration, MIN_UNTRANSCRIBED_SECONDS);
+    if ranges.is_empty() {
+        log_info!("All recovered audio already has transcripts");
+        return Ok(Vec::new());
+    }
+    log_info!(
+        "🎙️ Re-transcribing {} untranscribed range(s) with {}",
+        ranges.len(),
+        engine.provider_name()
+    );
+
+    let mut speech_segments = Vec::new();
+    for (start, end) in ranges {
+        let from = (start * IMPORT_SAMPLE_RATE as f64) as usize;
+        let to = ((end * IMPORT_SAMPLE_RATE as f64) as usize).min(samples_16k.len());
+        if from >= to {
+            continue;
+        }
+        speech_segments.extend(detect_speech_segments(samples_16k[from..to].to_vec(), start).await?);
+    }
+
+    crate::glossary::start_session(app).await;
+    crate::transcript_rules::start_session(app).await;
+    let mut recovered = transcribe_segments(&engine, speech_segments, base_time, |i, total| {
+        emit_progress(
+            app,
+            folder_path,
+            RecoveryStage::Transcribing,
+            10 + (i * 80 / total.max(1)) as u32,
+            format!("Transcribing segment {} of {}", i + 1, total),
+        );
+        Ok(())
+    })
+    .await?;
+
+    for segment in &mut recovered {
+        segment.id = format!("recovered-{}", segment.id);
+    }
+    log_info!("Recovered {} transcript segment(s) from audio", recovered.len());
+    Ok(recovered)
+}
+
+/// Dismiss an interrupted recording. With `delete_files` the folder is removed,
+/// otherwise it is marked as discarded and kept on disk.
+#[tauri::command]
+pub async fn api_discard_recovery_session(
+    folder_path: String,
+    delete_files: Option<bool>,
+) -> Result<(), String> {
+    log_info!(
+        "api_discard_recovery_session called for: {}, delete_files: {:?}",
+        folder_path,
+        delete_files
+    );
+
+    let folder = PathBuf::from(&folder_path);
+    // Only ever touch folders that look like an interrupted recording
+    if inspect_session(&folder).is_none() {
+        return Err(format!("No interrupted recording found in {}", folder_path));
+    }
+
+    if delete_files.unwrap_or(false) {
+        std::fs::remove_dir_all(&folder)
+            .map_err(|e| format!("Failed to delete recording folder: {}", e))?;
+        log_info!("Deleted interrupted recording {}", folder_path);
+        return Ok(());
+    }
+
+    match read_metadata(&folder) {
+        Some(mut metadata) => {
+            metadata.status = DISCARDED_STATUS.to_string();
+            write_metadata(&folder, &metadata)
+                .map_err(|e| format!("Failed to update metadata: {}", e))?;
+        }
+        None => {
+            // No metadata to mark - drop the checkpoints so the folder isn't flagged again
+            std::fs::remove_dir_all(folder.join(CHECKPOINTS_DIR))
+                .map_err(|e| format!("Failed to remove checkpoints: {}", e))?;
+        }
+    }
+    log_info!("Discarded interrupted recording {}", folder_path);
+    Ok(())
+}
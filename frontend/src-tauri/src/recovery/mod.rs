@@ -0,0 +1,381 @@
+/// Crash Recovery Module
+///
+/// Finds recordings that never finalized (app crash, power loss) and turns them
+/// back into meetings. A live recording writes 30s checkpoints to
+/// `.checkpoints/audio_chunk_NNN.mp4`, an incremental `transcripts.json` and a
+/// `metadata.json` whose status stays "recording" until the recording stops.
+/// Recovery: merge checkpoints -> audio.mp4, rebuild the meeting from
+/// transcripts.json, optionally re-transcribe audio that has no transcript.
+
+pub mod commands;
+
+use crate::api::TranscriptSegment as ApiTranscriptSegment;
+use crate::audio::incremental_saver::merge_audio_files;
+use crate::audio::recording_saver::{MeetingMetadata, TranscriptSegment};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration, Utc};
+use log::{info, warn};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+pub const CHECKPOINTS_DIR: &str = ".checkpoints";
+const CHECKPOINT_PREFIX: &str = "audio_chunk_";
+
+/// Status written to metadata.json when the user dismisses a session
+pub const DISCARDED_STATUS: &str = "discarded";
+
+/// Gaps shorter than this are normal pauses between sentences, not lost audio
+pub const MIN_UNTRANSCRIBED_SECONDS: f64 = 5.0;
+
+/// An interrupted recording found on disk
+#[derive(Debug, Clone, Serialize)]
+pub struct RecoverableSession {
+    pub folder_path: String,
+    pub meeting_name: String,
+    pub created_at: Option<String>,
+    /// metadata.json status, None if the file is missing or unreadable
+    pub status: Option<String>,
+    pub checkpoint_count: usize,
+    pub has_audio: bool,
+    pub transcript_count: usize,
+    /// End of the last transcribed segment (seconds from recording start)
+    pub transcribed_until: Option<f64>,
+}
+
+pub fn read_metadata(folder: &Path) -> Option<MeetingMetadata> {
+    let content = std::fs::read_to_string(folder.join("metadata.json")).ok()?;
+    match serde_json::from_str(&content) {
+        Ok(metadata) => Some(metadata),
+        Err(e) => {
+            warn!("Unreadable metadata.json in {}: {}", folder.display(), e);
+            None
+        }
+    }
+}
+
+/// Write metadata.json (atomic write with temp file, same as RecordingSaver)
+pub fn write_metadata(folder: &Path, metadata: &MeetingMetadata) -> Result<()> {
+    let metadata_path = folder.join("metadata.json");
+    let temp_path = folder.join(".metadata.json.tmp");
+
+    std::fs::write(&temp_path, serde_json::to_string_pretty(metadata)?)?;
+    std::fs::rename(&temp_path, &metadata_path)?;
+    Ok(())
+}
+
+/// Checkpoint files in recording order
+pub fn list_checkpoints(folder: &Path) -> Vec<PathBuf> {
+    let entries = match std::fs::read_dir(folder.join(CHECKPOINTS_DIR)) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut checkpoints: Vec<(u32, PathBuf)> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter_map(|path| {
+            let index = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .and_then(|s| s.strip_prefix(CHECKPOINT_PREFIX))
+                .and_then(|n| n.parse::<u32>().ok())?;
+            Some((index, path))
+        })
+        .collect();
+
+    checkpoints.sort_by_key(|(index, _)| *index);
+    checkpoints.into_iter().map(|(_, path)| path).collect()
+}
+
+/// Segments from the incremental transcripts.json.
+/// Falls back to the temp file if the crash hit between write and rename.
+pub fn read_transcript_segments(folder: &Path) -> Vec<TranscriptSegment> {
+    for name in ["transcripts.json", ".transcripts.json.tmp"] {
+        let content = match std::fs::read_to_string(folder.join(name)) {
+            Ok(content) => content,
+            Err(_) => continue,
+        };
+        match parse_transcripts_json(&content) {
+            Ok(segments) => return segments,
+            Err(e) => warn!("Unreadable {} in {}: {}", name, folder.display(), e),
+        }
+    }
+    Vec::new()
+}
+
+fn parse_transcripts_json(content: &str) -> Result<Vec<TranscriptSegment>> {
+    let mut json: serde_json::Value = serde_json::from_str(content)?;
+    let segments = json
+        .get_mut("segments")
+        .map(serde_json::Value::take)
+        .ok_or_else(|| anyhow!("missing segments"))?;
+    Ok(serde_json::from_value(segments)?)
+}
+
+/// Describe a meeting folder if it holds an interrupted recording
+pub fn inspect_session(folder: &Path) -> Option<RecoverableSession> {
+    let metadata = read_metadata(folder);
+    let status = metadata.as_ref().map(|m| m.status.clone());
+    if status.as_deref() == Some(DISCARDED_STATUS) {
+        return None;
+    }
+
+    let checkpoint_count = list_checkpoints(folder).len();
+    if status.as_deref() != Some("recording") && checkpoint_count == 0 {
+        return None;
+    }
+
+    let segments = read_transcript_segments(folder);
+    let meeting_name = metadata
+        .as_ref()
+        .and_then(|m| m.meeting_name.clone())
+        .or_else(|| folder.file_name().map(|n| n.to_string_lossy().to_string()))
+        .unwrap_or_default();
+
+    Some(RecoverableSession {
+        folder_path: folder.to_string_lossy().to_string(),
+        meeting_name,
+        created_at: metadata.as_ref().map(|m| m.created_at.clone()),
+        status,
+        checkpoint_count,
+        has_audio: folder.join("audio.mp4").is_file(),
+        transcript_count: segments.len(),
+        transcribed_until: segments
+            .iter()
+            .map(|s| s.audio_end_time)
+            .fold(None, |max: Option<f64>, end| Some(max.map_or(end, |m| m.max(end)))),
+    })
+}
+
+/// Scan the recordings folders for interrupted sessions.
+/// `exclude` is the folder of the recording currently in progress, if any.
+pub fn scan_for_sessions(base_folders: &[PathBuf], exclude: Option<&Path>) -> Vec<RecoverableSession> {
+    let mut sessions = Vec::new();
+    let mut seen = Vec::new();
+
+    for base in base_folders {
+        let entries = match std::fs::read_dir(base) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        for entry in entries.filter_map(|e| e.ok()) {
+            let folder = entry.path();
+            if !folder.is_dir() {
+                continue;
+            }
+            let canonical = folder.canonicalize().unwrap_or_else(|_| folder.clone());
+            if seen.contains(&canonical)
+                || exclude.map_or(false, |ex| {
+                    ex.canonicalize().unwrap_or_else(|_| ex.to_path_buf()) == canonical
+                })
+            {
+                continue;
+            }
+            seen.push(canonical);
+
+            if let Some(session) = inspect_session(&folder) {
+                sessions.push(session);
+            }
+        }
+    }
+
+    sessions.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    sessions
+}
+
+/// Merge leftover checkpoints into `audio.mp4` and remove `.checkpoints`.
+///
+/// The last checkpoint may be truncated if the crash hit while it was being
+/// encoded; if the full merge fails, retry without it.
+pub fn merge_checkpoints(folder: &Path) -> Result<PathBuf> {
+    let checkpoints = list_checkpoints(folder);
+    if checkpoints.is_empty() {
+        return Err(anyhow!("No checkpoints found in {}", folder.display()));
+    }
+
+    let checkpoints_dir = folder.join(CHECKPOINTS_DIR);
+    let output = folder.join("audio.mp4");
+
+    if let Err(e) = merge_audio_files(&checkpoints, &checkpoints_dir, &output) {
+        if checkpoints.len() < 2 {
+            return Err(e);
+        }
+        warn!("Merging all checkpoints failed ({}), retrying without the last one", e);
+        merge_audio_files(&checkpoints[..checkpoints.len() - 1], &checkpoints_dir, &output)?;
+    }
+
+    if let Err(e) = std::fs::remove_dir_all(&checkpoints_dir) {
+        warn!("Failed to clean up checkpoints directory: {}", e);
+    }
+
+    info!("✅ Recovered {} checkpoints into {}", checkpoints.len(), output.display());
+    Ok(output)
+}
+
+/// Time ranges of the recording (seconds) not covered by any transcript
+/// segment and at least `min_gap` long.
+pub fn untranscribed_ranges(covered: &[(f64, f64)], total_duration: f64, min_gap: f64) -> Vec<(f64, f64)> {
+    let mut covered = covered.to_vec();
+    covered.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+    let mut ranges = Vec::new();
+    let mut cursor = 0.0;
+    for (start, end) in covered {
+        if start - cursor >= min_gap {
+            ranges.push((cursor, start.min(total_duration)));
+        }
+        cursor = f64::max(cursor, end);
+    }
+    if total_duration - cursor >= min_gap {
+        ranges.push((cursor, total_duration));
+    }
+
+    ranges.retain(|(start, end)| end > start);
+    ranges
+}
+
+/// Convert recorder segments into the shape `TranscriptsRepository::save_transcript` takes.
+/// `created_at` is the wall-clock start of the recording.
+pub fn to_api_segments(segments: &[TranscriptSegment], created_at: DateTime<Utc>) -> Vec<ApiTranscriptSegment> {
+    segments
+        .iter()
+        .filter(|s| !s.text.trim().is_empty())
+        .map(|s| ApiTranscriptSegment {
+            id: s.id.clone(),
+            text: s.text.clone(),
+            timestamp: (created_at + Duration::milliseconds((s.audio_start_time * 1000.0) as i64))
+                .format("%H:%M:%S")
+                .to_string(),
+            audio_start_time: Some(s.audio_start_time),
+            audio_end_time: Some(s.audio_end_time),
+            duration: Some(s.duration),
+            words: s.words.clone(),
+            speaker_id: s.speaker_id.clone(),
+            channel: s.channel,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::recording_saver::DeviceInfo;
+    use tempfile::tempdir;
+
+    fn metadata(status: &str) -> MeetingMetadata {
+        MeetingMetadata {
+            version: "1.0".to_string(),
+            meeting_id: None,
+            meeting_name: Some("Standup".to_string()),
+            created_at: "2025-12-01T10:00:00+00:00".to_string(),
+            completed_at: None,
+            duration_seconds: None,
+            devices: DeviceInfo {
+                microphone: None,
+                system_audio: None,
+            },
+            audio_file: "audio.mp4".to_string(),
+            transcript_file: "transcripts.json".to_string(),
+            sample_rate: 48000,
+            status: status.to_string(),
+            silence_trim: None,
+        }
+    }
+
+    fn segment(text: &str, start: f64, end: f64) -> TranscriptSegment {
+        TranscriptSegment {
+            id: format!("seg-{}", start),
+            text: text.to_string(),
+            audio_start_time: start,
+            audio_end_time: end,
+            duration: end - start,
+            display_time: "[00:00]".to_string(),
+            confidence: 0.9,
+            sequence_id: start as u64,
+            words: Vec::new(),
+            speaker_id: None,
+            channel: None,
+        }
+    }
+
+    #[test]
+    fn test_inspect_interrupted_session() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("Standup_2025-12-01_10-00-00");
+        std::fs::create_dir_all(folder.join(CHECKPOINTS_DIR)).unwrap();
+        write_metadata(&folder, &metadata("recording")).unwrap();
+        for name in ["audio_chunk_010.mp4", "audio_chunk_002.mp4", "concat_list.txt"] {
+            std::fs::write(folder.join(CHECKPOINTS_DIR).join(name), b"").unwrap();
+        }
+        let json = serde_json::json!({
+            "version": "1.0",
+            "segments": [segment("hello", 0.5, 2.0), segment("world", 3.0, 4.5)],
+        });
+        std::fs::write(folder.join("transcripts.json"), json.to_string()).unwrap();
+
+        let checkpoints = list_checkpoints(&folder);
+        assert_eq!(checkpoints.len(), 2);
+        assert!(checkpoints[0].ends_with("audio_chunk_002.mp4"));
+
+        let session = inspect_session(&folder).unwrap();
+        assert_eq!(session.meeting_name, "Standup");
+        assert_eq!(session.checkpoint_count, 2);
+        assert_eq!(session.transcript_count, 2);
+        assert_eq!(session.transcribed_until, Some(4.5));
+        assert!(!session.has_audio);
+
+        let sessions = scan_for_sessions(&[dir.path().to_path_buf()], None);
+        assert_eq!(sessions.len(), 1);
+        assert!(scan_for_sessions(&[dir.path().to_path_buf()], Some(&folder)).is_empty());
+    }
+
+    #[test]
+    fn test_completed_and_discarded_sessions_are_ignored() {
+        let dir = tempdir().unwrap();
+        let completed = dir.path().join("done");
+        std::fs::create_dir_all(&completed).unwrap();
+        write_metadata(&completed, &metadata("completed")).unwrap();
+        assert!(inspect_session(&completed).is_none());
+
+        let discarded = dir.path().join("discarded");
+        std::fs::create_dir_all(discarded.join(CHECKPOINTS_DIR)).unwrap();
+        std::fs::write(discarded.join(CHECKPOINTS_DIR).join("audio_chunk_000.mp4"), b"").unwrap();
+        write_metadata(&discarded, &metadata(DISCARDED_STATUS)).unwrap();
+        assert!(inspect_session(&discarded).is_none());
+    }
+
+    #[test]
+    fn test_transcripts_fall_back_to_temp_file() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("transcripts.json"), "{ truncated").unwrap();
+        let json = serde_json::json!({ "segments": [segment("saved", 1.0, 2.0)] });
+        std::fs::write(dir.path().join(".transcripts.json.tmp"), json.to_string()).unwrap();
+
+        let segments = read_transcript_segments(dir.path());
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].text, "saved");
+    }
+
+    #[test]
+    fn test_untranscribed_ranges() {
+        let covered = vec![(12.0, 20.0), (0.5, 10.0), (21.0, 30.0)];
+        assert_eq!(untranscribed_ranges(&covered, 30.0, 5.0), Vec::<(f64, f64)>::new());
+        assert_eq!(untranscribed_ranges(&covered, 95.0, 5.0), vec![(30.0, 95.0)]);
+        assert_eq!(
+            untranscribed_ranges(&[(40.0, 50.0)], 50.0, 5.0),
+            vec![(0.0, 40.0)]
+        );
+        assert_eq!(untranscribed_ranges(&[], 60.0, 5.0), vec![(0.0, 60.0)]);
+    }
+
+    #[test]
+    fn test_to_api_segments() {
+        let created_at = DateTime::parse_from_rfc3339("2025-12-01T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let segments = to_api_segments(&[segment("  ", 0.0, 1.0), segment("hi", 75.0, 76.0)], created_at);
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].timestamp, "10:01:15");
+        assert_eq!(segments[0].audio_start_time, Some(75.0));
+    }
+}
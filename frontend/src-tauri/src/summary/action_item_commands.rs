@@ -0,0 +1,100 @@
+use crate::database::models::{ActionItem, ActionItemWithMeeting};
+use crate::database::repositories::{
+    action_item::ActionItemsRepository, setting::SettingsRepository,
+};
+use crate::state::AppState;
+use crate::summary::action_items;
+use crate::summary::llm_client::CompletionParams;
+use log::{error as log_error, info as log_info};
+use tauri::State;
+
+/// Open action items of every meeting, earliest due date first
+#[tauri::command]
+pub async fn api_list_open_action_items(
+    state: State<'_, AppState>,
+) -> Result<Vec<ActionItemWithMeeting>, String> {
+    ActionItemsRepository::list_open(state.db_manager.pool())
+        .await
+        .map_err(|e| {
+            log_error!("Failed to load open action items: {}", e);
+            format!("Failed to load open action items: {}", e)
+        })
+}
+
+#[tauri::command]
+pub async fn api_get_meeting_action_items(
+    state: State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Vec<ActionItem>, String> {
+    ActionItemsRepository::list_for_meeting(state.db_manager.pool(), &meeting_id)
+        .await
+        .map_err(|e| {
+            log_error!("Failed to load action items for {}: {}", meeting_id, e);
+            format!("Failed to load action items: {}", e)
+        })
+}
+
+/// Marks an action item "open" or "done"
+#[tauri::command]
+pub async fn api_set_action_item_status(
+    state: State<'_, AppState>,
+    item_id: String,
+    status: String,
+) -> Result<ActionItem, String> {
+    log_info!(
+        "api_set_action_item_status called: {} -> {}",
+        item_id,
+        status
+    );
+    if status != "open" && status != "done" {
+        return Err(format!(
+            "Invalid status '{}', expected 'open' or 'done'",
+            status
+        ));
+    }
+
+    ActionItemsRepository::set_status(state.db_manager.pool(), &item_id, &status)
+        .await
+        .map_err(|e| {
+            log_error!("Failed to update action item {}: {}", item_id, e);
+            format!("Failed to update action item: {}", e)
+        })?
+        .ok_or_else(|| format!("Action item not found: {}", item_id))
+}
+
+/// Re-runs the extraction for a meeting, with the summary model from the
+/// settings unless a provider and model are given
+#[tauri::command]
+pub async fn api_extract_action_items(
+    state: State<'_, AppState>,
+    meeting_id: String,
+    model_provider: Option<String>,
+    model_name: Option<String>,
+) -> Result<Vec<ActionItem>, String> {
+    log_info!(
+        "api_extract_action_items called for meeting_id: {}",
+        meeting_id
+    );
+    let pool = state.db_manager.pool();
+
+    let config = SettingsRepository::get_model_config(pool)
+        .await
+        .map_err(|e| format!("Failed to read model settings: {}", e))?;
+    let completion_params = config
+        .as_ref()
+        .and_then(|c| c.completion_params.as_deref())
+        .and_then(|params| serde_json::from_str::<CompletionParams>(params).ok());
+    let provider = model_provider
+        .or_else(|| config.as_ref().map(|c| c.provider.clone()))
+        .ok_or("No summary provider configured")?;
+    let model = model_name
+        .or_else(|| config.map(|c| c.model))
+        .ok_or("No summary model configured")?;
+
+    action_items::extract_for_meeting(pool, &meeting_id, &provider, &model, completion_params)
+        .await
+        .map_err(|e| {
+            log_error!("Action item extraction failed for {}: {}", meeting_id, e);
+            e
+        })
+}
@@ -0,0 +1,157 @@
+use crate::database::repositories::setting::SettingsRepository;
+use crate::state::AppState;
+use crate::summary::backends::{backends, Capabilities};
+use crate::summary::failover::{self, FailoverEntry};
+use crate::summary::llm_client::LLMProvider;
+use crate::summary::service::{ResolvedProvider, SummaryService};
+use log::{error as log_error, info as log_info};
+use serde::Serialize;
+use tauri::State;
+
+/// A registered LLM backend, for the provider picker
+#[derive(Debug, Serialize)]
+pub struct LlmBackendInfo {
+    /// Provider id used in settings and commands (e.g. "azure-openai")
+    pub id: String,
+    pub name: String,
+    pub capabilities: Capabilities,
+    /// Stored endpoint, for backends that accept one
+    pub endpoint: Option<String>,
+}
+
+/// Lists the LLM backends summaries and chat can use
+#[tauri::command]
+pub async fn api_list_llm_backends(
+    state: State<'_, AppState>,
+) -> Result<Vec<LlmBackendInfo>, String> {
+    let pool = state.db_manager.pool();
+    let mut infos = Vec::new();
+    for backend in backends() {
+        let endpoint = SettingsRepository::get_endpoint(pool, backend.id())
+            .await
+            .map_err(|e| {
+                log_error!("Failed to load {} endpoint: {}", backend.display_name(), e);
+                format!("Failed to load endpoint: {}", e)
+            })?;
+        infos.push(LlmBackendInfo {
+            id: backend.id().to_string(),
+            name: backend.display_name().to_string(),
+            capabilities: backend.capabilities(),
+            endpoint,
+        });
+    }
+    Ok(infos)
+}
+
+/// Lists the models a provider offers, using its stored API key and endpoint
+///
+/// `api_key` and `endpoint` override the stored values, so models can be
+/// listed before the settings are saved.
+#[tauri::command]
+pub async fn api_list_llm_models(
+    state: State<'_, AppState>,
+    provider: String,
+    api_key: Option<String>,
+    endpoint: Option<String>,
+) -> Result<Vec<String>, String> {
+    let pool = state.db_manager.pool();
+    let llm_provider = LLMProvider::from_str(&provider)?;
+    let backend = llm_provider.backend();
+    if !backend.capabilities().list_models {
+        return Err(format!("{} can't list models", backend.display_name()));
+    }
+
+    let config = if api_key.is_some() || endpoint.is_some() {
+        let mut config = match SummaryService::resolve_provider(pool, &provider).await {
+            Ok(ResolvedProvider { config, .. }) => config,
+            Err(_) => Default::default(),
+        };
+        if let Some(api_key) = api_key {
+            config.api_key = api_key;
+        }
+        if let Some(endpoint) = endpoint.filter(|e| !e.trim().is_empty()) {
+            config.endpoint = Some(endpoint);
+        }
+        config
+    } else {
+        SummaryService::resolve_provider(pool, &provider)
+            .await?
+            .config
+    };
+
+    let client = reqwest::Client::new();
+    let models = backend.list_models(&client, &config).await.map_err(|e| {
+        log_error!("Failed to list {} models: {}", backend.display_name(), e);
+        format!("Failed to list models: {}", e)
+    })?;
+    log_info!("Listed {} {} models", models.len(), backend.display_name());
+    Ok(models)
+}
+
+/// Saves the endpoint for a provider that accepts one; None or blank clears it
+#[tauri::command]
+pub async fn api_save_llm_endpoint(
+    state: State<'_, AppState>,
+    provider: String,
+    endpoint: Option<String>,
+) -> Result<(), String> {
+    let llm_provider = LLMProvider::from_str(&provider)?;
+    let backend = llm_provider.backend();
+    if !backend.capabilities().custom_endpoint {
+        return Err(format!(
+            "{} has no configurable endpoint",
+            backend.display_name()
+        ));
+    }
+
+    let endpoint = endpoint
+        .map(|e| e.trim().trim_end_matches('/').to_string())
+        .filter(|e| !e.is_empty());
+    SettingsRepository::save_endpoint(state.db_manager.pool(), backend.id(), endpoint.as_deref())
+        .await
+        .map_err(|e| {
+            log_error!("Failed to save {} endpoint: {}", backend.display_name(), e);
+            format!("Failed to save endpoint: {}", e)
+        })?;
+    log_info!("Saved {} endpoint: {:?}", backend.display_name(), endpoint);
+    Ok(())
+}
+
+/// Fallback providers tried in order when the selected one keeps failing
+#[tauri::command]
+pub async fn api_get_llm_failover_chain(
+    state: State<'_, AppState>,
+) -> Result<Vec<FailoverEntry>, String> {
+    failover::load_failover_chain(state.db_manager.pool())
+        .await
+        .map_err(|e| {
+            log_error!("{}", e);
+            e
+        })
+}
+
+/// Saves the fallback providers; an empty list turns failover off
+///
+/// Entries are checked against the backend registry here, but their API
+/// keys and endpoints are only resolved when a job runs, so a fallback can
+/// be set up before its key is saved.
+#[tauri::command]
+pub async fn api_save_llm_failover_chain(
+    state: State<'_, AppState>,
+    chain: Vec<FailoverEntry>,
+) -> Result<Vec<FailoverEntry>, String> {
+    let chain = failover::validate_failover_chain(chain)?;
+    let json = if chain.is_empty() {
+        None
+    } else {
+        Some(serde_json::to_string(&chain).map_err(|e| e.to_string())?)
+    };
+    SettingsRepository::save_failover_chain(state.db_manager.pool(), json.as_deref())
+        .await
+        .map_err(|e| {
+            log_error!("Failed to save failover chain: {}", e);
+            format!("Failed to save failover chain: {}", e)
+        })?;
+    log_info!("Saved LLM failover chain with {} fallback(s)", chain.len());
+    Ok(chain)
+}
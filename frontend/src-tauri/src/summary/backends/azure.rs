@@ -0,0 +1,90 @@
+/// Azure OpenAI deployments
+///
+/// The configured endpoint is the resource URL
+/// (`https://<resource>.openai.azure.com`) and the model name is the
+/// deployment name. Deployments can't be listed with an API key alone, so
+/// the model has to be entered by hand.
+use super::backend::{BackendConfig, Capabilities, JsonMode, LlmBackend};
+use super::openai_format::json_headers;
+use crate::summary::llm_client::LLMProvider;
+use async_trait::async_trait;
+use reqwest::header::HeaderMap;
+use reqwest::Client;
+
+const API_VERSION: &str = "2024-10-21";
+
+pub struct AzureOpenAi;
+
+#[async_trait]
+impl LlmBackend for AzureOpenAi {
+    fn provider(&self) -> LLMProvider {
+        LLMProvider::AzureOpenAI
+    }
+
+    fn id(&self) -> &'static str {
+        "azure-openai"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Azure OpenAI"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            json_mode: JsonMode::Schema,
+            list_models: false,
+            requires_api_key: true,
+            requires_endpoint: true,
+            custom_endpoint: true,
+            local: false,
+        }
+    }
+
+    fn chat_endpoint(
+        &self,
+        config: &BackendConfig,
+        model: &str,
+    ) -> Result<(String, HeaderMap), String> {
+        let base = config.required_endpoint(self.display_name())?;
+        let url = format!(
+            "{}/openai/deployments/{}/chat/completions?api-version={}",
+            base, model, API_VERSION
+        );
+        let mut headers = json_headers()?;
+        headers.insert(
+            "api-key",
+            config
+                .api_key
+                .parse()
+                .map_err(|_| "Invalid API key format".to_string())?,
+        );
+        Ok((url, headers))
+    }
+
+    async fn list_models(
+        &self,
+        _client: &Client,
+        _config: &BackendConfig,
+    ) -> Result<Vec<String>, String> {
+        Err("Azure OpenAI deployments can't be listed; enter the deployment name".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chat_endpoint_uses_deployment() {
+        let config = BackendConfig::new("key", Some("https://team.openai.azure.com/".to_string()));
+        let (url, headers) = AzureOpenAi.chat_endpoint(&config, "gpt-4o-notes").unwrap();
+        assert_eq!(
+            url,
+            "https://team.openai.azure.com/openai/deployments/gpt-4o-notes/chat/completions?api-version=2024-10-21"
+        );
+        assert_eq!(headers["api-key"], "key");
+
+        let config = BackendConfig::new("key", None);
+        assert!(AzureOpenAi.chat_endpoint(&config, "gpt-4o-notes").is_err());
+    }
+}
@@ -0,0 +1,250 @@
+/// The `LlmBackend` trait and the types shared by its implementations
+use super::error::LlmError;
+use crate::summary::llm_client::{
+    ChatMessage, CompletionParams, LLMProvider, StreamUsage, SummaryResult,
+};
+use async_trait::async_trait;
+use reqwest::header::HeaderMap;
+use reqwest::Client;
+use serde::Serialize;
+use serde_json::Value;
+
+/// How a backend can be held to JSON output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JsonMode {
+    /// No JSON mode, the prompt has to ask for JSON
+    Prompt,
+    /// Any JSON object (`response_format: json_object`)
+    Object,
+    /// The reply is held to the schema (`response_format: json_schema`)
+    Schema,
+}
+
+/// What a backend supports and needs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Capabilities {
+    pub json_mode: JsonMode,
+    pub list_models: bool,
+    pub requires_api_key: bool,
+    /// Has no default endpoint, so one must be configured
+    pub requires_endpoint: bool,
+    /// Accepts a configured endpoint
+    pub custom_endpoint: bool,
+    /// Runs on the user's machine rather than a hosted API
+    pub local: bool,
+}
+
+/// API key and endpoint for a backend, from the settings table or a request
+#[derive(Debug, Clone, Default)]
+pub struct BackendConfig {
+    /// Empty when the backend doesn't need one
+    pub api_key: String,
+    pub endpoint: Option<String>,
+}
+
+impl BackendConfig {
+    pub fn new(api_key: impl Into<String>, endpoint: Option<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            endpoint: endpoint.filter(|e| !e.trim().is_empty()),
+        }
+    }
+
+    /// The configured endpoint without a trailing slash, or `default`
+    pub fn endpoint_or(&self, default: &str) -> String {
+        self.endpoint
+            .as_deref()
+            .unwrap_or(default)
+            .trim_end_matches('/')
+            .to_string()
+    }
+
+    /// The configured endpoint without a trailing slash, failing if there is none
+    pub fn required_endpoint(&self, display_name: &str) -> Result<String, String> {
+        self.endpoint
+            .as_deref()
+            .map(|e| e.trim_end_matches('/').to_string())
+            .ok_or_else(|| format!("{} endpoint not configured", display_name))
+    }
+}
+
+/// One completion call
+#[derive(Debug, Clone, Copy)]
+pub struct CompletionRequest<'a> {
+    pub model: &'a str,
+    /// The conversation, starting with the system message if there is one
+    pub messages: &'a [ChatMessage],
+    pub params: Option<&'a CompletionParams>,
+    /// Schema the reply must match, enforced as far as `Capabilities::json_mode` allows
+    pub json_schema: Option<&'a Value>,
+}
+
+/// Result of a streamed completion
+#[derive(Debug, Clone, Default)]
+pub struct StreamOutcome {
+    pub content: String,
+    pub usage: Option<StreamUsage>,
+    pub finish_reason: Option<String>,
+    /// Time to first token in microseconds, measured from sending the request
+    pub ttft_us: Option<u64>,
+    pub total_time_us: u64,
+}
+
+impl From<StreamOutcome> for SummaryResult {
+    fn from(outcome: StreamOutcome) -> Self {
+        SummaryResult {
+            content: outcome.content,
+            ttft_us: outcome.ttft_us,
+            total_time_us: outcome.total_time_us,
+            usage: outcome.usage,
+        }
+    }
+}
+
+/// An LLM provider the summary and chat features can talk to
+#[async_trait]
+pub trait LlmBackend: Send + Sync {
+    /// The provider this backend implements
+    fn provider(&self) -> LLMProvider;
+
+    /// Id used in settings and commands (e.g. "openai-compatible")
+    fn id(&self) -> &'static str;
+
+    /// Name used in logs and the UI
+    fn display_name(&self) -> &'static str;
+
+    fn capabilities(&self) -> Capabilities;
+
+    /// URL and headers for OpenAI-format chat completions
+    ///
+    /// Used by the default `complete` and `stream`; backends with their own
+    /// wire format override those instead.
+    fn chat_endpoint(
+        &self,
+        _config: &BackendConfig,
+        _model: &str,
+    ) -> Result<(String, HeaderMap), String> {
+        Err(format!(
+            "{} has no chat completions endpoint",
+            self.display_name()
+        ))
+    }
+
+    /// Runs a completion and returns the whole reply
+    ///
+    /// # Returns
+    /// SummaryResult with the trimmed reply and total_time_us; ttft_us is None
+    async fn complete(
+        &self,
+        client: &Client,
+        config: &BackendConfig,
+        request: CompletionRequest<'_>,
+    ) -> Result<SummaryResult, LlmError> {
+        let (url, headers) = self.chat_endpoint(config, request.model)?;
+        super::openai_format::complete(
+            client,
+            self.display_name(),
+            &url,
+            headers,
+            request,
+            self.capabilities().json_mode,
+        )
+        .await
+    }
+
+    /// Streams a completion, passing each piece of text to `on_token`
+    async fn stream(
+        &self,
+        client: &Client,
+        config: &BackendConfig,
+        request: CompletionRequest<'_>,
+        on_token: &mut (dyn FnMut(&str) + Send),
+    ) -> Result<StreamOutcome, LlmError> {
+        let (url, headers) = self.chat_endpoint(config, request.model)?;
+        super::openai_format::stream(
+            client,
+            self.display_name(),
+            &url,
+            headers,
+            request,
+            self.capabilities().json_mode,
+            on_token,
+        )
+        .await
+    }
+
+    /// Ids of the models available with this config
+    async fn list_models(
+        &self,
+        client: &Client,
+        config: &BackendConfig,
+    ) -> Result<Vec<String>, String>;
+
+    /// Context window of `model` in tokens, if the provider reports it
+    ///
+    /// Callers fall back to `context_window::known_context_window` on None.
+    async fn context_window(
+        &self,
+        _client: &Client,
+        _config: &BackendConfig,
+        _model: &str,
+    ) -> Result<Option<usize>, String> {
+        Ok(None)
+    }
+}
+
+/// Splits a server-sent event stream into the payloads of its `data:` lines
+///
+/// Bytes are buffered until an event is complete, so events split across
+/// network chunks come out whole.
+#[derive(Debug, Default)]
+pub struct SseDecoder {
+    buffer: String,
+}
+
+impl SseDecoder {
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<String> {
+        self.buffer
+            .push_str(&String::from_utf8_lossy(chunk).replace("\r\n", "\n"));
+
+        let mut payloads = Vec::new();
+        while let Some(end) = self.buffer.find("\n\n") {
+            let event: String = self.buffer.drain(..end + 2).collect();
+            payloads.extend(event.lines().filter_map(|line| {
+                line.strip_prefix("data:")
+                    .map(|data| data.strip_prefix(' ').unwrap_or(data).to_string())
+            }));
+        }
+        payloads
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sse_decoder_joins_split_events() {
+        let mut decoder = SseDecoder::default();
+        assert!(decoder.push(b"event: delta\ndata: {\"a\"").is_empty());
+        assert_eq!(
+            decoder.push(b": 1}\n\ndata:[DONE]\r\n\r\n: keep-alive\n\n"),
+            vec!["{\"a\": 1}".to_string(), "[DONE]".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_backend_config_endpoints() {
+        let config = BackendConfig::new("", Some("http://host:8080/".to_string()));
+        assert_eq!(config.endpoint_or("http://localhost"), "http://host:8080");
+        assert_eq!(config.required_endpoint("X").unwrap(), "http://host:8080");
+
+        let config = BackendConfig::new("key", Some(" ".to_string()));
+        assert_eq!(config.endpoint_or("http://localhost/"), "http://localhost");
+        assert_eq!(
+            config.required_endpoint("Azure OpenAI").unwrap_err(),
+            "Azure OpenAI endpoint not configured"
+        );
+    }
+}
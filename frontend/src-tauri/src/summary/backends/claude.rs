@@ -0,0 +1,401 @@
+/// Anthropic Messages API
+///
+/// Claude takes the system prompt as a separate field and streams typed
+/// events instead of OpenAI-style chunks. It has no JSON mode, so structured
+/// replies rely on the prompt.
+use super::backend::{
+    BackendConfig, Capabilities, CompletionRequest, JsonMode, LlmBackend, SseDecoder, StreamOutcome,
+};
+use super::error::LlmError;
+use super::openai_format::{get_json, json_headers, model_ids};
+use crate::summary::llm_client::{ChatMessage, LLMProvider, StreamUsage, SummaryResult};
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use reqwest::{header, Client};
+use serde::Deserialize;
+use std::time::Instant;
+use tracing::{info, warn};
+
+const API_URL: &str = "https://api.anthropic.com/v1";
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// Used when no `max_tokens` is configured; the API requires one
+const DEFAULT_MAX_TOKENS: u32 = 2048;
+
+#[derive(Deserialize, Debug)]
+struct ClaudeChatResponse {
+    content: Vec<ClaudeChatContent>,
+    usage: Option<ClaudeUsageDelta>,
+}
+
+#[derive(Deserialize, Debug)]
+struct ClaudeChatContent {
+    #[serde(default)]
+    text: String,
+}
+
+/// Claude-specific streaming event types
+#[derive(Deserialize, Debug)]
+#[serde(tag = "type")]
+enum ClaudeStreamEvent {
+    #[serde(rename = "message_start")]
+    MessageStart { message: ClaudeMessageStart },
+    #[serde(rename = "content_block_delta")]
+    ContentBlockDelta { delta: ClaudeContentDelta },
+    #[serde(rename = "message_delta")]
+    MessageDelta {
+        delta: ClaudeMessageDelta,
+        usage: ClaudeUsageDelta,
+    },
+    #[serde(rename = "error")]
+    Error { error: ClaudeError },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize, Debug)]
+struct ClaudeMessageStart {
+    id: String,
+    usage: ClaudeUsageDelta,
+}
+
+#[derive(Deserialize, Debug)]
+struct ClaudeContentDelta {
+    #[serde(default)]
+    text: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct ClaudeMessageDelta {
+    stop_reason: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct ClaudeUsageDelta {
+    input_tokens: Option<u32>,
+    output_tokens: Option<u32>,
+}
+
+fn usage(input: Option<u32>, output: Option<u32>) -> Option<StreamUsage> {
+    if input.is_none() && output.is_none() {
+        return None;
+    }
+    Some(StreamUsage {
+        prompt_tokens: input,
+        completion_tokens: output,
+        total_tokens: match (input, output) {
+            (Some(i), Some(o)) => Some(i + o),
+            _ => None,
+        },
+    })
+}
+
+#[derive(Deserialize, Debug)]
+struct ClaudeError {
+    #[serde(rename = "type", default)]
+    kind: String,
+    message: String,
+}
+
+impl ClaudeError {
+    /// Overload and internal errors can arrive mid-stream after a 200
+    fn into_llm_error(self) -> LlmError {
+        let message = format!("Claude error: {}", self.message);
+        match self.kind.as_str() {
+            "overloaded_error" | "api_error" => LlmError::transient(message),
+            _ => LlmError::fatal(message),
+        }
+    }
+}
+
+pub struct Claude;
+
+fn headers(api_key: &str) -> Result<header::HeaderMap, String> {
+    let mut headers = json_headers()?;
+    headers.insert(
+        "x-api-key",
+        api_key
+            .parse()
+            .map_err(|_| "Invalid API key format".to_string())?,
+    );
+    headers.insert(
+        "anthropic-version",
+        ANTHROPIC_VERSION
+            .parse()
+            .map_err(|_| "Invalid anthropic version".to_string())?,
+    );
+    Ok(headers)
+}
+
+/// Messages API body: system messages move to `system`, the rest stay in order
+fn body(request: &CompletionRequest<'_>, stream: bool) -> serde_json::Value {
+    let system_prompt = request
+        .messages
+        .iter()
+        .filter(|m| m.role == "system")
+        .map(|m| m.content.as_str())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    let messages: Vec<&ChatMessage> = request
+        .messages
+        .iter()
+        .filter(|m| m.role != "system")
+        .collect();
+
+    let params = request.params;
+    let mut body = serde_json::json!({
+        "model": request.model,
+        "max_tokens": params.and_then(|p| p.max_tokens).unwrap_or(DEFAULT_MAX_TOKENS),
+        "messages": messages,
+    });
+    if stream {
+        body["stream"] = serde_json::json!(true);
+    }
+    if !system_prompt.is_empty() {
+        body["system"] = serde_json::json!(system_prompt);
+    }
+    if let Some(temp) = params.and_then(|p| p.temperature) {
+        body["temperature"] = serde_json::json!(temp);
+    }
+    if let Some(p) = params.and_then(|p| p.top_p) {
+        body["top_p"] = serde_json::json!(p);
+    }
+    body
+}
+
+async fn send(
+    client: &Client,
+    api_key: &str,
+    body: &serde_json::Value,
+) -> Result<reqwest::Response, LlmError> {
+    let response = client
+        .post(format!("{}/messages", API_URL))
+        .headers(headers(api_key)?)
+        .json(body)
+        .send()
+        .await
+        .map_err(|e| {
+            LlmError::from_reqwest(&e, format!("Failed to send request to Claude: {}", e))
+        })?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let response_headers = response.headers().clone();
+        let error_body = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(LlmError::from_response(
+            status,
+            &response_headers,
+            format!("Claude API request failed: {}", error_body),
+        ));
+    }
+    Ok(response)
+}
+
+#[async_trait]
+impl LlmBackend for Claude {
+    fn provider(&self) -> LLMProvider {
+        LLMProvider::Claude
+    }
+
+    fn id(&self) -> &'static str {
+        "claude"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Claude"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            json_mode: JsonMode::Prompt,
+            list_models: true,
+            requires_api_key: true,
+            requires_endpoint: false,
+            custom_endpoint: false,
+            local: false,
+        }
+    }
+
+    async fn complete(
+        &self,
+        client: &Client,
+        config: &BackendConfig,
+        request: CompletionRequest<'_>,
+    ) -> Result<SummaryResult, LlmError> {
+        let request_start_time = Instant::now();
+        let body = body(&request, false);
+        info!(
+            "🐞 LLM Request to Claude: model={} (non-streaming)",
+            request.model
+        );
+        info!(
+            "🐞 Full request body: {}",
+            serde_json::to_string_pretty(&body).unwrap_or_default()
+        );
+
+        let chat_response = send(client, &config.api_key, &body)
+            .await?
+            .json::<ClaudeChatResponse>()
+            .await
+            .map_err(|e| {
+                LlmError::from_reqwest(&e, format!("Failed to parse LLM response: {}", e))
+            })?;
+
+        info!("🐞 LLM Response received from Claude");
+
+        let content = chat_response
+            .content
+            .first()
+            .ok_or("No content in LLM response")?
+            .text
+            .trim()
+            .to_string();
+
+        Ok(SummaryResult {
+            content,
+            ttft_us: None, // Non-streaming doesn't capture TTFT
+            total_time_us: request_start_time.elapsed().as_micros() as u64,
+            usage: chat_response
+                .usage
+                .and_then(|u| usage(u.input_tokens, u.output_tokens)),
+        })
+    }
+
+    async fn stream(
+        &self,
+        client: &Client,
+        config: &BackendConfig,
+        request: CompletionRequest<'_>,
+        on_token: &mut (dyn FnMut(&str) + Send),
+    ) -> Result<StreamOutcome, LlmError> {
+        let request_start_time = Instant::now();
+        let body = body(&request, true);
+        info!(
+            "🚀 Sending streaming request to Claude: {}/messages",
+            API_URL
+        );
+        info!(
+            "🐞 Full request body: {}",
+            serde_json::to_string_pretty(&body).unwrap_or_default()
+        );
+
+        let mut stream = send(client, &config.api_key, &body).await?.bytes_stream();
+        let mut decoder = SseDecoder::default();
+        let mut outcome = StreamOutcome::default();
+        let mut usage_input: Option<u32> = None;
+        let mut usage_output: Option<u32> = None;
+
+        while let Some(chunk_result) = stream.next().await {
+            let chunk = chunk_result
+                .map_err(|e| LlmError::from_reqwest(&e, format!("Claude stream error: {}", e)))?;
+            for data in decoder.push(&chunk) {
+                let event = match serde_json::from_str::<ClaudeStreamEvent>(&data) {
+                    Ok(event) => event,
+                    Err(e) => {
+                        warn!(
+                            "⚠️ Failed to parse Claude streaming event: {} | Data: {}",
+                            e, data
+                        );
+                        continue;
+                    }
+                };
+
+                match event {
+                    ClaudeStreamEvent::MessageStart { message } => {
+                        info!("📝 Claude message started: {}", message.id);
+                        usage_input = message.usage.input_tokens.or(usage_input);
+                    }
+                    ClaudeStreamEvent::ContentBlockDelta { delta } => {
+                        if delta.text.is_empty() {
+                            continue;
+                        }
+                        if outcome.ttft_us.is_none() {
+                            outcome.ttft_us = Some(request_start_time.elapsed().as_micros() as u64);
+                        }
+                        outcome.content.push_str(&delta.text);
+                        on_token(&delta.text);
+                    }
+                    ClaudeStreamEvent::MessageDelta { delta, usage } => {
+                        if let Some(reason) = delta.stop_reason {
+                            outcome.finish_reason = Some(reason);
+                        }
+                        usage_output = usage.output_tokens.or(usage_output);
+                    }
+                    ClaudeStreamEvent::Error { error } => {
+                        return Err(error.into_llm_error());
+                    }
+                    ClaudeStreamEvent::Other => {}
+                }
+            }
+        }
+
+        outcome.usage = usage(usage_input, usage_output);
+        outcome.total_time_us = request_start_time.elapsed().as_micros() as u64;
+        Ok(outcome)
+    }
+
+    async fn list_models(
+        &self,
+        client: &Client,
+        config: &BackendConfig,
+    ) -> Result<Vec<String>, String> {
+        let listing = get_json(
+            client,
+            &format!("{}/models?limit=1000", API_URL),
+            headers(&config.api_key)?,
+        )
+        .await?;
+        Ok(model_ids(&listing))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::summary::llm_client::CompletionParams;
+
+    #[test]
+    fn test_body_moves_system_prompt() {
+        let messages = vec![
+            ChatMessage {
+                role: "system".to_string(),
+                content: "Be brief.".to_string(),
+            },
+            ChatMessage {
+                role: "user".to_string(),
+                content: "Hi".to_string(),
+            },
+        ];
+        let params = CompletionParams {
+            max_tokens: Some(500),
+            ..Default::default()
+        };
+        let request = CompletionRequest {
+            model: "claude-x",
+            messages: &messages,
+            params: Some(&params),
+            json_schema: None,
+        };
+
+        let value = body(&request, true);
+        assert_eq!(value["system"], "Be brief.");
+        assert_eq!(value["max_tokens"], 500);
+        assert_eq!(value["stream"], true);
+        assert_eq!(
+            value["messages"],
+            serde_json::json!([{"role": "user", "content": "Hi"}])
+        );
+
+        let request = CompletionRequest {
+            params: None,
+            ..request
+        };
+        let value = body(&request, false);
+        assert_eq!(value["max_tokens"], DEFAULT_MAX_TOKENS);
+        assert!(value.get("stream").is_none());
+        assert!(value.get("temperature").is_none());
+    }
+}
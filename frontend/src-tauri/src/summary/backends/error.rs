@@ -0,0 +1,136 @@
+/// Errors from completion calls, classified for retries
+///
+/// Rate limits (429), server errors (5xx), timeouts and dropped connections
+/// are worth retrying; anything else (bad key, unknown model, malformed
+/// request) fails the same way every time.
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::StatusCode;
+use std::fmt;
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+pub struct LlmError {
+    pub message: String,
+    /// HTTP status of the failed response, if there was one
+    pub status: Option<u16>,
+    /// Wait the server asked for with `Retry-After`
+    pub retry_after: Option<Duration>,
+    pub retryable: bool,
+}
+
+impl LlmError {
+    /// An error that won't go away by retrying
+    pub fn fatal(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            status: None,
+            retry_after: None,
+            retryable: false,
+        }
+    }
+
+    /// A transient error, e.g. an overloaded server reported mid-stream
+    pub fn transient(message: impl Into<String>) -> Self {
+        Self {
+            retryable: true,
+            ..Self::fatal(message)
+        }
+    }
+
+    /// Error for a non-success HTTP response
+    pub fn from_response(status: StatusCode, headers: &HeaderMap, message: String) -> Self {
+        Self {
+            message,
+            status: Some(status.as_u16()),
+            retry_after: headers
+                .get(RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(parse_retry_after),
+            retryable: status == StatusCode::TOO_MANY_REQUESTS
+                || status == StatusCode::REQUEST_TIMEOUT
+                || status.is_server_error(),
+        }
+    }
+
+    /// Error for a request that got no response or lost it halfway
+    pub fn from_reqwest(error: &reqwest::Error, message: String) -> Self {
+        Self {
+            message,
+            status: error.status().map(|s| s.as_u16()),
+            retry_after: None,
+            retryable: error.is_timeout() || error.is_connect() || error.is_body(),
+        }
+    }
+}
+
+/// `Retry-After` as delay-seconds or an HTTP date
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let wait = at.with_timezone(&chrono::Utc) - chrono::Utc::now();
+    Some(wait.to_std().unwrap_or(Duration::ZERO))
+}
+
+impl fmt::Display for LlmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for LlmError {}
+
+impl From<String> for LlmError {
+    fn from(message: String) -> Self {
+        Self::fatal(message)
+    }
+}
+
+impl From<&str> for LlmError {
+    fn from(message: &str) -> Self {
+        Self::fatal(message)
+    }
+}
+
+impl From<LlmError> for String {
+    fn from(error: LlmError) -> Self {
+        error.message
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_response_classification() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, "12".parse().unwrap());
+        let error =
+            LlmError::from_response(StatusCode::TOO_MANY_REQUESTS, &headers, "slow down".into());
+        assert!(error.retryable);
+        assert_eq!(error.status, Some(429));
+        assert_eq!(error.retry_after, Some(Duration::from_secs(12)));
+
+        let error =
+            LlmError::from_response(StatusCode::BAD_GATEWAY, &HeaderMap::new(), String::new());
+        assert!(error.retryable);
+        assert_eq!(error.retry_after, None);
+
+        let error =
+            LlmError::from_response(StatusCode::UNAUTHORIZED, &HeaderMap::new(), String::new());
+        assert!(!error.retryable);
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after(" 3 "), Some(Duration::from_secs(3)));
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon"), None);
+    }
+}
@@ -0,0 +1,108 @@
+/// Google Gemini through its OpenAI-compatible endpoint
+///
+/// Completions and the model listing go through `/v1beta/openai`. Context
+/// windows come from the native model resource, which reports
+/// `inputTokenLimit`.
+use super::backend::{BackendConfig, Capabilities, JsonMode, LlmBackend};
+use super::openai_format::{bearer_headers, get_json, json_headers, model_ids};
+use crate::summary::llm_client::LLMProvider;
+use async_trait::async_trait;
+use reqwest::header::HeaderMap;
+use reqwest::Client;
+
+const API_URL: &str = "https://generativelanguage.googleapis.com/v1beta";
+
+pub struct Gemini;
+
+/// Listing ids look like "models/gemini-2.0-flash"; requests take either form
+fn model_name(model: &str) -> &str {
+    model.strip_prefix("models/").unwrap_or(model)
+}
+
+#[async_trait]
+impl LlmBackend for Gemini {
+    fn provider(&self) -> LLMProvider {
+        LLMProvider::Gemini
+    }
+
+    fn id(&self) -> &'static str {
+        "gemini"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Gemini"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            json_mode: JsonMode::Schema,
+            list_models: true,
+            requires_api_key: true,
+            requires_endpoint: false,
+            custom_endpoint: false,
+            local: false,
+        }
+    }
+
+    fn chat_endpoint(
+        &self,
+        config: &BackendConfig,
+        _model: &str,
+    ) -> Result<(String, HeaderMap), String> {
+        Ok((
+            format!("{}/openai/chat/completions", API_URL),
+            bearer_headers(&config.api_key)?,
+        ))
+    }
+
+    async fn list_models(
+        &self,
+        client: &Client,
+        config: &BackendConfig,
+    ) -> Result<Vec<String>, String> {
+        let listing = get_json(
+            client,
+            &format!("{}/openai/models", API_URL),
+            bearer_headers(&config.api_key)?,
+        )
+        .await?;
+        Ok(model_ids(&listing)
+            .iter()
+            .map(|id| model_name(id).to_string())
+            .collect())
+    }
+
+    async fn context_window(
+        &self,
+        client: &Client,
+        config: &BackendConfig,
+        model: &str,
+    ) -> Result<Option<usize>, String> {
+        let mut headers = json_headers()?;
+        headers.insert(
+            "x-goog-api-key",
+            config
+                .api_key
+                .parse()
+                .map_err(|_| "Invalid API key format".to_string())?,
+        );
+        let info = get_json(
+            client,
+            &format!("{}/models/{}", API_URL, model_name(model)),
+            headers,
+        )
+        .await?;
+        Ok(info["inputTokenLimit"].as_u64().map(|n| n as usize))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_model_name() {
+        assert_eq!(model_name("models/gemini-2.0-flash"), "gemini-2.0-flash");
+        assert_eq!(model_name("gemini-2.5-pro"), "gemini-2.5-pro");
+    }
+}
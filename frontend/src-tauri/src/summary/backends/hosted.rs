@@ -0,0 +1,240 @@
+/// Hosted APIs that speak the OpenAI format
+///
+/// OpenAI, Groq, OpenRouter and Mistral differ only in base URL, JSON mode
+/// and where their model listing keeps the context window. OpenAI's listing
+/// has none, so its models use the static table.
+use super::backend::{BackendConfig, Capabilities, JsonMode, LlmBackend};
+use super::openai_format::{bearer_headers, get_json, model_field, model_ids};
+use crate::summary::llm_client::LLMProvider;
+use async_trait::async_trait;
+use reqwest::header::HeaderMap;
+use reqwest::Client;
+
+const OPENAI_URL: &str = "https://api.openai.com/v1";
+const GROQ_URL: &str = "https://api.groq.com/openai/v1";
+const OPENROUTER_URL: &str = "https://openrouter.ai/api/v1";
+const MISTRAL_URL: &str = "https://api.mistral.ai/v1";
+
+fn hosted(json_mode: JsonMode) -> Capabilities {
+    Capabilities {
+        json_mode,
+        list_models: true,
+        requires_api_key: true,
+        requires_endpoint: false,
+        custom_endpoint: false,
+        local: false,
+    }
+}
+
+async fn listing(
+    client: &Client,
+    base: &str,
+    config: &BackendConfig,
+) -> Result<serde_json::Value, String> {
+    get_json(
+        client,
+        &format!("{}/models", base),
+        bearer_headers(&config.api_key)?,
+    )
+    .await
+}
+
+pub struct OpenAi;
+
+#[async_trait]
+impl LlmBackend for OpenAi {
+    fn provider(&self) -> LLMProvider {
+        LLMProvider::OpenAI
+    }
+
+    fn id(&self) -> &'static str {
+        "openai"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "OpenAI"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        hosted(JsonMode::Schema)
+    }
+
+    fn chat_endpoint(
+        &self,
+        config: &BackendConfig,
+        _model: &str,
+    ) -> Result<(String, HeaderMap), String> {
+        Ok((
+            format!("{}/chat/completions", OPENAI_URL),
+            bearer_headers(&config.api_key)?,
+        ))
+    }
+
+    async fn list_models(
+        &self,
+        client: &Client,
+        config: &BackendConfig,
+    ) -> Result<Vec<String>, String> {
+        Ok(model_ids(&listing(client, OPENAI_URL, config).await?))
+    }
+}
+
+pub struct Groq;
+
+#[async_trait]
+impl LlmBackend for Groq {
+    fn provider(&self) -> LLMProvider {
+        LLMProvider::Groq
+    }
+
+    fn id(&self) -> &'static str {
+        "groq"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Groq"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        hosted(JsonMode::Object)
+    }
+
+    fn chat_endpoint(
+        &self,
+        config: &BackendConfig,
+        _model: &str,
+    ) -> Result<(String, HeaderMap), String> {
+        Ok((
+            format!("{}/chat/completions", GROQ_URL),
+            bearer_headers(&config.api_key)?,
+        ))
+    }
+
+    async fn list_models(
+        &self,
+        client: &Client,
+        config: &BackendConfig,
+    ) -> Result<Vec<String>, String> {
+        Ok(model_ids(&listing(client, GROQ_URL, config).await?))
+    }
+
+    async fn context_window(
+        &self,
+        client: &Client,
+        config: &BackendConfig,
+        model: &str,
+    ) -> Result<Option<usize>, String> {
+        let listing = listing(client, GROQ_URL, config).await?;
+        Ok(model_field(&listing, model, "context_window"))
+    }
+}
+
+pub struct OpenRouter;
+
+#[async_trait]
+impl LlmBackend for OpenRouter {
+    fn provider(&self) -> LLMProvider {
+        LLMProvider::OpenRouter
+    }
+
+    fn id(&self) -> &'static str {
+        "openrouter"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "OpenRouter"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        hosted(JsonMode::Object)
+    }
+
+    fn chat_endpoint(
+        &self,
+        config: &BackendConfig,
+        _model: &str,
+    ) -> Result<(String, HeaderMap), String> {
+        Ok((
+            format!("{}/chat/completions", OPENROUTER_URL),
+            bearer_headers(&config.api_key)?,
+        ))
+    }
+
+    async fn list_models(
+        &self,
+        client: &Client,
+        config: &BackendConfig,
+    ) -> Result<Vec<String>, String> {
+        Ok(model_ids(&listing(client, OPENROUTER_URL, config).await?))
+    }
+
+    async fn context_window(
+        &self,
+        client: &Client,
+        config: &BackendConfig,
+        model: &str,
+    ) -> Result<Option<usize>, String> {
+        let listing = listing(client, OPENROUTER_URL, config).await?;
+        let entry = listing["data"]
+            .as_array()
+            .and_then(|models| models.iter().find(|m| m["id"].as_str() == Some(model)));
+        // The provider that serves the model may cap it below the model's own limit
+        Ok(entry
+            .and_then(|m| {
+                m["top_provider"]["context_length"]
+                    .as_u64()
+                    .or_else(|| m["context_length"].as_u64())
+            })
+            .map(|n| n as usize))
+    }
+}
+
+pub struct Mistral;
+
+#[async_trait]
+impl LlmBackend for Mistral {
+    fn provider(&self) -> LLMProvider {
+        LLMProvider::Mistral
+    }
+
+    fn id(&self) -> &'static str {
+        "mistral"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Mistral"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        hosted(JsonMode::Schema)
+    }
+
+    fn chat_endpoint(
+        &self,
+        config: &BackendConfig,
+        _model: &str,
+    ) -> Result<(String, HeaderMap), String> {
+        Ok((
+            format!("{}/chat/completions", MISTRAL_URL),
+            bearer_headers(&config.api_key)?,
+        ))
+    }
+
+    async fn list_models(
+        &self,
+        client: &Client,
+        config: &BackendConfig,
+    ) -> Result<Vec<String>, String> {
+        Ok(model_ids(&listing(client, MISTRAL_URL, config).await?))
+    }
+
+    async fn context_window(
+        &self,
+        client: &Client,
+        config: &BackendConfig,
+        model: &str,
+    ) -> Result<Option<usize>, String> {
+        let listing = listing(client, MISTRAL_URL, config).await?;
+        Ok(model_field(&listing, model, "max_context_length"))
+    }
+}
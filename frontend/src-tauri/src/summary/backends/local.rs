@@ -0,0 +1,199 @@
+/// Backends running on the user's machine or network
+///
+/// Ollama and llama.cpp's `server` have default local endpoints that a
+/// configured endpoint overrides; OpenAI-compatible servers always need one.
+/// An API key is optional for all three and only sent when set.
+use super::backend::{BackendConfig, Capabilities, JsonMode, LlmBackend};
+use super::openai_format::{bearer_headers, get_json, model_ids};
+use crate::ollama::metadata::fetch_model_info;
+use crate::summary::llm_client::LLMProvider;
+use async_trait::async_trait;
+use reqwest::header::HeaderMap;
+use reqwest::Client;
+
+const OLLAMA_URL: &str = "http://localhost:11434";
+const LLAMA_CPP_URL: &str = "http://localhost:8080";
+
+fn local(requires_endpoint: bool) -> Capabilities {
+    Capabilities {
+        json_mode: JsonMode::Schema,
+        list_models: true,
+        requires_api_key: false,
+        requires_endpoint,
+        custom_endpoint: true,
+        local: true,
+    }
+}
+
+pub struct Ollama;
+
+#[async_trait]
+impl LlmBackend for Ollama {
+    fn provider(&self) -> LLMProvider {
+        LLMProvider::Ollama
+    }
+
+    fn id(&self) -> &'static str {
+        "ollama"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Ollama"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        local(false)
+    }
+
+    fn chat_endpoint(
+        &self,
+        config: &BackendConfig,
+        _model: &str,
+    ) -> Result<(String, HeaderMap), String> {
+        Ok((
+            format!("{}/v1/chat/completions", config.endpoint_or(OLLAMA_URL)),
+            bearer_headers(&config.api_key)?,
+        ))
+    }
+
+    async fn list_models(
+        &self,
+        client: &Client,
+        config: &BackendConfig,
+    ) -> Result<Vec<String>, String> {
+        let tags = get_json(
+            client,
+            &format!("{}/api/tags", config.endpoint_or(OLLAMA_URL)),
+            bearer_headers(&config.api_key)?,
+        )
+        .await?;
+        Ok(tags["models"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|m| m["name"].as_str().map(str::to_string))
+            .collect())
+    }
+
+    async fn context_window(
+        &self,
+        _client: &Client,
+        config: &BackendConfig,
+        model: &str,
+    ) -> Result<Option<usize>, String> {
+        let metadata = fetch_model_info(model, config.endpoint.as_deref()).await?;
+        Ok(Some(metadata.context_size))
+    }
+}
+
+pub struct LlamaCpp;
+
+#[async_trait]
+impl LlmBackend for LlamaCpp {
+    fn provider(&self) -> LLMProvider {
+        LLMProvider::LlamaCpp
+    }
+
+    fn id(&self) -> &'static str {
+        "llama-cpp"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "llama.cpp"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        local(false)
+    }
+
+    fn chat_endpoint(
+        &self,
+        config: &BackendConfig,
+        _model: &str,
+    ) -> Result<(String, HeaderMap), String> {
+        Ok((
+            format!("{}/v1/chat/completions", config.endpoint_or(LLAMA_CPP_URL)),
+            bearer_headers(&config.api_key)?,
+        ))
+    }
+
+    async fn list_models(
+        &self,
+        client: &Client,
+        config: &BackendConfig,
+    ) -> Result<Vec<String>, String> {
+        let listing = get_json(
+            client,
+            &format!("{}/v1/models", config.endpoint_or(LLAMA_CPP_URL)),
+            bearer_headers(&config.api_key)?,
+        )
+        .await?;
+        Ok(model_ids(&listing))
+    }
+
+    /// The server runs one model, so its `n_ctx` applies whatever `model` is
+    async fn context_window(
+        &self,
+        client: &Client,
+        config: &BackendConfig,
+        _model: &str,
+    ) -> Result<Option<usize>, String> {
+        let props = get_json(
+            client,
+            &format!("{}/props", config.endpoint_or(LLAMA_CPP_URL)),
+            bearer_headers(&config.api_key)?,
+        )
+        .await?;
+        Ok(props["default_generation_settings"]["n_ctx"]
+            .as_u64()
+            .map(|n| n as usize))
+    }
+}
+
+pub struct OpenAiCompatible;
+
+#[async_trait]
+impl LlmBackend for OpenAiCompatible {
+    fn provider(&self) -> LLMProvider {
+        LLMProvider::OpenAICompatible
+    }
+
+    fn id(&self) -> &'static str {
+        "openai-compatible"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "OpenAI Compatible"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        local(true)
+    }
+
+    fn chat_endpoint(
+        &self,
+        config: &BackendConfig,
+        _model: &str,
+    ) -> Result<(String, HeaderMap), String> {
+        let base = config.required_endpoint(self.display_name())?;
+        Ok((
+            format!("{}/chat/completions", base),
+            bearer_headers(&config.api_key)?,
+        ))
+    }
+
+    async fn list_models(
+        &self,
+        client: &Client,
+        config: &BackendConfig,
+    ) -> Result<Vec<String>, String> {
+        let base = config.required_endpoint(self.display_name())?;
+        let listing = get_json(
+            client,
+            &format!("{}/models", base),
+            bearer_headers(&config.api_key)?,
+        )
+        .await?;
+        Ok(model_ids(&listing))
+    }
+}
@@ -0,0 +1,26 @@
+/// LLM backends - one `LlmBackend` implementation per provider
+///
+/// This module contains:
+/// - The `LlmBackend` trait with capabilities and per-call config
+/// - The OpenAI Chat Completions wire format most backends share
+/// - Claude's Messages API
+/// - Hosted APIs (OpenAI, Groq, OpenRouter, Mistral, Gemini, Azure OpenAI)
+/// - Local servers (Ollama, llama.cpp, OpenAI-compatible)
+/// - The registry provider ids resolve against
+/// - `LlmError`, which tells retryable failures from permanent ones
+
+pub mod azure;
+pub mod backend;
+pub mod claude;
+pub mod error;
+pub mod gemini;
+pub mod hosted;
+pub mod local;
+pub mod openai_format;
+pub mod registry;
+
+pub use backend::{
+    BackendConfig, Capabilities, CompletionRequest, JsonMode, LlmBackend, StreamOutcome,
+};
+pub use error::LlmError;
+pub use registry::{backends, find_backend};
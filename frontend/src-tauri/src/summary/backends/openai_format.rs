@@ -0,0 +1,374 @@
+/// OpenAI Chat Completions wire format
+///
+/// Most backends speak this format and differ only in URL and auth headers,
+/// so they build those and hand off to `complete` and `stream` here.
+use super::backend::{CompletionRequest, JsonMode, SseDecoder, StreamOutcome};
+use super::error::LlmError;
+use crate::summary::llm_client::{ChatMessage, CompletionParams, StreamUsage, SummaryResult};
+use futures_util::StreamExt;
+use reqwest::{header, Client};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::Instant;
+use tracing::{info, warn};
+
+#[derive(Debug, Serialize)]
+struct ChatCompletionBody<'a> {
+    model: &'a str,
+    messages: &'a [ChatMessage],
+    stream: bool,
+    #[serde(flatten)]
+    params: Option<&'a CompletionParams>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<Value>,
+    /// Asks for token usage in the last streamed chunk
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<Value>,
+}
+
+#[derive(Deserialize, Debug)]
+struct ChatResponse {
+    choices: Vec<Choice>,
+    usage: Option<StreamUsageResponse>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Choice {
+    message: MessageContent,
+}
+
+#[derive(Deserialize, Debug)]
+struct MessageContent {
+    content: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct StreamChatChunk {
+    #[serde(default)]
+    choices: Vec<StreamChoice>,
+    usage: Option<StreamUsageResponse>,
+}
+
+#[derive(Deserialize, Debug)]
+struct StreamChoice {
+    delta: StreamDelta,
+    finish_reason: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct StreamDelta {
+    content: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct StreamUsageResponse {
+    prompt_tokens: Option<u32>,
+    completion_tokens: Option<u32>,
+    total_tokens: Option<u32>,
+}
+
+impl From<StreamUsageResponse> for StreamUsage {
+    fn from(usage: StreamUsageResponse) -> Self {
+        StreamUsage {
+            prompt_tokens: usage.prompt_tokens,
+            completion_tokens: usage.completion_tokens,
+            total_tokens: usage.total_tokens,
+        }
+    }
+}
+
+/// JSON content type plus `Authorization: Bearer`, skipped when there is no key
+pub fn bearer_headers(api_key: &str) -> Result<header::HeaderMap, String> {
+    let mut headers = json_headers()?;
+    if !api_key.is_empty() {
+        headers.insert(
+            header::AUTHORIZATION,
+            format!("Bearer {}", api_key)
+                .parse()
+                .map_err(|_| "Invalid authorization header".to_string())?,
+        );
+    }
+    Ok(headers)
+}
+
+pub fn json_headers() -> Result<header::HeaderMap, String> {
+    let mut headers = header::HeaderMap::new();
+    headers.insert(
+        header::CONTENT_TYPE,
+        "application/json"
+            .parse()
+            .map_err(|_| "Invalid content type".to_string())?,
+    );
+    Ok(headers)
+}
+
+/// `response_format` for a JSON schema, as far as `json_mode` supports it
+pub fn response_format(json_mode: JsonMode, schema: Option<&Value>) -> Option<Value> {
+    let schema = schema?;
+    match json_mode {
+        JsonMode::Schema => Some(serde_json::json!({
+            "type": "json_schema",
+            "json_schema": { "name": "meeting_summary", "schema": schema },
+        })),
+        JsonMode::Object => Some(serde_json::json!({ "type": "json_object" })),
+        JsonMode::Prompt => None,
+    }
+}
+
+fn body<'a>(
+    request: &CompletionRequest<'a>,
+    stream: bool,
+    json_mode: JsonMode,
+) -> ChatCompletionBody<'a> {
+    ChatCompletionBody {
+        model: request.model,
+        messages: request.messages,
+        stream,
+        params: request.params,
+        response_format: response_format(json_mode, request.json_schema),
+        stream_options: stream.then(|| serde_json::json!({ "include_usage": true })),
+    }
+}
+
+async fn send(
+    client: &Client,
+    url: &str,
+    headers: header::HeaderMap,
+    body: &ChatCompletionBody<'_>,
+) -> Result<reqwest::Response, LlmError> {
+    let response = client
+        .post(url)
+        .headers(headers)
+        .json(body)
+        .send()
+        .await
+        .map_err(|e| LlmError::from_reqwest(&e, format!("Failed to send request to LLM: {}", e)))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let response_headers = response.headers().clone();
+        let error_body = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(LlmError::from_response(
+            status,
+            &response_headers,
+            format!("LLM API request failed: {}", error_body),
+        ));
+    }
+    Ok(response)
+}
+
+/// Non-streaming completion against a chat completions `url`
+pub async fn complete(
+    client: &Client,
+    display_name: &str,
+    url: &str,
+    headers: header::HeaderMap,
+    request: CompletionRequest<'_>,
+    json_mode: JsonMode,
+) -> Result<SummaryResult, LlmError> {
+    let request_start_time = Instant::now();
+    let body = body(&request, false, json_mode);
+
+    info!(
+        "🐞 LLM Request to {}: model={} (non-streaming)",
+        display_name, request.model
+    );
+    info!(
+        "🐞 Full request body: {}",
+        serde_json::to_string_pretty(&body).unwrap_or_default()
+    );
+
+    let chat_response = send(client, url, headers, &body)
+        .await?
+        .json::<ChatResponse>()
+        .await
+        .map_err(|e| LlmError::from_reqwest(&e, format!("Failed to parse LLM response: {}", e)))?;
+
+    info!("🐞 LLM Response received from {}", display_name);
+
+    let content = chat_response
+        .choices
+        .first()
+        .ok_or("No content in LLM response")?
+        .message
+        .content
+        .trim()
+        .to_string();
+
+    Ok(SummaryResult {
+        content,
+        ttft_us: None, // Non-streaming doesn't capture TTFT
+        total_time_us: request_start_time.elapsed().as_micros() as u64,
+        usage: chat_response.usage.map(StreamUsage::from),
+    })
+}
+
+/// Streaming completion against a chat completions `url`
+pub async fn stream(
+    client: &Client,
+    display_name: &str,
+    url: &str,
+    headers: header::HeaderMap,
+    request: CompletionRequest<'_>,
+    json_mode: JsonMode,
+    on_token: &mut (dyn FnMut(&str) + Send),
+) -> Result<StreamOutcome, LlmError> {
+    let request_start_time = Instant::now();
+    let body = body(&request, true, json_mode);
+
+    info!("🚀 Sending streaming request to {}: {}", display_name, url);
+    if let Ok(json_payload) = serde_json::to_string_pretty(&body) {
+        info!("📤 LLM Request Payload:\n{}", json_payload);
+    }
+
+    let mut stream = send(client, url, headers, &body).await?.bytes_stream();
+    let mut decoder = SseDecoder::default();
+    let mut outcome = StreamOutcome::default();
+
+    while let Some(chunk_result) = stream.next().await {
+        let chunk =
+            chunk_result.map_err(|e| LlmError::from_reqwest(&e, format!("Stream error: {}", e)))?;
+        for data in decoder.push(&chunk) {
+            if data.trim() == "[DONE]" {
+                info!("🏁 Received [DONE] signal");
+                continue;
+            }
+
+            let parsed_chunk = match serde_json::from_str::<StreamChatChunk>(&data) {
+                Ok(parsed_chunk) => parsed_chunk,
+                Err(e) => {
+                    warn!("⚠️ Failed to parse streaming chunk: {} | Data: {}", e, data);
+                    continue;
+                }
+            };
+
+            if let Some(choice) = parsed_chunk.choices.first() {
+                if let Some(content) = choice.delta.content.as_deref().filter(|c| !c.is_empty()) {
+                    if outcome.ttft_us.is_none() {
+                        outcome.ttft_us = Some(request_start_time.elapsed().as_micros() as u64);
+                    }
+                    outcome.content.push_str(content);
+                    on_token(content);
+                }
+                if let Some(reason) = &choice.finish_reason {
+                    outcome.finish_reason = Some(reason.clone());
+                }
+            }
+
+            if let Some(usage) = parsed_chunk.usage {
+                outcome.usage = Some(usage.into());
+            }
+        }
+    }
+
+    outcome.total_time_us = request_start_time.elapsed().as_micros() as u64;
+    Ok(outcome)
+}
+
+/// GET request returning JSON, for model listings and metadata
+pub async fn get_json(
+    client: &Client,
+    url: &str,
+    headers: header::HeaderMap,
+) -> Result<Value, String> {
+    let response = client
+        .get(url)
+        .headers(headers)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to make HTTP request: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "HTTP request failed with status: {}",
+            response.status()
+        ));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse JSON response: {}", e))
+}
+
+/// Ids from an OpenAI-style `{"data": [{"id": ...}]}` model listing
+pub fn model_ids(listing: &Value) -> Vec<String> {
+    listing["data"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|m| m["id"].as_str().map(str::to_string))
+        .collect()
+}
+
+/// A number field of one entry in an OpenAI-style model listing
+pub fn model_field(listing: &Value, model: &str, field: &str) -> Option<usize> {
+    listing["data"]
+        .as_array()?
+        .iter()
+        .find(|m| m["id"].as_str() == Some(model))?[field]
+        .as_u64()
+        .map(|n| n as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_body_flattens_params() {
+        let messages = vec![ChatMessage {
+            role: "user".to_string(),
+            content: "hi".to_string(),
+        }];
+        let params = CompletionParams {
+            temperature: Some(0.5),
+            top_p: None,
+            max_tokens: Some(100),
+            repeat_penalty: None,
+            repeat_last_n: None,
+            chat_template_kwargs: None,
+        };
+        let schema = serde_json::json!({"type": "object"});
+        let request = CompletionRequest {
+            model: "m",
+            messages: &messages,
+            params: Some(&params),
+            json_schema: Some(&schema),
+        };
+
+        let value = serde_json::to_value(body(&request, true, JsonMode::Object)).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "model": "m",
+                "messages": [{"role": "user", "content": "hi"}],
+                "stream": true,
+                "temperature": 0.5,
+                "max_tokens": 100,
+                "response_format": {"type": "json_object"},
+                "stream_options": {"include_usage": true},
+            })
+        );
+
+        let request = CompletionRequest {
+            params: None,
+            ..request
+        };
+        let value = serde_json::to_value(body(&request, false, JsonMode::Prompt)).unwrap();
+        assert_eq!(value.as_object().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_model_listing() {
+        let listing = serde_json::json!({"data": [
+            {"id": "a", "context_window": 8192},
+            {"id": "b"},
+        ]});
+        assert_eq!(model_ids(&listing), vec!["a", "b"]);
+        assert_eq!(model_field(&listing, "a", "context_window"), Some(8192));
+        assert_eq!(model_field(&listing, "b", "context_window"), None);
+        assert_eq!(model_ids(&serde_json::json!({})), Vec::<String>::new());
+    }
+}
@@ -0,0 +1,90 @@
+/// Backend Registry
+///
+/// Every backend is listed here once. Provider ids from settings and
+/// commands resolve against this list, so a new backend only needs an
+/// `LLMProvider` variant, an `LlmBackend` impl and an entry below.
+use super::azure::AzureOpenAi;
+use super::backend::LlmBackend;
+use super::claude::Claude;
+use super::gemini::Gemini;
+use super::hosted::{Groq, Mistral, OpenAi, OpenRouter};
+use super::local::{LlamaCpp, Ollama, OpenAiCompatible};
+use crate::summary::llm_client::LLMProvider;
+
+static BACKENDS: &[&dyn LlmBackend] = &[
+    &OpenAi,
+    &Claude,
+    &Groq,
+    &Ollama,
+    &OpenRouter,
+    &OpenAiCompatible,
+    &Gemini,
+    &AzureOpenAi,
+    &Mistral,
+    &LlamaCpp,
+];
+
+/// All registered backends, in the order the UI lists them
+pub fn backends() -> &'static [&'static dyn LlmBackend] {
+    BACKENDS
+}
+
+/// Backend with the given id (case-insensitive)
+pub fn find_backend(id: &str) -> Option<&'static dyn LlmBackend> {
+    BACKENDS
+        .iter()
+        .find(|backend| backend.id().eq_ignore_ascii_case(id.trim()))
+        .copied()
+}
+
+impl LLMProvider {
+    /// The backend implementing this provider
+    pub fn backend(&self) -> &'static dyn LlmBackend {
+        BACKENDS
+            .iter()
+            .find(|backend| backend.provider() == *self)
+            .copied()
+            .expect("every LLMProvider has a registered backend")
+    }
+
+    /// Id used in settings and commands
+    pub fn id(&self) -> &'static str {
+        self.backend().id()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_every_backend_round_trips() {
+        let mut ids = HashSet::new();
+        for backend in backends() {
+            assert!(ids.insert(backend.id()), "duplicate id {}", backend.id());
+            let provider = LLMProvider::from_str(backend.id()).unwrap();
+            assert_eq!(provider, backend.provider());
+            assert_eq!(provider.backend().id(), backend.id());
+        }
+        assert_eq!(
+            LLMProvider::from_str("Azure-OpenAI").unwrap(),
+            LLMProvider::AzureOpenAI
+        );
+        assert!(LLMProvider::from_str("palm").is_err());
+    }
+
+    #[test]
+    fn test_capabilities_are_consistent() {
+        for backend in backends() {
+            let capabilities = backend.capabilities();
+            if capabilities.requires_endpoint {
+                assert!(capabilities.custom_endpoint, "{}", backend.id());
+            }
+        }
+        let ollama = LLMProvider::Ollama.backend().capabilities();
+        assert!(!ollama.requires_api_key && ollama.local);
+        let azure = LLMProvider::AzureOpenAI.backend().capabilities();
+        assert!(azure.requires_api_key && azure.requires_endpoint);
+    }
+}
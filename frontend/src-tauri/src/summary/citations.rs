@@ -0,0 +1,214 @@
+/// Transcript Citations
+///
+/// Summaries of stored meetings are generated from transcript lines that
+/// start with their recording time, e.g. `[12:34] Ana: ...`. The prompts ask
+/// the model to end every point with the times it is based on, and those
+/// markers survive the chunk and combine passes. Once the summary is done,
+/// `link_citations` turns each marker into a `#t=<seconds>` link to the
+/// segment's `audio_start_time` and lists the citations next to the markdown.
+use crate::api::MeetingTranscript;
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use serde::Serialize;
+
+const TIME: &str = r"\d{1,2}:\d{2}(?::\d{2})?";
+
+// "[12:34]", "[1:02:03]" or "[12:34, 15:02]", unless already a link
+static MARKER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(&format!(r"\[({TIME}(?:\s*[,;]\s*{TIME})*)\](\([^)]*\))?")).unwrap());
+
+static LINE_START: Lazy<Regex> = Lazy::new(|| Regex::new(&format!(r"^\[{TIME}\]")).unwrap());
+
+/// A summary point and the transcript segment it came from
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Citation {
+    /// The marker as written, e.g. "12:34"
+    pub time: String,
+    pub audio_start_time: f64,
+    pub transcript_id: String,
+    /// The cited line of the summary, without markers
+    pub text: String,
+}
+
+/// Seconds -> "mm:ss", or "h:mm:ss" from one hour on
+pub fn format_timestamp(secs: f64) -> String {
+    let secs = secs.max(0.0) as u64;
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs % 3600 / 60, secs % 60)
+    } else {
+        format!("{:02}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+/// "12:34" or "1:02:03" -> seconds
+fn parse_timestamp(time: &str) -> Option<u64> {
+    time.split(':').try_fold(0u64, |acc, part| {
+        part.parse::<u64>().ok().map(|n| acc * 60 + n)
+    })
+}
+
+/// The transcript as `[mm:ss] Speaker: text` lines
+///
+/// Returns None when no segment has a recording time, e.g. for meetings
+/// transcribed before audio sync was added.
+pub fn timestamped_transcript(transcripts: &[MeetingTranscript]) -> Option<String> {
+    if transcripts.iter().all(|t| t.audio_start_time.is_none()) {
+        return None;
+    }
+    let lines: Vec<String> = transcripts
+        .iter()
+        .filter(|t| !t.text.trim().is_empty())
+        .map(|t| {
+            let time = t
+                .audio_start_time
+                .map(|secs| format!("[{}] ", format_timestamp(secs)))
+                .unwrap_or_default();
+            let speaker = t
+                .speaker_name
+                .as_deref()
+                .map(|name| format!("{}: ", name))
+                .unwrap_or_default();
+            format!("{}{}{}", time, speaker, t.text.trim())
+        })
+        .collect();
+    Some(lines.join("\n"))
+}
+
+/// Whether `text` has lines starting with a `[mm:ss]` marker
+pub fn has_timestamps(text: &str) -> bool {
+    text.lines()
+        .any(|line| LINE_START.is_match(line.trim_start()))
+}
+
+/// Segment a marker points at: the last one starting at or before it
+fn resolve<'a>(transcripts: &'a [MeetingTranscript], secs: u64) -> Option<(&'a str, f64)> {
+    let timed = transcripts
+        .iter()
+        .filter_map(|t| t.audio_start_time.map(|start| (t.id.as_str(), start)));
+    timed
+        .clone()
+        .filter(|(_, start)| *start as u64 <= secs)
+        .last()
+        .or_else(|| timed.min_by(|a, b| a.1.total_cmp(&b.1)))
+}
+
+/// Turns the `[mm:ss]` markers in a summary into links to the cited segments
+///
+/// Markers that don't resolve to a segment are left as they are.
+///
+/// # Returns
+/// Tuple of (linked_markdown, citations)
+pub fn link_citations(
+    markdown: &str,
+    transcripts: &[MeetingTranscript],
+) -> (String, Vec<Citation>) {
+    let mut citations = Vec::new();
+    let lines: Vec<String> = markdown
+        .lines()
+        .map(|line| {
+            let text = MARKER.replace_all(line, "").trim().to_string();
+            let text = text
+                .trim_start_matches(['-', '*', '|', ' '])
+                .trim_end_matches(['|', ' '])
+                .to_string();
+            MARKER
+                .replace_all(line, |caps: &Captures| {
+                    if caps.get(2).is_some() {
+                        return caps[0].to_string();
+                    }
+                    let mut links = Vec::new();
+                    for time in caps[1].split([',', ';']).map(str::trim) {
+                        match parse_timestamp(time).and_then(|secs| resolve(transcripts, secs)) {
+                            Some((transcript_id, start)) => {
+                                links.push(format!("[{}](#t={})", time, start));
+                                citations.push(Citation {
+                                    time: time.to_string(),
+                                    audio_start_time: start,
+                                    transcript_id: transcript_id.to_string(),
+                                    text: text.clone(),
+                                });
+                            }
+                            None => links.push(format!("[{}]", time)),
+                        }
+                    }
+                    links.join(" ")
+                })
+                .into_owned()
+        })
+        .collect();
+    (lines.join("\n"), citations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(
+        id: &str,
+        start: Option<f64>,
+        speaker: Option<&str>,
+        text: &str,
+    ) -> MeetingTranscript {
+        MeetingTranscript {
+            id: id.to_string(),
+            text: text.to_string(),
+            timestamp: String::new(),
+            audio_start_time: start,
+            audio_end_time: None,
+            duration: None,
+            words: Vec::new(),
+            speaker_id: None,
+            speaker_name: speaker.map(str::to_string),
+            channel: None,
+        }
+    }
+
+    fn meeting() -> Vec<MeetingTranscript> {
+        vec![
+            segment("t-0", Some(3.2), Some("Ana"), " Let's start. "),
+            segment("t-1", Some(754.6), None, "Budget is due Friday."),
+            segment("t-2", Some(3725.0), Some("Raj"), "I'll send it."),
+        ]
+    }
+
+    #[test]
+    fn test_timestamped_transcript() {
+        let text = timestamped_transcript(&meeting()).unwrap();
+        assert_eq!(
+            text,
+            "[00:03] Ana: Let's start.\n[12:34] Budget is due Friday.\n[1:02:05] Raj: I'll send it."
+        );
+        assert!(has_timestamps(&text));
+        assert!(!has_timestamps("Let's start.\nBudget is due Friday."));
+        assert_eq!(
+            timestamped_transcript(&[segment("t-0", None, None, "Hi")]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_link_citations() {
+        let markdown = "**Decisions**\n- Budget due Friday [12:34]\n- Raj sends it [12:34, 1:02:05]\n- Kickoff [00:00](#t=0)";
+        let (linked, citations) = link_citations(markdown, &meeting());
+        assert_eq!(
+            linked,
+            "**Decisions**\n- Budget due Friday [12:34](#t=754.6)\n- Raj sends it [12:34](#t=754.6) [1:02:05](#t=3725)\n- Kickoff [00:00](#t=0)"
+        );
+        assert_eq!(citations.len(), 3);
+        assert_eq!(citations[0].transcript_id, "t-1");
+        assert_eq!(citations[0].text, "Budget due Friday");
+        assert_eq!(citations[2].transcript_id, "t-2");
+        assert_eq!(citations[2].audio_start_time, 3725.0);
+    }
+
+    #[test]
+    fn test_markers_before_first_segment_resolve_to_it() {
+        let (linked, citations) = link_citations("- Intro [00:01]", &meeting());
+        assert_eq!(linked, "- Intro [00:01](#t=3.2)");
+        assert_eq!(citations[0].transcript_id, "t-0");
+
+        let (linked, citations) = link_citations("- Intro [00:01]", &[]);
+        assert_eq!(linked, "- Intro [00:01]");
+        assert!(citations.is_empty());
+    }
+}
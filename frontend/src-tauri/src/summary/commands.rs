@@ -3,7 +3,7 @@ use crate::database::repositories::{
     transcript_chunk::TranscriptChunksRepository,
 };
 use crate::state::AppState;
-use crate::summary::service::SummaryService;
+use crate::summary::queue::{self, SummaryJob, SummaryJobInfo};
 use log::{error as log_error, info as log_info, warn as log_warn};
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Runtime};
@@ -66,7 +66,8 @@ pub async fn api_save_meeting_summary<R: Runtime>(
 
 /// Gets summary status and data (Native SQLx implementation)
 ///
-/// Returns summary status (pending/processing/completed/failed) and parsed result data
+/// Returns summary status (pending/processing/completed/failed/cancelled) and
+/// the parsed stored summary
 #[tauri::command]
 pub async fn api_get_summary<R: Runtime>(
     _app: AppHandle<R>,
@@ -85,18 +86,15 @@ pub async fn api_get_summary<R: Runtime>(
             let status = process.status.to_lowercase();
             let error = process.error;
 
-            // Parse result data if completed
-            let data = if status == "completed" {
-                if let Some(result_str) = process.result {
-                    match serde_json::from_str::<serde_json::Value>(&result_str) {
-                        Ok(parsed) => Some(parsed),
-                        Err(e) => {
-                            log_error!("Failed to parse summary result JSON: {}", e);
-                            None
-                        }
+            // Parse the stored summary. While a regeneration runs, or after one
+            // failed or was cancelled, this is the previous summary.
+            let data = if let Some(result_str) = process.result {
+                match serde_json::from_str::<serde_json::Value>(&result_str) {
+                    Ok(parsed) => Some(parsed),
+                    Err(e) => {
+                        log_error!("Failed to parse summary result JSON: {}", e);
+                        None
                     }
-                } else {
-                    None
                 }
             } else {
                 None
@@ -165,10 +163,10 @@ pub async fn api_get_summary<R: Runtime>(
 
 /// Processes transcript and generates summary (Native SQLx implementation)
 ///
-/// Spawns a background task and returns immediately with process_id
+/// Queues a summary job and returns immediately with process_id
 #[tauri::command]
 pub async fn api_process_transcript<R: Runtime>(
-    app: AppHandle<R>,
+    _app: AppHandle<R>,
     state: tauri::State<'_, AppState>,
     text: String,
     model: String,
@@ -222,28 +220,53 @@ pub async fn api_process_transcript<R: Runtime>(
 
     log_info!("✓ Transcript chunks saved for meeting_id: {}", &m_id);
 
-    // Spawn background task for actual processing
-    let meeting_id_clone = m_id.clone();
-    tauri::async_runtime::spawn(async move {
-        SummaryService::process_transcript_background(
-            app,
-            pool,
-            meeting_id_clone.clone(),
-            text,
-            model,
+    // Queue the job; it runs once its provider has a free slot
+    queue::enqueue(
+        &pool,
+        SummaryJob {
+            meeting_id: m_id.clone(),
+            model_provider: model,
             model_name,
-            final_prompt,
-            final_template_id,
-            final_language_id,
+            custom_prompt: final_prompt,
+            template_id: final_template_id,
+            language_id: final_language_id,
             completion_params,
-        )
-        .await;
-    });
+        },
+    )
+    .await?;
 
-    log_info!("🚀 Background task spawned for meeting_id: {}", &m_id);
+    log_info!("🚀 Summary job queued for meeting_id: {}", &m_id);
 
     Ok(ProcessTranscriptResponse {
-        message: "Summary generation started".to_string(),
+        message: "Summary generation queued".to_string(),
         process_id: m_id,
     })
 }
+
+/// Cancels a meeting's queued or running summary job
+///
+/// A running job is stopped mid-request. Returns false if there was no
+/// unfinished job for the meeting.
+#[tauri::command]
+pub async fn api_cancel_summary_job<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<bool, String> {
+    log_info!(
+        "api_cancel_summary_job called for meeting_id: {}",
+        meeting_id
+    );
+    queue::cancel(&app, state.db_manager.pool(), &meeting_id)
+        .await
+        .map_err(|e| {
+            log_error!("{}", e);
+            e
+        })
+}
+
+/// Lists running summary jobs, then queued ones in the order they will start
+#[tauri::command]
+pub async fn api_list_summary_jobs() -> Result<Vec<SummaryJobInfo>, String> {
+    Ok(queue::list())
+}
@@ -0,0 +1,188 @@
+/// Model Context Windows
+///
+/// The summarizer sizes its chunks from the context window of the model it
+/// talks to. Backends that can look it up (Ollama's `/api/show`, llama.cpp's
+/// `/props`, model listings) report it through `LlmBackend::context_window`;
+/// for the rest a static table of well-known models is used, with a
+/// conservative default for anything unknown.
+use crate::summary::backends::BackendConfig;
+use crate::summary::llm_client::{CompletionParams, LLMProvider};
+use once_cell::sync::Lazy;
+use reqwest::Client;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+/// Context size assumed when a model's window can't be determined
+pub const DEFAULT_CONTEXT_WINDOW: usize = 8192;
+
+/// Ollama's own default `num_ctx`, used when a local server can't be asked
+const LOCAL_FALLBACK_CONTEXT_WINDOW: usize = 4096;
+
+/// Tokens kept free for the instructions and template around the source text
+const PROMPT_OVERHEAD_TOKENS: usize = 1000;
+
+/// Smallest source budget handed out, even for tiny context windows
+const MIN_SOURCE_TOKENS: usize = 1000;
+
+const CACHE_TTL: Duration = Duration::from_secs(300);
+
+// "provider::endpoint::model" -> (fetched_at, context window), 5 minute TTL
+static CACHE: Lazy<RwLock<HashMap<String, (Instant, usize)>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Known context windows by model name prefix, most specific first
+const KNOWN_CONTEXT_WINDOWS: &[(&str, usize)] = &[
+    // OpenAI
+    ("gpt-4.1", 1_047_576),
+    ("gpt-4o", 128_000),
+    ("gpt-4-turbo", 128_000),
+    ("gpt-4-32k", 32_768),
+    ("gpt-4", 8_192),
+    ("gpt-3.5-turbo", 16_385),
+    ("gpt-5", 400_000),
+    ("o1-mini", 128_000),
+    ("o1", 200_000),
+    ("o3", 200_000),
+    ("o4", 200_000),
+    // Anthropic
+    ("claude", 200_000),
+    // Google
+    ("gemini", 1_048_576),
+    // Groq and other hosted open models
+    ("llama-3.1", 131_072),
+    ("llama-3.2", 131_072),
+    ("llama-3.3", 131_072),
+    ("llama3-", 8_192),
+    ("meta-llama/llama-4", 131_072),
+    ("mixtral-8x7b", 32_768),
+    ("gemma2", 8_192),
+    ("qwen", 32_768),
+    ("deepseek", 64_000),
+    ("moonshotai/kimi-k2", 131_072),
+    ("openai/gpt-oss", 131_072),
+];
+
+/// Context window from the static table, matched on the model name prefix
+pub fn known_context_window(model_name: &str) -> Option<usize> {
+    let name = model_name.to_lowercase();
+    KNOWN_CONTEXT_WINDOWS
+        .iter()
+        .find(|(prefix, _)| name.starts_with(prefix))
+        .map(|(_, size)| *size)
+}
+
+/// Context window of `model_name` in tokens
+///
+/// Never fails: lookup errors are logged and fall back to the static table,
+/// or for local servers to Ollama's default `num_ctx`.
+pub async fn context_window(
+    client: &Client,
+    provider: &LLMProvider,
+    model_name: &str,
+    config: &BackendConfig,
+) -> usize {
+    let backend = provider.backend();
+    let cache_key = format!(
+        "{}::{}::{}",
+        backend.id(),
+        config.endpoint.as_deref().unwrap_or("default"),
+        model_name
+    );
+    {
+        let cache = CACHE.read().await;
+        if let Some((fetched_at, size)) = cache.get(&cache_key) {
+            if fetched_at.elapsed() < CACHE_TTL {
+                return *size;
+            }
+        }
+    }
+
+    let size = match backend.context_window(client, config, model_name).await {
+        Ok(Some(size)) => size,
+        Ok(None) => static_or_default(model_name),
+        Err(e) if backend.capabilities().local => {
+            warn!(
+                "⚠️ Failed to fetch context for {}: {}. Using default {}",
+                model_name, e, LOCAL_FALLBACK_CONTEXT_WINDOW
+            );
+            return LOCAL_FALLBACK_CONTEXT_WINDOW;
+        }
+        Err(e) => {
+            warn!(
+                "⚠️ Failed to fetch context for {} from {}: {}",
+                model_name,
+                backend.display_name(),
+                e
+            );
+            return static_or_default(model_name);
+        }
+    };
+
+    CACHE
+        .write()
+        .await
+        .insert(cache_key, (Instant::now(), size));
+    size
+}
+
+/// Tokens of source text that fit in one call next to the prompt and the reply
+pub fn source_token_budget(
+    context_window: usize,
+    completion_params: Option<&CompletionParams>,
+) -> usize {
+    let reply_tokens = completion_params.and_then(|p| p.max_tokens).unwrap_or(2048) as usize;
+    context_window
+        .saturating_sub(reply_tokens + PROMPT_OVERHEAD_TOKENS)
+        .max(MIN_SOURCE_TOKENS)
+}
+
+fn static_or_default(model_name: &str) -> usize {
+    // OpenRouter ids carry a vendor prefix ("openai/gpt-4o"), Gemini ids a
+    // "models/" one
+    known_context_window(model_name)
+        .or_else(|| {
+            model_name
+                .split_once('/')
+                .and_then(|(_, name)| known_context_window(name))
+        })
+        .unwrap_or_else(|| {
+            info!(
+                "No known context window for {}, assuming {} tokens",
+                model_name, DEFAULT_CONTEXT_WINDOW
+            );
+            DEFAULT_CONTEXT_WINDOW
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_context_window() {
+        assert_eq!(known_context_window("gpt-4o-mini"), Some(128_000));
+        assert_eq!(known_context_window("gpt-4-0613"), Some(8_192));
+        assert_eq!(known_context_window("GPT-4.1-nano"), Some(1_047_576));
+        assert_eq!(
+            known_context_window("claude-3-5-sonnet-latest"),
+            Some(200_000)
+        );
+        assert_eq!(known_context_window("my-finetune"), None);
+        assert_eq!(static_or_default("anthropic/claude-sonnet-4"), 200_000);
+        assert_eq!(static_or_default("models/gemini-2.0-flash"), 1_048_576);
+        assert_eq!(static_or_default("my-finetune"), DEFAULT_CONTEXT_WINDOW);
+    }
+
+    #[test]
+    fn test_source_token_budget() {
+        let params = CompletionParams {
+            max_tokens: Some(4000),
+            ..Default::default()
+        };
+        assert_eq!(source_token_budget(128_000, Some(&params)), 123_000);
+        assert_eq!(source_token_budget(8192, None), 5144);
+        assert_eq!(source_token_budget(2048, None), MIN_SOURCE_TOKENS);
+    }
+}
@@ -0,0 +1,611 @@
+/// Provider failover chains with retry and backoff
+///
+/// A chain is the selected provider followed by the fallbacks configured in
+/// settings (e.g. Ollama → OpenRouter → OpenAI). A call is retried on the
+/// same link while its error is transient (429, 5xx, timeouts), waiting with
+/// exponential backoff or as long as `Retry-After` asks, and then moves on to
+/// the next link. Every attempt is logged, so a summary job can record which
+/// backend actually produced its result.
+///
+/// A chain with a `UsageRecorder` also stores the tokens and cost of each
+/// successful call, and checks the budget caps before calling a cloud link;
+/// a blocked link is skipped like a failed one. A chain with a progress
+/// sink reports each finished chunk, so the queue can show how far a job is.
+use crate::database::repositories::setting::SettingsRepository;
+use crate::summary::backends::{BackendConfig, LlmError};
+use crate::summary::context_window;
+use crate::summary::llm_client::{self, CompletionParams, LLMProvider, SummaryResult};
+use crate::summary::service::{ResolvedProvider, SummaryService};
+use crate::summary::usage::{self, CallUsage, UsageRecorder};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::SqlitePool;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// Retries on one link after its first attempt, before failing over
+pub const MAX_RETRIES: u32 = 3;
+
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(1);
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// A fallback as stored in settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FailoverEntry {
+    /// Provider id (e.g. "openrouter")
+    pub provider: String,
+    pub model: String,
+}
+
+/// A provider and model a chain can call
+#[derive(Debug, Clone)]
+pub struct ChainLink {
+    pub provider: LLMProvider,
+    pub model: String,
+    pub config: BackendConfig,
+}
+
+/// One LLM call made through a chain
+#[derive(Debug, Clone, Serialize)]
+pub struct LlmAttempt {
+    /// Step of the job that made the call (e.g. "chunk 2/5", "final")
+    pub stage: String,
+    pub provider: String,
+    pub model: String,
+    /// 1 for the first try on this link
+    pub attempt: u32,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// HTTP status of the failed response, if there was one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    pub elapsed_ms: u64,
+    /// RFC 3339 time the attempt finished
+    pub at: String,
+}
+
+/// How far a job has got through one of its stages
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StageProgress {
+    /// "chunk", "combine level 1", "final", ...
+    pub stage: String,
+    pub done: usize,
+    pub total: usize,
+}
+
+/// Receives a job's progress as its LLM calls finish
+pub type ProgressSink = Arc<dyn Fn(StageProgress) + Send + Sync>;
+
+/// Wait before retry number `retry` (1-based)
+///
+/// Honours `Retry-After`; None if the server asks for longer than
+/// `RETRY_MAX_DELAY`, in which case the next link is tried instead.
+pub fn retry_delay(retry: u32, retry_after: Option<Duration>) -> Option<Duration> {
+    match retry_after {
+        Some(wait) if wait > RETRY_MAX_DELAY => None,
+        Some(wait) => Some(wait),
+        None => Some(
+            RETRY_BASE_DELAY
+                .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)))
+                .min(RETRY_MAX_DELAY),
+        ),
+    }
+}
+
+/// The providers to try for one job or chat request, in order
+pub struct LlmChain {
+    links: Vec<ChainLink>,
+    attempts: Mutex<Vec<LlmAttempt>>,
+    usage: Option<UsageRecorder>,
+    progress: Option<ProgressSink>,
+}
+
+impl LlmChain {
+    pub fn new(primary: ChainLink, fallbacks: Vec<ChainLink>) -> Self {
+        let mut links = vec![primary];
+        links.extend(fallbacks);
+        Self {
+            links,
+            attempts: Mutex::new(Vec::new()),
+            usage: None,
+            progress: None,
+        }
+    }
+
+    /// Records token usage and enforces budget caps for the chain's calls
+    pub fn with_usage(mut self, recorder: UsageRecorder) -> Self {
+        self.usage = Some(recorder);
+        self
+    }
+
+    /// Reports the job's progress to `sink`
+    pub fn with_progress(mut self, sink: ProgressSink) -> Self {
+        self.progress = Some(sink);
+        self
+    }
+
+    pub fn report_progress(&self, stage: &str, done: usize, total: usize) {
+        if let Some(sink) = &self.progress {
+            sink(StageProgress {
+                stage: stage.to_string(),
+                done,
+                total,
+            });
+        }
+    }
+
+    /// The provider and model the job was started with
+    pub fn primary(&self) -> &ChainLink {
+        &self.links[0]
+    }
+
+    pub fn links(&self) -> &[ChainLink] {
+        &self.links
+    }
+
+    /// Whether any link runs on the user's machine
+    pub fn has_local(&self) -> bool {
+        self.links
+            .iter()
+            .any(|link| link.provider.backend().capabilities().local)
+    }
+
+    /// Every call made so far, in the order they finished
+    pub fn attempts(&self) -> Vec<LlmAttempt> {
+        self.attempts.lock().map(|a| a.clone()).unwrap_or_default()
+    }
+
+    /// The last successful call
+    pub fn produced_by(&self) -> Option<LlmAttempt> {
+        self.attempts().into_iter().rev().find(|a| a.ok)
+    }
+
+    /// Attempts plus the provider and model of the last successful call, for
+    /// `summary_processes.metadata`
+    pub fn metadata(&self) -> Value {
+        let attempts = self.attempts();
+        let produced_by = self
+            .produced_by()
+            .map(|a| serde_json::json!({ "provider": a.provider, "model": a.model }));
+        serde_json::json!({
+            "llm_attempts": attempts,
+            "produced_by": produced_by,
+        })
+    }
+
+    /// Smallest context window across the links, so chunks fit whichever
+    /// link ends up answering
+    pub async fn context_window(&self, client: &Client) -> usize {
+        let mut smallest = usize::MAX;
+        for link in &self.links {
+            let window =
+                context_window::context_window(client, &link.provider, &link.model, &link.config)
+                    .await;
+            smallest = smallest.min(window);
+        }
+        smallest
+    }
+
+    /// Runs `call` against each link in turn until one succeeds
+    pub async fn run<'a, T, F, Fut>(&'a self, stage: &str, call: F) -> Result<T, String>
+    where
+        F: FnMut(&'a ChainLink) -> Fut,
+        Fut: Future<Output = Result<T, LlmError>>,
+    {
+        self.run_while(stage, call, || true).await
+    }
+
+    /// Like `run`, but gives up on the first error once `may_retry` returns
+    /// false, e.g. after a stream has already shown tokens
+    pub async fn run_while<'a, T, F, Fut>(
+        &'a self,
+        stage: &str,
+        mut call: F,
+        may_retry: impl Fn() -> bool,
+    ) -> Result<T, String>
+    where
+        F: FnMut(&'a ChainLink) -> Fut,
+        Fut: Future<Output = Result<T, LlmError>>,
+    {
+        let mut failures: Vec<(String, LlmError)> = Vec::new();
+        let mut failed_attempts = 0;
+        for link in &self.links {
+            let name = link.provider.backend().display_name();
+            let mut attempt = 1;
+            loop {
+                let started = Instant::now();
+                let result = match self.check_budget(link).await {
+                    Ok(()) => call(link).await,
+                    Err(error) => Err(error),
+                };
+                self.record(stage, link, attempt, started, result.as_ref().err());
+
+                let error = match result {
+                    Ok(value) => {
+                        if failed_attempts > 0 {
+                            info!(
+                                "✓ {} succeeded on {} ({}) after {} failed attempt(s)",
+                                stage, name, link.model, failed_attempts
+                            );
+                        }
+                        return Ok(value);
+                    }
+                    Err(error) => error,
+                };
+                failed_attempts += 1;
+                if !may_retry() {
+                    return Err(error.message);
+                }
+
+                let delay = if error.retryable && attempt <= MAX_RETRIES {
+                    retry_delay(attempt, error.retry_after)
+                } else {
+                    None
+                };
+                match delay {
+                    Some(delay) => {
+                        warn!(
+                            "⚠️ {} failed on {} ({}), attempt {}: {}. Retrying in {:?}",
+                            stage, name, link.model, attempt, error, delay
+                        );
+                        tokio::time::sleep(delay).await;
+                        attempt += 1;
+                    }
+                    None => {
+                        warn!(
+                            "⚠️ {} failed on {} ({}): {}. Trying the next backend",
+                            stage, name, link.model, error
+                        );
+                        failures.push((format!("{} ({})", name, link.model), error));
+                        break;
+                    }
+                }
+            }
+        }
+
+        // A lone provider fails with its own error, as before chains existed
+        if let [(_, error)] = failures.as_slice() {
+            return Err(error.message.clone());
+        }
+        Err(format!(
+            "All LLM backends failed: {}",
+            failures
+                .iter()
+                .map(|(link, error)| format!("{}: {}", link, error))
+                .collect::<Vec<_>>()
+                .join("; ")
+        ))
+    }
+
+    /// Blocks cloud links once a budget cap is reached, and unpriced ones
+    /// while a cap is set
+    async fn check_budget(&self, link: &ChainLink) -> Result<(), LlmError> {
+        match &self.usage {
+            Some(recorder) if !link.provider.backend().capabilities().local => {
+                usage::check_budget(recorder, link)
+                    .await
+                    .map_err(LlmError::fatal)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Stores the tokens and cost of a successful call, if the chain has a recorder
+    pub async fn record_usage(&self, stage: &str, link: &ChainLink, call: &CallUsage) {
+        if let Some(recorder) = &self.usage {
+            usage::record(recorder, stage, link, call).await;
+        }
+    }
+
+    fn record(
+        &self,
+        stage: &str,
+        link: &ChainLink,
+        attempt: u32,
+        started: Instant,
+        error: Option<&LlmError>,
+    ) {
+        let entry = LlmAttempt {
+            stage: stage.to_string(),
+            provider: link.provider.id().to_string(),
+            model: link.model.clone(),
+            attempt,
+            ok: error.is_none(),
+            error: error.map(|e| e.message.clone()),
+            status: error.and_then(|e| e.status),
+            elapsed_ms: started.elapsed().as_millis() as u64,
+            at: chrono::Utc::now().to_rfc3339(),
+        };
+        if let Ok(mut attempts) = self.attempts.lock() {
+            attempts.push(entry);
+        }
+    }
+
+    /// `llm_client::generate_summary` with retries and failover
+    pub async fn generate_summary(
+        &self,
+        client: &Client,
+        stage: &str,
+        system_prompt: &str,
+        user_prompt: &str,
+        stream: bool,
+        completion_params: Option<CompletionParams>,
+    ) -> Result<SummaryResult, String> {
+        self.run(stage, |link| {
+            let completion_params = completion_params.clone();
+            async move {
+                let result = llm_client::generate_summary(
+                    client,
+                    &link.provider,
+                    &link.model,
+                    &link.config,
+                    system_prompt,
+                    user_prompt,
+                    stream,
+                    completion_params,
+                )
+                .await?;
+                self.record_summary_usage(stage, link, system_prompt, user_prompt, &result)
+                    .await;
+                Ok(result)
+            }
+        })
+        .await
+    }
+
+    /// `llm_client::generate_json` with retries and failover
+    pub async fn generate_json(
+        &self,
+        client: &Client,
+        stage: &str,
+        system_prompt: &str,
+        user_prompt: &str,
+        schema: &Value,
+        completion_params: Option<CompletionParams>,
+    ) -> Result<SummaryResult, String> {
+        self.run(stage, |link| {
+            let completion_params = completion_params.clone();
+            async move {
+                let result = llm_client::generate_json(
+                    client,
+                    &link.provider,
+                    &link.model,
+                    &link.config,
+                    system_prompt,
+                    user_prompt,
+                    schema,
+                    completion_params,
+                )
+                .await?;
+                self.record_summary_usage(stage, link, system_prompt, user_prompt, &result)
+                    .await;
+                Ok(result)
+            }
+        })
+        .await
+    }
+
+    async fn record_summary_usage(
+        &self,
+        stage: &str,
+        link: &ChainLink,
+        system_prompt: &str,
+        user_prompt: &str,
+        result: &SummaryResult,
+    ) {
+        let call = CallUsage::new(
+            result.usage.as_ref(),
+            &[system_prompt, user_prompt],
+            &result.content,
+            result.ttft_us,
+            result.total_time_us,
+        );
+        self.record_usage(stage, link, &call).await;
+    }
+}
+
+/// Checks configured fallbacks and normalizes their provider ids
+pub fn validate_failover_chain(entries: Vec<FailoverEntry>) -> Result<Vec<FailoverEntry>, String> {
+    entries
+        .into_iter()
+        .map(|entry| {
+            let provider = LLMProvider::from_str(&entry.provider)?;
+            let model = entry.model.trim();
+            if model.is_empty() {
+                return Err(format!("No model set for fallback {}", entry.provider));
+            }
+            Ok(FailoverEntry {
+                provider: provider.id().to_string(),
+                model: model.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// The fallbacks stored in settings, empty if none are configured
+pub async fn load_failover_chain(pool: &SqlitePool) -> Result<Vec<FailoverEntry>, String> {
+    let stored = SettingsRepository::get_failover_chain(pool)
+        .await
+        .map_err(|e| format!("Failed to load failover chain: {}", e))?;
+    match stored {
+        Some(json) if !json.trim().is_empty() => {
+            serde_json::from_str(&json).map_err(|e| format!("Invalid failover chain: {}", e))
+        }
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// Resolves the configured fallbacks for a chain starting at `primary`
+///
+/// Fallbacks that repeat an earlier link or lack a required API key or
+/// endpoint are skipped with a warning rather than failing the job.
+pub async fn resolve_fallbacks(pool: &SqlitePool, primary: &ChainLink) -> Vec<ChainLink> {
+    let entries = match load_failover_chain(pool).await {
+        Ok(entries) => entries,
+        Err(e) => {
+            warn!("⚠️ {}, continuing without fallbacks", e);
+            return Vec::new();
+        }
+    };
+
+    let mut fallbacks: Vec<ChainLink> = Vec::new();
+    for entry in entries {
+        match SummaryService::resolve_provider(pool, &entry.provider).await {
+            Ok(ResolvedProvider { provider, config }) => {
+                let repeated = std::iter::once(primary)
+                    .chain(&fallbacks)
+                    .any(|link| link.provider == provider && link.model == entry.model);
+                if !repeated {
+                    fallbacks.push(ChainLink {
+                        provider,
+                        model: entry.model,
+                        config,
+                    });
+                }
+            }
+            Err(e) => warn!(
+                "⚠️ Skipping fallback {} ({}): {}",
+                entry.provider, entry.model, e
+            ),
+        }
+    }
+    fallbacks
+}
+
+/// The chain for a summary job: the stored settings for `model_provider`,
+/// then the configured fallbacks. Usage is charged to `meeting_id`.
+pub async fn build_chain(
+    pool: &SqlitePool,
+    model_provider: &str,
+    model_name: &str,
+    meeting_id: Option<&str>,
+) -> Result<LlmChain, String> {
+    let ResolvedProvider { provider, config } =
+        SummaryService::resolve_provider(pool, model_provider).await?;
+    let primary = ChainLink {
+        provider,
+        model: model_name.to_string(),
+        config,
+    };
+    let fallbacks = resolve_fallbacks(pool, &primary).await;
+    if !fallbacks.is_empty() {
+        info!(
+            "✓ Failover chain: {}",
+            std::iter::once(&primary)
+                .chain(&fallbacks)
+                .map(|link| format!("{} ({})", link.provider.id(), link.model))
+                .collect::<Vec<_>>()
+                .join(" → ")
+        );
+    }
+    Ok(LlmChain::new(primary, fallbacks).with_usage(UsageRecorder {
+        pool: pool.clone(),
+        meeting_id: meeting_id.map(str::to_string),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link(provider: LLMProvider, model: &str) -> ChainLink {
+        ChainLink {
+            provider,
+            model: model.to_string(),
+            config: BackendConfig::default(),
+        }
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        assert_eq!(retry_delay(1, None), Some(Duration::from_secs(1)));
+        assert_eq!(retry_delay(3, None), Some(Duration::from_secs(4)));
+        assert_eq!(retry_delay(10, None), Some(RETRY_MAX_DELAY));
+        assert_eq!(
+            retry_delay(1, Some(Duration::from_secs(7))),
+            Some(Duration::from_secs(7))
+        );
+        assert_eq!(retry_delay(1, Some(Duration::from_secs(600))), None);
+    }
+
+    #[tokio::test]
+    async fn test_retries_then_fails_over() {
+        let chain = LlmChain::new(
+            link(LLMProvider::Ollama, "llama3"),
+            vec![
+                link(LLMProvider::OpenRouter, "a"),
+                link(LLMProvider::OpenAI, "b"),
+            ],
+        );
+        let result = chain
+            .run("final", |link| async move {
+                match link.provider {
+                    LLMProvider::Ollama => Err(LlmError {
+                        retry_after: Some(Duration::ZERO),
+                        ..LlmError::transient("busy")
+                    }),
+                    LLMProvider::OpenRouter => Err(LlmError::fatal("bad key")),
+                    _ => Ok(link.model.clone()),
+                }
+            })
+            .await;
+        assert_eq!(result.unwrap(), "b");
+
+        let attempts = chain.attempts();
+        assert_eq!(attempts.len(), MAX_RETRIES as usize + 3);
+        assert_eq!(attempts[MAX_RETRIES as usize].attempt, MAX_RETRIES + 1);
+        assert_eq!(attempts[MAX_RETRIES as usize + 1].provider, "openrouter");
+        assert_eq!(
+            chain.metadata()["produced_by"],
+            serde_json::json!({"provider": "openai", "model": "b"})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_errors_when_every_link_fails() {
+        let chain = LlmChain::new(link(LLMProvider::OpenAI, "a"), Vec::new());
+        let result: Result<(), _> = chain
+            .run("final", |_| async { Err(LlmError::fatal("bad key")) })
+            .await;
+        assert_eq!(result.unwrap_err(), "bad key");
+        assert_eq!(chain.metadata()["produced_by"], Value::Null);
+
+        let chain = LlmChain::new(
+            link(LLMProvider::OpenAI, "a"),
+            vec![link(LLMProvider::Groq, "b")],
+        );
+        let result: Result<(), _> = chain
+            .run_while(
+                "chat",
+                |_| async { Err(LlmError::transient("cut off")) },
+                || false,
+            )
+            .await;
+        assert_eq!(result.unwrap_err(), "cut off");
+        assert_eq!(chain.attempts().len(), 1);
+    }
+
+    #[test]
+    fn test_validate_failover_chain() {
+        let entries = vec![FailoverEntry {
+            provider: "OpenRouter".to_string(),
+            model: " gpt-4o ".to_string(),
+        }];
+        assert_eq!(
+            validate_failover_chain(entries).unwrap(),
+            vec![FailoverEntry {
+                provider: "openrouter".to_string(),
+                model: "gpt-4o".to_string(),
+            }]
+        );
+        let entries = vec![FailoverEntry {
+            provider: "ollama".to_string(),
+            model: String::new(),
+        }];
+        assert!(validate_failover_chain(entries).is_err());
+    }
+}
@@ -1,7 +1,10 @@
-use reqwest::{header, Client};
+use crate::summary::backends::{find_backend, BackendConfig, CompletionRequest, LlmError};
+use crate::summary::failover::LlmChain;
+use crate::summary::usage::CallUsage;
+use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use tracing::{info, error, warn};
-use futures_util::StreamExt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::{info, error};
 use tauri::{Emitter, Runtime};
 
 // ============================================================================
@@ -85,13 +88,6 @@ pub struct ChatMessage {
     pub content: String,
 }
 
-// Generic structure for OpenAI-compatible API chat requests (non-streaming)
-#[derive(Debug, Serialize)]
-pub struct ChatRequest {
-    pub model: String,
-    pub messages: Vec<ChatMessage>,
-}
-
 // Chat template kwargs structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatTemplateKwargs {
@@ -131,180 +127,10 @@ impl Default for CompletionParams {
     }
 }
 
-// Generic structure for OpenAI-compatible API chat requests (with streaming option)
-#[derive(Debug, Serialize)]
-pub struct StreamingChatRequest {
-    pub model: String,
-    pub messages: Vec<ChatMessage>,
-    pub stream: bool,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub temperature: Option<f32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub top_p: Option<f32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub max_tokens: Option<u32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub repeat_penalty: Option<f32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub repeat_last_n: Option<i32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub chat_template_kwargs: Option<ChatTemplateKwargs>,
-}
-
-// Generic structure for OpenAI-compatible API chat responses
-#[derive(Deserialize, Debug)]
-pub struct ChatResponse {
-    pub choices: Vec<Choice>,
-}
-
-#[derive(Deserialize, Debug)]
-pub struct Choice {
-    pub message: MessageContent,
-}
-
-#[derive(Deserialize, Debug)]
-pub struct MessageContent {
-    pub content: String,
-}
-
-// Claude-specific request structure
-#[derive(Debug, Serialize)]
-pub struct ClaudeRequest {
-    pub model: String,
-    pub max_tokens: u32,
-    pub system: String,
-    pub messages: Vec<ChatMessage>,
-}
-
-// Claude-specific response structure
-#[derive(Deserialize, Debug)]
-pub struct ClaudeChatResponse {
-    pub content: Vec<ClaudeChatContent>,
-}
-
-#[derive(Deserialize, Debug)]
-pub struct ClaudeChatContent {
-    pub text: String,
-}
-
-// ============================================================================
-// STREAMING RESPONSE STRUCTURES
-// ============================================================================
-
-/// OpenAI-compatible streaming response chunk
-#[derive(Deserialize, Debug)]
-pub struct StreamChatChunk {
-    #[serde(default)]
-    pub choices: Vec<StreamChoice>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub usage: Option<StreamUsageResponse>,
-    /// Unix timestamp (seconds since epoch) when this chunk was created
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub created: Option<i64>,
-}
-
-#[derive(Deserialize, Debug)]
-pub struct StreamChoice {
-    pub delta: StreamDelta,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub finish_reason: Option<String>,
-}
-
-#[derive(Deserialize, Debug)]
-pub struct StreamDelta {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub content: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub role: Option<String>,
-}
-
-#[derive(Deserialize, Debug)]
-pub struct StreamUsageResponse {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub prompt_tokens: Option<u32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub completion_tokens: Option<u32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub total_tokens: Option<u32>,
-}
-
-/// Claude-specific streaming event types
-#[derive(Deserialize, Debug)]
-#[serde(tag = "type")]
-pub enum ClaudeStreamEvent {
-    #[serde(rename = "message_start")]
-    MessageStart { message: ClaudeMessageStart },
-    #[serde(rename = "content_block_start")]
-    ContentBlockStart { content_block: ClaudeContentBlock },
-    #[serde(rename = "content_block_delta")]
-    ContentBlockDelta { delta: ClaudeContentDelta },
-    #[serde(rename = "content_block_stop")]
-    ContentBlockStop,
-    #[serde(rename = "message_delta")]
-    MessageDelta { delta: ClaudeMessageDelta, usage: ClaudeUsageDelta },
-    #[serde(rename = "message_stop")]
-    MessageStop,
-    #[serde(rename = "error")]
-    Error { error: ClaudeError },
-    #[serde(rename = "ping")]
-    Ping,
-}
-
-#[derive(Deserialize, Debug)]
-pub struct ClaudeMessageStart {
-    pub id: String,
-    pub model: String,
-    pub role: String,
-    pub usage: ClaudeUsageDelta,
-}
-
-#[derive(Deserialize, Debug)]
-pub struct ClaudeContentBlock {
-    #[serde(rename = "type")]
-    pub block_type: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub text: Option<String>,
-}
-
-#[derive(Deserialize, Debug)]
-pub struct ClaudeContentDelta {
-    #[serde(rename = "type")]
-    pub delta_type: String,
-    pub text: String,
-}
-
-#[derive(Deserialize, Debug)]
-pub struct ClaudeMessageDelta {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub stop_reason: Option<String>,
-}
-
-#[derive(Deserialize, Debug)]
-pub struct ClaudeUsageDelta {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub input_tokens: Option<u32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub output_tokens: Option<u32>,
-}
-
-#[derive(Deserialize, Debug)]
-pub struct ClaudeError {
-    #[serde(rename = "type")]
-    pub error_type: String,
-    pub message: String,
-}
-
-/// Claude-specific streaming request
-#[derive(Debug, Serialize)]
-pub struct ClaudeStreamRequest {
-    pub model: String,
-    pub max_tokens: u32,
-    pub system: String,
-    pub messages: Vec<ChatMessage>,
-    pub stream: bool,
-}
-
 /// LLM Provider enumeration for multi-provider support
+///
+/// Each variant is implemented by one `LlmBackend` in `summary::backends`;
+/// `backend()` returns it.
 #[derive(Debug, Clone, PartialEq)]
 pub enum LLMProvider {
     OpenAI,
@@ -313,20 +139,18 @@ pub enum LLMProvider {
     Ollama,
     OpenRouter,
     OpenAICompatible,
+    Gemini,
+    AzureOpenAI,
+    Mistral,
+    LlamaCpp,
 }
 
 impl LLMProvider {
-    /// Parse provider from string (case-insensitive)
+    /// Parse provider from string (case-insensitive), against the backend registry
     pub fn from_str(s: &str) -> Result<Self, String> {
-        match s.to_lowercase().as_str() {
-            "openai" => Ok(Self::OpenAI),
-            "claude" => Ok(Self::Claude),
-            "groq" => Ok(Self::Groq),
-            "ollama" => Ok(Self::Ollama),
-            "openrouter" => Ok(Self::OpenRouter),
-            "openai-compatible" => Ok(Self::OpenAICompatible),
-            _ => Err(format!("Unsupported LLM provider: {}", s)),
-        }
+        find_backend(s)
+            .map(|backend| backend.provider())
+            .ok_or_else(|| format!("Unsupported LLM provider: {}", s))
     }
 }
 
@@ -336,6 +160,20 @@ pub struct SummaryResult {
     pub content: String,
     pub ttft_us: Option<u64>,    // Time to first token in microseconds
     pub total_time_us: u64,       // Total generation time in microseconds (tn - t0)
+    pub usage: Option<StreamUsage>, // Token counts, when the provider reports them
+}
+
+fn prompt_messages(system_prompt: &str, user_prompt: &str) -> Vec<ChatMessage> {
+    vec![
+        ChatMessage {
+            role: "system".to_string(),
+            content: system_prompt.to_string(),
+        },
+        ChatMessage {
+            role: "user".to_string(),
+            content: user_prompt.to_string(),
+        },
+    ]
 }
 
 /// Generates a summary using the specified LLM provider
@@ -344,11 +182,9 @@ pub struct SummaryResult {
 /// * `client` - Reqwest HTTP client (reused for performance)
 /// * `provider` - The LLM provider to use
 /// * `model_name` - The specific model to use (e.g., "gpt-4", "claude-3-opus")
-/// * `api_key` - API key for the provider (not needed for Ollama)
+/// * `config` - API key and endpoint for the provider
 /// * `system_prompt` - System instructions for the LLM
 /// * `user_prompt` - User query/content to process
-/// * `ollama_endpoint` - Optional custom Ollama endpoint (defaults to localhost:11434)
-/// * `openai_compatible_endpoint` - Optional custom OpenAI-compatible endpoint
 /// * `stream` - Whether to use streaming mode (enables TTFT tracking)
 /// * `completion_params` - Optional completion parameters (temperature, max_tokens, etc.)
 ///
@@ -358,624 +194,63 @@ pub async fn generate_summary(
     client: &Client,
     provider: &LLMProvider,
     model_name: &str,
-    api_key: &str,
+    config: &BackendConfig,
     system_prompt: &str,
     user_prompt: &str,
-    ollama_endpoint: Option<&str>,
-    openai_compatible_endpoint: Option<&str>,
     stream: bool,
     completion_params: Option<CompletionParams>,
-) -> Result<SummaryResult, String> {
-    use std::time::Instant;
+) -> Result<SummaryResult, LlmError> {
+    let backend = provider.backend();
+    let messages = prompt_messages(system_prompt, user_prompt);
 
-    // Route to streaming or non-streaming based on parameter
     if stream {
-        _generate_summary_streaming(
-            client,
-            provider,
-            model_name,
-            api_key,
-            system_prompt,
-            user_prompt,
-            ollama_endpoint,
-            openai_compatible_endpoint,
-            completion_params,
-        )
-        .await
-    } else {
-        _generate_summary_non_streaming(
-            client,
-            provider,
-            model_name,
-            api_key,
-            system_prompt,
-            user_prompt,
-            ollama_endpoint,
-            openai_compatible_endpoint,
-            completion_params,
-        )
-        .await
-    }
-}
-
-/// Internal: Non-streaming summary generation (original logic, ttft_us is always None)
-async fn _generate_summary_non_streaming(
-    client: &Client,
-    provider: &LLMProvider,
-    model_name: &str,
-    api_key: &str,
-    system_prompt: &str,
-    user_prompt: &str,
-    ollama_endpoint: Option<&str>,
-    openai_compatible_endpoint: Option<&str>,
-    completion_params: Option<CompletionParams>,
-) -> Result<SummaryResult, String> {
-    use std::time::Instant;
-    let request_start_time = Instant::now();
-
-    let (api_url, mut headers) = match provider {
-        LLMProvider::OpenAI => (
-            "https://api.openai.com/v1/chat/completions".to_string(),
-            header::HeaderMap::new(),
-        ),
-        LLMProvider::Groq => (
-            "https://api.groq.com/openai/v1/chat/completions".to_string(),
-            header::HeaderMap::new(),
-        ),
-        LLMProvider::OpenRouter => (
-            "https://openrouter.ai/api/v1/chat/completions".to_string(),
-            header::HeaderMap::new(),
-        ),
-        LLMProvider::Ollama => {
-            let host = ollama_endpoint
-                .map(|s| s.to_string())
-                .unwrap_or_else(|| "http://localhost:11434".to_string());
-            (
-                format!("{}/v1/chat/completions", host),
-                header::HeaderMap::new(),
-            )
-        },
-        LLMProvider::Claude => {
-            let mut header_map = header::HeaderMap::new();
-            header_map.insert(
-                "x-api-key",
-                api_key
-                    .parse()
-                    .map_err(|_| "Invalid API key format".to_string())?,
-            );
-            header_map.insert(
-                "anthropic-version",
-                "2023-06-01"
-                    .parse()
-                    .map_err(|_| "Invalid anthropic version".to_string())?,
-            );
-            ("https://api.anthropic.com/v1/messages".to_string(), header_map)
-        },
-        LLMProvider::OpenAICompatible => {
-            let base_url = openai_compatible_endpoint
-                .ok_or("OpenAI Compatible endpoint not configured")?
-                .trim_end_matches('/');
-            (
-                format!("{}/chat/completions", base_url),
-                header::HeaderMap::new(),
-            )
-        }
-    };
-
-    // Add authorization header for non-Claude providers
-    if provider != &LLMProvider::Claude {
-        // OpenAI Compatible might not need API key (local services)
-        if !api_key.is_empty() || provider != &LLMProvider::OpenAICompatible {
-            headers.insert(
-                header::AUTHORIZATION,
-                format!("Bearer {}", api_key)
-                    .parse()
-                    .map_err(|_| "Invalid authorization header".to_string())?,
-            );
-        }
-    }
-    headers.insert(
-        header::CONTENT_TYPE,
-        "application/json"
-            .parse()
-            .map_err(|_| "Invalid content type".to_string())?,
-    );
-
-    // Build request body based on provider
-    let request_body = if provider != &LLMProvider::Claude {
-        serde_json::json!(ChatRequest {
-            model: model_name.to_string(),
-            messages: vec![
-                ChatMessage {
-                    role: "system".to_string(),
-                    content: system_prompt.to_string(),
-                },
-                ChatMessage {
-                    role: "user".to_string(),
-                    content: user_prompt.to_string(),
-                }
-            ],
-        })
-    } else {
-        serde_json::json!(ClaudeRequest {
-            system: system_prompt.to_string(),
-            model: model_name.to_string(),
-            max_tokens: 2048,
-            messages: vec![ChatMessage {
-                role: "user".to_string(),
-                content: user_prompt.to_string(),
-            }]
-        })
-    };
-
-    info!("🐞 LLM Request to {}: model={} (non-streaming)", provider_name(provider), model_name);
-    info!("🐞 Full request body: {}", serde_json::to_string_pretty(&request_body).unwrap_or_default());
-
-    // Send request
-    let response = client
-        .post(api_url)
-        .headers(headers)
-        .json(&request_body)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to send request to LLM: {}", e))?;
-
-    if !response.status().is_success() {
-        let error_body = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!("LLM API request failed: {}", error_body));
-    }
-
-    // Parse response based on provider
-    if provider == &LLMProvider::Claude {
-        let chat_response = response
-            .json::<ClaudeChatResponse>()
-            .await
-            .map_err(|e| format!("Failed to parse LLM response: {}", e))?;
-
-        info!("🐞 LLM Response received from Claude");
-
-        let content = chat_response
-            .content
-            .get(0)
-            .ok_or("No content in LLM response")?
-            .text
-            .trim();
-
-        let total_time_us = request_start_time.elapsed().as_micros() as u64;
-        info!("⏱️ Summary generation total time: {:.2}ms", total_time_us as f64 / 1000.0);
-
-        // For non-streaming, we don't have TTFT but can measure total time
-        Ok(SummaryResult {
-            content: content.to_string(),
-            ttft_us: None, // Non-streaming doesn't capture TTFT
-            total_time_us,
-        })
+        // Streaming consumes the tokens internally to capture TTFT;
+        // the caller still receives the complete response
+        let params = completion_params.unwrap_or_default();
+        let request = CompletionRequest {
+            model: model_name,
+            messages: &messages,
+            params: Some(&params),
+            json_schema: None,
+        };
+        let outcome = backend.stream(client, config, request, &mut |_: &str| {}).await?;
+        log_ttft_metrics(outcome.ttft_us, outcome.total_time_us);
+        Ok(outcome.into())
     } else {
-        let chat_response = response
-            .json::<ChatResponse>()
-            .await
-            .map_err(|e| format!("Failed to parse LLM response: {}", e))?;
-
-        info!("🐞 LLM Response received from {}", provider_name(provider));
-
-        let content = chat_response
-            .choices
-            .get(0)
-            .ok_or("No content in LLM response")?
-            .message
-            .content
-            .trim();
-
-        let total_time_us = request_start_time.elapsed().as_micros() as u64;
-        info!("⏱️ Summary generation total time: {:.2}ms", total_time_us as f64 / 1000.0);
-
-        Ok(SummaryResult {
-            content: content.to_string(),
-            ttft_us: None, // Non-streaming doesn't capture TTFT
-            total_time_us,
-        })
+        let request = CompletionRequest {
+            model: model_name,
+            messages: &messages,
+            params: completion_params.as_ref(),
+            json_schema: None,
+        };
+        backend.complete(client, config, request).await
     }
 }
 
-/// Internal: Streaming summary generation (consumes stream internally, captures TTFT)
+/// Generates a reply in JSON mode, as far as the backend supports it
 ///
-/// This function uses streaming to capture TTFT metrics but accumulates all tokens
-/// internally before returning, so the caller receives a complete response.
-async fn generate_summary_streaming(
+/// Backends with `JsonMode::Schema` are held to the schema itself, those with
+/// `JsonMode::Object` only to a JSON object, and the rest rely on the prompt.
+/// The reply is not validated here; callers check it against `schema` with
+/// `summary::schema`. Always non-streaming, so `ttft_us` is None.
+pub async fn generate_json(
     client: &Client,
     provider: &LLMProvider,
     model_name: &str,
-    api_key: &str,
+    config: &BackendConfig,
     system_prompt: &str,
     user_prompt: &str,
-    ollama_endpoint: Option<&str>,
-    openai_compatible_endpoint: Option<&str>,
+    schema: &serde_json::Value,
     completion_params: Option<CompletionParams>,
-) -> Result<SummaryResult, String> {
-    use std::time::Instant;
-
-    // Route to appropriate streaming handler based on provider
-    if provider == &LLMProvider::Claude {
-        _generate_summary_streaming_claude(
-            client,
-            model_name,
-            api_key,
-            system_prompt,
-            user_prompt,
-            completion_params,
-        )
-        .await
-    } else {
-        _generate_summary_streaming(
-            client,
-            provider,
-            model_name,
-            api_key,
-            system_prompt,
-            user_prompt,
-            ollama_endpoint,
-            openai_compatible_endpoint,
-            completion_params,
-        )
-        .await
-    }
-}
-
-/// Internal: Streaming handler for OpenAI-compatible providers (reuses chat streaming logic)
-async fn _generate_summary_streaming(
-    client: &Client,
-    provider: &LLMProvider,
-    model_name: &str,
-    api_key: &str,
-    system_prompt: &str,
-    user_prompt: &str,
-    ollama_endpoint: Option<&str>,
-    openai_compatible_endpoint: Option<&str>,
-    completion_params: Option<CompletionParams>,
-) -> Result<SummaryResult, String> {
-    use std::time::Instant;
-    let request_start_time = Instant::now();
-
-    // Determine API endpoint (reuse logic from stream_chat_openai_compatible)
-    let (api_url, mut headers) = match provider {
-        LLMProvider::OpenAI => (
-            "https://api.openai.com/v1/chat/completions".to_string(),
-            header::HeaderMap::new(),
-        ),
-        LLMProvider::Groq => (
-            "https://api.groq.com/openai/v1/chat/completions".to_string(),
-            header::HeaderMap::new(),
-        ),
-        LLMProvider::OpenRouter => (
-            "https://openrouter.ai/api/v1/chat/completions".to_string(),
-            header::HeaderMap::new(),
-        ),
-        LLMProvider::Ollama => {
-            let host = ollama_endpoint
-                .map(|s| s.to_string())
-                .unwrap_or_else(|| "http://localhost:11434".to_string());
-            (
-                format!("{}/v1/chat/completions", host),
-                header::HeaderMap::new(),
-            )
-        }
-        LLMProvider::OpenAICompatible => {
-            let base_url = openai_compatible_endpoint
-                .ok_or("OpenAI Compatible endpoint not configured")?
-                .trim_end_matches('/');
-            (
-                format!("{}/chat/completions", base_url),
-                header::HeaderMap::new(),
-            )
-        }
-        _ => return Err("Unsupported provider for OpenAI-compatible streaming".to_string()),
+) -> Result<SummaryResult, LlmError> {
+    let messages = prompt_messages(system_prompt, user_prompt);
+    let request = CompletionRequest {
+        model: model_name,
+        messages: &messages,
+        params: completion_params.as_ref(),
+        json_schema: Some(schema),
     };
-
-    // Add authorization header
-    if !api_key.is_empty() || provider != &LLMProvider::OpenAICompatible {
-        headers.insert(
-            header::AUTHORIZATION,
-            format!("Bearer {}", api_key)
-                .parse()
-                .map_err(|_| "Invalid authorization header".to_string())?,
-        );
-    }
-    headers.insert(
-        header::CONTENT_TYPE,
-        "application/json"
-            .parse()
-            .map_err(|_| "Invalid content type".to_string())?,
-    );
-
-    // Build streaming request body
-    let messages = vec![
-        ChatMessage {
-            role: "system".to_string(),
-            content: system_prompt.to_string(),
-        },
-        ChatMessage {
-            role: "user".to_string(),
-            content: user_prompt.to_string(),
-        },
-    ];
-
-    // Use provided completion params or defaults
-    let params = completion_params.unwrap_or_default();
-
-    let request_body = StreamingChatRequest {
-        model: model_name.to_string(),
-        messages,
-        stream: true,
-        temperature: params.temperature,
-        top_p: params.top_p,
-        max_tokens: params.max_tokens,
-        repeat_penalty: params.repeat_penalty,
-        repeat_last_n: params.repeat_last_n,
-        chat_template_kwargs: params.chat_template_kwargs,
-    };
-
-    info!("🚀 Sending streaming summary request to {}: {}", provider_name(provider), api_url);
-
-    // Log complete request payload in JSON format
-    if let Ok(json_payload) = serde_json::to_string_pretty(&request_body) {
-        info!("📤 LLM Request Payload:\n{}", json_payload);
-    }
-
-    // Send request
-    let response = client
-        .post(&api_url)
-        .headers(headers)
-        .json(&request_body)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to send streaming request: {}", e))?;
-
-    if !response.status().is_success() {
-        let error_body = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!("Streaming API request failed: {}", error_body));
-    }
-
-    // Process streaming response - accumulate tokens internally
-    let mut stream = response.bytes_stream();
-    let mut buffer = String::new();
-    let mut accumulated_content = String::new();
-    let mut ttft_us: Option<u64> = None;
-    let mut first_token_received = false;
-
-    while let Some(chunk_result) = stream.next().await {
-        match chunk_result {
-            Ok(chunk) => {
-                // Convert bytes to string
-                let chunk_str = String::from_utf8_lossy(&chunk);
-                buffer.push_str(&chunk_str);
-
-                // Process complete lines (SSE format: "data: {...}\n\n")
-                while let Some(line_end) = buffer.find("\n\n") {
-                    let line = buffer[..line_end].to_string();
-                    buffer = buffer[line_end + 2..].to_string();
-
-                    // Parse SSE line
-                    for sse_line in line.lines() {
-                        if sse_line.starts_with("data: ") {
-                            let data = &sse_line[6..]; // Skip "data: " prefix
-
-                            // Check for [DONE] signal
-                            if data.trim() == "[DONE]" {
-                                info!("🏁 Received [DONE] signal for summary");
-                                break;
-                            }
-
-                            // Parse JSON chunk
-                            match serde_json::from_str::<StreamChatChunk>(data) {
-                                Ok(parsed_chunk) => {
-                                    // Extract content delta
-                                    if let Some(choice) = parsed_chunk.choices.first() {
-                                        if let Some(content) = &choice.delta.content {
-                                            if !content.is_empty() {
-                                                // Calculate TTFT on first token
-                                                if !first_token_received {
-                                                    first_token_received = true;
-                                                    ttft_us = Some(request_start_time.elapsed().as_micros() as u64);
-                                                    info!("⏱️ First token received for summary: {:.2}ms",
-                                                          ttft_us.unwrap() as f64 / 1000.0);
-                                                }
-
-                                                // Accumulate content internally
-                                                accumulated_content.push_str(content);
-                                            }
-                                        }
-                                    }
-                                }
-                                Err(e) => {
-                                    warn!("⚠️ Failed to parse streaming chunk: {} | Data: {}", e, data);
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-            Err(e) => {
-                return Err(format!("Stream error: {}", e));
-            }
-        }
-    }
-
-    // Calculate total time
-    let total_time_us = request_start_time.elapsed().as_micros() as u64;
-
-    // Log unified TTFT metrics
-    log_ttft_metrics(ttft_us, total_time_us);
-
-    info!("✅ Summary streaming completed: {} chars, TTFT: {:?}μs",
-          accumulated_content.len(), ttft_us);
-
-    Ok(SummaryResult {
-        content: accumulated_content,
-        ttft_us,
-        total_time_us,
-    })
-}
-
-/// Internal: Streaming handler for Claude (reuses Claude streaming logic)
-async fn _generate_summary_streaming_claude(
-    client: &Client,
-    model_name: &str,
-    api_key: &str,
-    system_prompt: &str,
-    user_prompt: &str,
-    completion_params: Option<CompletionParams>,
-) -> Result<SummaryResult, String> {
-    use std::time::Instant;
-    let request_start_time = Instant::now();
-
-    // Build headers
-    let mut headers = header::HeaderMap::new();
-    headers.insert(
-        "x-api-key",
-        api_key
-            .parse()
-            .map_err(|_| "Invalid API key format".to_string())?,
-    );
-    headers.insert(
-        "anthropic-version",
-        "2023-06-01"
-            .parse()
-            .map_err(|_| "Invalid anthropic version".to_string())?,
-    );
-    headers.insert(
-        header::CONTENT_TYPE,
-        "application/json"
-            .parse()
-            .map_err(|_| "Invalid content type".to_string())?,
-    );
-
-    // Build streaming request body
-    let request_body = serde_json::json!({
-        "model": model_name,
-        "max_tokens": 2048,
-        "messages": vec![ChatMessage {
-            role: "user".to_string(),
-            content: user_prompt.to_string(),
-        }],
-        "system": system_prompt,
-        "stream": true,
-    });
-
-    let api_url = "https://api.anthropic.com/v1/messages";
-    info!("🚀 Sending streaming summary request to Claude: {}", api_url);
-    info!("🐞 Full request body: {}", serde_json::to_string_pretty(&request_body).unwrap_or_default());
-
-    // Send request
-    let response = client
-        .post(api_url)
-        .headers(headers)
-        .json(&request_body)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to send streaming request to Claude: {}", e))?;
-
-    if !response.status().is_success() {
-        let error_body = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!("Claude streaming API request failed: {}", error_body));
-    }
-
-    // Process streaming response - accumulate tokens internally
-    let mut stream = response.bytes_stream();
-    let mut buffer = String::new();
-    let mut accumulated_content = String::new();
-    let mut ttft_us: Option<u64> = None;
-    let mut first_token_received = false;
-
-    while let Some(chunk_result) = stream.next().await {
-        match chunk_result {
-            Ok(chunk) => {
-                let chunk_str = String::from_utf8_lossy(&chunk);
-                buffer.push_str(&chunk_str);
-
-                // Process complete lines (SSE format: "event: ...\ndata: {...}\n\n")
-                while let Some(line_end) = buffer.find("\n\n") {
-                    let block = buffer[..line_end].to_string();
-                    buffer = buffer[line_end + 2..].to_string();
-
-                    // Parse SSE block
-                    let mut data: Option<String> = None;
-
-                    for line in block.lines() {
-                        if line.starts_with("data: ") {
-                            data = Some(line[6..].to_string());
-                        }
-                    }
-
-                    if let Some(data_str) = data {
-                        match serde_json::from_str::<ClaudeStreamEvent>(&data_str) {
-                            Ok(event) => {
-                                match event {
-                                    ClaudeStreamEvent::ContentBlockDelta { delta } => {
-                                        // Calculate TTFT on first token
-                                        if !first_token_received && !delta.text.is_empty() {
-                                            first_token_received = true;
-                                            ttft_us = Some(request_start_time.elapsed().as_micros() as u64);
-                                            info!("⏱️ First token received for Claude summary: {:.2}ms",
-                                                  ttft_us.unwrap() as f64 / 1000.0);
-                                        }
-
-                                        // Accumulate content internally
-                                        accumulated_content.push_str(&delta.text);
-                                    }
-                                    ClaudeStreamEvent::Error { error } => {
-                                        return Err(format!("Claude error: {}", error.message));
-                                    }
-                                    _ => {}
-                                }
-                            }
-                            Err(e) => {
-                                warn!("⚠️ Failed to parse Claude streaming event: {} | Data: {}", e, data_str);
-                            }
-                        }
-                    }
-                }
-            }
-            Err(e) => {
-                return Err(format!("Claude stream error: {}", e));
-            }
-        }
-    }
-
-    // Calculate total time
-    let total_time_us = request_start_time.elapsed().as_micros() as u64;
-
-    // Log unified TTFT metrics
-    log_ttft_metrics(ttft_us, total_time_us);
-
-    info!("✅ Claude summary streaming completed: {} chars, TTFT: {:?}μs",
-          accumulated_content.len(), ttft_us);
-
-    Ok(SummaryResult {
-        content: accumulated_content,
-        ttft_us,
-        total_time_us,
-    })
-}
-
-/// Helper function to get provider name for logging
-fn provider_name(provider: &LLMProvider) -> &str {
-    match provider {
-        LLMProvider::OpenAI => "OpenAI",
-        LLMProvider::Claude => "Claude",
-        LLMProvider::Groq => "Groq",
-        LLMProvider::Ollama => "Ollama",
-        LLMProvider::OpenRouter => "OpenRouter",
-        LLMProvider::OpenAICompatible => "OpenAI Compatible",
-    }
+    provider.backend().complete(client, config, request).await
 }
 
 // ============================================================================
@@ -984,19 +259,17 @@ fn provider_name(provider: &LLMProvider) -> &str {
 
 /// Streams chat completion from LLM provider and emits Tauri events
 ///
+/// Errors before the first token are retried and fail over along `chain`;
+/// once tokens have reached the UI a retry would repeat them, so later
+/// errors end the stream.
+///
 /// # Arguments
 /// * `app` - Tauri app handle for emitting events
 /// * `client` - Reqwest HTTP client
-/// * `provider` - The LLM provider to use
-/// * `model_name` - The specific model to use
-/// * `api_key` - API key for the provider (not needed for Ollama)
+/// * `chain` - Providers and models to try, in order
 /// * `messages` - Chat history (system + user messages)
 /// * `request_id` - Unique identifier for this request (for event routing)
-/// * `ollama_endpoint` - Optional custom Ollama endpoint
-/// * `openai_compatible_endpoint` - Optional custom OpenAI-compatible endpoint
-/// * `temperature` - Optional sampling temperature (0.0 to 2.0)
-/// * `top_p` - Optional nucleus sampling parameter
-/// * `max_tokens` - Optional maximum tokens to generate
+/// * `completion_params` - Sampling parameters (temperature, top_p, max_tokens, etc.)
 ///
 /// # Events Emitted
 /// * `llm:chat:token` - Each token/chunk received
@@ -1005,67 +278,79 @@ fn provider_name(provider: &LLMProvider) -> &str {
 pub async fn stream_chat<R: Runtime>(
     app: tauri::AppHandle<R>,
     client: &Client,
-    provider: &LLMProvider,
-    model_name: &str,
-    api_key: &str,
+    chain: &LlmChain,
     messages: Vec<ChatMessage>,
     request_id: String,
-    ollama_endpoint: Option<&str>,
-    openai_compatible_endpoint: Option<&str>,
-    temperature: Option<f32>,
-    top_p: Option<f32>,
-    max_tokens: Option<u32>,
-    repeat_penalty: Option<f32>,
-    repeat_last_n: Option<i32>,
-    chat_template_kwargs: Option<ChatTemplateKwargs>,
+    completion_params: CompletionParams,
 ) -> Result<(), String> {
+    let primary = chain.primary();
     info!(
         "🌊 Starting streaming chat for request_id: {} with provider: {:?}, model: {}",
-        request_id, provider, model_name
+        request_id, primary.provider, primary.model
     );
 
-    let result = match provider {
-        LLMProvider::Claude => {
-            stream_chat_claude(
-                app.clone(),
-                client,
-                model_name,
-                api_key,
-                messages,
-                request_id.clone(),
-                temperature,
-                top_p,
-                max_tokens,
-                repeat_penalty,
-                repeat_last_n,
-            )
-            .await
-        }
-        _ => {
-            // OpenAI, Groq, OpenRouter, Ollama, OpenAI-compatible all use OpenAI-compatible format
-            stream_chat_openai_compatible(
-                app.clone(),
-                client,
-                provider,
-                model_name,
-                api_key,
-                messages,
-                request_id.clone(),
-                ollama_endpoint,
-                openai_compatible_endpoint,
-                temperature,
-                top_p,
-                max_tokens,
-                repeat_penalty,
-                repeat_last_n,
-                chat_template_kwargs,
-            )
-            .await
-        }
-    };
+    let streamed = AtomicBool::new(false);
+    let result = chain
+        .run_while(
+            "chat",
+            |link| {
+                let (app, request_id, streamed) = (&app, &request_id, &streamed);
+                let (messages, completion_params) = (&messages, &completion_params);
+                async move {
+                    let request = CompletionRequest {
+                        model: &link.model,
+                        messages,
+                        params: Some(completion_params),
+                        json_schema: None,
+                    };
+                    let mut emit_token = |content: &str| {
+                        streamed.store(true, Ordering::Relaxed);
+                        let _ = app.emit(
+                            "llm:chat:token",
+                            StreamTokenPayload {
+                                request_id: request_id.clone(),
+                                content_delta: content.to_string(),
+                            },
+                        );
+                    };
+                    let outcome = link
+                        .provider
+                        .backend()
+                        .stream(client, &link.config, request, &mut emit_token)
+                        .await?;
+                    Ok::<_, LlmError>((outcome, link))
+                }
+            },
+            || !streamed.load(Ordering::Relaxed),
+        )
+        .await;
 
     match result {
-        Ok(_) => {
+        Ok((outcome, link)) => {
+            // Log unified TTFT metrics at completion
+            log_ttft_metrics(outcome.ttft_us, outcome.total_time_us);
+
+            let prompts: Vec<&str> = messages.iter().map(|m| m.content.as_str()).collect();
+            let call = CallUsage::new(
+                outcome.usage.as_ref(),
+                &prompts,
+                &outcome.content,
+                outcome.ttft_us,
+                outcome.total_time_us,
+            );
+            chain.record_usage("chat", link, &call).await;
+
+            let _ = app.emit(
+                "llm:chat:done",
+                StreamDonePayload {
+                    request_id: request_id.clone(),
+                    usage: outcome.usage,
+                    finish_reason: outcome.finish_reason,
+                    model: Some(link.model.clone()),
+                    provider: Some(link.provider.backend().display_name().to_string()),
+                    ttft_us: outcome.ttft_us,
+                },
+            );
             info!("✅ Streaming completed successfully for request_id: {}", request_id);
             Ok(())
         }
@@ -1083,420 +368,3 @@ pub async fn stream_chat<R: Runtime>(
         }
     }
 }
-
-/// Streams chat from OpenAI-compatible providers (OpenAI, Groq, OpenRouter, Ollama, OpenAI-compatible)
-async fn stream_chat_openai_compatible<R: Runtime>(
-    app: tauri::AppHandle<R>,
-    client: &Client,
-    provider: &LLMProvider,
-    model_name: &str,
-    api_key: &str,
-    messages: Vec<ChatMessage>,
-    request_id: String,
-    ollama_endpoint: Option<&str>,
-    openai_compatible_endpoint: Option<&str>,
-    temperature: Option<f32>,
-    top_p: Option<f32>,
-    max_tokens: Option<u32>,
-    repeat_penalty: Option<f32>,
-    repeat_last_n: Option<i32>,
-    chat_template_kwargs: Option<ChatTemplateKwargs>,
-) -> Result<(), String> {
-    // Determine API endpoint
-    let (api_url, mut headers) = match provider {
-        LLMProvider::OpenAI => (
-            "https://api.openai.com/v1/chat/completions".to_string(),
-            header::HeaderMap::new(),
-        ),
-        LLMProvider::Groq => (
-            "https://api.groq.com/openai/v1/chat/completions".to_string(),
-            header::HeaderMap::new(),
-        ),
-        LLMProvider::OpenRouter => (
-            "https://openrouter.ai/api/v1/chat/completions".to_string(),
-            header::HeaderMap::new(),
-        ),
-        LLMProvider::Ollama => {
-            let host = ollama_endpoint
-                .map(|s| s.to_string())
-                .unwrap_or_else(|| "http://localhost:11434".to_string());
-            (
-                format!("{}/v1/chat/completions", host),
-                header::HeaderMap::new(),
-            )
-        }
-        LLMProvider::OpenAICompatible => {
-            let base_url = openai_compatible_endpoint
-                .ok_or("OpenAI Compatible endpoint not configured")?
-                .trim_end_matches('/');
-            (
-                format!("{}/chat/completions", base_url),
-                header::HeaderMap::new(),
-            )
-        }
-        _ => return Err("Unsupported provider for OpenAI-compatible streaming".to_string()),
-    };
-
-    // Add authorization header
-    if !api_key.is_empty() || provider != &LLMProvider::OpenAICompatible {
-        headers.insert(
-            header::AUTHORIZATION,
-            format!("Bearer {}", api_key)
-                .parse()
-                .map_err(|_| "Invalid authorization header".to_string())?,
-        );
-    }
-    headers.insert(
-        header::CONTENT_TYPE,
-        "application/json"
-            .parse()
-            .map_err(|_| "Invalid content type".to_string())?,
-    );
-
-    // Build streaming request body
-    let request_body = StreamingChatRequest {
-        model: model_name.to_string(),
-        messages,
-        stream: true,
-        temperature,
-        top_p,
-        max_tokens,
-        repeat_penalty,
-        repeat_last_n,
-        chat_template_kwargs,
-    };
-
-    info!("🚀 Sending streaming request to {}: {}", provider_name(provider), api_url);
-
-    // Log complete request payload in JSON format
-    if let Ok(json_payload) = serde_json::to_string_pretty(&request_body) {
-        info!("📤 LLM Request Payload:\n{}", json_payload);
-    }
-
-    // Send request
-    let response = client
-        .post(&api_url)
-        .headers(headers)
-        .json(&request_body)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to send streaming request: {}", e))?;
-
-    if !response.status().is_success() {
-        let error_body = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!("Streaming API request failed: {}", error_body));
-    }
-
-    // Process streaming response
-    let mut stream = response.bytes_stream();
-    let mut buffer = String::new();
-    let mut finish_reason: Option<String> = None;
-    let mut usage_stats: Option<StreamUsage> = None;
-    let mut ttft_us: Option<u64> = None;
-    let request_start_time = std::time::Instant::now();
-    let mut first_token_received = false;
-
-    while let Some(chunk_result) = stream.next().await {
-        match chunk_result {
-            Ok(chunk) => {
-                // Convert bytes to string
-                let chunk_str = String::from_utf8_lossy(&chunk);
-                buffer.push_str(&chunk_str);
-
-                // Process complete lines (SSE format: "data: {...}\n\n")
-                while let Some(line_end) = buffer.find("\n\n") {
-                    let line = buffer[..line_end].to_string();
-                    buffer = buffer[line_end + 2..].to_string();
-
-                    // Parse SSE line
-                    for sse_line in line.lines() {
-                        if sse_line.starts_with("data: ") {
-                            let data = &sse_line[6..]; // Skip "data: " prefix
-
-                            // Check for [DONE] signal
-                            if data.trim() == "[DONE]" {
-                                info!("🏁 Received [DONE] signal");
-                                break;
-                            }
-
-                            // Parse JSON chunk
-                            match serde_json::from_str::<StreamChatChunk>(data) {
-                                Ok(parsed_chunk) => {
-                                    // Extract content delta
-                                    if let Some(choice) = parsed_chunk.choices.first() {
-                                        if let Some(content) = &choice.delta.content {
-                                            if !content.is_empty() {
-                                                // Calculate TTFT on first token
-                                                if !first_token_received {
-                                                    first_token_received = true;
-                                                    // Calculate TTFT in microseconds for higher precision
-                                                    ttft_us = Some(request_start_time.elapsed().as_micros() as u64);
-                                                }
-
-                                                // Emit token event
-                                                let _ = app.emit(
-                                                    "llm:chat:token",
-                                                    StreamTokenPayload {
-                                                        request_id: request_id.clone(),
-                                                        content_delta: content.clone(),
-                                                    },
-                                                );
-                                            }
-                                        }
-
-                                        // Capture finish reason
-                                        if let Some(reason) = &choice.finish_reason {
-                                            finish_reason = Some(reason.clone());
-                                        }
-                                    }
-
-                                    // Capture usage stats
-                                    if let Some(usage) = parsed_chunk.usage {
-                                        usage_stats = Some(StreamUsage {
-                                            prompt_tokens: usage.prompt_tokens,
-                                            completion_tokens: usage.completion_tokens,
-                                            total_tokens: usage.total_tokens,
-                                        });
-                                    }
-                                }
-                                Err(e) => {
-                                    warn!("⚠️ Failed to parse streaming chunk: {} | Data: {}", e, data);
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-            Err(e) => {
-                return Err(format!("Stream error: {}", e));
-            }
-        }
-    }
-
-    // Log unified TTFT metrics at completion
-    let total_time_us = request_start_time.elapsed().as_micros() as u64;
-    log_ttft_metrics(ttft_us, total_time_us);
-
-    // Emit completion event
-    let _ = app.emit(
-        "llm:chat:done",
-        StreamDonePayload {
-            request_id: request_id.clone(),
-            usage: usage_stats,
-            finish_reason,
-            model: Some(model_name.to_string()),
-            provider: Some(provider_name(provider).to_string()),
-            ttft_us,
-        },
-    );
-
-    Ok(())
-}
-
-/// Streams chat from Claude (Anthropic)
-async fn stream_chat_claude<R: Runtime>(
-    app: tauri::AppHandle<R>,
-    client: &Client,
-    model_name: &str,
-    api_key: &str,
-    messages: Vec<ChatMessage>,
-    request_id: String,
-    temperature: Option<f32>,
-    top_p: Option<f32>,
-    max_tokens: Option<u32>,
-    _repeat_penalty: Option<f32>,
-    _repeat_last_n: Option<i32>,
-) -> Result<(), String> {
-    // Separate system message from user messages
-    let system_prompt = messages
-        .iter()
-        .find(|m| m.role == "system")
-        .map(|m| m.content.clone())
-        .unwrap_or_default();
-
-    let user_messages: Vec<ChatMessage> = messages
-        .into_iter()
-        .filter(|m| m.role != "system")
-        .collect();
-
-    // Build headers
-    let mut headers = header::HeaderMap::new();
-    headers.insert(
-        "x-api-key",
-        api_key
-            .parse()
-            .map_err(|_| "Invalid API key format".to_string())?,
-    );
-    headers.insert(
-        "anthropic-version",
-        "2023-06-01"
-            .parse()
-            .map_err(|_| "Invalid anthropic version".to_string())?,
-    );
-    headers.insert(
-        header::CONTENT_TYPE,
-        "application/json"
-            .parse()
-            .map_err(|_| "Invalid content type".to_string())?,
-    );
-
-    // Build streaming request body
-    let mut request_body = serde_json::json!({
-        "model": model_name,
-        "max_tokens": max_tokens.unwrap_or(2048),
-        "messages": user_messages,
-        "stream": true,
-    });
-
-    if !system_prompt.is_empty() {
-        request_body["system"] = serde_json::json!(system_prompt);
-    }
-    if let Some(temp) = temperature {
-        request_body["temperature"] = serde_json::json!(temp);
-    }
-    if let Some(p) = top_p {
-        request_body["top_p"] = serde_json::json!(p);
-    }
-
-    let api_url = "https://api.anthropic.com/v1/messages";
-    info!("🚀 Sending streaming request to Claude: {}", api_url);
-    info!("🐞 Full request body: {}", serde_json::to_string_pretty(&request_body).unwrap_or_default());
-
-    // Send request
-    let response = client
-        .post(api_url)
-        .headers(headers)
-        .json(&request_body)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to send streaming request to Claude: {}", e))?;
-
-    if !response.status().is_success() {
-        let error_body = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!("Claude streaming API request failed: {}", error_body));
-    }
-
-    // Process streaming response
-    let mut stream = response.bytes_stream();
-    let mut buffer = String::new();
-    let mut finish_reason: Option<String> = None;
-    let mut usage_input: Option<u32> = None;
-    let mut usage_output: Option<u32> = None;
-    let mut ttft_us: Option<u64> = None;
-    let request_start_time = std::time::Instant::now();
-    let mut first_token_received = false;
-
-    while let Some(chunk_result) = stream.next().await {
-        match chunk_result {
-            Ok(chunk) => {
-                let chunk_str = String::from_utf8_lossy(&chunk);
-                buffer.push_str(&chunk_str);
-
-                // Process complete lines (SSE format: "event: ...\ndata: {...}\n\n")
-                while let Some(line_end) = buffer.find("\n\n") {
-                    let block = buffer[..line_end].to_string();
-                    buffer = buffer[line_end + 2..].to_string();
-
-                    // Parse SSE block
-                    let mut event_type: Option<String> = None;
-                    let mut data: Option<String> = None;
-
-                    for line in block.lines() {
-                        if line.starts_with("event: ") {
-                            event_type = Some(line[7..].to_string());
-                        } else if line.starts_with("data: ") {
-                            data = Some(line[6..].to_string());
-                        }
-                    }
-
-                    if let Some(data_str) = data {
-                        match serde_json::from_str::<ClaudeStreamEvent>(&data_str) {
-                            Ok(event) => {
-                                match event {
-                                    ClaudeStreamEvent::MessageStart { message } => {
-                                        info!("📝 Claude message started: {}", message.id);
-                                        if let Some(input) = message.usage.input_tokens {
-                                            usage_input = Some(input);
-                                        }
-                                    }
-                                    ClaudeStreamEvent::ContentBlockDelta { delta } => {
-                                        // Calculate TTFT on first token
-                                        if !first_token_received && !delta.text.is_empty() {
-                                            first_token_received = true;
-                                            ttft_us = Some(request_start_time.elapsed().as_micros() as u64);
-                                        }
-
-                                        // Emit token event
-                                        let _ = app.emit(
-                                            "llm:chat:token",
-                                            StreamTokenPayload {
-                                                request_id: request_id.clone(),
-                                                content_delta: delta.text.clone(),
-                                            },
-                                        );
-                                    }
-                                    ClaudeStreamEvent::MessageDelta { delta, usage } => {
-                                        if let Some(reason) = delta.stop_reason {
-                                            finish_reason = Some(reason);
-                                        }
-                                        if let Some(output) = usage.output_tokens {
-                                            usage_output = Some(output);
-                                        }
-                                    }
-                                    ClaudeStreamEvent::Error { error } => {
-                                        return Err(format!("Claude error: {}", error.message));
-                                    }
-                                    _ => {}
-                                }
-                            }
-                            Err(e) => {
-                                warn!("⚠️ Failed to parse Claude streaming event: {} | Data: {}", e, data_str);
-                            }
-                        }
-                    }
-                }
-            }
-            Err(e) => {
-                return Err(format!("Claude stream error: {}", e));
-            }
-        }
-    }
-
-    // Emit completion event
-    let usage = if usage_input.is_some() || usage_output.is_some() {
-        Some(StreamUsage {
-            prompt_tokens: usage_input,
-            completion_tokens: usage_output,
-            total_tokens: match (usage_input, usage_output) {
-                (Some(i), Some(o)) => Some(i + o),
-                _ => None,
-            },
-        })
-    } else {
-        None
-    };
-
-    // Log unified TTFT metrics at completion
-    let total_time_us = request_start_time.elapsed().as_micros() as u64;
-    log_ttft_metrics(ttft_us, total_time_us);
-
-    let _ = app.emit(
-        "llm:chat:done",
-        StreamDonePayload {
-            request_id: request_id.clone(),
-            usage,
-            finish_reason,
-            model: Some(model_name.to_string()),
-            provider: Some("Claude".to_string()),
-            ttft_us,
-        },
-    );
-
-    Ok(())
-}
@@ -2,22 +2,77 @@
 ///
 /// This module contains:
 /// - LLM client for communicating with various AI providers (OpenAI, Claude, Groq, Ollama, OpenRouter)
+/// - Pluggable LLM backends and the registry provider ids resolve against
+/// - Failover chains that retry with backoff and fall back to other providers
+/// - Token usage and cost accounting with budget caps
 /// - Processor for chunking transcripts and generating summaries
+/// - Context window lookup that sizes the chunks for each model
+/// - Citations linking summary points to transcript timestamps
 /// - Service layer for orchestrating summary generation
+/// - Persistent job queue that runs, cancels and resumes summary jobs
+/// - Version history of generated and edited summaries, with section diffs
 /// - Templates for structured meeting summary generation
+/// - Structured action item extraction and tracking
 /// - Tauri commands for frontend integration
 
+pub mod action_item_commands;
+pub mod action_items;
+pub mod backend_commands;
+pub mod backends;
+pub mod citations;
 pub mod commands;
+pub mod context_window;
+pub mod failover;
 pub mod llm_client;
 pub mod processor;
+pub mod queue;
+pub mod schema;
 pub mod service;
 pub mod template_commands;
 pub mod templates;
+pub mod usage;
+pub mod usage_commands;
+pub mod version_commands;
+pub mod versions;
 
 // Re-export Tauri commands (with their generated __cmd__ variants)
 pub use commands::{
-    __cmd__api_get_summary, __cmd__api_process_transcript, __cmd__api_save_meeting_summary,
-    api_get_summary, api_process_transcript, api_save_meeting_summary,
+    __cmd__api_cancel_summary_job, __cmd__api_get_summary, __cmd__api_list_summary_jobs,
+    __cmd__api_process_transcript, __cmd__api_save_meeting_summary, api_cancel_summary_job,
+    api_get_summary, api_list_summary_jobs, api_process_transcript, api_save_meeting_summary,
+};
+
+// Re-export action item commands
+pub use action_item_commands::{
+    __cmd__api_extract_action_items, __cmd__api_get_meeting_action_items,
+    __cmd__api_list_open_action_items, __cmd__api_set_action_item_status,
+    api_extract_action_items, api_get_meeting_action_items, api_list_open_action_items,
+    api_set_action_item_status,
+};
+
+// Re-export LLM backend commands
+pub use backend_commands::{
+    __cmd__api_get_llm_failover_chain, __cmd__api_list_llm_backends, __cmd__api_list_llm_models,
+    __cmd__api_save_llm_endpoint, __cmd__api_save_llm_failover_chain, api_get_llm_failover_chain,
+    api_list_llm_backends, api_list_llm_models, api_save_llm_endpoint, api_save_llm_failover_chain,
+};
+
+// Re-export LLM usage and cost commands
+pub use usage_commands::{
+    __cmd__api_delete_llm_price, __cmd__api_get_llm_budget, __cmd__api_get_llm_spend_by_meeting,
+    __cmd__api_get_llm_spend_by_model, __cmd__api_get_llm_spend_by_month,
+    __cmd__api_get_meeting_llm_usage, __cmd__api_list_llm_prices, __cmd__api_save_llm_budget,
+    __cmd__api_save_llm_price, __cmd__api_sync_openrouter_prices, api_delete_llm_price,
+    api_get_llm_budget, api_get_llm_spend_by_meeting, api_get_llm_spend_by_model,
+    api_get_llm_spend_by_month, api_get_meeting_llm_usage, api_list_llm_prices,
+    api_save_llm_budget, api_save_llm_price, api_sync_openrouter_prices,
+};
+
+// Re-export summary version commands
+pub use version_commands::{
+    __cmd__api_diff_summary_versions, __cmd__api_list_summary_versions,
+    __cmd__api_restore_summary_version, api_diff_summary_versions, api_list_summary_versions,
+    api_restore_summary_version,
 };
 
 // Re-export template commands
@@ -38,7 +93,7 @@ pub use llm_client::{
     SummaryResult,
 };
 pub use processor::{
-    chunk_text, clean_llm_markdown_output, extract_meeting_name_from_markdown,
-    generate_meeting_summary, rough_token_count,
+    chunk_text, chunk_transcript, clean_llm_markdown_output, extract_meeting_name_from_markdown,
+    generate_meeting_summary, rough_token_count, GeneratedSummary,
 };
 pub use service::SummaryService;
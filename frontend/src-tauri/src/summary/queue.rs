@@ -0,0 +1,551 @@
+/// Persistent summary job queue
+///
+/// `api_process_transcript` queues a job here instead of spawning it. The
+/// job's `summary_processes` row keeps its status and request, so jobs still
+/// queued or running when the app quit are picked up again on startup. A job
+/// interrupted `MAX_JOB_ATTEMPTS` times is failed instead, as are unfinished
+/// rows without a stored request (from older versions or the CLI).
+///
+/// At most `LOCAL_CONCURRENCY` jobs run at once per local provider and
+/// `CLOUD_CONCURRENCY` per cloud provider. Queued jobs wait while a recording
+/// is live, so they don't compete with live transcription. Cancelling a
+/// running job drops it, which closes its in-flight HTTP streams.
+///
+/// Emits `summary:job` when a job's status changes and `summary:progress`
+/// as its chunks finish.
+use crate::audio;
+use crate::database::repositories::{
+    summary::SummaryProcessesRepository, transcript_chunk::TranscriptChunksRepository,
+};
+use crate::state::AppState;
+use crate::summary::failover::{ProgressSink, StageProgress};
+use crate::summary::llm_client::{CompletionParams, LLMProvider};
+use crate::summary::service::SummaryService;
+use futures_util::future::{AbortHandle, AbortRegistration, Abortable};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tokio::sync::Notify;
+use tracing::{error, info, warn};
+
+/// Jobs run at once on one local provider, which shares the user's machine
+const LOCAL_CONCURRENCY: usize = 1;
+
+/// Jobs run at once on one cloud provider
+const CLOUD_CONCURRENCY: usize = 3;
+
+/// Starts after which an interrupted job is failed rather than resumed
+pub const MAX_JOB_ATTEMPTS: i64 = 3;
+
+/// How often waiting jobs check whether the recording has ended
+const RECORDING_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+const JOB_EVENT: &str = "summary:job";
+const PROGRESS_EVENT: &str = "summary:progress";
+
+static QUEUE: Lazy<Mutex<QueueState>> = Lazy::new(|| Mutex::new(QueueState::default()));
+static WAKE: Lazy<Notify> = Lazy::new(Notify::new);
+
+/// A summary request, as stored in `summary_processes.job`
+///
+/// The transcript itself is read from `transcript_chunks` when the job starts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SummaryJob {
+    pub meeting_id: String,
+    pub model_provider: String,
+    pub model_name: String,
+    pub custom_prompt: String,
+    pub template_id: String,
+    pub language_id: String,
+    pub completion_params: Option<CompletionParams>,
+}
+
+impl SummaryJob {
+    /// Jobs with the same key share a concurrency limit
+    fn provider_key(&self) -> String {
+        LLMProvider::from_str(&self.model_provider)
+            .map(|provider| provider.id().to_string())
+            .unwrap_or_else(|_| self.model_provider.to_lowercase())
+    }
+
+    fn concurrency(&self) -> usize {
+        match LLMProvider::from_str(&self.model_provider) {
+            Ok(provider) if !provider.backend().capabilities().local => CLOUD_CONCURRENCY,
+            _ => LOCAL_CONCURRENCY,
+        }
+    }
+}
+
+/// A queued or running job, for the queue view
+#[derive(Debug, Clone, Serialize)]
+pub struct SummaryJobInfo {
+    pub meeting_id: String,
+    pub provider: String,
+    pub model: String,
+    /// "queued" or "processing"
+    pub status: String,
+    /// 1-based place in the queue, None while running
+    pub position: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct JobStatusPayload {
+    meeting_id: String,
+    /// "processing", "completed", "failed" or "cancelled"
+    status: String,
+    error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct JobProgressPayload {
+    meeting_id: String,
+    #[serde(flatten)]
+    progress: StageProgress,
+}
+
+struct RunningJob {
+    job: SummaryJob,
+    /// Tells this run apart from a later one for the same meeting
+    run_id: u64,
+    abort: AbortHandle,
+}
+
+#[derive(Default)]
+struct QueueState {
+    queued: VecDeque<SummaryJob>,
+    running: HashMap<String, RunningJob>,
+    next_run_id: u64,
+}
+
+impl QueueState {
+    /// Index of the first queued job whose provider has a free slot
+    fn next_runnable(&self) -> Option<usize> {
+        self.queued.iter().position(|job| {
+            let key = job.provider_key();
+            let running = self
+                .running
+                .values()
+                .filter(|r| r.job.provider_key() == key)
+                .count();
+            running < job.concurrency()
+        })
+    }
+
+    /// Drops a meeting's queued job and aborts its running one
+    ///
+    /// Returns whether there was either.
+    fn remove(&mut self, meeting_id: &str) -> bool {
+        let running = self.running.remove(meeting_id);
+        if let Some(running) = &running {
+            running.abort.abort();
+        }
+        let queued = self.queued.len();
+        self.queued.retain(|job| job.meeting_id != meeting_id);
+        running.is_some() || self.queued.len() != queued
+    }
+}
+
+fn queue() -> MutexGuard<'static, QueueState> {
+    QUEUE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn emit_status<R: Runtime>(
+    app: &AppHandle<R>,
+    meeting_id: &str,
+    status: &str,
+    error: Option<String>,
+) {
+    let _ = app.emit(
+        JOB_EVENT,
+        JobStatusPayload {
+            meeting_id: meeting_id.to_string(),
+            status: status.to_string(),
+            error,
+        },
+    );
+}
+
+/// Resumes the jobs left unfinished by the last run and starts dispatching
+pub async fn init<R: Runtime>(app: &AppHandle<R>) {
+    let Some(state) = app.try_state::<AppState>() else {
+        warn!("Database not ready, summary job queue not started");
+        return;
+    };
+    let pool = state.db_manager.pool().clone();
+    recover_jobs(&pool).await;
+    tauri::async_runtime::spawn(run_dispatcher(app.clone(), pool));
+}
+
+async fn recover_jobs(pool: &SqlitePool) {
+    let processes = match SummaryProcessesRepository::get_unfinished_processes(pool).await {
+        Ok(processes) => processes,
+        Err(e) => {
+            error!("Failed to load unfinished summary jobs: {}", e);
+            return;
+        }
+    };
+
+    let mut resumed = Vec::new();
+    for process in processes {
+        let job = process
+            .job
+            .as_deref()
+            .and_then(|job| serde_json::from_str::<SummaryJob>(job).ok());
+        let failure = match &job {
+            None => Some("Interrupted: the app closed before the summary finished".to_string()),
+            Some(_) if process.attempts >= MAX_JOB_ATTEMPTS => Some(format!(
+                "Interrupted {} times, not resuming",
+                process.attempts
+            )),
+            Some(_) => None,
+        };
+        if let Some(failure) = failure {
+            warn!(
+                "⚠️ Failing stale summary job for {}: {}",
+                process.meeting_id, failure
+            );
+            if let Err(e) = SummaryProcessesRepository::update_process_failed(
+                pool,
+                &process.meeting_id,
+                &failure,
+            )
+            .await
+            {
+                error!("Failed to fail stale job for {}: {}", process.meeting_id, e);
+            }
+            continue;
+        }
+        if process.status == "processing" {
+            if let Err(e) =
+                SummaryProcessesRepository::update_process_queued(pool, &process.meeting_id).await
+            {
+                error!("Failed to requeue job for {}: {}", process.meeting_id, e);
+                continue;
+            }
+        }
+        resumed.extend(job);
+    }
+
+    if !resumed.is_empty() {
+        info!("🔁 Resuming {} summary job(s)", resumed.len());
+        let mut state = queue();
+        for job in resumed {
+            if !state.running.contains_key(&job.meeting_id)
+                && !state.queued.iter().any(|q| q.meeting_id == job.meeting_id)
+            {
+                state.queued.push_back(job);
+            }
+        }
+    }
+}
+
+/// Queues a job, replacing any queued or running job for the same meeting
+///
+/// The meeting's `summary_processes` row must already be reset to 'PENDING'.
+pub async fn enqueue(pool: &SqlitePool, job: SummaryJob) -> Result<(), String> {
+    let request = serde_json::to_value(&job).map_err(|e| e.to_string())?;
+    SummaryProcessesRepository::set_process_job(pool, &job.meeting_id, &request)
+        .await
+        .map_err(|e| format!("Failed to queue summary job: {}", e))?;
+
+    {
+        let mut state = queue();
+        if state.remove(&job.meeting_id) {
+            info!(
+                "Replacing the unfinished summary job for {}",
+                job.meeting_id
+            );
+        }
+        info!(
+            "📥 Queued summary job for {} ({} ahead)",
+            job.meeting_id,
+            state.queued.len() + state.running.len()
+        );
+        state.queued.push_back(job);
+    }
+    WAKE.notify_one();
+    Ok(())
+}
+
+/// Cancels a meeting's queued or running job
+///
+/// Returns false if the meeting had no unfinished job.
+pub async fn cancel<R: Runtime>(
+    app: &AppHandle<R>,
+    pool: &SqlitePool,
+    meeting_id: &str,
+) -> Result<bool, String> {
+    let removed = queue().remove(meeting_id);
+    let cancelled = SummaryProcessesRepository::update_process_cancelled(pool, meeting_id)
+        .await
+        .map_err(|e| format!("Failed to cancel summary job: {}", e))?;
+
+    if removed || cancelled {
+        info!("🛑 Cancelled summary job for {}", meeting_id);
+        emit_status(app, meeting_id, "cancelled", None);
+        WAKE.notify_one();
+    }
+    Ok(removed || cancelled)
+}
+
+/// Running jobs, then queued jobs in the order they will start
+pub fn list() -> Vec<SummaryJobInfo> {
+    let state = queue();
+    let running = state.running.values().map(|r| SummaryJobInfo {
+        meeting_id: r.job.meeting_id.clone(),
+        provider: r.job.model_provider.clone(),
+        model: r.job.model_name.clone(),
+        status: "processing".to_string(),
+        position: None,
+    });
+    let queued = state
+        .queued
+        .iter()
+        .enumerate()
+        .map(|(i, job)| SummaryJobInfo {
+            meeting_id: job.meeting_id.clone(),
+            provider: job.model_provider.clone(),
+            model: job.model_name.clone(),
+            status: "queued".to_string(),
+            position: Some(i + 1),
+        });
+    running.chain(queued).collect()
+}
+
+async fn run_dispatcher<R: Runtime>(app: AppHandle<R>, pool: SqlitePool) {
+    loop {
+        start_runnable_jobs(&app, &pool).await;
+        // Recording state changes aren't signalled here, so also wake on a timer
+        let _ = tokio::time::timeout(RECORDING_POLL_INTERVAL, WAKE.notified()).await;
+    }
+}
+
+async fn start_runnable_jobs<R: Runtime>(app: &AppHandle<R>, pool: &SqlitePool) {
+    let waiting = !queue().queued.is_empty();
+    if !waiting || audio::is_recording().await {
+        return;
+    }
+
+    loop {
+        let (job, run_id, registration) = {
+            let mut state = queue();
+            let Some(index) = state.next_runnable() else {
+                break;
+            };
+            let Some(job) = state.queued.remove(index) else {
+                break;
+            };
+            let (abort, registration) = AbortHandle::new_pair();
+            state.next_run_id += 1;
+            let run_id = state.next_run_id;
+            state.running.insert(
+                job.meeting_id.clone(),
+                RunningJob {
+                    job: job.clone(),
+                    run_id,
+                    abort,
+                },
+            );
+            (job, run_id, registration)
+        };
+        tauri::async_runtime::spawn(run_job(
+            app.clone(),
+            pool.clone(),
+            job,
+            run_id,
+            registration,
+        ));
+    }
+}
+
+async fn run_job<R: Runtime>(
+    app: AppHandle<R>,
+    pool: SqlitePool,
+    job: SummaryJob,
+    run_id: u64,
+    registration: AbortRegistration,
+) {
+    let meeting_id = job.meeting_id.clone();
+    let outcome = Abortable::new(execute_job(app, pool, job), registration).await;
+    if outcome.is_err() {
+        info!("Summary job for {} stopped", meeting_id);
+    }
+
+    {
+        let mut state = queue();
+        if state
+            .running
+            .get(&meeting_id)
+            .is_some_and(|r| r.run_id == run_id)
+        {
+            state.running.remove(&meeting_id);
+        }
+    }
+    WAKE.notify_one();
+}
+
+async fn execute_job<R: Runtime>(app: AppHandle<R>, pool: SqlitePool, job: SummaryJob) {
+    let meeting_id = job.meeting_id.clone();
+    info!(
+        "🚀 Starting summary job for {} on {} ({})",
+        meeting_id, job.model_provider, job.model_name
+    );
+    if let Err(e) = SummaryProcessesRepository::update_process_running(&pool, &meeting_id).await {
+        warn!(
+            "⚠️ Failed to mark summary job for {} as running: {}",
+            meeting_id, e
+        );
+    }
+    emit_status(&app, &meeting_id, "processing", None);
+
+    let text = match TranscriptChunksRepository::get_transcript_text(&pool, &meeting_id).await {
+        Ok(Some(text)) => text,
+        Ok(None) => {
+            fail_job(
+                &app,
+                &pool,
+                &meeting_id,
+                "No transcript saved for this meeting",
+            )
+            .await;
+            return;
+        }
+        Err(e) => {
+            let error = format!("Failed to load transcript: {}", e);
+            fail_job(&app, &pool, &meeting_id, &error).await;
+            return;
+        }
+    };
+
+    let progress: ProgressSink = {
+        let (app, meeting_id) = (app.clone(), meeting_id.clone());
+        Arc::new(move |progress| {
+            let _ = app.emit(
+                PROGRESS_EVENT,
+                JobProgressPayload {
+                    meeting_id: meeting_id.clone(),
+                    progress,
+                },
+            );
+        })
+    };
+
+    let result = SummaryService::process_transcript_background(
+        app.clone(),
+        pool,
+        meeting_id.clone(),
+        text,
+        job.model_provider,
+        job.model_name,
+        job.custom_prompt,
+        job.template_id,
+        job.language_id,
+        job.completion_params,
+        Some(progress),
+    )
+    .await;
+
+    match result {
+        Ok(()) => emit_status(&app, &meeting_id, "completed", None),
+        Err(e) => emit_status(&app, &meeting_id, "failed", Some(e)),
+    }
+}
+
+async fn fail_job<R: Runtime>(
+    app: &AppHandle<R>,
+    pool: &SqlitePool,
+    meeting_id: &str,
+    error: &str,
+) {
+    error!("❌ Summary job for {} failed: {}", meeting_id, error);
+    if let Err(e) = SummaryProcessesRepository::update_process_failed(pool, meeting_id, error).await
+    {
+        error!(
+            "⚠️ Failed to update DB status to failed for {}: {}",
+            meeting_id, e
+        );
+    }
+    emit_status(app, meeting_id, "failed", Some(error.to_string()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(meeting_id: &str, provider: &str) -> SummaryJob {
+        SummaryJob {
+            meeting_id: meeting_id.to_string(),
+            model_provider: provider.to_string(),
+            model_name: "model".to_string(),
+            custom_prompt: String::new(),
+            template_id: "daily_standup".to_string(),
+            language_id: "en".to_string(),
+            completion_params: None,
+        }
+    }
+
+    fn running(state: &mut QueueState, job: SummaryJob) {
+        let (abort, _) = AbortHandle::new_pair();
+        state.running.insert(
+            job.meeting_id.clone(),
+            RunningJob {
+                job,
+                run_id: 0,
+                abort,
+            },
+        );
+    }
+
+    #[test]
+    fn test_next_runnable_respects_provider_limits() {
+        let mut state = QueueState::default();
+        running(&mut state, job("a", "ollama"));
+        state.queued.push_back(job("b", "Ollama"));
+        state.queued.push_back(job("c", "openai"));
+        assert_eq!(state.next_runnable(), Some(1));
+
+        for id in ["d", "e", "f"] {
+            running(&mut state, job(id, "openai"));
+        }
+        assert_eq!(state.next_runnable(), None);
+
+        state.running.remove("a");
+        assert_eq!(state.next_runnable(), Some(0));
+    }
+
+    #[test]
+    fn test_remove_aborts_running_and_drops_queued() {
+        let mut state = QueueState::default();
+        let (abort, registration) = AbortHandle::new_pair();
+        state.running.insert(
+            "a".to_string(),
+            RunningJob {
+                job: job("a", "ollama"),
+                run_id: 1,
+                abort,
+            },
+        );
+        state.queued.push_back(job("b", "ollama"));
+
+        assert!(state.remove("a"));
+        assert!(registration.handle().is_aborted());
+        assert!(state.remove("b"));
+        assert!(state.queued.is_empty());
+        assert!(!state.remove("c"));
+    }
+
+    #[test]
+    fn test_job_round_trips_through_json() {
+        let stored = serde_json::to_string(&job("a", "claude")).unwrap();
+        let loaded: SummaryJob = serde_json::from_str(&stored).unwrap();
+        assert_eq!(loaded.meeting_id, "a");
+        assert_eq!(loaded.provider_key(), "claude");
+        assert_eq!(loaded.concurrency(), CLOUD_CONCURRENCY);
+    }
+}
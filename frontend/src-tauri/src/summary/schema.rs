@@ -0,0 +1,411 @@
+/// JSON Schema checks for structured LLM output
+///
+/// Models asked for JSON often return almost-JSON: wrapped in code fences,
+/// preceded by a sentence, or with a field of the wrong type. `parse_json`
+/// digs the object out of the reply and `validate` checks it against the
+/// subset of JSON Schema the prompts use: `type` (a name or a list of names),
+/// `enum`, `properties`, `required`, `additionalProperties: false`, `items`,
+/// `minimum` and `maximum`. `repair` fixes small mistakes first, and the
+/// errors that remain name the offending path so they can be fed back to the
+/// model on retry.
+use serde_json::Value;
+
+/// Parses the JSON object or array in an LLM reply
+pub fn parse_json(reply: &str) -> Result<Value, String> {
+    let trimmed = reply.trim();
+    if let Ok(value) = serde_json::from_str::<Value>(trimmed) {
+        return Ok(value);
+    }
+
+    // Fall back to the outermost {...} or [...] span, which skips code fences
+    // and any prose around the JSON
+    let start = trimmed.find(|c| c == '{' || c == '[');
+    let end = trimmed.rfind(|c| c == '}' || c == ']');
+    match (start, end) {
+        (Some(start), Some(end)) if start < end => {
+            serde_json::from_str(&trimmed[start..=end]).map_err(|e| format!("Invalid JSON: {}", e))
+        }
+        _ => Err("The reply contains no JSON object".to_string()),
+    }
+}
+
+/// Checks `value` against `schema`, returning every violation found
+pub fn validate(value: &Value, schema: &Value) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+    check(value, schema, "$", &mut errors);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn check(value: &Value, schema: &Value, path: &str, errors: &mut Vec<String>) {
+    if let Some(expected) = schema.get("type") {
+        let names: Vec<&str> = match expected {
+            Value::String(name) => vec![name.as_str()],
+            Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !names.is_empty() && !names.iter().any(|name| has_type(value, name)) {
+            errors.push(format!(
+                "{}: expected {}, got {}",
+                path,
+                names.join(" or "),
+                type_name(value)
+            ));
+            return;
+        }
+    }
+
+    if let Some(Value::Array(allowed)) = schema.get("enum") {
+        if !allowed.contains(value) {
+            let allowed: Vec<String> = allowed.iter().map(Value::to_string).collect();
+            errors.push(format!("{}: must be one of {}", path, allowed.join(", ")));
+        }
+    }
+
+    if let Some(number) = value.as_f64() {
+        if let Some(minimum) = schema.get("minimum").and_then(Value::as_f64) {
+            if number < minimum {
+                errors.push(format!("{}: must be at least {}", path, minimum));
+            }
+        }
+        if let Some(maximum) = schema.get("maximum").and_then(Value::as_f64) {
+            if number > maximum {
+                errors.push(format!("{}: must be at most {}", path, maximum));
+            }
+        }
+    }
+
+    if let Value::Object(object) = value {
+        if let Some(Value::Array(required)) = schema.get("required") {
+            for key in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(key) {
+                    errors.push(format!("{}: missing required field \"{}\"", path, key));
+                }
+            }
+        }
+
+        let properties = schema.get("properties").and_then(Value::as_object);
+        let closed = schema.get("additionalProperties") == Some(&Value::Bool(false));
+        for (key, field) in object {
+            match properties.and_then(|p| p.get(key)) {
+                Some(field_schema) => {
+                    check(field, field_schema, &format!("{}.{}", path, key), errors)
+                }
+                None if closed => errors.push(format!("{}: unexpected field \"{}\"", path, key)),
+                None => {}
+            }
+        }
+    }
+
+    if let (Value::Array(items), Some(item_schema)) = (value, schema.get("items")) {
+        for (i, item) in items.iter().enumerate() {
+            check(item, item_schema, &format!("{}[{}]", path, i), errors);
+        }
+    }
+}
+
+/// Checks that a schema only uses the supported keywords correctly, so a bad
+/// template fails when it's loaded rather than when a summary is generated
+pub fn check_schema(schema: &Value) -> Result<(), String> {
+    check_schema_at(schema, "$")
+}
+
+fn check_schema_at(schema: &Value, path: &str) -> Result<(), String> {
+    let object = schema
+        .as_object()
+        .ok_or_else(|| format!("{}: schema must be an object", path))?;
+
+    if let Some(expected) = object.get("type") {
+        let names: Vec<&Value> = match expected {
+            Value::Array(names) => names.iter().collect(),
+            name => vec![name],
+        };
+        for name in names {
+            match name.as_str() {
+                Some(name) if TYPE_NAMES.contains(&name) => {}
+                _ => return Err(format!("{}: unknown type {}", path, name)),
+            }
+        }
+    }
+
+    if let Some(allowed) = object.get("enum") {
+        if !allowed.as_array().is_some_and(|a| !a.is_empty()) {
+            return Err(format!("{}: enum must be a non-empty array", path));
+        }
+    }
+
+    if let Some(properties) = object.get("properties") {
+        let properties = properties
+            .as_object()
+            .ok_or_else(|| format!("{}: properties must be an object", path))?;
+        for (key, field) in properties {
+            check_schema_at(field, &format!("{}.{}", path, key))?;
+        }
+        if let Some(required) = object.get("required") {
+            for key in required
+                .as_array()
+                .ok_or_else(|| format!("{}: required must be an array", path))?
+            {
+                match key.as_str() {
+                    Some(key) if properties.contains_key(key) => {}
+                    _ => {
+                        return Err(format!(
+                            "{}: required field {} is not a property",
+                            path, key
+                        ))
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(items) = object.get("items") {
+        check_schema_at(items, &format!("{}[]", path))?;
+    } else if has_type_name(schema, "array") {
+        return Err(format!("{}: arrays need an items schema", path));
+    }
+
+    Ok(())
+}
+
+/// Fixes the common near-misses in model output before validation: a single
+/// value where a list belongs, numbers or booleans where text belongs, an
+/// enum value in the wrong case, missing fields that can be left empty, and
+/// fields a closed object doesn't allow. Anything else is left for `validate`
+/// to report.
+pub fn repair(value: &mut Value, schema: &Value) {
+    if has_type_name(schema, "array") && !value.is_array() && !value.is_null() {
+        *value = Value::Array(vec![value.take()]);
+    }
+
+    if has_type_name(schema, "string") && !value.is_string() {
+        match value {
+            Value::Number(n) => *value = Value::String(n.to_string()),
+            Value::Bool(b) => *value = Value::String(b.to_string()),
+            _ => {}
+        }
+    }
+
+    if let (Some(Value::Array(allowed)), Value::String(text)) = (schema.get("enum"), &*value) {
+        let matched = allowed
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|option| option.eq_ignore_ascii_case(text.trim()));
+        if let Some(option) = matched {
+            *value = Value::String(option.to_string());
+        }
+    }
+
+    if let Value::Object(object) = value {
+        let properties = schema.get("properties").and_then(Value::as_object);
+
+        if let Some(Value::Array(required)) = schema.get("required") {
+            for key in required.iter().filter_map(Value::as_str) {
+                if object.contains_key(key) {
+                    continue;
+                }
+                let field_schema = properties.and_then(|p| p.get(key));
+                if let Some(empty) = field_schema.and_then(empty_value) {
+                    object.insert(key.to_string(), empty);
+                }
+            }
+        }
+
+        if schema.get("additionalProperties") == Some(&Value::Bool(false)) {
+            object.retain(|key, _| properties.is_some_and(|p| p.contains_key(key)));
+        }
+
+        if let Some(properties) = properties {
+            for (key, field) in object.iter_mut() {
+                if let Some(field_schema) = properties.get(key) {
+                    repair(field, field_schema);
+                }
+            }
+        }
+    }
+
+    if let (Value::Array(items), Some(item_schema)) = (value, schema.get("items")) {
+        for item in items {
+            repair(item, item_schema);
+        }
+    }
+}
+
+/// Stand-in for a missing field: null if allowed, else an empty list or text
+fn empty_value(schema: &Value) -> Option<Value> {
+    if has_type_name(schema, "null") {
+        Some(Value::Null)
+    } else if has_type_name(schema, "array") {
+        Some(Value::Array(Vec::new()))
+    } else if has_type_name(schema, "string") && schema.get("enum").is_none() {
+        Some(Value::String(String::new()))
+    } else {
+        None
+    }
+}
+
+/// Whether the schema's `type` is or includes `name`
+pub fn has_type_name(schema: &Value, name: &str) -> bool {
+    match schema.get("type") {
+        Some(Value::String(t)) => t == name,
+        Some(Value::Array(types)) => types.iter().any(|t| t.as_str() == Some(name)),
+        _ => false,
+    }
+}
+
+const TYPE_NAMES: &[&str] = &[
+    "object", "array", "string", "number", "integer", "boolean", "null",
+];
+
+fn has_type(value: &Value, name: &str) -> bool {
+    match name {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        _ => false,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_json_strips_fences_and_prose() {
+        let reply = "Here you go:\n```json\n{\"a\": [1, 2]}\n```\nLet me know!";
+        assert_eq!(parse_json(reply).unwrap(), json!({"a": [1, 2]}));
+        assert_eq!(parse_json(" [true] ").unwrap(), json!([true]));
+        assert!(parse_json("No items were found.").is_err());
+        assert!(parse_json("{\"a\": }").is_err());
+    }
+
+    #[test]
+    fn test_check_schema() {
+        assert!(check_schema(&json!({
+            "type": "object",
+            "required": ["topics"],
+            "properties": {
+                "topics": {"type": "array", "items": {"type": "string"}},
+                "mood": {"enum": ["good", "bad"]}
+            }
+        }))
+        .is_ok());
+
+        assert!(check_schema(&json!({"type": "text"})).is_err());
+        assert!(check_schema(&json!({"type": "array"})).is_err());
+        assert!(check_schema(&json!({"enum": []})).is_err());
+        assert!(check_schema(&json!({
+            "type": "object",
+            "required": ["missing"],
+            "properties": {"present": {"type": "string"}}
+        }))
+        .is_err());
+    }
+
+    #[test]
+    fn test_repair() {
+        let schema = json!({
+            "type": "object",
+            "required": ["summary", "decisions", "owner", "status"],
+            "additionalProperties": false,
+            "properties": {
+                "summary": {"type": "string"},
+                "decisions": {"type": "array", "items": {"type": "string"}},
+                "owner": {"type": ["string", "null"]},
+                "status": {"enum": ["on_track", "at_risk"]},
+                "count": {"type": "string"}
+            }
+        });
+
+        let mut value = json!({
+            "decisions": "Ship on Friday",
+            "status": "AT_RISK",
+            "count": 3,
+            "notes": "dropped"
+        });
+        repair(&mut value, &schema);
+        assert_eq!(
+            value,
+            json!({
+                "summary": "",
+                "decisions": ["Ship on Friday"],
+                "owner": null,
+                "status": "at_risk",
+                "count": "3"
+            })
+        );
+        assert!(validate(&value, &schema).is_ok());
+
+        // Nothing sensible to fill in for a missing enum
+        let mut value = json!({});
+        repair(&mut value, &schema);
+        assert!(validate(&value, &schema).is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        let schema = json!({
+            "type": "object",
+            "required": ["items"],
+            "additionalProperties": false,
+            "properties": {
+                "items": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "required": ["text"],
+                        "properties": {
+                            "text": {"type": "string"},
+                            "owner": {"type": ["string", "null"]},
+                            "status": {"enum": ["open", "done"]},
+                            "confidence": {"type": "number", "minimum": 0, "maximum": 1}
+                        }
+                    }
+                }
+            }
+        });
+
+        let valid = json!({"items": [
+            {"text": "Send notes", "owner": null, "status": "open", "confidence": 0.5},
+            {"text": "Book room", "owner": "Ana"}
+        ]});
+        assert!(validate(&valid, &schema).is_ok());
+
+        let invalid = json!({
+            "items": [{"owner": 3, "status": "later", "confidence": 2}],
+            "extra": true
+        });
+        // Field order depends on serde_json features, so compare sorted
+        let mut errors = validate(&invalid, &schema).unwrap_err();
+        errors.sort();
+        assert_eq!(
+            errors,
+            vec![
+                "$.items[0].confidence: must be at most 1",
+                "$.items[0].owner: expected string or null, got number",
+                "$.items[0].status: must be one of \"open\", \"done\"",
+                "$.items[0]: missing required field \"text\"",
+                "$: unexpected field \"extra\"",
+            ]
+        );
+    }
+}
@@ -1,19 +1,25 @@
 use crate::database::repositories::{
     meeting::MeetingsRepository, setting::SettingsRepository, summary::SummaryProcessesRepository,
+    summary_version::VersionOrigin,
 };
+use crate::hooks::{self, HookContext, HookEvent};
+use crate::summary::backends::BackendConfig;
+use crate::summary::failover::{self, LlmChain, ProgressSink};
+use crate::summary::{action_items, citations, context_window};
 use crate::summary::llm_client::{CompletionParams, LLMProvider};
-use crate::summary::processor::{extract_meeting_name_from_markdown, generate_meeting_summary};
-use crate::ollama::metadata::ModelMetadataCache;
+use crate::summary::processor::{
+    extract_meeting_name_from_markdown, generate_meeting_summary, GeneratedSummary,
+};
 use sqlx::SqlitePool;
-use std::time::{Duration, Instant};
+use std::time::Instant;
 use tauri::AppHandle;
 use tracing::{error, info, warn};
-use once_cell::sync::Lazy;
 
-// Global cache for model metadata (5 minute TTL)
-static METADATA_CACHE: Lazy<ModelMetadataCache> = Lazy::new(|| {
-    ModelMetadataCache::new(Duration::from_secs(300))
-});
+/// An LLM provider with the API key and endpoint stored for it
+pub struct ResolvedProvider {
+    pub provider: LLMProvider,
+    pub config: BackendConfig,
+}
 
 /// Summary service - handles all summary generation logic
 pub struct SummaryService;
@@ -21,7 +27,7 @@ pub struct SummaryService;
 impl SummaryService {
     /// Processes transcript in the background and generates summary
     ///
-    /// This function is designed to be spawned as an async task and does not block
+    /// Run by the summary job queue as an async task, so it does not block
     /// the main thread. It updates the database with progress and results.
     ///
     /// # Arguments
@@ -34,6 +40,9 @@ impl SummaryService {
     /// * `custom_prompt` - Optional user-provided context
     /// * `template_id` - Template identifier (e.g., "daily_standup", "standard_meeting")
     /// * `language_id` - Language code (e.g., "en", "zh-tw")
+    /// * `progress` - Receives per-chunk progress
+    ///
+    /// Returns the error that was recorded, after the hooks have been started.
     pub async fn process_transcript_background<R: tauri::Runtime>(
         _app: AppHandle<R>,
         pool: SqlitePool,
@@ -45,160 +54,116 @@ impl SummaryService {
         template_id: String,
         language_id: String,
         completion_params: Option<CompletionParams>,
-    ) {
+        progress: Option<ProgressSink>,
+    ) -> Result<(), String> {
+        let result = Self::process_transcript(
+            &pool,
+            &meeting_id,
+            &text,
+            &model_provider,
+            &model_name,
+            &custom_prompt,
+            &template_id,
+            &language_id,
+            completion_params,
+            progress,
+        )
+        .await;
+
+        match &result {
+            Ok(()) => hooks::fire(&pool, HookEvent::SummaryCompleted, HookContext::meeting(meeting_id)),
+            Err(e) => hooks::fire(
+                &pool,
+                HookEvent::SummaryFailed,
+                HookContext {
+                    error: Some(e.clone()),
+                    ..HookContext::meeting(meeting_id)
+                },
+            ),
+        }
+        result
+    }
+
+    /// Generates the summary for a meeting and records the outcome in its
+    /// summary process row (completed or failed), then extracts the meeting's
+    /// action items. Does not need a running app, so the CLI uses it directly.
+    /// Returns the error that was recorded.
+    pub async fn process_transcript(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        text: &str,
+        model_provider: &str,
+        model_name: &str,
+        custom_prompt: &str,
+        template_id: &str,
+        language_id: &str,
+        completion_params: Option<CompletionParams>,
+        progress: Option<ProgressSink>,
+    ) -> Result<(), String> {
         let start_time = Instant::now();
         info!(
             "🚀 Starting background processing for meeting_id: {}, provider: {}, model_name: {}",
             meeting_id, model_provider, model_name
         );
 
-        // Parse provider
-        let provider = match LLMProvider::from_str(&model_provider) {
-            Ok(p) => {
-                info!("✓ Parsed provider: {:?}", p);
-                p
-            }
-            Err(e) => {
-                let err_msg = format!("Failed to parse provider '{}': {}", model_provider, e);
-                error!("❌ {}", err_msg);
-                Self::update_process_failed(&pool, &meeting_id, &err_msg).await;
-                return;
-            }
-        };
-
-        // Validate and setup api_key, Flexible for Ollama
-        let api_key = match SettingsRepository::get_api_key(&pool, &model_provider).await {
-            Ok(Some(key)) if !key.is_empty() => {
-                info!("✓ Retrieved API key for {} (length: {})", &model_provider, key.len());
-                key
-            }
-            Ok(None) | Ok(Some(_)) => {
-                if provider != LLMProvider::Ollama {
-                    let err_msg = format!("Api key not found for {}", &model_provider);
-                    error!("❌ {}", err_msg);
-                    Self::update_process_failed(&pool, &meeting_id, &err_msg).await;
-                    return;
-                }
-                info!("✓ No API key needed for Ollama provider");
-                String::new()
-            }
+        // The stored segments carry the recording times the summary cites
+        let transcripts = match MeetingsRepository::get_meeting(pool, meeting_id).await {
+            Ok(meeting) => meeting.map(|m| m.transcripts).unwrap_or_default(),
             Err(e) => {
-                let err_msg = format!("Failed to retrieve api key for {} : {}", &model_provider, e);
-                error!("❌ {}", err_msg);
-                Self::update_process_failed(&pool, &meeting_id, &err_msg).await;
-                return;
-            }
-        };
-
-        // Get model config once and extract endpoints based on provider
-        let (ollama_endpoint, openai_compatible_endpoint) = match SettingsRepository::get_model_config(&pool).await {
-            Ok(Some(config)) => {
-                info!(
-                    "✓ Retrieved model config: ollama_endpoint={:?}, openai_compatible_endpoint={:?}",
-                    config.ollama_endpoint, config.openai_compatible_endpoint
+                warn!(
+                    "⚠️ Failed to load transcript segments for {}: {}",
+                    meeting_id, e
                 );
-                (config.ollama_endpoint, config.openai_compatible_endpoint)
-            }
-            Ok(None) => {
-                warn!("⚠️ Model config not found in database, endpoints will be None");
-                (None, None)
-            }
-            Err(e) => {
-                warn!("⚠️ Failed to retrieve model config: {}, endpoints will be None", e);
-                (None, None)
+                Vec::new()
             }
         };
+        let timestamped = citations::timestamped_transcript(&transcripts);
 
-        // Log which endpoint will be used based on provider
-        match provider {
-            LLMProvider::Ollama => {
-                if let Some(ref ep) = ollama_endpoint {
-                    info!("✓ Using Ollama endpoint: {}", ep);
-                } else {
-                    warn!("⚠️ Ollama endpoint not configured, will use default (localhost:11434)");
-                }
-            }
-            LLMProvider::OpenAICompatible => {
-                if let Some(ref ep) = openai_compatible_endpoint {
-                    info!("✓ Using OpenAI Compatible endpoint: {}", ep);
-                } else {
-                    error!("❌ OpenAI Compatible endpoint not configured in database");
-                    let err_msg = "OpenAI Compatible endpoint not configured".to_string();
-                    Self::update_process_failed(&pool, &meeting_id, &err_msg).await;
-                    return;
-                }
-            }
-            _ => {
-                // Other providers don't need custom endpoints
-            }
-        };
-
-        // Dynamically fetch context size for Ollama models
-        let token_threshold = if provider == LLMProvider::Ollama {
-            match METADATA_CACHE.get_or_fetch(&model_name, ollama_endpoint.as_deref()).await {
-                Ok(metadata) => {
-                    // Reserve 300 tokens for prompt overhead
-                    let optimal = metadata.context_size.saturating_sub(300);
-                    info!(
-                        "✓ Using dynamic context for {}: {} tokens (chunk size: {})",
-                        model_name, metadata.context_size, optimal
-                    );
-                    optimal
-                }
+        let chain =
+            match failover::build_chain(pool, model_provider, model_name, Some(meeting_id)).await {
+                Ok(chain) => chain,
                 Err(e) => {
-                    warn!(
-                        "⚠️ Failed to fetch context for {}: {}. Using default 4000",
-                        model_name, e
-                    );
-                    4000  // Fallback to safe default
+                    Self::update_process_failed(pool, meeting_id, &e).await;
+                    return Err(e);
                 }
-            }
-        } else {
-            // Cloud providers (OpenAI, Claude, Groq) handle large contexts automatically
-            100000  // Effectively unlimited for single-pass processing
+            };
+        let chain = match progress {
+            Some(sink) => chain.with_progress(sink),
+            None => chain,
         };
 
-        // Generate summary
-        info!(
-            "📝 Calling generate_meeting_summary with: provider={:?}, model_name={}, template_id={}, language_id={}, token_threshold={}, text_length={}",
-            provider, model_name, template_id, language_id, token_threshold, text.len()
-        );
-        if let Some(ref ep) = ollama_endpoint {
-            info!("  → Ollama endpoint: {}", ep);
-        }
-        if let Some(ref ep) = openai_compatible_endpoint {
-            info!("  → OpenAI Compatible endpoint: {}", ep);
-        }
-
-        let client = reqwest::Client::new();
-        let result = generate_meeting_summary(
-            &client,
-            &provider,
-            &model_name,
-            &api_key,
-            &text,
-            &custom_prompt,
-            &template_id,
-            &language_id,
-            token_threshold,
-            ollama_endpoint.as_deref(),
-            openai_compatible_endpoint.as_deref(),
-            completion_params,
+        let result = Self::generate_with_chain(
+            &chain,
+            timestamped.as_deref().unwrap_or(text),
+            custom_prompt,
+            template_id,
+            language_id,
+            completion_params.clone(),
         )
         .await;
 
         let duration = start_time.elapsed().as_secs_f64();
 
+        // Record every LLM attempt and which backend produced the summary
+        if let Err(e) =
+            SummaryProcessesRepository::update_process_metadata(pool, meeting_id, &chain.metadata())
+                .await
+        {
+            warn!("⚠️ Failed to save LLM attempts for {}: {}", meeting_id, e);
+        }
+
         match result {
-            Ok((mut final_markdown, num_chunks, ttft_us, total_time_us)) => {
+            Ok(GeneratedSummary {
+                markdown: mut final_markdown,
+                structured,
+                num_chunks,
+                ttft_us,
+                total_time_us,
+            }) => {
                 if num_chunks == 0 && final_markdown.is_empty() {
-                    Self::update_process_failed(
-                        &pool,
-                        &meeting_id,
-                        "Summary generation failed: No content was processed.",
-                    )
-                    .await;
-                    return;
+                    let err_msg = "Summary generation failed: No content was processed.";
+                    Self::update_process_failed(pool, meeting_id, err_msg).await;
+                    return Err(err_msg.to_string());
                 }
 
                 info!(
@@ -224,7 +189,7 @@ impl SummaryService {
                             name, meeting_id
                         );
                         if let Err(e) =
-                            MeetingsRepository::update_meeting_title(&pool, &meeting_id, &name).await
+                            MeetingsRepository::update_meeting_title(pool, meeting_id, &name).await
                         {
                             error!("⚠️ Failed to update meeting name for {}: {}", meeting_id, e);
                         }
@@ -248,24 +213,49 @@ impl SummaryService {
                     }
                 }
 
+                // Link `[mm:ss]` markers to the segments they cite
+                let (final_markdown, summary_citations) = if timestamped.is_some() {
+                    citations::link_citations(&final_markdown, &transcripts)
+                } else {
+                    (final_markdown, Vec::new())
+                };
+
                 // Create result JSON with markdown and timing metrics (summary_json will be added on first edit)
                 let mut result_json = serde_json::json!({
                     "markdown": final_markdown,
                     "total_time_us": total_time_us,
                 });
 
+                if !summary_citations.is_empty() {
+                    result_json["citations"] = serde_json::json!(summary_citations);
+                }
+
                 // Add TTFT if available
                 if let Some(ttft) = ttft_us {
                     result_json["ttft_us"] = serde_json::json!(ttft);
                 }
 
+                // Templates with an output schema also keep the JSON the markdown was rendered from
+                if let Some(structured) = structured {
+                    result_json["structured"] = structured;
+                }
+
+                // Keep the backend that actually produced the summary in its version
+                let (provider, model) = chain
+                    .produced_by()
+                    .map(|a| (a.provider, a.model))
+                    .unwrap_or_else(|| (model_provider.to_string(), model_name.to_string()));
+                let origin =
+                    VersionOrigin::generated(&provider, &model, template_id, custom_prompt);
+
                 // Update database with completed status
                 if let Err(e) = SummaryProcessesRepository::update_process_completed(
-                    &pool,
-                    &meeting_id,
+                    pool,
+                    meeting_id,
                     result_json,
                     num_chunks,
                     duration,
+                    &origin,
                 )
                 .await
                 {
@@ -273,17 +263,184 @@ impl SummaryService {
                         "⚠️ Failed to save completed process for {}: {}",
                         meeting_id, e
                     );
+                    Err(format!("Failed to save summary: {}", e))
                 } else {
                     info!(
                         "💾 Summary saved successfully for meeting_id: {}",
                         meeting_id
                     );
+
+                    // The summary stands even if the action items can't be extracted
+                    if let Err(e) = action_items::extract_for_meeting(
+                        pool,
+                        meeting_id,
+                        model_provider,
+                        model_name,
+                        completion_params,
+                    )
+                    .await
+                    {
+                        warn!(
+                            "⚠️ Action item extraction failed for {}: {}",
+                            meeting_id, e
+                        );
+                    }
+                    Ok(())
+                }
+            }
+            Err(e) => {
+                Self::update_process_failed(pool, meeting_id, &e).await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Generates a summary for `text` with the stored provider settings
+    ///
+    /// Resolves the API key and endpoint from the settings table, adds the
+    /// configured fallbacks, then runs `generate_with_chain`.
+    /// Nothing is written to the database apart from the LLM usage records.
+    ///
+    /// Returns the markdown, plus the JSON for templates with an output schema.
+    pub async fn generate(
+        pool: &SqlitePool,
+        model_provider: &str,
+        model_name: &str,
+        text: &str,
+        custom_prompt: &str,
+        template_id: &str,
+        language_id: &str,
+        completion_params: Option<CompletionParams>,
+    ) -> Result<GeneratedSummary, String> {
+        let chain = failover::build_chain(pool, model_provider, model_name, None).await?;
+        Self::generate_with_chain(
+            &chain,
+            text,
+            custom_prompt,
+            template_id,
+            language_id,
+            completion_params,
+        )
+        .await
+    }
+
+    /// Generates a summary for `text` through a failover chain
+    ///
+    /// Chunks are sized from the smallest context window in the chain, so
+    /// any link can take over mid-job. The chain's attempts are left for the
+    /// caller to record.
+    pub async fn generate_with_chain(
+        chain: &LlmChain,
+        text: &str,
+        custom_prompt: &str,
+        template_id: &str,
+        language_id: &str,
+        completion_params: Option<CompletionParams>,
+    ) -> Result<GeneratedSummary, String> {
+        let client = reqwest::Client::new();
+        let primary = chain.primary();
+
+        // Size chunks from the models' context windows
+        let context_window = chain.context_window(&client).await;
+        let token_threshold =
+            context_window::source_token_budget(context_window, completion_params.as_ref());
+        info!(
+            "✓ Using context window for {}: {} tokens (chunk size: {})",
+            primary.model, context_window, token_threshold
+        );
+
+        // Generate summary
+        info!(
+            "📝 Calling generate_meeting_summary with: provider={:?}, model_name={}, template_id={}, language_id={}, token_threshold={}, text_length={}",
+            primary.provider, primary.model, template_id, language_id, token_threshold, text.len()
+        );
+        if let Some(ref ep) = primary.config.endpoint {
+            info!("  → Endpoint: {}", ep);
+        }
+
+        generate_meeting_summary(
+            &client,
+            chain,
+            text,
+            custom_prompt,
+            template_id,
+            language_id,
+            token_threshold,
+            completion_params,
+        )
+        .await
+    }
+
+    /// Parses the provider and loads its API key and endpoint from the
+    /// settings table, failing if a required one is missing
+    pub async fn resolve_provider(
+        pool: &SqlitePool,
+        model_provider: &str,
+    ) -> Result<ResolvedProvider, String> {
+        // Parse provider
+        let provider = match LLMProvider::from_str(model_provider) {
+            Ok(p) => {
+                info!("✓ Parsed provider: {:?}", p);
+                p
+            }
+            Err(e) => {
+                let err_msg = format!("Failed to parse provider '{}': {}", model_provider, e);
+                error!("❌ {}", err_msg);
+                return Err(err_msg);
+            }
+        };
+
+        let backend = provider.backend();
+        let capabilities = backend.capabilities();
+
+        // Validate and setup api_key, optional for local backends
+        let api_key = match SettingsRepository::get_api_key(pool, provider.id()).await {
+            Ok(Some(key)) if !key.is_empty() => {
+                info!("✓ Retrieved API key for {} (length: {})", model_provider, key.len());
+                key
+            }
+            Ok(None) | Ok(Some(_)) => {
+                if capabilities.requires_api_key {
+                    let err_msg = format!("Api key not found for {}", model_provider);
+                    error!("❌ {}", err_msg);
+                    return Err(err_msg);
                 }
+                info!("✓ No API key needed for {} provider", backend.display_name());
+                String::new()
             }
             Err(e) => {
-                Self::update_process_failed(&pool, &meeting_id, &e).await;
+                let err_msg = format!("Failed to retrieve api key for {} : {}", model_provider, e);
+                error!("❌ {}", err_msg);
+                return Err(err_msg);
+            }
+        };
+
+        // Endpoint stored for this provider, if it takes one
+        let endpoint = match SettingsRepository::get_endpoint(pool, provider.id()).await {
+            Ok(endpoint) => endpoint,
+            Err(e) => {
+                warn!("⚠️ Failed to retrieve endpoint: {}, endpoint will be None", e);
+                None
+            }
+        };
+        let config = BackendConfig::new(api_key, endpoint);
+
+        match (&config.endpoint, capabilities.requires_endpoint) {
+            (Some(ep), _) => info!("✓ Using {} endpoint: {}", backend.display_name(), ep),
+            (None, true) => {
+                let err_msg = format!("{} endpoint not configured", backend.display_name());
+                error!("❌ {} in database", err_msg);
+                return Err(err_msg);
+            }
+            (None, false) if capabilities.custom_endpoint => {
+                warn!("⚠️ {} endpoint not configured, will use default", backend.display_name());
+            }
+            (None, false) => {
+                // Hosted providers don't need custom endpoints
             }
         }
+
+        Ok(ResolvedProvider { provider, config })
     }
 
     /// Updates the summary process status to failed with error message
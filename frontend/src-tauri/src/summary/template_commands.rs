@@ -30,6 +30,10 @@ pub struct TemplateDetails {
 
     /// List of section titles in order
     pub sections: Vec<String>,
+
+    /// JSON Schema of the summary, for templates with structured output
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_schema: Option<serde_json::Value>,
 }
 
 /// Lists all available templates
@@ -77,17 +81,32 @@ pub async fn api_get_template_details<R: Runtime>(
 
     let template = templates::get_template(&template_id)?;
 
-    let section_titles: Vec<String> = template
-        .sections
-        .iter()
-        .map(|section| section.title.clone())
-        .collect();
+    // Structured templates are laid out by their schema's top-level properties
+    let section_titles: Vec<String> = match template.output_schema {
+        Some(_) => template
+            .output_fields()
+            .into_iter()
+            .map(|(key, property)| {
+                property
+                    .get("title")
+                    .and_then(|title| title.as_str())
+                    .unwrap_or(key)
+                    .to_string()
+            })
+            .collect(),
+        None => template
+            .sections
+            .iter()
+            .map(|section| section.title.clone())
+            .collect(),
+    };
 
     let details = TemplateDetails {
         id: template_id,
         name: template.name,
         description: template.description,
         sections: section_titles,
+        output_schema: template.output_schema,
     };
 
     info!("Retrieved template details for '{}'", details.name);
@@ -157,6 +176,25 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_validate_structured_template() {
+        let structured_json = r#"
+        {
+            "name": "Structured",
+            "description": "Summary as JSON",
+            "output_schema": {
+                "type": "object",
+                "properties": {
+                    "decisions": {"type": "array", "items": {"type": "string"}}
+                }
+            }
+        }"#;
+
+        let template = templates::validate_and_parse_template(structured_json).unwrap();
+        assert!(template.sections.is_empty());
+        assert!(template.output_schema.is_some());
+    }
+
     #[tokio::test]
     async fn test_validate_template_invalid() {
         let invalid_json = "invalid json";
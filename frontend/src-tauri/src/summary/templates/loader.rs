@@ -125,7 +125,7 @@ pub fn get_template(template_id: &str) -> Result<Template, String> {
 /// # Returns
 /// Parsed and validated Template struct
 pub fn validate_and_parse_template(json_content: &str) -> Result<Template, String> {
-    let template: Template = serde_json::from_str(json_content)
+    let template = Template::from_json(json_content)
         .map_err(|e| format!("Failed to parse template JSON: {}", e))?;
 
     template.validate()?;
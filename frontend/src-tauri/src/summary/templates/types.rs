@@ -1,4 +1,8 @@
+use crate::summary::schema;
+use serde::de::{self, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::fmt;
 
 /// Represents a single section in a meeting template
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,11 +34,172 @@ pub struct Template {
     /// Brief description of the template's purpose
     pub description: String,
 
-    /// List of sections in the template
+    /// List of sections in the template (optional when `output_schema` is set)
+    #[serde(default)]
     pub sections: Vec<TemplateSection>,
+
+    /// JSON Schema of the summary. When set, the LLM returns JSON matching
+    /// it instead of markdown; each top-level property becomes a section of
+    /// the rendered markdown, titled by its "title" (or its key).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_schema: Option<Value>,
+
+    /// Property order of `output_schema` in the template file, which `Value`
+    /// doesn't keep. Filled in by `Template::from_json`.
+    #[serde(skip)]
+    pub output_order: FieldOrder,
+}
+
+/// Property order of an object schema, as written in the template file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FieldOrder {
+    /// `properties` keys in file order, each with its own schema's order
+    pub properties: Vec<(String, FieldOrder)>,
+    /// Order within an array's `items` schema
+    pub items: Option<Box<FieldOrder>>,
+}
+
+static NO_ORDER: FieldOrder = FieldOrder {
+    properties: Vec::new(),
+    items: None,
+};
+
+impl FieldOrder {
+    /// Order of the property `key`'s schema
+    fn property(&self, key: &str) -> &FieldOrder {
+        self.properties
+            .iter()
+            .find(|(k, _)| k == key)
+            .map_or(&NO_ORDER, |(_, order)| order)
+    }
+
+    fn items(&self) -> &FieldOrder {
+        self.items.as_deref().unwrap_or(&NO_ORDER)
+    }
+
+    /// Entries of `object` in property order; keys the schema doesn't list
+    /// follow in key order
+    fn sorted<'a>(&self, object: &'a Map<String, Value>) -> Vec<(&'a String, &'a Value)> {
+        let mut entries: Vec<_> = object.iter().collect();
+        entries.sort_by_key(|(key, _)| {
+            self.properties
+                .iter()
+                .position(|(k, _)| k == *key)
+                .unwrap_or(usize::MAX)
+        });
+        entries
+    }
+}
+
+impl<'de> Deserialize<'de> for FieldOrder {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(FieldOrderVisitor)
+    }
+}
+
+/// Reads a schema, keeping only the order of its `properties` and of those
+/// of its `items`
+struct FieldOrderVisitor;
+
+impl<'de> Visitor<'de> for FieldOrderVisitor {
+    type Value = FieldOrder;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a JSON schema")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<FieldOrder, A::Error> {
+        let mut order = FieldOrder::default();
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "properties" => order.properties = map.next_value::<Properties>()?.0,
+                "items" => order.items = Some(Box::new(map.next_value()?)),
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(order)
+    }
+
+    // Boolean schemas, tuple `items` and the like have no properties to order
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<FieldOrder, A::Error> {
+        while seq.next_element::<IgnoredAny>()?.is_some() {}
+        Ok(FieldOrder::default())
+    }
+
+    fn visit_bool<E: de::Error>(self, _: bool) -> Result<FieldOrder, E> {
+        Ok(FieldOrder::default())
+    }
+
+    fn visit_i64<E: de::Error>(self, _: i64) -> Result<FieldOrder, E> {
+        Ok(FieldOrder::default())
+    }
+
+    fn visit_u64<E: de::Error>(self, _: u64) -> Result<FieldOrder, E> {
+        Ok(FieldOrder::default())
+    }
+
+    fn visit_f64<E: de::Error>(self, _: f64) -> Result<FieldOrder, E> {
+        Ok(FieldOrder::default())
+    }
+
+    fn visit_str<E: de::Error>(self, _: &str) -> Result<FieldOrder, E> {
+        Ok(FieldOrder::default())
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<FieldOrder, E> {
+        Ok(FieldOrder::default())
+    }
+}
+
+/// A schema's `properties`, in file order
+struct Properties(Vec<(String, FieldOrder)>);
+
+impl<'de> Deserialize<'de> for Properties {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct PropertiesVisitor;
+
+        impl<'de> Visitor<'de> for PropertiesVisitor {
+            type Value = Properties;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("an object of property schemas")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Properties, A::Error> {
+                let mut properties = Vec::new();
+                while let Some(entry) = map.next_entry::<String, FieldOrder>()? {
+                    properties.push(entry);
+                }
+                Ok(Properties(properties))
+            }
+        }
+
+        deserializer.deserialize_map(PropertiesVisitor)
+    }
+}
+
+/// The parts of a template file read for their order
+#[derive(Deserialize)]
+struct TemplateOrder {
+    #[serde(default)]
+    output_schema: FieldOrder,
 }
 
 impl Template {
+    /// Parses a template file, keeping the property order of its output schema
+    pub fn from_json(json_content: &str) -> serde_json::Result<Self> {
+        let mut template: Template = serde_json::from_str(json_content)?;
+        if template.output_schema.is_some() {
+            // A schema with an unreadable order fails `validate` anyway
+            template.output_order = serde_json::from_str::<TemplateOrder>(json_content)
+                .map(|order| order.output_schema)
+                .unwrap_or_default();
+        }
+        Ok(template)
+    }
+
     /// Validates the template structure
     pub fn validate(&self) -> Result<(), String> {
         if self.name.is_empty() {
@@ -45,7 +210,17 @@ impl Template {
             return Err("Template description cannot be empty".to_string());
         }
 
-        if self.sections.is_empty() {
+        if let Some(output_schema) = &self.output_schema {
+            let has_properties = output_schema
+                .get("properties")
+                .and_then(Value::as_object)
+                .is_some_and(|p| !p.is_empty());
+            if !schema::has_type_name(output_schema, "object") || !has_properties {
+                return Err("output_schema must be an object schema with properties".to_string());
+            }
+            schema::check_schema(output_schema)
+                .map_err(|e| format!("Invalid output_schema: {}", e))?;
+        } else if self.sections.is_empty() {
             return Err("Template must have at least one section".to_string());
         }
 
@@ -81,6 +256,75 @@ impl Template {
         markdown
     }
 
+    /// The output schema with a required meeting `title` added, the JSON
+    /// counterpart of the markdown template's `# <Add Title here>` line.
+    /// None for markdown templates.
+    pub fn structured_output_schema(&self) -> Option<Value> {
+        let mut output_schema = self.output_schema.clone()?;
+
+        if let Some(properties) = output_schema
+            .get_mut("properties")
+            .and_then(Value::as_object_mut)
+        {
+            properties.entry("title").or_insert_with(|| {
+                json!({
+                    "type": "string",
+                    "description": "Concise, descriptive title for the meeting"
+                })
+            });
+        }
+
+        let required = output_schema
+            .as_object_mut()?
+            .entry("required")
+            .or_insert_with(|| json!([]));
+        if let Some(required) = required.as_array_mut() {
+            if !required.iter().any(|key| key == "title") {
+                required.insert(0, json!("title"));
+            }
+        }
+
+        Some(output_schema)
+    }
+
+    /// Top-level `output_schema` properties in template file order
+    pub fn output_fields(&self) -> Vec<(&String, &Value)> {
+        self.output_schema
+            .as_ref()
+            .and_then(|s| s.get("properties"))
+            .and_then(Value::as_object)
+            .map(|properties| self.output_order.sorted(properties))
+            .unwrap_or_default()
+    }
+
+    /// Renders a structured summary as markdown: the title as a `#` heading,
+    /// then one bold-titled section per top-level schema property
+    pub fn render_structured_markdown(&self, value: &Value) -> String {
+        let mut markdown = String::new();
+        if let Some(title) = value
+            .get("title")
+            .and_then(Value::as_str)
+            .filter(|title| !title.trim().is_empty())
+        {
+            markdown.push_str(&format!("# {}\n\n", title.trim()));
+        }
+
+        for (key, field_schema) in self.output_fields() {
+            if key == "title" {
+                continue;
+            }
+            markdown.push_str(&format!("**{}**\n\n", label(key, field_schema)));
+            markdown.push_str(&render_value(
+                value.get(key),
+                field_schema,
+                self.output_order.property(key),
+            ));
+            markdown.push_str("\n\n");
+        }
+
+        markdown.trim_end().to_string()
+    }
+
     /// Generates section-specific instructions for the LLM
     pub fn to_section_instructions(&self) -> String {
         let mut instructions = String::from(
@@ -109,6 +353,125 @@ impl Template {
     }
 }
 
+/// A property's "title", or its key in title case ("action_items" -> "Action Items")
+fn label(key: &str, field_schema: &Value) -> String {
+    if let Some(title) = field_schema.get("title").and_then(Value::as_str) {
+        return title.to_string();
+    }
+    key.split(|c: char| c == '_' || c == '-' || c.is_whitespace())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn render_value(value: Option<&Value>, field_schema: &Value, order: &FieldOrder) -> String {
+    const EMPTY: &str = "None noted in this section.";
+
+    match value {
+        None | Some(Value::Null) => EMPTY.to_string(),
+        Some(Value::Array(items)) if items.is_empty() => EMPTY.to_string(),
+        Some(Value::Array(items)) if items.iter().all(Value::is_object) => {
+            render_table(items, field_schema.get("items"), order.items())
+        }
+        Some(Value::Array(items)) => items
+            .iter()
+            .map(|item| format!("- {}", inline_text(item)))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Some(Value::Object(object)) => {
+            let properties = field_schema.get("properties");
+            order
+                .sorted(object)
+                .into_iter()
+                .map(|(key, field)| {
+                    let field_schema = properties.and_then(|p| p.get(key)).unwrap_or(&Value::Null);
+                    format!("- **{}**: {}", label(key, field_schema), inline_text(field))
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+        Some(scalar) => {
+            let text = inline_text(scalar);
+            if text.is_empty() {
+                EMPTY.to_string()
+            } else {
+                text
+            }
+        }
+    }
+}
+
+/// Arrays of objects become a table with one column per item property
+fn render_table(rows: &[Value], item_schema: Option<&Value>, order: &FieldOrder) -> String {
+    let mut columns: Vec<(String, String)> = item_schema
+        .and_then(|s| s.get("properties"))
+        .and_then(Value::as_object)
+        .map(|properties| {
+            order
+                .sorted(properties)
+                .into_iter()
+                .map(|(key, field_schema)| (key.clone(), label(key, field_schema)))
+                .collect()
+        })
+        .unwrap_or_default();
+    // Keys the schema doesn't list still get a column
+    for row in rows.iter().filter_map(Value::as_object) {
+        for key in row.keys() {
+            if !columns.iter().any(|(k, _)| k == key) {
+                columns.push((key.clone(), label(key, &Value::Null)));
+            }
+        }
+    }
+
+    let cell = |text: String| text.replace('|', "\\|").replace('\n', " ");
+    let mut lines = vec![
+        format!(
+            "| {} |",
+            columns
+                .iter()
+                .map(|(_, l)| format!("**{}**", l))
+                .collect::<Vec<_>>()
+                .join(" | ")
+        ),
+        format!("|{}", " --- |".repeat(columns.len())),
+    ];
+    for row in rows {
+        let cells: Vec<String> = columns
+            .iter()
+            .map(|(key, _)| cell(row.get(key).map(inline_text).unwrap_or_default()))
+            .collect();
+        lines.push(format!("| {} |", cells.join(" | ")));
+    }
+    lines.join("\n")
+}
+
+/// A value as one line of text; nested lists are comma-separated
+fn inline_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.trim().to_string(),
+        Value::Array(items) => items
+            .iter()
+            .map(inline_text)
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join(", "),
+        Value::Object(object) => object
+            .iter()
+            .map(|(key, field)| format!("{}: {}", key, inline_text(field)))
+            .collect::<Vec<_>>()
+            .join(", "),
+        other => other.to_string(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -127,6 +490,8 @@ mod tests {
                     example_item_format: None,
                 },
             ],
+            output_schema: None,
+            output_order: FieldOrder::default(),
         };
 
         assert!(template.validate().is_ok());
@@ -138,6 +503,8 @@ mod tests {
             name: "".to_string(),
             description: "A test template".to_string(),
             sections: vec![],
+            output_schema: None,
+            output_order: FieldOrder::default(),
         };
 
         assert!(template.validate().is_err());
@@ -157,8 +524,110 @@ mod tests {
                     example_item_format: None,
                 },
             ],
+            output_schema: None,
+            output_order: FieldOrder::default(),
         };
 
         assert!(template.validate().is_err());
     }
+
+    fn structured_template() -> Template {
+        Template::from_json(
+            r#"{
+                "name": "Project Sync",
+                "description": "Status per workstream",
+                "output_schema": {
+                    "type": "object",
+                    "required": ["summary", "status", "risks"],
+                    "properties": {
+                        "summary": {"type": "string"},
+                        "status": {"title": "Overall Status", "enum": ["on_track", "at_risk"]},
+                        "risks": {
+                            "type": "array",
+                            "items": {
+                                "type": "object",
+                                "properties": {
+                                    "risk": {"type": "string"},
+                                    "owner": {"type": ["string", "null"]}
+                                }
+                            }
+                        },
+                        "next_steps": {"type": "array", "items": {"type": "string"}}
+                    }
+                }
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_validate_structured_template() {
+        assert!(structured_template().validate().is_ok());
+
+        let mut template = structured_template();
+        template.output_schema = Some(json!({"type": "array", "items": {"type": "string"}}));
+        assert!(template.validate().is_err());
+
+        let mut template = structured_template();
+        template.output_schema = Some(json!({
+            "type": "object",
+            "properties": {"status": {"type": "enum"}}
+        }));
+        assert!(template.validate().is_err());
+    }
+
+    #[test]
+    fn test_structured_output_schema_adds_title() {
+        let output_schema = structured_template().structured_output_schema().unwrap();
+        assert_eq!(
+            output_schema["required"],
+            json!(["title", "summary", "status", "risks"])
+        );
+        assert_eq!(output_schema["properties"]["title"]["type"], "string");
+        assert_eq!(output_schema["properties"].as_object().unwrap().len(), 5);
+    }
+
+    #[test]
+    fn test_output_fields_keep_file_order() {
+        let template = structured_template();
+        let keys: Vec<&String> = template
+            .output_fields()
+            .into_iter()
+            .map(|(k, _)| k)
+            .collect();
+        assert_eq!(keys, ["summary", "status", "risks", "next_steps"]);
+        let columns: Vec<&str> = template
+            .output_order
+            .property("risks")
+            .items()
+            .properties
+            .iter()
+            .map(|(k, _)| k.as_str())
+            .collect();
+        assert_eq!(columns, ["risk", "owner"]);
+    }
+
+    #[test]
+    fn test_render_structured_markdown() {
+        let value = json!({
+            "title": "Q3 Planning",
+            "summary": "Scope agreed.",
+            "status": "at_risk",
+            "risks": [
+                {"risk": "Vendor | delay", "owner": "Ana"},
+                {"risk": "Hiring", "owner": null}
+            ],
+            "next_steps": []
+        });
+        assert_eq!(
+            structured_template().render_structured_markdown(&value),
+            "# Q3 Planning\n\n\
+             **Summary**\n\nScope agreed.\n\n\
+             **Overall Status**\n\nat_risk\n\n\
+             **Risks**\n\n\
+             | **Risk** | **Owner** |\n| --- | --- |\n\
+             | Vendor \\| delay | Ana |\n| Hiring |  |\n\n\
+             **Next Steps**\n\nNone noted in this section."
+        );
+    }
 }
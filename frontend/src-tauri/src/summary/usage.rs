@@ -0,0 +1,384 @@
+/// LLM usage and cost accounting
+///
+/// Every successful call made through an `LlmChain` with a `UsageRecorder`
+/// is stored in `llm_usage` with its tokens, timing and cost. Prices come
+/// from `llm_prices`: ones the user entered first, then OpenRouter's
+/// published prices, synced when a call needs a price and the last sync is
+/// over a day old. Calls to local backends cost nothing.
+///
+/// Optional budget caps block cloud calls once this month's spend (UTC) or
+/// the meeting's spend reaches them; local backends are never blocked. While
+/// a cap is set, cloud models without a known price are blocked too, since
+/// their spend can't be counted against it.
+use crate::database::models::LlmPrice;
+use crate::database::repositories::llm_usage::{LlmUsageRepository, NewLlmUsage};
+use crate::database::repositories::setting::SettingsRepository;
+use crate::summary::failover::ChainLink;
+use crate::summary::llm_client::{LLMProvider, StreamUsage};
+use crate::summary::processor::rough_token_count;
+use chrono::{Datelike, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::SqlitePool;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+const OPENROUTER_MODELS_URL: &str = "https://openrouter.ai/api/v1/models";
+
+/// Synced prices older than this are refreshed
+const PRICE_MAX_AGE_HOURS: i64 = 24;
+
+/// Minimum wait between sync attempts, so an offline machine doesn't try on every call
+const SYNC_RETRY_INTERVAL: Duration = Duration::from_secs(3600);
+
+static LAST_SYNC_ATTEMPT: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Where a chain records its calls
+#[derive(Debug, Clone)]
+pub struct UsageRecorder {
+    pub pool: SqlitePool,
+    /// Meeting the calls are charged to, None for calls outside a meeting
+    pub meeting_id: Option<String>,
+}
+
+/// Budget caps in USD; None means no cap
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct LlmBudget {
+    pub monthly_usd: Option<f64>,
+    pub per_meeting_usd: Option<f64>,
+}
+
+/// Tokens and timing of one successful call
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallUsage {
+    pub prompt_tokens: i64,
+    pub completion_tokens: i64,
+    /// Some counts were estimated because the provider didn't report them
+    pub estimated: bool,
+    pub ttft_us: Option<u64>,
+    pub total_time_us: u64,
+}
+
+impl CallUsage {
+    /// Uses the provider's counts where there are any, and estimates the
+    /// rest from the prompt and reply text
+    pub fn new(
+        usage: Option<&StreamUsage>,
+        prompts: &[&str],
+        reply: &str,
+        ttft_us: Option<u64>,
+        total_time_us: u64,
+    ) -> Self {
+        let prompt_tokens = usage.and_then(|u| u.prompt_tokens);
+        let completion_tokens = usage.and_then(|u| u.completion_tokens);
+        Self {
+            prompt_tokens: prompt_tokens
+                .map(i64::from)
+                .unwrap_or_else(|| prompts.iter().map(|p| rough_token_count(p) as i64).sum()),
+            completion_tokens: completion_tokens
+                .map(i64::from)
+                .unwrap_or_else(|| rough_token_count(reply) as i64),
+            estimated: prompt_tokens.is_none() || completion_tokens.is_none(),
+            ttft_us,
+            total_time_us,
+        }
+    }
+}
+
+pub fn cost_usd(price: &LlmPrice, prompt_tokens: i64, completion_tokens: i64) -> f64 {
+    (prompt_tokens as f64 * price.prompt_usd_per_mtok
+        + completion_tokens as f64 * price.completion_usd_per_mtok)
+        / 1_000_000.0
+}
+
+/// The OpenRouter id of a model served by its own vendor's API
+fn openrouter_model_id(provider: &LLMProvider, model: &str) -> Option<String> {
+    let vendor = match provider {
+        LLMProvider::OpenRouter => return Some(model.to_string()),
+        LLMProvider::OpenAI => "openai",
+        LLMProvider::Claude => "anthropic",
+        LLMProvider::Gemini => "google",
+        LLMProvider::Mistral => "mistralai",
+        _ => return None,
+    };
+    Some(format!(
+        "{}/{}",
+        vendor,
+        model.strip_prefix("models/").unwrap_or(model)
+    ))
+}
+
+/// `(model, prompt, completion)` prices in USD per million tokens from
+/// OpenRouter's model list, which gives USD per token as strings. Models
+/// with variable pricing (negative values) are skipped.
+fn parse_openrouter_prices(listing: &Value) -> Vec<(String, f64, f64)> {
+    let per_mtok = |value: &Value| {
+        value
+            .as_str()
+            .and_then(|price| price.parse::<f64>().ok())
+            .filter(|price| *price >= 0.0)
+            .map(|price| price * 1_000_000.0)
+    };
+    listing["data"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|model| {
+            Some((
+                model["id"].as_str()?.to_string(),
+                per_mtok(&model["pricing"]["prompt"])?,
+                per_mtok(&model["pricing"]["completion"])?,
+            ))
+        })
+        .collect()
+}
+
+/// Fetches OpenRouter's public model list and stores its prices
+///
+/// # Returns
+/// The number of prices stored
+pub async fn sync_openrouter_prices(pool: &SqlitePool) -> Result<usize, String> {
+    let listing: Value = reqwest::Client::new()
+        .get(OPENROUTER_MODELS_URL)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to make HTTP request: {}", e))?
+        .error_for_status()
+        .map_err(|e| format!("HTTP request failed: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse JSON response: {}", e))?;
+
+    let prices = parse_openrouter_prices(&listing);
+    let saved = LlmUsageRepository::save_synced_prices(pool, &prices)
+        .await
+        .map_err(|e| format!("Failed to save prices: {}", e))?;
+    info!("💲 Synced {} OpenRouter prices", saved);
+    Ok(saved)
+}
+
+/// Syncs OpenRouter's prices if they are missing or stale, at most once an hour
+async fn sync_if_stale(pool: &SqlitePool) {
+    {
+        let Ok(mut last_attempt) = LAST_SYNC_ATTEMPT.lock() else {
+            return;
+        };
+        if last_attempt.is_some_and(|at| at.elapsed() < SYNC_RETRY_INTERVAL) {
+            return;
+        }
+        *last_attempt = Some(Instant::now());
+    }
+
+    let stale = match LlmUsageRepository::last_price_sync(pool).await {
+        Ok(Some(at)) => Utc::now() - at > chrono::Duration::hours(PRICE_MAX_AGE_HOURS),
+        Ok(None) => true,
+        Err(e) => {
+            warn!("⚠️ Failed to check the last price sync: {}", e);
+            false
+        }
+    };
+    if stale {
+        if let Err(e) = sync_openrouter_prices(pool).await {
+            warn!("⚠️ Failed to sync OpenRouter prices: {}", e);
+        }
+    }
+}
+
+/// Price of `model` on `provider`: the user's own price, then OpenRouter's
+pub async fn find_price(
+    pool: &SqlitePool,
+    provider: &LLMProvider,
+    model: &str,
+) -> Option<LlmPrice> {
+    match LlmUsageRepository::find_price(pool, provider.id(), model).await {
+        Ok(Some(price)) => return Some(price),
+        Ok(None) => {}
+        Err(e) => {
+            warn!("⚠️ Failed to load price for {}: {}", model, e);
+            return None;
+        }
+    }
+
+    let openrouter_id = openrouter_model_id(provider, model)?;
+    if let Ok(Some(price)) =
+        LlmUsageRepository::find_price(pool, "openrouter", &openrouter_id).await
+    {
+        return Some(price);
+    }
+    sync_if_stale(pool).await;
+    LlmUsageRepository::find_price(pool, "openrouter", &openrouter_id)
+        .await
+        .ok()
+        .flatten()
+}
+
+/// Stores one successful call with its cost
+pub async fn record(recorder: &UsageRecorder, stage: &str, link: &ChainLink, call: &CallUsage) {
+    let local = link.provider.backend().capabilities().local;
+    let cost_usd = if local {
+        Some(0.0)
+    } else {
+        let price = find_price(&recorder.pool, &link.provider, &link.model).await;
+        if price.is_none() {
+            warn!(
+                "⚠️ No price known for {} model {}, its cost isn't counted in LLM spend",
+                link.provider.id(),
+                link.model
+            );
+        }
+        price.map(|price| cost_usd(&price, call.prompt_tokens, call.completion_tokens))
+    };
+
+    let usage = NewLlmUsage {
+        meeting_id: recorder.meeting_id.clone(),
+        stage: stage.to_string(),
+        provider: link.provider.id().to_string(),
+        model: link.model.clone(),
+        local,
+        prompt_tokens: call.prompt_tokens,
+        completion_tokens: call.completion_tokens,
+        estimated: call.estimated,
+        ttft_us: call.ttft_us.map(|t| t as i64),
+        total_time_us: call.total_time_us as i64,
+        cost_usd,
+    };
+    if let Err(e) = LlmUsageRepository::insert(&recorder.pool, &usage).await {
+        warn!("⚠️ Failed to record LLM usage for {}: {}", stage, e);
+    }
+}
+
+pub async fn get_budget(pool: &SqlitePool) -> Result<LlmBudget, String> {
+    let (monthly_usd, per_meeting_usd) = SettingsRepository::get_llm_budget(pool)
+        .await
+        .map_err(|e| format!("Failed to load LLM budget: {}", e))?;
+    Ok(LlmBudget {
+        monthly_usd,
+        per_meeting_usd,
+    })
+}
+
+/// Fails if a budget cap has been reached, or a cap applies and the
+/// model has no known price, so the cloud call to `link` isn't made
+///
+/// If the budget or spend can't be read, the call goes ahead.
+pub async fn check_budget(recorder: &UsageRecorder, link: &ChainLink) -> Result<(), String> {
+    let budget = match get_budget(&recorder.pool).await {
+        Ok(budget) => budget,
+        Err(e) => {
+            warn!("⚠️ {}, not enforcing it", e);
+            return Ok(());
+        }
+    };
+
+    let capped = budget.monthly_usd.is_some()
+        || (budget.per_meeting_usd.is_some() && recorder.meeting_id.is_some());
+    if capped
+        && find_price(&recorder.pool, &link.provider, &link.model)
+            .await
+            .is_none()
+    {
+        return Err(format!(
+            "No price is known for {} model {}, so its cost can't be counted against the LLM budget; add a price for it or remove the budget cap",
+            link.provider.id(),
+            link.model
+        ));
+    }
+
+    if let Some(cap) = budget.monthly_usd {
+        let now = Utc::now();
+        let month_start = Utc
+            .with_ymd_and_hms(now.year(), now.month(), 1, 0, 0, 0)
+            .single()
+            .unwrap_or(now);
+        match LlmUsageRepository::cloud_spend_since(&recorder.pool, month_start).await {
+            Ok(spent) if spent >= cap => {
+                return Err(format!(
+                    "Monthly LLM budget of ${:.2} reached (${:.2} spent this month); cloud calls are blocked",
+                    cap, spent
+                ));
+            }
+            Ok(_) => {}
+            Err(e) => warn!("⚠️ Failed to load this month's LLM spend: {}", e),
+        }
+    }
+
+    if let (Some(cap), Some(meeting_id)) = (budget.per_meeting_usd, &recorder.meeting_id) {
+        match LlmUsageRepository::meeting_cloud_spend(&recorder.pool, meeting_id).await {
+            Ok(spent) if spent >= cap => {
+                return Err(format!(
+                    "Per-meeting LLM budget of ${:.2} reached (${:.2} spent on this meeting); cloud calls are blocked",
+                    cap, spent
+                ));
+            }
+            Ok(_) => {}
+            Err(e) => warn!("⚠️ Failed to load LLM spend for {}: {}", meeting_id, e),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_call_usage_estimates_missing_counts() {
+        let reported = StreamUsage {
+            prompt_tokens: Some(1200),
+            completion_tokens: Some(300),
+            total_tokens: Some(1500),
+        };
+        let call = CallUsage::new(Some(&reported), &["ignored"], "ignored", Some(5), 10);
+        assert_eq!((call.prompt_tokens, call.completion_tokens), (1200, 300));
+        assert!(!call.estimated);
+
+        let call = CallUsage::new(None, &["12345678", "1234"], "123456", None, 10);
+        assert_eq!((call.prompt_tokens, call.completion_tokens), (3, 2));
+        assert!(call.estimated);
+    }
+
+    #[test]
+    fn test_cost_usd() {
+        let price = LlmPrice {
+            provider: "openai".to_string(),
+            model: "gpt-4o".to_string(),
+            prompt_usd_per_mtok: 2.5,
+            completion_usd_per_mtok: 10.0,
+            source: "user".to_string(),
+            updated_at: Utc::now(),
+        };
+        let cost = cost_usd(&price, 200_000, 10_000);
+        assert!((cost - 0.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_openrouter_model_id() {
+        assert_eq!(
+            openrouter_model_id(&LLMProvider::OpenRouter, "meta-llama/llama-3-70b").as_deref(),
+            Some("meta-llama/llama-3-70b")
+        );
+        assert_eq!(
+            openrouter_model_id(&LLMProvider::Gemini, "models/gemini-2.0-flash").as_deref(),
+            Some("google/gemini-2.0-flash")
+        );
+        assert_eq!(
+            openrouter_model_id(&LLMProvider::AzureOpenAI, "notes"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_openrouter_prices() {
+        let listing = serde_json::json!({"data": [
+            {"id": "openai/gpt-4o", "pricing": {"prompt": "0.0000025", "completion": "0.00001"}},
+            {"id": "openrouter/auto", "pricing": {"prompt": "-1", "completion": "-1"}},
+            {"id": "x/no-pricing"},
+        ]});
+        let prices = parse_openrouter_prices(&listing);
+        assert_eq!(prices.len(), 1);
+        assert_eq!(prices[0].0, "openai/gpt-4o");
+        assert!((prices[0].1 - 2.5).abs() < 1e-9);
+        assert!((prices[0].2 - 10.0).abs() < 1e-9);
+    }
+}
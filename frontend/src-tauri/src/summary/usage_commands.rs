@@ -0,0 +1,227 @@
+use crate::database::models::{LlmPrice, LlmSpend, LlmUsage};
+use crate::database::repositories::llm_usage::LlmUsageRepository;
+use crate::database::repositories::setting::SettingsRepository;
+use crate::state::AppState;
+use crate::summary::llm_client::LLMProvider;
+use crate::summary::usage::{self, LlmBudget};
+use chrono::{DateTime, Utc};
+use log::{error as log_error, info as log_info};
+use tauri::State;
+
+/// Parses an optional RFC 3339 start date for the spend reports
+fn parse_since(since: Option<String>) -> Result<Option<DateTime<Utc>>, String> {
+    since
+        .filter(|s| !s.trim().is_empty())
+        .map(|s| {
+            DateTime::parse_from_rfc3339(s.trim())
+                .map(|d| d.with_timezone(&Utc))
+                .map_err(|e| format!("Invalid date '{}': {}", s, e))
+        })
+        .transpose()
+}
+
+/// Rejects negative or non-finite amounts in USD
+fn validate_usd(name: &str, value: f64) -> Result<f64, String> {
+    if value.is_finite() && value >= 0.0 {
+        Ok(value)
+    } else {
+        Err(format!("{} must be a non-negative amount", name))
+    }
+}
+
+/// LLM spend per meeting, optionally since an RFC 3339 date
+#[tauri::command]
+pub async fn api_get_llm_spend_by_meeting(
+    state: State<'_, AppState>,
+    since: Option<String>,
+) -> Result<Vec<LlmSpend>, String> {
+    let since = parse_since(since)?;
+    LlmUsageRepository::spend_by_meeting(state.db_manager.pool(), since)
+        .await
+        .map_err(|e| {
+            log_error!("Failed to load LLM spend by meeting: {}", e);
+            format!("Failed to load LLM spend: {}", e)
+        })
+}
+
+/// LLM spend per provider and model, optionally since an RFC 3339 date
+#[tauri::command]
+pub async fn api_get_llm_spend_by_model(
+    state: State<'_, AppState>,
+    since: Option<String>,
+) -> Result<Vec<LlmSpend>, String> {
+    let since = parse_since(since)?;
+    LlmUsageRepository::spend_by_model(state.db_manager.pool(), since)
+        .await
+        .map_err(|e| {
+            log_error!("Failed to load LLM spend by model: {}", e);
+            format!("Failed to load LLM spend: {}", e)
+        })
+}
+
+/// LLM spend per calendar month, latest first
+#[tauri::command]
+pub async fn api_get_llm_spend_by_month(
+    state: State<'_, AppState>,
+) -> Result<Vec<LlmSpend>, String> {
+    LlmUsageRepository::spend_by_month(state.db_manager.pool())
+        .await
+        .map_err(|e| {
+            log_error!("Failed to load LLM spend by month: {}", e);
+            format!("Failed to load LLM spend: {}", e)
+        })
+}
+
+/// Every LLM call made for a meeting, with its tokens, timing and cost
+#[tauri::command]
+pub async fn api_get_meeting_llm_usage(
+    state: State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Vec<LlmUsage>, String> {
+    LlmUsageRepository::list_for_meeting(state.db_manager.pool(), &meeting_id)
+        .await
+        .map_err(|e| {
+            log_error!("Failed to load LLM usage for {}: {}", meeting_id, e);
+            format!("Failed to load LLM usage: {}", e)
+        })
+}
+
+#[tauri::command]
+pub async fn api_list_llm_prices(state: State<'_, AppState>) -> Result<Vec<LlmPrice>, String> {
+    LlmUsageRepository::list_prices(state.db_manager.pool())
+        .await
+        .map_err(|e| {
+            log_error!("Failed to load LLM prices: {}", e);
+            format!("Failed to load LLM prices: {}", e)
+        })
+}
+
+/// Sets the price of a model in USD per million tokens
+///
+/// A price set here takes precedence over OpenRouter's and is never
+/// overwritten by a sync.
+#[tauri::command]
+pub async fn api_save_llm_price(
+    state: State<'_, AppState>,
+    provider: String,
+    model: String,
+    prompt_usd_per_mtok: f64,
+    completion_usd_per_mtok: f64,
+) -> Result<LlmPrice, String> {
+    let provider = LLMProvider::from_str(&provider)?;
+    let model = model.trim();
+    if model.is_empty() {
+        return Err("Model is required".to_string());
+    }
+    let prompt = validate_usd("Prompt price", prompt_usd_per_mtok)?;
+    let completion = validate_usd("Completion price", completion_usd_per_mtok)?;
+
+    let price = LlmUsageRepository::save_price(
+        state.db_manager.pool(),
+        provider.id(),
+        model,
+        prompt,
+        completion,
+    )
+    .await
+    .map_err(|e| {
+        log_error!("Failed to save price for {}: {}", model, e);
+        format!("Failed to save price: {}", e)
+    })?
+    .ok_or_else(|| format!("Failed to save price for {}", model))?;
+    log_info!("Saved price for {} ({})", model, provider.id());
+    Ok(price)
+}
+
+/// Removes a price; calls to the model fall back to OpenRouter's price
+#[tauri::command]
+pub async fn api_delete_llm_price(
+    state: State<'_, AppState>,
+    provider: String,
+    model: String,
+) -> Result<bool, String> {
+    LlmUsageRepository::delete_price(state.db_manager.pool(), &provider, &model)
+        .await
+        .map_err(|e| {
+            log_error!("Failed to delete price for {}: {}", model, e);
+            format!("Failed to delete price: {}", e)
+        })
+}
+
+/// Refreshes the prices published by OpenRouter
+///
+/// # Returns
+/// The number of prices stored
+#[tauri::command]
+pub async fn api_sync_openrouter_prices(state: State<'_, AppState>) -> Result<usize, String> {
+    usage::sync_openrouter_prices(state.db_manager.pool())
+        .await
+        .map_err(|e| {
+            log_error!("{}", e);
+            e
+        })
+}
+
+#[tauri::command]
+pub async fn api_get_llm_budget(state: State<'_, AppState>) -> Result<LlmBudget, String> {
+    usage::get_budget(state.db_manager.pool())
+        .await
+        .map_err(|e| {
+            log_error!("{}", e);
+            e
+        })
+}
+
+/// Sets the monthly and per-meeting caps on cloud LLM spend; None removes a cap
+///
+/// Once a cap is reached, cloud calls are blocked and jobs fall back to a
+/// local backend in the failover chain, if there is one.
+#[tauri::command]
+pub async fn api_save_llm_budget(
+    state: State<'_, AppState>,
+    budget: LlmBudget,
+) -> Result<LlmBudget, String> {
+    let budget = LlmBudget {
+        monthly_usd: budget
+            .monthly_usd
+            .map(|v| validate_usd("Monthly budget", v))
+            .transpose()?,
+        per_meeting_usd: budget
+            .per_meeting_usd
+            .map(|v| validate_usd("Per-meeting budget", v))
+            .transpose()?,
+    };
+    SettingsRepository::save_llm_budget(
+        state.db_manager.pool(),
+        budget.monthly_usd,
+        budget.per_meeting_usd,
+    )
+    .await
+    .map_err(|e| {
+        log_error!("Failed to save LLM budget: {}", e);
+        format!("Failed to save LLM budget: {}", e)
+    })?;
+    log_info!("Saved LLM budget: {:?}", budget);
+    Ok(budget)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_since() {
+        assert_eq!(parse_since(None).unwrap(), None);
+        assert_eq!(parse_since(Some(" ".to_string())).unwrap(), None);
+        let since = parse_since(Some("2025-03-01T00:00:00+02:00".to_string())).unwrap();
+        assert_eq!(since.unwrap().to_rfc3339(), "2025-02-28T22:00:00+00:00");
+        assert!(parse_since(Some("March".to_string())).is_err());
+    }
+
+    #[test]
+    fn test_validate_usd() {
+        assert_eq!(validate_usd("Budget", 0.0), Ok(0.0));
+        assert!(validate_usd("Budget", -1.0).is_err());
+        assert!(validate_usd("Budget", f64::NAN).is_err());
+    }
+}
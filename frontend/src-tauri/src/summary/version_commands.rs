@@ -0,0 +1,118 @@
+use crate::database::models::SummaryVersion;
+use crate::database::repositories::{
+    summary::SummaryProcessesRepository, summary_version::SummaryVersionsRepository,
+};
+use crate::state::AppState;
+use crate::summary::versions::{self, SectionDiff};
+use log::{error as log_error, info as log_info};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use tauri::State;
+
+/// A meeting's summary versions, newest first
+#[derive(Debug, Serialize)]
+pub struct SummaryVersionList {
+    /// The version the meeting's summary shows
+    pub current_version_id: Option<String>,
+    pub versions: Vec<SummaryVersion>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SummaryVersionDiff {
+    pub from_version_id: String,
+    pub to_version_id: String,
+    pub sections: Vec<SectionDiff>,
+}
+
+async fn load_version(
+    pool: &SqlitePool,
+    meeting_id: &str,
+    version_id: &str,
+) -> Result<SummaryVersion, String> {
+    SummaryVersionsRepository::get_version(pool, meeting_id, version_id)
+        .await
+        .map_err(|e| {
+            log_error!("Failed to load summary version {}: {}", version_id, e);
+            format!("Failed to load summary version: {}", e)
+        })?
+        .ok_or_else(|| format!("Summary version not found: {}", version_id))
+}
+
+/// Lists every generated and edited summary of a meeting
+#[tauri::command]
+pub async fn api_list_summary_versions(
+    state: State<'_, AppState>,
+    meeting_id: String,
+) -> Result<SummaryVersionList, String> {
+    let pool = state.db_manager.pool();
+    let versions = SummaryVersionsRepository::list_for_meeting(pool, &meeting_id)
+        .await
+        .map_err(|e| {
+            log_error!("Failed to list summary versions for {}: {}", meeting_id, e);
+            format!("Failed to list summary versions: {}", e)
+        })?;
+    let current_version_id = SummaryProcessesRepository::get_summary_data(pool, &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load summary: {}", e))?
+        .and_then(|process| process.current_version_id);
+    Ok(SummaryVersionList {
+        current_version_id,
+        versions,
+    })
+}
+
+/// Makes an earlier version the meeting's summary again
+///
+/// Not allowed while a summary is being generated, since the running job
+/// would overwrite it.
+#[tauri::command]
+pub async fn api_restore_summary_version(
+    state: State<'_, AppState>,
+    meeting_id: String,
+    version_id: String,
+) -> Result<SummaryVersion, String> {
+    let pool = state.db_manager.pool();
+    let process = SummaryProcessesRepository::get_summary_data(pool, &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load summary: {}", e))?;
+    let unfinished = process
+        .is_some_and(|p| matches!(p.status.to_lowercase().as_str(), "pending" | "processing"));
+    if unfinished {
+        return Err("Wait for the summary job to finish before restoring a version".into());
+    }
+
+    let version = SummaryVersionsRepository::restore(pool, &meeting_id, &version_id)
+        .await
+        .map_err(|e| {
+            log_error!("Failed to restore summary version {}: {}", version_id, e);
+            format!("Failed to restore summary version: {}", e)
+        })?
+        .ok_or_else(|| format!("Summary version not found: {}", version_id))?;
+    log_info!(
+        "Restored summary version {} for meeting_id: {}",
+        version_id,
+        meeting_id
+    );
+    Ok(version)
+}
+
+/// Compares two versions of a meeting's summary section by section
+#[tauri::command]
+pub async fn api_diff_summary_versions(
+    state: State<'_, AppState>,
+    meeting_id: String,
+    from_version_id: String,
+    to_version_id: String,
+) -> Result<SummaryVersionDiff, String> {
+    let pool = state.db_manager.pool();
+    let from = load_version(pool, &meeting_id, &from_version_id).await?;
+    let to = load_version(pool, &meeting_id, &to_version_id).await?;
+    Ok(SummaryVersionDiff {
+        sections: versions::diff_sections(
+            &versions::result_markdown(&from.result),
+            &versions::result_markdown(&to.result),
+        ),
+        from_version_id,
+        to_version_id,
+    })
+}
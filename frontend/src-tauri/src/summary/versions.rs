@@ -0,0 +1,242 @@
+/// Section-level comparison of summary versions
+///
+/// A summary's markdown is split at its headings, and sections are matched
+/// between two versions by heading text (case-insensitive, the nth repeat of
+/// a heading with the nth). Text before the first heading is a section with
+/// an empty heading. Headings inside code fences don't start sections.
+use serde::Serialize;
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SectionStatus {
+    Added,
+    Removed,
+    Changed,
+    Unchanged,
+}
+
+/// One section in a comparison of two versions
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SectionDiff {
+    /// Heading text without the `#` markers, empty before the first heading
+    pub heading: String,
+    pub status: SectionStatus,
+    /// Section body in the older version
+    pub old: Option<String>,
+    /// Section body in the newer version
+    pub new: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Section {
+    heading: String,
+    body: String,
+}
+
+impl Section {
+    fn key(&self) -> String {
+        self.heading.to_lowercase()
+    }
+}
+
+/// The markdown of a stored summary result
+///
+/// Results are `{"markdown": ...}` JSON; anything else is taken as markdown.
+pub fn result_markdown(result: &str) -> String {
+    serde_json::from_str::<Value>(result)
+        .ok()
+        .and_then(|value| value["markdown"].as_str().map(str::to_string))
+        .unwrap_or_else(|| result.to_string())
+}
+
+/// Heading text if `line` is an ATX heading (`#` to `######`)
+fn heading_text(line: &str) -> Option<String> {
+    let trimmed = line.trim_start();
+    let level = trimmed.chars().take_while(|c| *c == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let rest = &trimmed[level..];
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some(rest.trim().trim_end_matches('#').trim_end().to_string())
+}
+
+fn split_sections(markdown: &str) -> Vec<Section> {
+    let mut sections = vec![Section {
+        heading: String::new(),
+        body: String::new(),
+    }];
+    let mut in_fence = false;
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        }
+        match heading_text(line).filter(|_| !in_fence) {
+            Some(heading) => sections.push(Section {
+                heading,
+                body: String::new(),
+            }),
+            None => {
+                let body = &mut sections.last_mut().expect("sections is never empty").body;
+                body.push_str(line);
+                body.push('\n');
+            }
+        }
+    }
+    for section in &mut sections {
+        section.body = section.body.trim().to_string();
+    }
+    // Drop an empty preamble, which most summaries have
+    if sections[0].body.is_empty() {
+        sections.remove(0);
+    }
+    sections
+}
+
+/// Index in `old` of each section of `new`, pairing repeated headings in order
+fn match_sections(old: &[Section], new: &[Section]) -> Vec<Option<usize>> {
+    let mut taken = vec![false; old.len()];
+    new.iter()
+        .map(|section| {
+            let key = section.key();
+            let index = (0..old.len()).find(|&i| !taken[i] && old[i].key() == key)?;
+            taken[index] = true;
+            Some(index)
+        })
+        .collect()
+}
+
+/// Compares two summaries section by section
+///
+/// Sections come in the newer version's order, with removed sections placed
+/// where they stood in the older one.
+pub fn diff_sections(old_markdown: &str, new_markdown: &str) -> Vec<SectionDiff> {
+    let old = split_sections(old_markdown);
+    let new = split_sections(new_markdown);
+    let matches = match_sections(&old, &new);
+
+    let mut matched = vec![false; old.len()];
+    for index in matches.iter().flatten() {
+        matched[*index] = true;
+    }
+    let removed = |section: &Section| SectionDiff {
+        heading: section.heading.clone(),
+        status: SectionStatus::Removed,
+        old: Some(section.body.clone()),
+        new: None,
+    };
+
+    let mut diffs = Vec::new();
+    let mut next_old = 0;
+    for (section, old_index) in new.iter().zip(matches) {
+        let Some(old_index) = old_index else {
+            diffs.push(SectionDiff {
+                heading: section.heading.clone(),
+                status: SectionStatus::Added,
+                old: None,
+                new: Some(section.body.clone()),
+            });
+            continue;
+        };
+        while next_old < old_index {
+            if !matched[next_old] {
+                diffs.push(removed(&old[next_old]));
+            }
+            next_old += 1;
+        }
+        next_old = next_old.max(old_index + 1);
+
+        let old_section = &old[old_index];
+        diffs.push(SectionDiff {
+            heading: section.heading.clone(),
+            status: if old_section.body == section.body {
+                SectionStatus::Unchanged
+            } else {
+                SectionStatus::Changed
+            },
+            old: Some(old_section.body.clone()),
+            new: Some(section.body.clone()),
+        });
+    }
+    diffs.extend(
+        old.iter()
+            .enumerate()
+            .skip(next_old)
+            .filter(|(i, _)| !matched[*i])
+            .map(|(_, section)| removed(section)),
+    );
+    diffs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn statuses(diffs: &[SectionDiff]) -> Vec<(&str, SectionStatus)> {
+        diffs
+            .iter()
+            .map(|d| (d.heading.as_str(), d.status))
+            .collect()
+    }
+
+    #[test]
+    fn test_split_sections() {
+        let markdown =
+            "Intro\n\n## Decisions ##\n- Ship it\n```\n# not a heading\n```\n#hashtag\n### Notes\n";
+        let sections = split_sections(markdown);
+        assert_eq!(sections.len(), 3);
+        assert_eq!(sections[0].heading, "");
+        assert_eq!(sections[1].heading, "Decisions");
+        assert_eq!(
+            sections[1].body,
+            "- Ship it\n```\n# not a heading\n```\n#hashtag"
+        );
+        assert_eq!(sections[2].heading, "Notes");
+        assert_eq!(sections[2].body, "");
+    }
+
+    #[test]
+    fn test_diff_sections() {
+        let old = "# Summary\nA\n# Risks\nNone\n# Decisions\nShip\n";
+        let new = "# Summary\nA\n# Decisions\nDelay\n# Next Steps\nTest\n";
+        let diffs = diff_sections(old, new);
+        assert_eq!(
+            statuses(&diffs),
+            vec![
+                ("Summary", SectionStatus::Unchanged),
+                ("Risks", SectionStatus::Removed),
+                ("Decisions", SectionStatus::Changed),
+                ("Next Steps", SectionStatus::Added),
+            ]
+        );
+        assert_eq!(diffs[2].old.as_deref(), Some("Ship"));
+        assert_eq!(diffs[2].new.as_deref(), Some("Delay"));
+    }
+
+    #[test]
+    fn test_diff_sections_repeated_and_trailing_removed() {
+        let old = "## Notes\none\n## notes\ntwo\n## Extra\nx\n";
+        let new = "## Notes\none\n## Notes\n2\n";
+        assert_eq!(
+            statuses(&diff_sections(old, new)),
+            vec![
+                ("Notes", SectionStatus::Unchanged),
+                ("Notes", SectionStatus::Changed),
+                ("Extra", SectionStatus::Removed),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_result_markdown() {
+        assert_eq!(
+            result_markdown(r##"{"markdown":"# A","summary_json":[]}"##),
+            "# A"
+        );
+        assert_eq!(result_markdown("# Plain"), "# Plain");
+    }
+}
@@ -0,0 +1,181 @@
+use super::{CompiledRule, RuleSet};
+use crate::database::models::ReplacementRule;
+use crate::database::repositories::meeting::MeetingsRepository;
+use crate::database::repositories::replacement_rule::{
+    ReplacementRuleInput, ReplacementRulesRepository,
+};
+use crate::database::repositories::transcript::TranscriptsRepository;
+use crate::state::AppState;
+use log::{error as log_error, info as log_info, warn as log_warn};
+use sqlx::SqlitePool;
+use tauri::State;
+
+async fn reload_after_change(pool: &SqlitePool) {
+    if let Err(e) = super::reload_active_rules(pool).await {
+        log_warn!("Failed to reload replacement rules after change: {}", e);
+    }
+}
+
+#[tauri::command]
+pub async fn api_list_replacement_rules(
+    state: State<'_, AppState>,
+) -> Result<Vec<ReplacementRule>, String> {
+    ReplacementRulesRepository::list_rules(state.db_manager.pool())
+        .await
+        .map_err(|e| {
+            log_error!("Failed to load replacement rules: {}", e);
+            format!("Failed to load replacement rules: {}", e)
+        })
+}
+
+#[tauri::command]
+pub async fn api_create_replacement_rule(
+    state: State<'_, AppState>,
+    rule: ReplacementRuleInput,
+) -> Result<ReplacementRule, String> {
+    log_info!("api_create_replacement_rule called for pattern: '{}'", rule.pattern);
+
+    // Reject patterns that can't run before they are stored
+    CompiledRule::compile(&rule)?;
+
+    let pool = state.db_manager.pool();
+    let created = ReplacementRulesRepository::create_rule(pool, &rule)
+        .await
+        .map_err(|e| {
+            log_error!("Failed to create replacement rule: {}", e);
+            format!("Failed to create replacement rule: {}", e)
+        })?;
+    reload_after_change(pool).await;
+    Ok(created)
+}
+
+#[tauri::command]
+pub async fn api_update_replacement_rule(
+    state: State<'_, AppState>,
+    rule_id: String,
+    rule: ReplacementRuleInput,
+) -> Result<ReplacementRule, String> {
+    log_info!("api_update_replacement_rule called for rule_id: {}", rule_id);
+
+    CompiledRule::compile(&rule)?;
+
+    let pool = state.db_manager.pool();
+    let updated = ReplacementRulesRepository::update_rule(pool, &rule_id, &rule)
+        .await
+        .map_err(|e| {
+            log_error!("Failed to update replacement rule {}: {}", rule_id, e);
+            format!("Failed to update replacement rule: {}", e)
+        })?
+        .ok_or_else(|| format!("Replacement rule not found: {}", rule_id))?;
+    reload_after_change(pool).await;
+    Ok(updated)
+}
+
+#[tauri::command]
+pub async fn api_delete_replacement_rule(
+    state: State<'_, AppState>,
+    rule_id: String,
+) -> Result<bool, String> {
+    log_info!("api_delete_replacement_rule called for rule_id: {}", rule_id);
+
+    let pool = state.db_manager.pool();
+    let deleted = ReplacementRulesRepository::delete_rule(pool, &rule_id)
+        .await
+        .map_err(|e| {
+            log_error!("Failed to delete replacement rule {}: {}", rule_id, e);
+            format!("Failed to delete replacement rule: {}", e)
+        })?;
+    if deleted {
+        reload_after_change(pool).await;
+    }
+    Ok(deleted)
+}
+
+/// Set the order rules are applied in
+#[tauri::command]
+pub async fn api_reorder_replacement_rules(
+    state: State<'_, AppState>,
+    rule_ids: Vec<String>,
+) -> Result<(), String> {
+    let pool = state.db_manager.pool();
+    ReplacementRulesRepository::reorder_rules(pool, &rule_ids)
+        .await
+        .map_err(|e| {
+            log_error!("Failed to reorder replacement rules: {}", e);
+            format!("Failed to reorder replacement rules: {}", e)
+        })?;
+    reload_after_change(pool).await;
+    Ok(())
+}
+
+/// Run the enabled rules over sample text, for trying rules out in settings
+#[tauri::command]
+pub async fn api_preview_replacement_rules(
+    state: State<'_, AppState>,
+    text: String,
+) -> Result<String, String> {
+    let rules = ReplacementRulesRepository::get_enabled_rules(state.db_manager.pool())
+        .await
+        .map_err(|e| format!("Failed to load replacement rules: {}", e))?;
+    Ok(RuleSet::from_rules(&rules).apply(&text))
+}
+
+/// Apply the enabled rules to a saved meeting's transcripts.
+/// Returns the number of segments that changed.
+#[tauri::command]
+pub async fn api_apply_replacement_rules(
+    state: State<'_, AppState>,
+    meeting_id: String,
+) -> Result<usize, String> {
+    log_info!("api_apply_replacement_rules called for meeting_id: {}", meeting_id);
+
+    let pool = state.db_manager.pool();
+    let rules = ReplacementRulesRepository::get_enabled_rules(pool)
+        .await
+        .map_err(|e| format!("Failed to load replacement rules: {}", e))?;
+    let rule_set = RuleSet::from_rules(&rules);
+    if rule_set.is_empty() {
+        return Ok(0);
+    }
+
+    let meeting = MeetingsRepository::get_meeting(pool, &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load meeting: {}", e))?
+        .ok_or_else(|| format!("Meeting not found: {}", meeting_id))?;
+    let changes: Vec<_> = meeting
+        .transcripts
+        .into_iter()
+        .filter_map(|transcript| {
+            let text = rule_set.apply(&transcript.text);
+            let new_words = rule_set.apply_words(transcript.words.clone());
+            let words_changed = new_words.len() != transcript.words.len()
+                || new_words.iter().zip(&transcript.words).any(|(a, b)| a.word != b.word);
+            let new_words = words_changed.then_some(new_words);
+            if text == transcript.text && new_words.is_none() {
+                None
+            } else {
+                Some((transcript.id, text, new_words))
+            }
+        })
+        .collect();
+
+    if changes.is_empty() {
+        log_info!("Replacement rules changed nothing in meeting {}", meeting_id);
+        return Ok(0);
+    }
+
+    TranscriptsRepository::update_transcript_texts(pool, &meeting_id, &changes)
+        .await
+        .map_err(|e| {
+            log_error!("Failed to save corrected transcripts for {}: {}", meeting_id, e);
+            format!("Failed to save corrected transcripts: {}", e)
+        })?;
+
+    log_info!(
+        "✅ Applied {} replacement rule(s) to meeting {}: {} segment(s) changed",
+        rule_set.len(),
+        meeting_id,
+        changes.len()
+    );
+    Ok(changes.len())
+}
@@ -0,0 +1,309 @@
+/// Transcript Find-and-Replace Rules
+///
+/// User-defined literal or regex replacements that fix recurring
+/// misrecognitions ("meat lee" -> "Meetily"). Rules are stored in SQLite and
+/// applied in order to every transcript segment before it is emitted and
+/// saved; they can also be re-applied to a saved meeting.
+///
+/// With case preservation a replacement follows the case of the matched text:
+/// "ACME" stays upper case and a capitalized match gives a capitalized
+/// replacement. Lower-case matches keep the replacement as written, so entity
+/// fixes keep their capitals.
+
+pub mod commands;
+
+use crate::audio::transcription::WordTimestamp;
+use crate::database::models::ReplacementRule;
+use crate::database::repositories::replacement_rule::{
+    ReplacementRuleInput, ReplacementRulesRepository,
+};
+use crate::state::AppState;
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex, RegexBuilder};
+use sqlx::SqlitePool;
+use std::sync::{Arc, RwLock};
+use tauri::{AppHandle, Manager, Runtime};
+
+// Enabled rules, compiled, used by the live transcription workers
+static ACTIVE_RULES: Lazy<RwLock<Arc<RuleSet>>> =
+    Lazy::new(|| RwLock::new(Arc::new(RuleSet::default())));
+
+#[derive(Debug, Clone)]
+pub struct CompiledRule {
+    regex: Regex,
+    replacement: String,
+    is_regex: bool,
+    preserve_case: bool,
+}
+
+impl CompiledRule {
+    pub fn compile(rule: &ReplacementRuleInput) -> Result<Self, String> {
+        let pattern = if rule.is_regex {
+            rule.pattern.clone()
+        } else {
+            let literal = rule.pattern.trim();
+            let mut pattern = regex::escape(literal);
+            if rule.whole_word {
+                if literal.chars().next().map_or(false, is_word_char) {
+                    pattern.insert_str(0, r"\b");
+                }
+                if literal.chars().last().map_or(false, is_word_char) {
+                    pattern.push_str(r"\b");
+                }
+            }
+            pattern
+        };
+        if pattern.trim().is_empty() {
+            return Err("Pattern cannot be empty".to_string());
+        }
+
+        let regex = RegexBuilder::new(&pattern)
+            .case_insensitive(!rule.case_sensitive)
+            .build()
+            .map_err(|e| format!("Invalid pattern '{}': {}", rule.pattern, e))?;
+        if regex.is_match("") {
+            return Err(format!("Pattern '{}' matches empty text", rule.pattern));
+        }
+
+        Ok(Self {
+            regex,
+            replacement: rule.replacement.clone(),
+            is_regex: rule.is_regex,
+            preserve_case: rule.preserve_case,
+        })
+    }
+
+    pub fn apply(&self, text: &str) -> String {
+        self.regex
+            .replace_all(text, |caps: &Captures| {
+                let mut replaced = String::new();
+                if self.is_regex {
+                    caps.expand(&self.replacement, &mut replaced);
+                } else {
+                    replaced.push_str(&self.replacement);
+                }
+                if self.preserve_case {
+                    match_case(&caps[0], &replaced)
+                } else {
+                    replaced
+                }
+            })
+            .into_owned()
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Give `replacement` the case pattern of `matched`
+pub fn match_case(matched: &str, replacement: &str) -> String {
+    let letters: Vec<char> = matched.chars().filter(|c| c.is_alphabetic()).collect();
+    if letters.len() > 1 && letters.iter().all(|c| c.is_uppercase()) {
+        return replacement.to_uppercase();
+    }
+    if letters.first().map_or(false, |c| c.is_uppercase()) {
+        let mut chars = replacement.chars();
+        if let Some(first) = chars.next() {
+            return first.to_uppercase().chain(chars).collect();
+        }
+    }
+    replacement.to_string()
+}
+
+/// Rules in application order
+#[derive(Debug, Clone, Default)]
+pub struct RuleSet {
+    rules: Vec<CompiledRule>,
+}
+
+impl RuleSet {
+    /// Compile enabled rules, skipping (and logging) any that don't compile
+    pub fn from_rules(rules: &[ReplacementRule]) -> Self {
+        let rules = rules
+            .iter()
+            .filter(|rule| rule.enabled)
+            .filter_map(|rule| match CompiledRule::compile(&ReplacementRuleInput::from(rule)) {
+                Ok(compiled) => Some(compiled),
+                Err(e) => {
+                    warn!("Skipping replacement rule {}: {}", rule.id, e);
+                    None
+                }
+            })
+            .collect();
+        Self { rules }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Apply every rule in order. Whitespace is tidied only if a rule
+    /// changed the text (e.g. a filler word was removed).
+    pub fn apply(&self, text: &str) -> String {
+        let mut result = text.to_string();
+        for rule in &self.rules {
+            result = rule.apply(&result);
+        }
+        if result != text {
+            result = result.split_whitespace().collect::<Vec<_>>().join(" ");
+        }
+        result
+    }
+
+    /// Apply the rules to each word timing on its own; words a rule removes
+    /// entirely are dropped. Phrases spanning several words only change in the
+    /// segment text.
+    pub fn apply_words(&self, words: Vec<WordTimestamp>) -> Vec<WordTimestamp> {
+        if self.is_empty() {
+            return words;
+        }
+        words
+            .into_iter()
+            .filter_map(|mut word| {
+                word.word = self.apply(&word.word);
+                if word.word.is_empty() {
+                    None
+                } else {
+                    Some(word)
+                }
+            })
+            .collect()
+    }
+}
+
+pub fn active_rules() -> Arc<RuleSet> {
+    ACTIVE_RULES
+        .read()
+        .map(|rules| Arc::clone(&rules))
+        .unwrap_or_default()
+}
+
+/// Reload enabled rules from the database. Returns how many are active.
+pub async fn reload_active_rules(pool: &SqlitePool) -> Result<usize, sqlx::Error> {
+    let rules = ReplacementRulesRepository::get_enabled_rules(pool).await?;
+    let rule_set = RuleSet::from_rules(&rules);
+    let count = rule_set.len();
+    if let Ok(mut active) = ACTIVE_RULES.write() {
+        *active = Arc::new(rule_set);
+    }
+    info!("Loaded {} transcript replacement rule(s)", count);
+    Ok(count)
+}
+
+/// Load the current rules before transcription starts
+pub async fn start_session<R: Runtime>(app: &AppHandle<R>) {
+    let Some(state) = app.try_state::<AppState>() else {
+        warn!("Database not ready, transcribing without replacement rules reload");
+        return;
+    };
+    if let Err(e) = reload_active_rules(state.db_manager.pool()).await {
+        warn!("Failed to load replacement rules: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn literal(pattern: &str, replacement: &str) -> ReplacementRuleInput {
+        ReplacementRuleInput {
+            pattern: pattern.to_string(),
+            replacement: replacement.to_string(),
+            is_regex: false,
+            case_sensitive: false,
+            whole_word: true,
+            preserve_case: true,
+            enabled: true,
+        }
+    }
+
+    fn apply(rule: &ReplacementRuleInput, text: &str) -> String {
+        CompiledRule::compile(rule).unwrap().apply(text)
+    }
+
+    #[test]
+    fn test_literal_whole_word() {
+        let rule = literal("meat lee", "Meetily");
+        assert_eq!(apply(&rule, "we use meat lee daily"), "we use Meetily daily");
+        assert_eq!(apply(&rule, "Meat Lee is great"), "Meetily is great");
+        assert_eq!(apply(&rule, "meat leeway"), "meat leeway");
+
+        let mut partial = literal("cat", "dog");
+        partial.whole_word = false;
+        assert_eq!(apply(&partial, "concatenate"), "condogenate");
+    }
+
+    #[test]
+    fn test_literal_is_not_a_regex() {
+        let rule = literal("c++", "C++");
+        assert_eq!(apply(&rule, "we write c++ code"), "we write C++ code");
+        let rule = literal("cost", "$1 price");
+        assert_eq!(apply(&rule, "the cost"), "the $1 price");
+    }
+
+    #[test]
+    fn test_case_preservation() {
+        assert_eq!(match_case("ACME", "acme corp"), "ACME CORP");
+        assert_eq!(match_case("Okay", "ok"), "Ok");
+        assert_eq!(match_case("okay", "OK"), "OK");
+        assert_eq!(match_case("I", "me"), "Me");
+
+        let mut rule = literal("kubernetes", "k8s");
+        assert_eq!(apply(&rule, "KUBERNETES and Kubernetes"), "K8S and K8s");
+        rule.preserve_case = false;
+        assert_eq!(apply(&rule, "KUBERNETES and Kubernetes"), "k8s and k8s");
+    }
+
+    #[test]
+    fn test_case_sensitive() {
+        let mut rule = literal("Go", "Golang");
+        rule.case_sensitive = true;
+        assert_eq!(apply(&rule, "Go is fine, go home"), "Golang is fine, go home");
+    }
+
+    #[test]
+    fn test_regex_with_captures() {
+        let mut rule = literal(r"(\d+) percent", "$1%");
+        rule.is_regex = true;
+        assert_eq!(apply(&rule, "up 20 percent today"), "up 20% today");
+    }
+
+    #[test]
+    fn test_invalid_patterns() {
+        let mut rule = literal("(unclosed", "x");
+        rule.is_regex = true;
+        assert!(CompiledRule::compile(&rule).is_err());
+        assert!(CompiledRule::compile(&literal("  ", "x")).is_err());
+
+        let mut empty_match = literal("a*", "x");
+        empty_match.is_regex = true;
+        assert!(CompiledRule::compile(&empty_match).is_err());
+    }
+
+    #[test]
+    fn test_rule_set_order_and_cleanup() {
+        let rules = RuleSet {
+            rules: vec![
+                CompiledRule::compile(&literal("um", "")).unwrap(),
+                CompiledRule::compile(&literal("meat lee", "Meetily")).unwrap(),
+                CompiledRule::compile(&literal("Meetily app", "Meetily")).unwrap(),
+            ],
+        };
+        assert_eq!(rules.apply("so um meat lee app works"), "so Meetily works");
+        assert_eq!(rules.apply("  nothing  to fix "), "  nothing  to fix ");
+
+        let words = vec![
+            WordTimestamp { word: "um".to_string(), start: 0.0, end: 0.2 },
+            WordTimestamp { word: "Meetily".to_string(), start: 0.2, end: 0.6 },
+        ];
+        let words = rules.apply_words(words);
+        assert_eq!(words.len(), 1);
+        assert_eq!(words[0].word, "Meetily");
+    }
+}
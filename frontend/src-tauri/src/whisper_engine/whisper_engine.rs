@@ -584,6 +584,12 @@ impl WhisperEngine {
         params.set_max_len(200);
         params.set_single_segment(false);
 
+        // Bias towards glossary spellings and continue from the previous chunk
+        let initial_prompt = crate::glossary::initial_prompt();
+        if let Some(prompt) = initial_prompt.as_deref() {
+            params.set_initial_prompt(prompt);
+        }
+
         // Set thread count based on hardware (if supported by whisper.cpp)
         if let Some(_max_threads) = adaptive_config.max_threads {
             // Note: whisper.cpp may or may not expose thread control through params
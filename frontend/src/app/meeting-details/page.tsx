@@ -137,8 +137,9 @@ function MeetingDetailsContent() {
 
         console.log('🔍 FETCH SUMMARY: Raw response:', summary);
 
-        // Check if the summary request failed with 404 or error status, or if no summary exists yet (idle)
-        if (summary.status === 'error' || summary.error || summary.status === 'idle') {
+        // Check if the summary request failed with 404 or error status, or if no summary exists yet (idle).
+        // A failed or cancelled regeneration still returns the previous summary in data.
+        if (!summary.data && (summary.status === 'error' || summary.error || summary.status === 'idle' || summary.status === 'cancelled')) {
           console.warn('Meeting summary not found, error occurred, or no summary generated yet:', summary.error || 'idle');
           setMeetingSummary(null);
           return;
@@ -963,7 +963,10 @@ export default function Home() {
             const hours = String(now.getHours()).padStart(2, '0');
             const minutes = String(now.getMinutes()).padStart(2, '0');
             const seconds = String(now.getSeconds()).padStart(2, '0');
-            const generatedMeetingTitle = `Meeting ${day}_${month}_${year}_${hours}_${minutes}_${seconds}`;
+            // The auto-recorder names the meeting after the app that started it
+            const autoStartMeetingName = sessionStorage.getItem('autoStartMeetingName');
+            sessionStorage.removeItem('autoStartMeetingName');
+            const generatedMeetingTitle = autoStartMeetingName || `Meeting ${day}_${month}_${year}_${hours}_${minutes}_${seconds}`;
 
             console.log('Auto-starting backend recording with meeting:', generatedMeetingTitle);
 
@@ -1476,6 +1479,13 @@ export default function Home() {
             return;
           }
 
+          // A cancelled job leaves the previous summary in place
+          if (result.status === 'cancelled') {
+            setSummaryStatus(result.data ? 'completed' : 'idle');
+            clearInterval(pollInterval);
+            return;
+          }
+
           if (result.status === 'completed' && result.data) {
             clearInterval(pollInterval);
 
@@ -1769,6 +1779,13 @@ export default function Home() {
             return;
           }
 
+          // A cancelled job leaves the previous summary in place
+          if (result.status === 'cancelled') {
+            setSummaryStatus(result.data ? 'completed' : 'idle');
+            clearInterval(pollInterval);
+            return;
+          }
+
           if (result.status === 'completed' && result.data) {
             clearInterval(pollInterval);
 
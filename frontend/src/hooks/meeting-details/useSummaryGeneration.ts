@@ -176,6 +176,14 @@ export function useSummaryGeneration({
           return;
         }
 
+        // Handle cancellation - the previous summary, if any, is still shown
+        if (pollingResult.status === 'cancelled') {
+          console.log('Summary generation cancelled');
+          setSummaryStatus(pollingResult.data ? 'completed' : 'idle');
+          toast.info(`Summary ${isRegeneration ? 'regeneration' : 'generation'} cancelled`);
+          return;
+        }
+
         // Handle successful completion
         if (pollingResult.status === 'completed' && pollingResult.data) {
           console.log('✅ Summary generation completed:', pollingResult.data);